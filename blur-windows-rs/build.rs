@@ -1,4 +1,23 @@
+/// Commands registered by `tauri_plugin_blur::init`, each getting an
+/// `allow-`/`deny-` permission.
+#[cfg(feature = "tauri-plugin")]
+const TAURI_COMMANDS: &[&str] = &[
+    "start_blur",
+    "stop_blur",
+    "update_blur_parameters",
+    "update_noise_parameters",
+    "update_rain_parameters",
+    "get_blur_fps",
+];
+
 fn main() {
+    #[cfg(feature = "tauri-plugin")]
+    tauri_plugin::Builder::new(TAURI_COMMANDS).build();
+
+    // The mock feature replaces the native library entirely
+    if std::env::var_os("CARGO_FEATURE_MOCK").is_some() {
+        return;
+    }
     println!("cargo:rustc-link-search=native=../build/lib/Release");
     println!("cargo:rustc-link-search=native=../build/bin/Release");
     println!("cargo:rustc-link-lib=dylib=blurwindow");
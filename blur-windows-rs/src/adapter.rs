@@ -0,0 +1,158 @@
+use super::*;
+use std::ffi::CStr;
+use std::fmt;
+
+/// A user-mode driver version, e.g. `31.0.15.3623`, most significant part
+/// first.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DriverVersion(pub [u16; 4]);
+
+impl DriverVersion {
+    fn from_raw(v: u64) -> Option<Self> {
+        let parts = [
+            (v >> 48) as u16,
+            (v >> 32) as u16,
+            (v >> 16) as u16,
+            v as u16,
+        ];
+        (v != 0).then_some(DriverVersion(parts))
+    }
+}
+
+impl fmt::Display for DriverVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b, c, d] = self.0;
+        write!(f, "{}.{}.{}.{}", a, b, c, d)
+    }
+}
+
+/// One graphics adapter, see [`BlurSystem::adapter_info`]. Its `Display`
+/// form is the block to paste into a bug report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdapterInfo {
+    /// Position in the order DXGI enumerates adapters.
+    pub index: u32,
+    /// PCI vendor ID, see [`vendor`](Self::vendor).
+    pub vendor_id: u32,
+    /// PCI device ID.
+    pub device_id: u32,
+    /// As reported by the driver, e.g. "NVIDIA GeForce RTX 3060".
+    pub name: String,
+    /// In bytes; 0 for integrated GPUs that only share system memory.
+    pub dedicated_video_memory: u64,
+    /// `None` if the driver doesn't report one.
+    pub driver_version: Option<DriverVersion>,
+    /// A software adapter such as the Microsoft Basic Render Driver, which
+    /// Windows falls back to without a working GPU driver.
+    pub software: bool,
+    /// The adapter the system renders on.
+    pub active: bool,
+}
+
+impl AdapterInfo {
+    /// The vendor's name for well-known vendor IDs, `"Unknown"` otherwise.
+    pub fn vendor(&self) -> &'static str {
+        match self.vendor_id {
+            0x10DE => "NVIDIA",
+            0x1002 | 0x1022 => "AMD",
+            0x8086 => "Intel",
+            0x1414 => "Microsoft",
+            0x5143 => "Qualcomm",
+            _ => "Unknown",
+        }
+    }
+}
+
+impl fmt::Display for AdapterInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Adapter {}: {} ({} {:04x}:{:04x}){}{}",
+            self.index,
+            self.name,
+            self.vendor(),
+            self.vendor_id,
+            self.device_id,
+            if self.software { ", software" } else { "" },
+            if self.active { ", active" } else { "" },
+        )?;
+        writeln!(
+            f,
+            "Dedicated video memory: {} MB",
+            self.dedicated_video_memory / (1024 * 1024)
+        )?;
+        match self.driver_version {
+            Some(version) => write!(f, "Driver version: {}", version),
+            None => write!(f, "Driver version: unknown"),
+        }
+    }
+}
+
+pub(crate) fn adapter_info(
+    system: BlurSystemHandle,
+    index: u32,
+) -> Result<AdapterInfo, BlurErrorCode> {
+    let mut raw = AdapterInfoC::default();
+    let code = unsafe { blur_get_adapter_info(system, index, &mut raw) };
+    if code != BlurErrorCode::Ok {
+        return Err(code);
+    }
+    // First call only asks for the size, so it fails by design
+    let mut len = 0u32;
+    unsafe { blur_get_adapter_description(system, index, std::ptr::null_mut(), &mut len) };
+    let mut buf = vec![0u8; len as usize];
+    let code = unsafe {
+        blur_get_adapter_description(system, index, buf.as_mut_ptr() as *mut c_char, &mut len)
+    };
+    if code != BlurErrorCode::Ok {
+        return Err(code);
+    }
+    let name = CStr::from_bytes_until_nul(&buf)
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    Ok(AdapterInfo {
+        index,
+        vendor_id: raw.vendor_id,
+        device_id: raw.device_id,
+        name,
+        dedicated_video_memory: raw.dedicated_video_memory,
+        driver_version: DriverVersion::from_raw(raw.driver_version),
+        software: raw.software != 0,
+        active: raw.active != 0,
+    })
+}
+
+pub(crate) fn active_adapter_info(system: BlurSystemHandle) -> Result<AdapterInfo, BlurErrorCode> {
+    for index in 0.. {
+        match adapter_info(system, index) {
+            Ok(info) if info.active => return Ok(info),
+            Ok(_) => {}
+            // Past the last adapter
+            Err(BlurErrorCode::InvalidParameter) => break,
+            Err(code) => return Err(code),
+        }
+    }
+    Err(BlurErrorCode::NotInitialized)
+}
+
+impl BlurSystem {
+    /// Vendor, name, video memory and driver version of the `index`th
+    /// graphics adapter, in the order DXGI enumerates them (the one driving
+    /// the primary monitor first). Returns `InvalidParameter` past the last
+    /// adapter.
+    pub fn adapter_info(&self, index: u32) -> Result<AdapterInfo, BlurErrorCode> {
+        adapter_info(self.handle, index)
+    }
+
+    /// The adapter the system renders on, which is what to ask for in
+    /// "black screen" or "low FPS" reports. Returns `NotInitialized` after
+    /// [`shutdown`](Self::shutdown).
+    pub fn active_adapter_info(&self) -> Result<AdapterInfo, BlurErrorCode> {
+        if !self.is_valid() {
+            return Err(BlurErrorCode::NotInitialized);
+        }
+        active_adapter_info(self.handle)
+    }
+}
@@ -0,0 +1,105 @@
+use super::*;
+use std::time::Duration;
+
+/// Drives a strength fade-in started by [`BlurWindow::start_with_fade`].
+///
+/// Nothing runs in the background: call [`tick`](Self::tick) from your own
+/// frame or event loop until it reports completion.
+pub struct FadeHandle<'a> {
+    window: &'a BlurWindow,
+    duration: Duration,
+    elapsed: Duration,
+    target: f32,
+    // Re-applied at the end, since the fade itself is uniform
+    strength_gradient: Option<(f32, f32)>,
+    blur_xy: Option<(f32, f32)>,
+}
+
+impl<'a> FadeHandle<'a> {
+    pub(crate) fn new(window: &'a BlurWindow, duration: Duration, target: &WindowState) -> Self {
+        FadeHandle {
+            window,
+            duration,
+            elapsed: Duration::ZERO,
+            target: target.strength,
+            strength_gradient: target.strength_gradient,
+            blur_xy: target.blur_xy,
+        }
+    }
+
+    /// Strength the fade ends at (the window's cached strength when it started).
+    pub fn target(&self) -> f32 {
+        self.target
+    }
+
+    /// Advance the fade by `elapsed` (time since the previous tick) and push
+    /// the eased strength to the window. Returns `true` once the target is reached.
+    pub fn tick(&mut self, elapsed: Duration) -> Result<bool, BlurErrorCode> {
+        self.elapsed += elapsed;
+        if self.elapsed >= self.duration {
+            apply_strength(
+                self.window,
+                self.target,
+                self.strength_gradient,
+                self.blur_xy,
+            )?;
+            return Ok(true);
+        }
+
+        let eased = ease(self.elapsed, self.duration);
+        let code = unsafe { blur_set_strength(self.window.handle, self.target * eased) };
+        if code == BlurErrorCode::Ok {
+            Ok(false)
+        } else {
+            Err(code)
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+}
+
+/// Smoothstep of how far `elapsed` is into `duration`, the easing fades and
+/// profile transitions share.
+pub(crate) fn ease(elapsed: Duration, duration: Duration) -> f32 {
+    let t = elapsed.as_secs_f32() / duration.as_secs_f32();
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// End a fade or transition that pushed uniform strengths: apply the
+/// gradient if there is one, else the per-axis strength, else `strength`.
+pub(crate) fn apply_strength(
+    window: &BlurWindow,
+    strength: f32,
+    strength_gradient: Option<(f32, f32)>,
+    blur_xy: Option<(f32, f32)>,
+) -> Result<(), BlurErrorCode> {
+    match (strength_gradient, blur_xy) {
+        (Some((top, bottom)), _) => window.set_strength_gradient(top, bottom),
+        (None, Some((x, y))) => window.set_blur_xy(x, y),
+        (None, None) => window.set_strength(strength),
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fade_keeps_a_strength_gradient() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let window = WindowBuilder::new().build(&system).unwrap();
+        window.set_strength_gradient(0.8, 0.2).unwrap();
+
+        let mut fade = window.start_with_fade(Duration::from_millis(100)).unwrap();
+        assert!(!fade.tick(Duration::from_millis(50)).unwrap());
+        assert!(fade.tick(Duration::from_millis(50)).unwrap());
+        assert_eq!(
+            window.call_log().last().unwrap().to_string(),
+            "blur_set_strength_gradient(0.8, 0.2)"
+        );
+        assert_eq!(window.state().strength_gradient, Some((0.8, 0.2)));
+    }
+}
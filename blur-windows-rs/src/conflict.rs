@@ -0,0 +1,193 @@
+use super::*;
+use std::fmt;
+
+/// Noise intensity above which grain drowns out the Rain effect's drops.
+pub const RAIN_NOISE_LIMIT: f32 = 0.5;
+
+/// A combination of settings that renders incorrectly, see
+/// [`BlurWindow::validate_state`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Conflict {
+    /// The Rain effect with noise intensity above [`RAIN_NOISE_LIMIT`]: the
+    /// grain is applied on top of the refracted drops and swamps them.
+    RainWithHeavyNoise { noise_intensity: f32 },
+    /// A strength gradient while the blur follows the cursor: the gradient
+    /// is laid out over the whole window, so the circle's strength changes
+    /// as it moves instead of staying put.
+    GradientWithCursorFollow,
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Conflict::RainWithHeavyNoise { noise_intensity } => write!(
+                f,
+                "rain effect with noise intensity {} (at most {} is supported)",
+                noise_intensity, RAIN_NOISE_LIMIT
+            ),
+            Conflict::GradientWithCursorFollow => {
+                write!(f, "strength gradient with cursor-follow blur")
+            }
+        }
+    }
+}
+
+/// Every [`Conflict`] found in a [`WindowState`], never empty.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConflictError {
+    pub conflicts: Vec<Conflict>,
+}
+
+impl ConflictError {
+    /// `Err` listing the conflicts in `state`, or `Ok` if there are none.
+    pub fn check(state: &WindowState) -> Result<(), ConflictError> {
+        let mut conflicts = Vec::new();
+        if state.effect == BlurEffectType::Rain && state.noise.intensity > RAIN_NOISE_LIMIT {
+            conflicts.push(Conflict::RainWithHeavyNoise {
+                noise_intensity: state.noise.intensity,
+            });
+        }
+        if state.strength_gradient.is_some() && state.cursor_follow {
+            conflicts.push(Conflict::GradientWithCursorFollow);
+        }
+        if conflicts.is_empty() {
+            Ok(())
+        } else {
+            Err(ConflictError { conflicts })
+        }
+    }
+}
+
+impl fmt::Display for ConflictError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "incompatible settings: ")?;
+        for (i, conflict) in self.conflicts.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{}", conflict)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConflictError {}
+
+impl From<ConflictError> for BlurErrorCode {
+    fn from(_: ConflictError) -> Self {
+        BlurErrorCode::InvalidParameter
+    }
+}
+
+impl BlurWindow {
+    /// Check the cached settings for combinations that render incorrectly
+    /// (see [`Conflict`]). Individual setters don't check, since a conflict
+    /// is often passed through on the way to a valid state;
+    /// [`batch`](Self::batch) checks once at the end.
+    pub fn validate_state(&self) -> Result<(), ConflictError> {
+        ConflictError::check(&self.state())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KINDS: [ParamKind; 11] = [
+        ParamKind::Strength,
+        ParamKind::GaussianSigma,
+        ParamKind::BoxRadius,
+        ParamKind::KawaseIterations,
+        ParamKind::RadialAmount,
+        ParamKind::KernelRadius,
+        ParamKind::NoiseIntensity,
+        ParamKind::NoiseScale,
+        ParamKind::NoiseSpeed,
+        ParamKind::RainIntensity,
+        ParamKind::DirectionStrength,
+    ];
+
+    // Push `kind` to the top of its range, the way its setter would cache it
+    fn set_to_max(state: &mut WindowState, kind: ParamKind) {
+        let max = param_range(kind).max;
+        match kind {
+            ParamKind::Strength => state.strength = max,
+            ParamKind::GaussianSigma => {
+                state.effect = BlurEffectType::Gaussian;
+                state.blur_param = Some(max);
+            }
+            ParamKind::BoxRadius => {
+                state.effect = BlurEffectType::Box;
+                state.blur_param = Some(max);
+            }
+            ParamKind::KawaseIterations => {
+                state.effect = BlurEffectType::Kawase;
+                state.blur_param = Some(max);
+            }
+            ParamKind::RadialAmount => {
+                state.effect = BlurEffectType::Radial;
+                state.blur_param = Some(max);
+            }
+            ParamKind::KernelRadius => state.kernel_radius = max as u32,
+            ParamKind::NoiseIntensity => state.noise.intensity = max,
+            ParamKind::NoiseScale => state.noise.scale = max,
+            ParamKind::NoiseSpeed => state.noise.speed = max,
+            // Not cached; setting it means the Rain effect is on
+            ParamKind::RainIntensity => state.effect = BlurEffectType::Rain,
+            ParamKind::DirectionStrength => state.direction_strength = max,
+        }
+    }
+
+    #[test]
+    fn only_rain_and_heavy_noise_conflict() {
+        let base = WindowState::new(BlurRect {
+            left: 0,
+            top: 0,
+            right: 100,
+            bottom: 100,
+        });
+        assert_eq!(ConflictError::check(&base), Ok(()));
+
+        for first in KINDS {
+            for second in KINDS {
+                let mut state = base.clone();
+                set_to_max(&mut state, first);
+                set_to_max(&mut state, second);
+
+                let pair = [first, second];
+                let expected = if pair.contains(&ParamKind::RainIntensity)
+                    && pair.contains(&ParamKind::NoiseIntensity)
+                {
+                    Err(ConflictError {
+                        conflicts: vec![Conflict::RainWithHeavyNoise {
+                            noise_intensity: 1.0,
+                        }],
+                    })
+                } else {
+                    Ok(())
+                };
+                assert_eq!(
+                    ConflictError::check(&state),
+                    expected,
+                    "{:?} + {:?}",
+                    first,
+                    second
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn gradient_conflicts_with_cursor_follow() {
+        let mut state = WindowState::new(BlurRect::default());
+        state.strength_gradient = Some((1.0, 0.0));
+        assert_eq!(ConflictError::check(&state), Ok(()));
+        state.cursor_follow = true;
+        let error = ConflictError::check(&state).unwrap_err();
+        assert_eq!(error.conflicts, [Conflict::GradientWithCursorFollow]);
+        assert_eq!(
+            error.to_string(),
+            "incompatible settings: strength gradient with cursor-follow blur"
+        );
+    }
+}
@@ -0,0 +1,159 @@
+use super::*;
+
+/// Highest contrast ratio WCAG defines (white on black).
+pub const MAX_CONTRAST_RATIO: f32 = 21.0;
+
+impl Color {
+    /// WCAG 2 relative luminance of the color channels, 0.0 (black) to 1.0
+    /// (white). Alpha is ignored.
+    pub fn relative_luminance(&self) -> f32 {
+        fn linear(c: f32) -> f32 {
+            let c = c.clamp(0.0, 1.0);
+            if c <= 0.03928 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        }
+        0.2126 * linear(self.r) + 0.7152 * linear(self.g) + 0.0722 * linear(self.b)
+    }
+
+    /// WCAG contrast ratio between the two colors, 1.0 (identical luminance)
+    /// to 21.0 (black and white). Alpha is ignored.
+    pub fn contrast_ratio(&self, other: Color) -> f32 {
+        let (a, b) = (self.relative_luminance(), other.relative_luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    /// Adjust this tint so text in `against` reads with at least `min_ratio`
+    /// contrast (4.5 is WCAG AA for body text, 7.0 is AAA).
+    ///
+    /// The tint is first moved towards white or black, whichever is further
+    /// from `against`, until the tint itself meets the ratio. Its alpha is
+    /// then raised until the tint composited over a backdrop matching the
+    /// text, the worst case, still does. A tint that already qualifies is
+    /// returned unchanged, and a ratio the extreme cannot reach (e.g. 21 for
+    /// grey text) yields that extreme at full alpha. `min_ratio` is clamped to
+    /// 1.0 to [`MAX_CONTRAST_RATIO`].
+    pub fn ensure_contrast(self, against: Color, min_ratio: f32) -> Color {
+        let ratio = if min_ratio.is_nan() {
+            1.0
+        } else {
+            min_ratio.clamp(1.0, MAX_CONTRAST_RATIO)
+        };
+        let text = against.relative_luminance();
+        let white = Color::new(1.0, 1.0, 1.0, 1.0);
+        let black = Color::new(0.0, 0.0, 0.0, 1.0);
+        let lighten = white.contrast_ratio(against) >= black.contrast_ratio(against);
+
+        // Luminance the tint must reach (lighten) or stay under (darken)
+        let meets = |c: Color| {
+            let l = c.relative_luminance();
+            if lighten {
+                l + 0.05 >= ratio * (text + 0.05)
+            } else {
+                (text + 0.05) >= ratio * (l + 0.05)
+            }
+        };
+
+        let extreme = if lighten { white } else { black };
+        let t = bisect(|t| meets(mix(self, extreme, t)));
+        let rgb = mix(self, extreme, t);
+
+        // The shader blends the tint by alpha squared
+        let weight = self.a.clamp(0.0, 1.0).powi(2);
+        let w = bisect(|w| meets(mix(against, rgb, weight + (1.0 - weight) * w)));
+        let alpha = (weight + (1.0 - weight) * w).sqrt();
+        Color::new(rgb.r, rgb.g, rgb.b, alpha)
+    }
+}
+
+/// Channel-wise interpolation from `from` (t = 0) to `to` (t = 1), keeping
+/// `from`'s alpha.
+fn mix(from: Color, to: Color, t: f32) -> Color {
+    Color::new(
+        from.r + (to.r - from.r) * t,
+        from.g + (to.g - from.g) * t,
+        from.b + (to.b - from.b) * t,
+        from.a,
+    )
+}
+
+/// Smallest `t` in 0.0 to 1.0 for which the monotonic predicate holds,
+/// or 1.0 if it never does.
+fn bisect(pred: impl Fn(f32) -> bool) -> f32 {
+    if pred(0.0) {
+        return 0.0;
+    }
+    let (mut lo, mut hi) = (0.0f32, 1.0f32);
+    for _ in 0..24 {
+        let mid = (lo + hi) * 0.5;
+        if pred(mid) {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    hi
+}
+
+impl BlurWindow {
+    /// Adjust the current tint with [`Color::ensure_contrast`] so text in
+    /// `text_color` stays legible over any backdrop, and apply it. Replaces
+    /// tint layers like [`set_tint_color`](Self::set_tint_color).
+    ///
+    /// Returns the tint that was applied, or `InvalidParameter` if
+    /// `min_ratio` is not a finite number.
+    pub fn set_readable_tint(
+        &self,
+        text_color: Color,
+        min_ratio: f32,
+    ) -> Result<Color, BlurErrorCode> {
+        if !min_ratio.is_finite() {
+            return Err(BlurErrorCode::InvalidParameter);
+        }
+        let tint = self.state().tint.ensure_contrast(text_color, min_ratio);
+        self.set_tint_color(tint)?;
+        Ok(tint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WHITE: Color = Color::new(1.0, 1.0, 1.0, 1.0);
+    const BLACK: Color = Color::new(0.0, 0.0, 0.0, 1.0);
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 1e-4,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn black_and_white_have_the_maximum_ratio() {
+        assert_close(BLACK.contrast_ratio(WHITE), MAX_CONTRAST_RATIO);
+        assert_close(WHITE.contrast_ratio(BLACK), MAX_CONTRAST_RATIO);
+    }
+
+    #[test]
+    fn identical_colors_have_ratio_one() {
+        let grey = Color::new(0.5, 0.5, 0.5, 1.0);
+        assert_close(grey.contrast_ratio(grey), 1.0);
+        // Alpha is ignored
+        assert_close(grey.contrast_ratio(grey.with_alpha(0.2)), 1.0);
+    }
+
+    #[test]
+    fn ensure_contrast_reaches_the_ratio() {
+        let tint = Color::new(0.4, 0.4, 0.4, 0.5);
+        let adjusted = tint.ensure_contrast(BLACK, 7.0);
+        assert!(adjusted.contrast_ratio(BLACK) >= 7.0 - 1e-3);
+        // A tint that already qualifies is left alone
+        assert_eq!(WHITE.ensure_contrast(BLACK, 4.5), WHITE);
+    }
+}
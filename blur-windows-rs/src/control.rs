@@ -0,0 +1,368 @@
+use super::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::{CloseHandle, ERROR_PIPE_CONNECTED, HANDLE};
+use windows::Win32::Storage::FileSystem::{
+    FlushFileBuffers, ReadFile, WriteFile, PIPE_ACCESS_DUPLEX,
+};
+use windows::Win32::System::Pipes::{
+    ConnectNamedPipe, CreateNamedPipeW, DisconnectNamedPipe, PIPE_READMODE_MESSAGE,
+    PIPE_TYPE_MESSAGE, PIPE_WAIT,
+};
+
+/// Pipe used when [`BlurWindow::serve_control`] is given this name.
+pub const DEFAULT_CONTROL_PIPE: &str = r"\\.\pipe\blur-windows";
+
+const PIPE_PREFIX: &str = r"\\.\pipe\";
+
+/// Largest command message, in bytes.
+const MAX_MESSAGE: usize = 4096;
+
+/// How long the pipe thread waits for [`ControlServer::poll`] to apply a command.
+const REPLY_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A command accepted over the control pipe, e.g.
+/// `{"cmd": "set_strength", "value": 0.7}` or `{"cmd": "pause"}`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "cmd", content = "value", rename_all = "snake_case")]
+pub enum ControlCommand {
+    SetStrength(f32),
+    SetBlurParam(f32),
+    SetKernelRadius(u32),
+    SetEffectType(BlurEffectType),
+    /// `[r, g, b, a]`, each 0.0 to 1.0.
+    SetTintColor([f32; 4]),
+    /// `null` returns to the preset's rate.
+    SetTargetFps(Option<u32>),
+    Pause,
+    Resume,
+}
+
+impl ControlCommand {
+    /// The parameter the command changes on `window`, for
+    /// [`ControlServer::lock_param`]; `None` for commands that change none.
+    fn param(&self, window: &BlurWindow) -> Option<ParamKind> {
+        match self {
+            ControlCommand::SetStrength(_) => Some(ParamKind::Strength),
+            ControlCommand::SetBlurParam(_) => ParamKind::blur_param(window.state().effect),
+            ControlCommand::SetKernelRadius(_) => Some(ParamKind::KernelRadius),
+            _ => None,
+        }
+    }
+
+    fn apply(&self, window: &BlurWindow) -> Result<(), BlurErrorCode> {
+        match *self {
+            ControlCommand::SetStrength(strength) => window.set_strength(strength),
+            ControlCommand::SetBlurParam(param) => window.set_blur_param(param),
+            ControlCommand::SetKernelRadius(radius) => window.set_kernel_radius(radius),
+            ControlCommand::SetEffectType(effect) => window.set_effect_type(effect),
+            ControlCommand::SetTintColor([r, g, b, a]) => {
+                window.set_tint_color(Color { r, g, b, a })
+            }
+            ControlCommand::SetTargetFps(fps) => window.set_target_fps(fps),
+            ControlCommand::Pause => window.pause(),
+            ControlCommand::Resume => window.resume(),
+        }
+    }
+}
+
+/// Reply written back for every message: `{"ok": true}` or
+/// `{"ok": false, "error": "..."}`.
+#[derive(Debug, Serialize)]
+struct ControlResponse {
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl ControlResponse {
+    fn from_result(result: Result<(), String>) -> Self {
+        match result {
+            Ok(()) => ControlResponse {
+                ok: true,
+                error: None,
+            },
+            Err(e) => ControlResponse {
+                ok: false,
+                error: Some(e),
+            },
+        }
+    }
+}
+
+type Request = (ControlCommand, Sender<Result<(), String>>);
+
+struct Pipe(HANDLE);
+
+// The handle is only used by the pipe thread after creation.
+unsafe impl Send for Pipe {}
+
+impl Pipe {
+    fn create(name: &[u16]) -> Option<Pipe> {
+        let handle = unsafe {
+            CreateNamedPipeW(
+                PCWSTR(name.as_ptr()),
+                PIPE_ACCESS_DUPLEX,
+                PIPE_TYPE_MESSAGE | PIPE_READMODE_MESSAGE | PIPE_WAIT,
+                1,
+                MAX_MESSAGE as u32,
+                MAX_MESSAGE as u32,
+                0,
+                None,
+            )
+        };
+        if handle.is_invalid() {
+            None
+        } else {
+            Some(Pipe(handle))
+        }
+    }
+
+    fn connect(&self) -> bool {
+        match unsafe { ConnectNamedPipe(self.0, None) } {
+            Ok(()) => true,
+            // The client connected between create and connect
+            Err(e) => e.code() == ERROR_PIPE_CONNECTED.to_hresult(),
+        }
+    }
+
+    fn read(&self, buffer: &mut [u8]) -> Option<usize> {
+        let mut read = 0u32;
+        unsafe { ReadFile(self.0, Some(buffer), Some(&mut read), None) }.ok()?;
+        Some(read as usize)
+    }
+
+    fn write(&self, message: &[u8]) -> bool {
+        let mut written = 0u32;
+        unsafe { WriteFile(self.0, Some(message), Some(&mut written), None) }.is_ok()
+    }
+}
+
+impl Drop for Pipe {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = FlushFileBuffers(self.0);
+            let _ = DisconnectNamedPipe(self.0);
+            let _ = CloseHandle(self.0);
+        }
+    }
+}
+
+/// Named-pipe server that lets an external tool tune a [`BlurWindow`] live.
+///
+/// The pipe is served on a background thread, but the window is not
+/// thread-safe, so commands are queued until [`poll`](Self::poll) applies them
+/// on the window's thread. Call it from the message loop or once per frame; a
+/// command that isn't applied within a second is answered with a timeout
+/// error. One client is served at a time.
+///
+/// Parameters can be [locked](Self::lock_param) so the app keeps them out of
+/// the client's reach.
+///
+/// Dropping the server stops accepting connections. A client that is still
+/// connected is disconnected after its next message.
+pub struct ControlServer<'a> {
+    window: &'a BlurWindow,
+    pipe_name: String,
+    requests: Receiver<Request>,
+    stop: Arc<AtomicBool>,
+    locked: Mutex<HashSet<ParamKind>>,
+}
+
+impl ControlServer<'_> {
+    /// Full pipe path, e.g. `\\.\pipe\blur-windows`.
+    pub fn pipe_name(&self) -> &str {
+        &self.pipe_name
+    }
+
+    /// Reject commands that change `param` with a `"<param> is locked"`
+    /// error response, leaving the window untouched. Only this server's
+    /// clients are affected: the app's own calls still change it.
+    ///
+    /// `SetBlurParam` counts as changing the current effect's
+    /// [`ParamKind::blur_param`], checked when the command is applied.
+    /// Commands that change no [`ParamKind`], such as pausing or switching
+    /// effects, can't be locked.
+    pub fn lock_param(&self, param: ParamKind) {
+        self.locked
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(param);
+    }
+
+    pub fn unlock_param(&self, param: ParamKind) {
+        self.locked
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&param);
+    }
+
+    pub fn is_param_locked(&self, param: ParamKind) -> bool {
+        self.locked
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains(&param)
+    }
+
+    /// Apply queued commands to the window and answer them. Returns how many
+    /// commands were processed, rejected ones included.
+    pub fn poll(&self) -> usize {
+        let mut count = 0;
+        while let Ok((command, reply)) = self.requests.try_recv() {
+            let result = match command.param(self.window) {
+                Some(param) if self.is_param_locked(param) => Err(format!("{:?} is locked", param)),
+                _ => command
+                    .apply(self.window)
+                    .map_err(|code| format!("{:?}", code)),
+            };
+            let _ = reply.send(result);
+            count += 1;
+        }
+        count
+    }
+}
+
+impl Drop for ControlServer<'_> {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        // Wake a pipe thread blocked waiting for a client. If a client is
+        // connected this fails, and the thread exits after its next message.
+        let _ = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&self.pipe_name);
+    }
+}
+
+impl BlurWindow {
+    /// Serve JSON tuning commands (see [`ControlCommand`]) for this window on
+    /// a named pipe. `pipe_name` is a full path or a bare name, which is
+    /// placed under `\\.\pipe\`.
+    ///
+    /// Returns `InvalidParameter` for an empty name or one that is already
+    /// being served.
+    pub fn serve_control(&self, pipe_name: &str) -> Result<ControlServer<'_>, BlurErrorCode> {
+        let full_name = if pipe_name.starts_with(PIPE_PREFIX) {
+            pipe_name.to_string()
+        } else {
+            format!("{}{}", PIPE_PREFIX, pipe_name)
+        };
+        if full_name.len() == PIPE_PREFIX.len() || full_name.contains('\0') {
+            return Err(BlurErrorCode::InvalidParameter);
+        }
+
+        let wide: Vec<u16> = full_name.encode_utf16().chain(Some(0)).collect();
+        // Create the first instance here so a taken name is reported to the caller
+        let first = Pipe::create(&wide).ok_or(BlurErrorCode::InvalidParameter)?;
+
+        let (sender, requests) = mpsc::channel::<Request>();
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+
+        thread::Builder::new()
+            .name("blur-control".into())
+            .spawn(move || serve(first, wide, sender, thread_stop))
+            .map_err(|_| BlurErrorCode::Unknown)?;
+
+        Ok(ControlServer {
+            window: self,
+            pipe_name: full_name,
+            requests,
+            stop,
+            locked: Mutex::new(HashSet::new()),
+        })
+    }
+}
+
+fn serve(first: Pipe, name: Vec<u16>, sender: Sender<Request>, stop: Arc<AtomicBool>) {
+    let mut pipe = Some(first);
+    while let Some(current) = pipe.take().or_else(|| Pipe::create(&name)) {
+        if !current.connect() || stop.load(Ordering::SeqCst) {
+            break;
+        }
+
+        let mut buffer = [0u8; MAX_MESSAGE];
+        while let Some(len) = current.read(&mut buffer) {
+            let response = ControlResponse::from_result(handle_message(&buffer[..len], &sender));
+            let Ok(json) = serde_json::to_string(&response) else {
+                break;
+            };
+            if !current.write(json.as_bytes()) || stop.load(Ordering::SeqCst) {
+                break;
+            }
+        }
+
+        if stop.load(Ordering::SeqCst) {
+            break;
+        }
+    }
+}
+
+fn handle_message(message: &[u8], sender: &Sender<Request>) -> Result<(), String> {
+    let command: ControlCommand =
+        serde_json::from_slice(message).map_err(|e| format!("Invalid command: {}", e))?;
+
+    let (reply_tx, reply_rx) = mpsc::channel();
+    sender
+        .send((command, reply_tx))
+        .map_err(|_| "Control server stopped".to_string())?;
+    reply_rx
+        .recv_timeout(REPLY_TIMEOUT)
+        .map_err(|_| "Timed out waiting for the window thread".to_string())?
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+
+    // A server without a pipe thread; requests are queued straight into it
+    fn server(window: &BlurWindow) -> (ControlServer<'_>, Sender<Request>) {
+        let (sender, requests) = mpsc::channel();
+        let server = ControlServer {
+            window,
+            pipe_name: String::new(),
+            requests,
+            stop: Arc::new(AtomicBool::new(false)),
+            locked: Mutex::new(HashSet::new()),
+        };
+        (server, sender)
+    }
+
+    fn send(
+        server: &ControlServer,
+        sender: &Sender<Request>,
+        command: ControlCommand,
+    ) -> Result<(), String> {
+        let (reply_tx, reply_rx) = mpsc::channel();
+        sender.send((command, reply_tx)).unwrap();
+        assert_eq!(server.poll(), 1);
+        reply_rx.recv().unwrap()
+    }
+
+    #[test]
+    fn locked_params_are_rejected_until_unlocked() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let window = WindowBuilder::new().build(&system).unwrap();
+        let (server, sender) = server(&window);
+
+        server.lock_param(ParamKind::Strength);
+        let locked = send(&server, &sender, ControlCommand::SetStrength(0.3));
+        assert_eq!(locked, Err("Strength is locked".to_string()));
+        assert!(window
+            .call_log()
+            .iter()
+            .all(|call| call.function != "blur_set_strength"));
+
+        server.unlock_param(ParamKind::Strength);
+        let unlocked = send(&server, &sender, ControlCommand::SetStrength(0.3));
+        assert_eq!(unlocked, Ok(()));
+        assert_eq!(window.state().strength, 0.3);
+    }
+}
@@ -0,0 +1,621 @@
+use super::*;
+use std::fmt;
+use std::time::Duration;
+
+/// One field that differs between two [`WindowState`]s, with the value
+/// before (`old`) and after (`new`).
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldChange {
+    Preset {
+        old: BlurQualityPreset,
+        new: BlurQualityPreset,
+    },
+    ActivePreset {
+        old: BlurQualityPreset,
+        new: BlurQualityPreset,
+    },
+    TargetFps {
+        old: Option<u32>,
+        new: Option<u32>,
+    },
+    CaptureInterval {
+        old: Duration,
+        new: Duration,
+    },
+    MinFrameInterval {
+        old: Duration,
+        new: Duration,
+    },
+    BufferCount {
+        old: u32,
+        new: u32,
+    },
+    Bounds {
+        old: BlurRect,
+        new: BlurRect,
+    },
+    ActiveRegion {
+        old: Option<BlurRect>,
+        new: Option<BlurRect>,
+    },
+    CursorFollow {
+        old: bool,
+        new: bool,
+    },
+    CursorFollowRadius {
+        old: f32,
+        new: f32,
+    },
+    Effect {
+        old: BlurEffectType,
+        new: BlurEffectType,
+    },
+    #[cfg(feature = "serde")]
+    EffectLayers {
+        old: Vec<PipelineStage>,
+        new: Vec<PipelineStage>,
+    },
+    Strength {
+        old: f32,
+        new: f32,
+    },
+    StrengthGradient {
+        old: Option<(f32, f32)>,
+        new: Option<(f32, f32)>,
+    },
+    BlurXy {
+        old: Option<(f32, f32)>,
+        new: Option<(f32, f32)>,
+    },
+    BlurParam {
+        old: Option<f32>,
+        new: Option<f32>,
+    },
+    KernelRadius {
+        old: u32,
+        new: u32,
+    },
+    Downsample {
+        old: (u32, u32),
+        new: (u32, u32),
+    },
+    EdgeAa {
+        old: u32,
+        new: u32,
+    },
+    EdgeFeather {
+        old: f32,
+        new: f32,
+    },
+    CustomShader {
+        old: Option<String>,
+        new: Option<String>,
+    },
+    DirectionAngle {
+        old: f32,
+        new: f32,
+    },
+    DirectionStrength {
+        old: f32,
+        new: f32,
+    },
+    Tint {
+        old: Color,
+        new: Color,
+    },
+    TintLayers {
+        old: Vec<Color>,
+        new: Vec<Color>,
+    },
+    RegionEffects {
+        old: Vec<RegionEffect>,
+        new: Vec<RegionEffect>,
+    },
+    FallbackColor {
+        old: Color,
+        new: Color,
+    },
+    ToneMapping {
+        old: ToneMapping,
+        new: ToneMapping,
+    },
+    CaptureSource {
+        old: Option<isize>,
+        new: Option<isize>,
+    },
+    SourceTexture {
+        old: Option<isize>,
+        new: Option<isize>,
+    },
+    NoiseIntensity {
+        old: f32,
+        new: f32,
+    },
+    NoiseScale {
+        old: f32,
+        new: f32,
+    },
+    NoiseSpeed {
+        old: f32,
+        new: f32,
+    },
+    NoiseType {
+        old: NoiseType,
+        new: NoiseType,
+    },
+    NoiseBlendMode {
+        old: NoiseBlendMode,
+        new: NoiseBlendMode,
+    },
+    NoiseColor {
+        old: Option<Color>,
+        new: Option<Color>,
+    },
+    NoiseSeed {
+        old: Option<u32>,
+        new: Option<u32>,
+    },
+    TimeMode {
+        old: TimeMode,
+        new: TimeMode,
+    },
+    TemporalMode {
+        old: TemporalMode,
+        new: TemporalMode,
+    },
+    Running {
+        old: bool,
+        new: bool,
+    },
+    Paused {
+        old: bool,
+        new: bool,
+    },
+    Frozen {
+        old: bool,
+        new: bool,
+    },
+    Visible {
+        old: bool,
+        new: bool,
+    },
+    TaskbarRelation {
+        old: Option<TaskbarRelation>,
+        new: Option<TaskbarRelation>,
+    },
+    DebugOverlay {
+        old: bool,
+        new: bool,
+    },
+}
+
+impl FieldChange {
+    /// The `WindowState` field name (noise fields as `noise.<name>`).
+    pub fn field(&self) -> &'static str {
+        match self {
+            FieldChange::Preset { .. } => "preset",
+            FieldChange::ActivePreset { .. } => "active_preset",
+            FieldChange::TargetFps { .. } => "target_fps",
+            FieldChange::CaptureInterval { .. } => "capture_interval",
+            FieldChange::MinFrameInterval { .. } => "min_frame_interval",
+            FieldChange::BufferCount { .. } => "buffer_count",
+            FieldChange::Bounds { .. } => "bounds",
+            FieldChange::ActiveRegion { .. } => "active_region",
+            FieldChange::CursorFollow { .. } => "cursor_follow",
+            FieldChange::CursorFollowRadius { .. } => "cursor_follow_radius",
+            FieldChange::Effect { .. } => "effect",
+            #[cfg(feature = "serde")]
+            FieldChange::EffectLayers { .. } => "effect_layers",
+            FieldChange::Strength { .. } => "strength",
+            FieldChange::StrengthGradient { .. } => "strength_gradient",
+            FieldChange::BlurXy { .. } => "blur_xy",
+            FieldChange::BlurParam { .. } => "blur_param",
+            FieldChange::KernelRadius { .. } => "kernel_radius",
+            FieldChange::Downsample { .. } => "downsample",
+            FieldChange::EdgeAa { .. } => "edge_aa",
+            FieldChange::EdgeFeather { .. } => "edge_feather",
+            FieldChange::CustomShader { .. } => "custom_shader",
+            FieldChange::DirectionAngle { .. } => "direction_angle",
+            FieldChange::DirectionStrength { .. } => "direction_strength",
+            FieldChange::Tint { .. } => "tint",
+            FieldChange::TintLayers { .. } => "tint_layers",
+            FieldChange::RegionEffects { .. } => "region_effects",
+            FieldChange::FallbackColor { .. } => "fallback_color",
+            FieldChange::ToneMapping { .. } => "tone_mapping",
+            FieldChange::CaptureSource { .. } => "capture_source",
+            FieldChange::SourceTexture { .. } => "source_texture",
+            FieldChange::NoiseIntensity { .. } => "noise.intensity",
+            FieldChange::NoiseScale { .. } => "noise.scale",
+            FieldChange::NoiseSpeed { .. } => "noise.speed",
+            FieldChange::NoiseType { .. } => "noise.noise_type",
+            FieldChange::NoiseBlendMode { .. } => "noise.blend_mode",
+            FieldChange::NoiseColor { .. } => "noise.color",
+            FieldChange::NoiseSeed { .. } => "noise.seed",
+            FieldChange::TimeMode { .. } => "time_mode",
+            FieldChange::TemporalMode { .. } => "temporal_mode",
+            FieldChange::Running { .. } => "running",
+            FieldChange::Paused { .. } => "paused",
+            FieldChange::Frozen { .. } => "frozen",
+            FieldChange::Visible { .. } => "visible",
+            FieldChange::TaskbarRelation { .. } => "taskbar_relation",
+            FieldChange::DebugOverlay { .. } => "debug_overlay",
+        }
+    }
+}
+
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.field())?;
+        match self {
+            FieldChange::Preset { old, new } | FieldChange::ActivePreset { old, new } => {
+                write!(f, "{:?} -> {:?}", old, new)
+            }
+            FieldChange::TargetFps { old, new } => write!(
+                f,
+                "{} -> {}",
+                old.map_or("preset".into(), |v| v.to_string()),
+                new.map_or("preset".into(), |v| v.to_string()),
+            ),
+            FieldChange::CaptureInterval { old, new } => {
+                write!(f, "{}ms -> {}ms", old.as_millis(), new.as_millis())
+            }
+            FieldChange::MinFrameInterval { old, new } => {
+                write!(f, "{}ms -> {}ms", old.as_millis(), new.as_millis())
+            }
+            FieldChange::BufferCount { old, new } => write!(f, "{} -> {}", old, new),
+            FieldChange::Bounds { old, new } => {
+                write!(f, "{} -> {}", DisplayRect(old), DisplayRect(new))
+            }
+            FieldChange::ActiveRegion { old, new } => write!(
+                f,
+                "{} -> {}",
+                old.as_ref()
+                    .map_or("full window".into(), |r| DisplayRect(r).to_string()),
+                new.as_ref()
+                    .map_or("full window".into(), |r| DisplayRect(r).to_string()),
+            ),
+            FieldChange::CursorFollowRadius { old, new } => write!(f, "{}px -> {}px", old, new),
+            FieldChange::Effect { old, new } => write!(f, "{:?} -> {:?}", old, new),
+            #[cfg(feature = "serde")]
+            FieldChange::EffectLayers { old, new } => {
+                let show = |layers: &[PipelineStage]| {
+                    layers
+                        .iter()
+                        .map(|l| format!("{:?}", l.effect))
+                        .collect::<Vec<_>>()
+                        .join(" + ")
+                };
+                write!(f, "[{}] -> [{}]", show(old), show(new))
+            }
+            FieldChange::Strength { old, new }
+            | FieldChange::DirectionStrength { old, new }
+            | FieldChange::NoiseIntensity { old, new }
+            | FieldChange::NoiseScale { old, new }
+            | FieldChange::NoiseSpeed { old, new } => write!(f, "{} -> {}", old, new),
+            FieldChange::StrengthGradient { old, new } => {
+                let show = |g: &Option<(f32, f32)>| {
+                    g.map_or("uniform".into(), |(top, bottom)| {
+                        format!("{} to {}", top, bottom)
+                    })
+                };
+                write!(f, "{} -> {}", show(old), show(new))
+            }
+            FieldChange::BlurXy { old, new } => {
+                let show = |xy: &Option<(f32, f32)>| {
+                    xy.map_or("uniform".into(), |(x, y)| format!("x {} y {}", x, y))
+                };
+                write!(f, "{} -> {}", show(old), show(new))
+            }
+            FieldChange::BlurParam { old, new } => write!(
+                f,
+                "{} -> {}",
+                old.map_or("default".into(), |v| v.to_string()),
+                new.map_or("default".into(), |v| v.to_string()),
+            ),
+            FieldChange::KernelRadius { old, new } => write!(f, "{}px -> {}px", old, new),
+            FieldChange::Downsample { old, new } => {
+                write!(f, "{}x{} -> {}x{}", old.0, old.1, new.0, new.1)
+            }
+            FieldChange::EdgeAa { old, new } => write!(f, "{}x -> {}x", old, new),
+            FieldChange::EdgeFeather { old, new } => write!(f, "{}px -> {}px", old, new),
+            FieldChange::CustomShader { old, new } => {
+                // The source itself is too long to show
+                let describe = |hlsl: &Option<String>| {
+                    hlsl.as_ref().map_or_else(
+                        || "none".to_string(),
+                        |hlsl| format!("{} bytes", hlsl.len()),
+                    )
+                };
+                write!(f, "{} -> {}", describe(old), describe(new))
+            }
+            FieldChange::DirectionAngle { old, new } => write!(f, "{}° -> {}°", old, new),
+            FieldChange::Tint { old, new } | FieldChange::FallbackColor { old, new } => {
+                write!(f, "{} -> {}", DisplayColor(old), DisplayColor(new))
+            }
+            FieldChange::CaptureSource { old, new } => write!(
+                f,
+                "{} -> {}",
+                old.map_or("desktop".into(), |h| format!("{:#x}", h)),
+                new.map_or("desktop".into(), |h| format!("{:#x}", h)),
+            ),
+            FieldChange::SourceTexture { old, new } => write!(
+                f,
+                "{} -> {}",
+                old.map_or("capture".into(), |h| format!("{:#x}", h)),
+                new.map_or("capture".into(), |h| format!("{:#x}", h)),
+            ),
+            FieldChange::TintLayers { old, new } => {
+                write!(f, "{} layers -> {} layers", old.len(), new.len())
+            }
+            FieldChange::RegionEffects { old, new } => {
+                write!(f, "{} regions -> {} regions", old.len(), new.len())
+            }
+            FieldChange::NoiseType { old, new } => write!(f, "{:?} -> {:?}", old, new),
+            FieldChange::ToneMapping { old, new } => write!(f, "{:?} -> {:?}", old, new),
+            FieldChange::NoiseBlendMode { old, new } => write!(f, "{:?} -> {:?}", old, new),
+            FieldChange::NoiseColor { old, new } => write!(
+                f,
+                "{} -> {}",
+                old.as_ref()
+                    .map_or("monochrome".into(), |c| DisplayColor(c).to_string()),
+                new.as_ref()
+                    .map_or("monochrome".into(), |c| DisplayColor(c).to_string()),
+            ),
+            FieldChange::NoiseSeed { old, new } => write!(
+                f,
+                "{} -> {}",
+                old.map_or("unset".into(), |v| v.to_string()),
+                new.map_or("unset".into(), |v| v.to_string()),
+            ),
+            FieldChange::TimeMode { old, new } => write!(f, "{:?} -> {:?}", old, new),
+            FieldChange::TemporalMode { old, new } => write!(f, "{:?} -> {:?}", old, new),
+            FieldChange::TaskbarRelation { old, new } => write!(
+                f,
+                "{} -> {}",
+                old.map_or("unset".into(), |v| format!("{:?}", v)),
+                new.map_or("unset".into(), |v| format!("{:?}", v)),
+            ),
+            FieldChange::CursorFollow { old, new }
+            | FieldChange::Running { old, new }
+            | FieldChange::Paused { old, new }
+            | FieldChange::Frozen { old, new }
+            | FieldChange::Visible { old, new }
+            | FieldChange::DebugOverlay { old, new } => {
+                write!(f, "{} -> {}", old, new)
+            }
+        }
+    }
+}
+
+struct DisplayRect<'a>(&'a BlurRect);
+
+impl fmt::Display for DisplayRect<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let r = self.0;
+        write!(
+            f,
+            "({}, {}, {}x{})",
+            r.left,
+            r.top,
+            r.right - r.left,
+            r.bottom - r.top
+        )
+    }
+}
+
+struct DisplayColor<'a>(&'a Color);
+
+impl fmt::Display for DisplayColor<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = self.0;
+        write!(f, "rgba({}, {}, {}, {})", c.r, c.g, c.b, c.a)
+    }
+}
+
+impl WindowState {
+    /// Fields that differ from `self` (old) to `other` (new), in declaration order.
+    ///
+    /// Useful for "modified" indicators against a preset baseline; an empty
+    /// result means the two states are equal.
+    pub fn diff(&self, other: &WindowState) -> Vec<FieldChange> {
+        let mut changes = Vec::new();
+
+        macro_rules! compare {
+            ($variant:ident, $($field:ident).+) => {
+                if self.$($field).+ != other.$($field).+ {
+                    changes.push(FieldChange::$variant {
+                        old: self.$($field).+.clone(),
+                        new: other.$($field).+.clone(),
+                    });
+                }
+            };
+        }
+
+        compare!(Preset, preset);
+        compare!(ActivePreset, active_preset);
+        compare!(TargetFps, target_fps);
+        compare!(CaptureInterval, capture_interval);
+        compare!(MinFrameInterval, min_frame_interval);
+        compare!(BufferCount, buffer_count);
+        compare!(Bounds, bounds);
+        compare!(ActiveRegion, active_region);
+        compare!(CursorFollow, cursor_follow);
+        compare!(CursorFollowRadius, cursor_follow_radius);
+        compare!(Effect, effect);
+        #[cfg(feature = "serde")]
+        compare!(EffectLayers, effect_layers);
+        compare!(Strength, strength);
+        compare!(StrengthGradient, strength_gradient);
+        compare!(BlurXy, blur_xy);
+        compare!(BlurParam, blur_param);
+        compare!(KernelRadius, kernel_radius);
+        compare!(Downsample, downsample);
+        compare!(EdgeAa, edge_aa);
+        compare!(EdgeFeather, edge_feather);
+        compare!(CustomShader, custom_shader);
+        compare!(DirectionAngle, direction_angle);
+        compare!(DirectionStrength, direction_strength);
+        compare!(Tint, tint);
+        compare!(TintLayers, tint_layers);
+        compare!(RegionEffects, region_effects);
+        compare!(FallbackColor, fallback_color);
+        compare!(ToneMapping, tone_mapping);
+        compare!(CaptureSource, capture_source);
+        compare!(SourceTexture, source_texture);
+        compare!(NoiseIntensity, noise.intensity);
+        compare!(NoiseScale, noise.scale);
+        compare!(NoiseSpeed, noise.speed);
+        compare!(NoiseType, noise.noise_type);
+        compare!(NoiseBlendMode, noise.blend_mode);
+        compare!(NoiseColor, noise.color);
+        compare!(NoiseSeed, noise.seed);
+        compare!(TimeMode, time_mode);
+        compare!(TemporalMode, temporal_mode);
+        compare!(Running, running);
+        compare!(Paused, paused);
+        compare!(Frozen, frozen);
+        compare!(Visible, visible);
+        compare!(TaskbarRelation, taskbar_relation);
+        compare!(DebugOverlay, debug_overlay);
+
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_reports_every_field() {
+        let old = WindowState::new(BlurRect {
+            left: 0,
+            top: 0,
+            right: 100,
+            bottom: 100,
+        });
+        let color = Color::new(0.1, 0.2, 0.3, 0.4);
+        // No `..old`: a new WindowState field doesn't compile here until it's
+        // given a changed value, and then fails the count below until `diff`
+        // compares it
+        let new = WindowState {
+            preset: BlurQualityPreset::High,
+            active_preset: BlurQualityPreset::Performance,
+            target_fps: Some(30),
+            capture_interval: Duration::from_millis(100),
+            min_frame_interval: Duration::from_millis(16),
+            buffer_count: 3,
+            bounds: BlurRect {
+                left: 10,
+                top: 10,
+                right: 200,
+                bottom: 200,
+            },
+            active_region: Some(BlurRect {
+                left: 0,
+                top: 0,
+                right: 50,
+                bottom: 50,
+            }),
+            cursor_follow: true,
+            cursor_follow_radius: 50.0,
+            effect: BlurEffectType::Kawase,
+            #[cfg(feature = "serde")]
+            effect_layers: vec![PipelineStage {
+                effect: BlurEffectType::Box,
+                params: StageParams::default(),
+            }],
+            strength: 0.5,
+            strength_gradient: Some((0.2, 0.8)),
+            blur_xy: Some((0.3, 0.6)),
+            blur_param: Some(4.0),
+            kernel_radius: 8,
+            downsample: (2, 2),
+            edge_aa: 4,
+            edge_feather: 2.0,
+            custom_shader: Some("float4 main() : SV_Target { return 0; }".into()),
+            direction_angle: 45.0,
+            direction_strength: 0.5,
+            tint: color,
+            tint_layers: vec![color],
+            region_effects: vec![RegionEffect {
+                rect: old.bounds,
+                effect: BlurEffectType::Box,
+            }],
+            fallback_color: color,
+            tone_mapping: ToneMapping::Reinhard,
+            capture_source: Some(0x1234),
+            source_texture: Some(0x5678),
+            noise: NoiseParams {
+                intensity: 0.1,
+                scale: 50.0,
+                speed: 2.0,
+                noise_type: NoiseType::Perlin,
+                blend_mode: NoiseBlendMode::Overlay,
+                color: Some(color),
+                seed: Some(7),
+            },
+            time_mode: TimeMode::Manual,
+            temporal_mode: TemporalMode::MotionOnly,
+            running: true,
+            paused: true,
+            frozen: true,
+            visible: false,
+            taskbar_relation: Some(TaskbarRelation::Below),
+            debug_overlay: true,
+        };
+
+        let fields: Vec<&str> = old.diff(&new).iter().map(FieldChange::field).collect();
+        let expected = if cfg!(feature = "serde") { 45 } else { 44 };
+        assert_eq!(fields.len(), expected, "{:?}", fields);
+        let unique: std::collections::HashSet<&str> = fields.iter().copied().collect();
+        assert_eq!(unique.len(), fields.len());
+        assert!(new.diff(&new).is_empty());
+    }
+
+    #[test]
+    fn diff_lists_several_changes_in_order() {
+        let old = WindowState::new(BlurRect {
+            left: 0,
+            top: 0,
+            right: 100,
+            bottom: 100,
+        });
+        let mut new = old.clone();
+        new.strength = 0.5;
+        new.kernel_radius = 12;
+        new.noise.intensity = 0.25;
+        new.paused = true;
+
+        assert_eq!(
+            old.diff(&new),
+            vec![
+                FieldChange::Strength { old: 1.0, new: 0.5 },
+                FieldChange::KernelRadius { old: 0, new: 12 },
+                FieldChange::NoiseIntensity {
+                    old: 0.0,
+                    new: 0.25
+                },
+                FieldChange::Paused {
+                    old: false,
+                    new: true
+                },
+            ]
+        );
+        let lines: Vec<String> = old.diff(&new).iter().map(ToString::to_string).collect();
+        assert_eq!(
+            lines,
+            [
+                "strength: 1 -> 0.5",
+                "kernel_radius: 0px -> 12px",
+                "noise.intensity: 0 -> 0.25",
+                "paused: false -> true"
+            ]
+        );
+        // Reversed, old and new swap
+        assert_eq!(
+            new.diff(&old)[0],
+            FieldChange::Strength { old: 0.5, new: 1.0 }
+        );
+    }
+}
@@ -0,0 +1,167 @@
+use super::*;
+use std::ops::{BitAnd, BitOr, BitOrAssign, Not, Sub};
+
+/// Extended window styles (`WS_EX_*`) of a blur window, see
+/// [`BlurWindow::set_ex_style`].
+///
+/// Only the styles relevant to how the overlay composites have named
+/// constants; other bits are kept as-is by [`BlurWindow::update_ex_style`].
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ExStyleFlags(u32);
+
+impl ExStyleFlags {
+    /// Always on top. Safe to toggle; applied through the z-order.
+    pub const TOPMOST: ExStyleFlags = ExStyleFlags(0x0000_0008);
+    /// Mouse input passes through (click-through). Safe to toggle.
+    pub const TRANSPARENT: ExStyleFlags = ExStyleFlags(0x0000_0020);
+    /// Hidden from the taskbar and Alt+Tab. Takes effect the next time the
+    /// window is shown.
+    pub const TOOLWINDOW: ExStyleFlags = ExStyleFlags(0x0000_0080);
+    /// Set at creation for the layered (`UpdateLayeredWindow`) backend.
+    /// Changing it requires recreating the window.
+    pub const LAYERED: ExStyleFlags = ExStyleFlags(0x0008_0000);
+    /// Set at creation for the DirectComposition backend. Changing it
+    /// requires recreating the window.
+    pub const NOREDIRECTIONBITMAP: ExStyleFlags = ExStyleFlags(0x0020_0000);
+    /// Never takes focus when clicked. Safe to toggle.
+    pub const NOACTIVATE: ExStyleFlags = ExStyleFlags(0x0800_0000);
+
+    pub const fn empty() -> Self {
+        ExStyleFlags(0)
+    }
+
+    /// Wrap raw `WS_EX_*` bits, including ones without a named constant.
+    pub const fn from_bits_retain(bits: u32) -> Self {
+        ExStyleFlags(bits)
+    }
+
+    pub const fn bits(self) -> u32 {
+        self.0
+    }
+
+    pub const fn contains(self, other: ExStyleFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn insert(&mut self, other: ExStyleFlags) {
+        self.0 |= other.0;
+    }
+
+    pub fn remove(&mut self, other: ExStyleFlags) {
+        self.0 &= !other.0;
+    }
+
+    pub fn set(&mut self, other: ExStyleFlags, value: bool) {
+        if value {
+            self.insert(other);
+        } else {
+            self.remove(other);
+        }
+    }
+
+    /// True if `self` and `other` differ in a style that the window can't
+    /// change without being recreated.
+    pub fn requires_recreation(self, other: ExStyleFlags) -> bool {
+        let fixed = ExStyleFlags::LAYERED | ExStyleFlags::NOREDIRECTIONBITMAP;
+        (self.0 ^ other.0) & fixed.0 != 0
+    }
+}
+
+impl BitOr for ExStyleFlags {
+    type Output = Self;
+    fn bitor(self, rhs: Self) -> Self {
+        ExStyleFlags(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for ExStyleFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitAnd for ExStyleFlags {
+    type Output = Self;
+    fn bitand(self, rhs: Self) -> Self {
+        ExStyleFlags(self.0 & rhs.0)
+    }
+}
+
+impl Sub for ExStyleFlags {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        ExStyleFlags(self.0 & !rhs.0)
+    }
+}
+
+impl Not for ExStyleFlags {
+    type Output = Self;
+    fn not(self) -> Self {
+        ExStyleFlags(!self.0)
+    }
+}
+
+impl BlurWindow {
+    /// Current extended styles. Returns `NotInitialized` before the native
+    /// window exists (the first [`start`](Self::start) or
+    /// [`render_once`](Self::render_once)).
+    pub fn ex_style(&self) -> Result<ExStyleFlags, BlurErrorCode> {
+        match unsafe { blur_get_ex_style(self.handle) } {
+            // Every blur window has at least NOACTIVATE, so 0 means no HWND yet
+            0 => Err(BlurErrorCode::NotInitialized),
+            bits => Ok(ExStyleFlags::from_bits_retain(bits)),
+        }
+    }
+
+    /// Replace the extended styles. A low-level escape hatch for compositor
+    /// edge cases; prefer the typed setters where one exists.
+    ///
+    /// See the [`ExStyleFlags`] constants for which styles are safe to change
+    /// at runtime. Changing [`LAYERED`](ExStyleFlags::LAYERED) or
+    /// [`NOREDIRECTIONBITMAP`](ExStyleFlags::NOREDIRECTIONBITMAP) breaks
+    /// presentation until the window is recreated, so this returns
+    /// `InvalidParameter` instead; check with
+    /// [`requires_recreation`](ExStyleFlags::requires_recreation).
+    pub fn set_ex_style(&self, style: ExStyleFlags) -> Result<(), BlurErrorCode> {
+        if self.ex_style()?.requires_recreation(style) {
+            return Err(BlurErrorCode::InvalidParameter);
+        }
+        let code = unsafe { blur_set_ex_style(self.handle, style.bits()) };
+        if code == BlurErrorCode::Ok {
+            Ok(())
+        } else {
+            Err(code)
+        }
+    }
+
+    /// Read-modify-write helper: add `insert` and clear `remove`, keeping
+    /// every other bit.
+    pub fn update_ex_style(
+        &self,
+        insert: ExStyleFlags,
+        remove: ExStyleFlags,
+    ) -> Result<(), BlurErrorCode> {
+        let style = (self.ex_style()? - remove) | insert;
+        self.set_ex_style(style)
+    }
+
+    /// Keep the window above the taskbar, or under it but above every other
+    /// window, e.g. for a fullscreen overlay that leaves the taskbar usable.
+    ///
+    /// Both relations make the window [`TOPMOST`](ExStyleFlags::TOPMOST),
+    /// overriding [`WindowBuilder::top_most(false)`](WindowBuilder::top_most);
+    /// clearing `TOPMOST` with [`set_ex_style`](Self::set_ex_style) undoes
+    /// it. `Below` goes under the taskbars of every monitor. The z-order is
+    /// set by this call, or when the native window is created if it doesn't
+    /// exist yet: a top-most window raised later, or a taskbar recreated by
+    /// an Explorer restart, may end up on the other side until it's called
+    /// again. Has no effect on child windows.
+    pub fn set_taskbar_relation(&self, relation: TaskbarRelation) -> Result<(), BlurErrorCode> {
+        let code = unsafe { blur_set_taskbar_relation(self.handle, relation as i32) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().taskbar_relation = Some(relation);
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+use super::*;
+use std::sync::Mutex;
+
+pub(crate) type FullscreenChangedFn = Box<dyn Fn(bool) + Send>;
+
+unsafe extern "C" fn fullscreen_changed_trampoline(
+    fullscreen: i32,
+    user_data: *mut std::ffi::c_void,
+) {
+    let handler = &*(user_data as *const FullscreenChangedFn);
+    // Unwinding into native code is undefined behavior
+    let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| handler(fullscreen != 0)));
+}
+
+impl BlurSystem {
+    /// Pause every blur window in the process, e.g. while a fullscreen game
+    /// runs, to free the GPU. Windows keep their render thread and resume
+    /// where they left off.
+    ///
+    /// Covers windows created from any system, including those living on an
+    /// [`OwnedWindowThread`], and updates their [`WindowState::paused`].
+    /// Every window is tried; the first error is returned.
+    pub fn pause_all(&self) -> Result<(), BlurErrorCode> {
+        registry::set_all_paused(true)
+    }
+
+    /// Resume every blur window in the process, including windows paused
+    /// individually with [`BlurWindow::pause`].
+    pub fn resume_all(&self) -> Result<(), BlurErrorCode> {
+        registry::set_all_paused(false)
+    }
+
+    /// Call `f` with `true` when a fullscreen app of another process comes to
+    /// the foreground and with `false` when it leaves. Replaces any previous
+    /// handler; [`pause_while_fullscreen`](Self::pause_while_fullscreen) is
+    /// the common use.
+    ///
+    /// Apps count as fullscreen when their foreground window covers a whole
+    /// monitor: exclusive and borderless games, but also videos and
+    /// presentations. The native library polls twice a second from its own
+    /// thread, which runs `f`; an app that is already fullscreen is reported
+    /// right away. `f` must not call `on_fullscreen_app_detected` or
+    /// [`off_fullscreen_app_detected`](Self::off_fullscreen_app_detected).
+    pub fn on_fullscreen_app_detected(
+        &self,
+        f: impl Fn(bool) + Send + 'static,
+    ) -> Result<(), BlurErrorCode> {
+        if !self.is_valid() {
+            return Err(BlurErrorCode::NotInitialized);
+        }
+        let handler: Box<FullscreenChangedFn> = Box::new(Box::new(f));
+        let user_data = &*handler as *const FullscreenChangedFn as *mut std::ffi::c_void;
+        let mut slot = self
+            .fullscreen_changed
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let code = unsafe {
+            blur_set_fullscreen_changed_callback(
+                self.handle,
+                Some(fullscreen_changed_trampoline),
+                user_data,
+            )
+        };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        // The native side no longer calls the old handler once the setter returns
+        *slot = Some(handler);
+        Ok(())
+    }
+
+    /// Pause every window while a fullscreen app is in front and resume them
+    /// once it leaves, replacing any
+    /// [`on_fullscreen_app_detected`](Self::on_fullscreen_app_detected) handler.
+    ///
+    /// Only windows this pauses are resumed: one already paused when the app
+    /// came to the front stays paused.
+    pub fn pause_while_fullscreen(&self) -> Result<(), BlurErrorCode> {
+        let paused = Mutex::new(Vec::new());
+        self.on_fullscreen_app_detected(move |fullscreen| {
+            // Nobody to report to on the watcher thread; a failing window
+            // doesn't stop the others
+            let mut paused = paused.lock().unwrap_or_else(|e| e.into_inner());
+            if fullscreen {
+                paused.extend(registry::pause_unpaused());
+            } else {
+                registry::resume(&paused);
+                paused.clear();
+            }
+        })
+    }
+
+    /// Stop watching for fullscreen apps and drop the handler.
+    pub fn off_fullscreen_app_detected(&self) -> Result<(), BlurErrorCode> {
+        if !self.is_valid() {
+            return Ok(());
+        }
+        let mut slot = self
+            .fullscreen_changed
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let code = unsafe {
+            blur_set_fullscreen_changed_callback(self.handle, None, std::ptr::null_mut())
+        };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        *slot = None;
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+
+    fn pause_calls(window: &BlurWindow) -> Vec<String> {
+        window
+            .call_log()
+            .iter()
+            .map(ToString::to_string)
+            .filter(|call| call.starts_with("blur_pause") || call.starts_with("blur_resume"))
+            .collect()
+    }
+
+    #[test]
+    fn pause_all_reaches_every_window() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let windows = [
+            WindowBuilder::new().build(&system).unwrap(),
+            WindowBuilder::new().build(&system).unwrap(),
+        ];
+
+        system.pause_all().unwrap();
+        for window in &windows {
+            assert!(window.state().paused);
+        }
+        system.resume_all().unwrap();
+        for window in &windows {
+            assert!(!window.state().paused);
+            assert_eq!(pause_calls(window), ["blur_pause()", "blur_resume()"]);
+        }
+    }
+
+    #[test]
+    fn fullscreen_resumes_only_what_it_paused() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let running = WindowBuilder::new().build(&system).unwrap();
+        let paused = WindowBuilder::new().build(&system).unwrap();
+        paused.pause().unwrap();
+        system.pause_while_fullscreen().unwrap();
+
+        system.simulate_fullscreen_change(true);
+        assert!(running.state().paused);
+        system.simulate_fullscreen_change(false);
+        assert!(!running.state().paused);
+        assert!(paused.state().paused);
+        assert_eq!(pause_calls(&paused), ["blur_pause()"]);
+
+        system.off_fullscreen_app_detected().unwrap();
+    }
+}
@@ -0,0 +1,169 @@
+use super::*;
+use std::collections::VecDeque;
+
+/// Undo/redo over parameter changes made to a window.
+///
+/// Every change goes through [`apply`](Self::apply), which snapshots the
+/// cached [`WindowState`] first; [`undo`](Self::undo) and
+/// [`redo`](Self::redo) move between snapshots with
+/// [`BlurWindow::restore`]. Changes made on the window directly, bypassing
+/// `apply`, are not recorded.
+pub struct History<'a> {
+    window: &'a BlurWindow,
+    max_depth: usize,
+    undo: VecDeque<WindowState>,
+    redo: Vec<WindowState>,
+}
+
+impl<'a> History<'a> {
+    /// Keep at most `max_depth` undo steps; the oldest is dropped beyond that.
+    pub fn new(window: &'a BlurWindow, max_depth: usize) -> Self {
+        History {
+            window,
+            max_depth,
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+        }
+    }
+
+    pub fn window(&self) -> &'a BlurWindow {
+        self.window
+    }
+
+    /// Run `change` against the window as one undoable step.
+    ///
+    /// Clears the redo stack. Nothing is recorded if `change` fails.
+    pub fn apply<F>(&mut self, change: F) -> Result<(), BlurErrorCode>
+    where
+        F: FnOnce(&BlurWindow) -> Result<(), BlurErrorCode>,
+    {
+        let before = self.window.snapshot();
+        change(self.window)?;
+        if self.max_depth == 0 {
+            return Ok(());
+        }
+        if self.undo.len() == self.max_depth {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(before);
+        self.redo.clear();
+        Ok(())
+    }
+
+    /// Revert the most recent step. Returns `false` if there was nothing to undo.
+    ///
+    /// If restoring fails the stacks are left unchanged.
+    pub fn undo(&mut self) -> Result<bool, BlurErrorCode> {
+        let Some(target) = self.undo.pop_back() else {
+            return Ok(false);
+        };
+        let current = self.window.snapshot();
+        if let Err(e) = self.window.restore(&target) {
+            self.undo.push_back(target);
+            return Err(e);
+        }
+        self.redo.push(current);
+        Ok(true)
+    }
+
+    /// Re-apply the most recently undone step. Returns `false` if there was nothing to redo.
+    ///
+    /// If restoring fails the stacks are left unchanged.
+    pub fn redo(&mut self) -> Result<bool, BlurErrorCode> {
+        let Some(target) = self.redo.pop() else {
+            return Ok(false);
+        };
+        let current = self.window.snapshot();
+        if let Err(e) = self.window.restore(&target) {
+            self.redo.push(target);
+            return Err(e);
+        }
+        self.undo.push_back(current);
+        Ok(true)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Forget all recorded steps without touching the window.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undo_and_redo_walk_the_steps() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let window = WindowBuilder::new().build(&system).unwrap();
+        let mut history = History::new(&window, 10);
+        assert!(!history.undo().unwrap());
+        assert!(!history.redo().unwrap());
+
+        history.apply(|w| w.set_strength(0.5)).unwrap();
+        history.apply(|w| w.set_strength(0.25)).unwrap();
+        assert!(history.undo().unwrap());
+        assert_eq!(window.state().strength, 0.5);
+        assert!(history.undo().unwrap());
+        assert_eq!(window.state().strength, 1.0);
+        assert!(!history.can_undo());
+
+        assert!(history.redo().unwrap());
+        assert_eq!(window.state().strength, 0.5);
+        assert!(history.can_redo());
+    }
+
+    #[test]
+    fn a_new_change_clears_redo() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let window = WindowBuilder::new().build(&system).unwrap();
+        let mut history = History::new(&window, 10);
+
+        history.apply(|w| w.set_strength(0.5)).unwrap();
+        history.undo().unwrap();
+        assert!(history.can_redo());
+        history.apply(|w| w.set_strength(0.75)).unwrap();
+        assert!(!history.can_redo());
+        assert!(!history.redo().unwrap());
+    }
+
+    #[test]
+    fn oldest_step_is_dropped_beyond_max_depth() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let window = WindowBuilder::new().build(&system).unwrap();
+        let mut history = History::new(&window, 2);
+
+        for strength in [0.1, 0.2, 0.3] {
+            history.apply(|w| w.set_strength(strength)).unwrap();
+        }
+        assert!(history.undo().unwrap());
+        assert!(history.undo().unwrap());
+        assert!(!history.undo().unwrap());
+        // The step back to 1.0 was dropped
+        assert_eq!(window.state().strength, 0.1);
+    }
+
+    #[test]
+    fn failed_change_records_nothing() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let window = WindowBuilder::new().build(&system).unwrap();
+        let mut history = History::new(&window, 10);
+
+        let failed = history.apply(|_| Err(BlurErrorCode::InvalidParameter));
+        assert_eq!(failed, Err(BlurErrorCode::InvalidParameter));
+        assert!(!history.can_undo());
+    }
+}
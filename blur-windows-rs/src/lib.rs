@@ -1,5 +1,11 @@
+#[cfg(all(feature = "no-logging", feature = "log"))]
+compile_error!("features `no-logging` and `log` are mutually exclusive");
+
 use std::ffi::c_char;
-use windows::Win32::Foundation::HWND;
+use windows::Win32::Foundation::{HWND, RECT};
+use windows::Win32::UI::WindowsAndMessaging::{
+    GetSystemMetrics, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN,
+};
 
 // Forward matches with c_api.h
 
@@ -11,8 +17,20 @@ pub struct BlurSystemHandle(pub *mut std::ffi::c_void);
 #[derive(Copy, Clone)]
 pub struct BlurWindowHandle(pub *mut std::ffi::c_void);
 
+/// D3D11 shared-texture handle, from `IDXGIResource::GetSharedHandle` or
+/// `IDXGIResource1::CreateSharedHandle`. See
+/// [`set_source_texture`](BlurWindow::set_source_texture).
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RawTextureHandle(pub *mut std::ffi::c_void);
+
 #[repr(i32)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
 pub enum BlurQualityPreset {
     High = 0,
     Balanced = 1,
@@ -20,6 +38,381 @@ pub enum BlurQualityPreset {
     Minimal = 3,
 }
 
+impl BlurQualityPreset {
+    pub(crate) fn from_raw(v: i32) -> Option<Self> {
+        match v {
+            0 => Some(BlurQualityPreset::High),
+            1 => Some(BlurQualityPreset::Balanced),
+            2 => Some(BlurQualityPreset::Performance),
+            3 => Some(BlurQualityPreset::Minimal),
+            _ => None,
+        }
+    }
+}
+
+/// Effect family, matching `blur_set_effect_type`.
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum BlurEffectType {
+    Gaussian = 0,
+    Kawase = 1,
+    Box = 2,
+    Radial = 3,
+    Rain = 4,
+}
+
+/// Blur family for a typed pipeline blur stage (`BlurStage`).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub enum BlurAlgorithm {
+    Gaussian,
+    Box,
+    Kawase,
+    /// Kawase over a downsampled image; serializes as a `kawase` stage with
+    /// `downsample` defaulting to 2.
+    DualKawase,
+}
+
+impl BlurAlgorithm {
+    pub const ALL: [BlurAlgorithm; 4] = [
+        BlurAlgorithm::Gaussian,
+        BlurAlgorithm::Box,
+        BlurAlgorithm::Kawase,
+        BlurAlgorithm::DualKawase,
+    ];
+
+    /// Bit in the `blur_get_supported_algorithms` mask (`BLUR_ALGORITHM_*`).
+    pub fn support_bit(self) -> u32 {
+        match self {
+            BlurAlgorithm::Gaussian => 1 << 0,
+            BlurAlgorithm::Kawase => 1 << 1,
+            BlurAlgorithm::Box => 1 << 2,
+            BlurAlgorithm::DualKawase => 1 << 5,
+        }
+    }
+}
+
+/// Noise pattern, matching `blur_set_noise_type`.
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "i32", into = "i32")
+)]
+pub enum NoiseType {
+    White = 0,
+    Sinusoid = 1,
+    Grid = 2,
+    Perlin = 3,
+    Simplex = 4,
+    Voronoi = 5,
+}
+
+/// How the noise layer is composited onto the blurred image.
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "i32", into = "i32")
+)]
+pub enum NoiseBlendMode {
+    /// Adds the noise value to the color; brightens and darkens evenly (the original look).
+    Additive = 0,
+    /// Overlay blend; pushes darks darker and lights lighter, giving a punchier grain.
+    Overlay = 1,
+    /// Scales the existing color; grain is strong on bright areas and vanishes on black.
+    Multiply = 2,
+}
+
+impl From<NoiseType> for i32 {
+    fn from(t: NoiseType) -> Self {
+        t as i32
+    }
+}
+
+impl TryFrom<i32> for BlurEffectType {
+    type Error = String;
+
+    fn try_from(v: i32) -> Result<Self, Self::Error> {
+        Ok(match v {
+            0 => BlurEffectType::Gaussian,
+            1 => BlurEffectType::Kawase,
+            2 => BlurEffectType::Box,
+            3 => BlurEffectType::Radial,
+            4 => BlurEffectType::Rain,
+            _ => return Err(format!("unknown effect type {}", v)),
+        })
+    }
+}
+
+impl TryFrom<i32> for NoiseType {
+    type Error = String;
+
+    fn try_from(v: i32) -> Result<Self, Self::Error> {
+        Ok(match v {
+            0 => NoiseType::White,
+            1 => NoiseType::Sinusoid,
+            2 => NoiseType::Grid,
+            3 => NoiseType::Perlin,
+            4 => NoiseType::Simplex,
+            5 => NoiseType::Voronoi,
+            _ => return Err(format!("unknown noise type {}", v)),
+        })
+    }
+}
+
+impl From<NoiseBlendMode> for i32 {
+    fn from(m: NoiseBlendMode) -> Self {
+        m as i32
+    }
+}
+
+impl TryFrom<i32> for NoiseBlendMode {
+    type Error = String;
+
+    fn try_from(v: i32) -> Result<Self, Self::Error> {
+        Ok(match v {
+            0 => NoiseBlendMode::Additive,
+            1 => NoiseBlendMode::Overlay,
+            2 => NoiseBlendMode::Multiply,
+            _ => return Err(format!("unknown noise blend mode {}", v)),
+        })
+    }
+}
+
+/// Clock that animated effects (noise, rain) follow, matching `blur_set_time_mode`.
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum TimeMode {
+    /// Animation advances with the wall clock.
+    Realtime = 0,
+    /// Animation advances only through `BlurWindow::set_time`.
+    Manual = 1,
+}
+
+/// How the blur follows changes of the backdrop, matching `blur_set_temporal_mode`.
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub enum TemporalMode {
+    /// Blur everything.
+    Off = 0,
+    /// Blur only what changed since the previous capture; static content
+    /// shows through sharp.
+    MotionOnly = 1,
+    /// Blur more the more a pixel changed.
+    MotionWeighted = 2,
+}
+
+impl TemporalMode {
+    /// Bit in the `blur_get_supported_algorithms` mask (`BLUR_ALGORITHM_TEMPORAL`).
+    pub const SUPPORT_BIT: u32 = 1 << 6;
+}
+
+/// Where a blur window sits relative to the taskbar, matching
+/// `blur_set_taskbar_relation`.
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum TaskbarRelation {
+    /// Covers the taskbar.
+    Above = 0,
+    /// Above ordinary windows, but under the taskbar.
+    Below = 1,
+}
+
+/// Presentation path chosen by the native window, matching `blur_get_backend`.
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Backend {
+    /// Graphics not created yet (the window has never been started).
+    None = 0,
+    DirectComposition = 1,
+    /// `UpdateLayeredWindow` fallback.
+    Layered = 2,
+}
+
+impl Backend {
+    pub(crate) fn from_raw(v: i32) -> Self {
+        match v {
+            1 => Backend::DirectComposition,
+            2 => Backend::Layered,
+            _ => Backend::None,
+        }
+    }
+}
+
+/// Outcome of the most recent capture attempt, matching `blur_get_capture_status`.
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum CaptureStatus {
+    Ok = 0,
+    /// Windows denied access to the screen, typically because screen capture
+    /// is disabled by group policy ("disabled by your administrator"). Also
+    /// reported while the secure desktop (UAC prompt, lock screen) is shown.
+    BlockedByPolicy = 1,
+    /// Capturing, but DRM-protected content is blacked out in the backdrop.
+    ProtectedContent = 2,
+    /// The GPU was reset or removed, or the display mode changed; capture
+    /// recovers by itself if the device comes back.
+    DeviceLost = 3,
+    /// Any other capture failure.
+    Failed = 4,
+}
+
+impl CaptureStatus {
+    pub(crate) fn from_raw(v: i32) -> Self {
+        match v {
+            0 => CaptureStatus::Ok,
+            1 => CaptureStatus::BlockedByPolicy,
+            2 => CaptureStatus::ProtectedContent,
+            3 => CaptureStatus::DeviceLost,
+            _ => CaptureStatus::Failed,
+        }
+    }
+}
+
+/// How the backdrop is captured, matching `blur_get_capture_method`.
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum CaptureMethod {
+    /// Nothing is captured: graphics aren't created yet, or no method could
+    /// start (see [`CaptureStatus`]).
+    None = 0,
+    /// DXGI Desktop Duplication of the desktop, the fast path.
+    DesktopDuplication = 1,
+    /// Windows.Graphics.Capture of a single window, see
+    /// [`BlurWindow::set_capture_source`].
+    WindowsGraphicsCapture = 2,
+}
+
+impl CaptureMethod {
+    pub(crate) fn from_raw(v: i32) -> Self {
+        match v {
+            1 => CaptureMethod::DesktopDuplication,
+            2 => CaptureMethod::WindowsGraphicsCapture,
+            _ => CaptureMethod::None,
+        }
+    }
+}
+
+/// Which capture method a [`BlurSystem`] prefers, see
+/// [`BlurSystemBuilder::capture_method`].
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum CapturePreference {
+    /// Desktop Duplication for the desktop, Windows.Graphics.Capture for a
+    /// capture source.
+    #[default]
+    Auto = 0,
+    /// Desktop Duplication, also for windows with a capture source.
+    DesktopDuplication = 1,
+    /// Windows.Graphics.Capture wherever it's available, i.e. for a capture
+    /// source.
+    WindowsGraphicsCapture = 2,
+}
+
+/// Pixel format of the backdrop the effects run on, matching
+/// `blur_get_capture_format`. Color parameters such as the tint are applied
+/// in this format's space.
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum CaptureFormat {
+    /// 8-bit, sRGB-encoded SDR. What desktop and window capture deliver,
+    /// also on HDR displays, where Windows maps the desktop to SDR first;
+    /// [`Color`] values apply as they are.
+    Srgb8 = 0,
+    /// FP16 scRGB: linear light, BT.709 primaries, 1.0 is SDR white (80
+    /// nits). Convert sRGB tint values to linear before applying them.
+    Rgba16Float = 1,
+    /// 10-bit HDR10: PQ-encoded, BT.2020 primaries. Tint values need the
+    /// full conversion from sRGB.
+    Rgb10A2 = 2,
+}
+
+impl CaptureFormat {
+    pub(crate) fn from_raw(v: i32) -> Self {
+        match v {
+            1 => CaptureFormat::Rgba16Float,
+            2 => CaptureFormat::Rgb10A2,
+            _ => CaptureFormat::Srgb8,
+        }
+    }
+
+    /// True for the formats that keep the HDR signal.
+    pub fn is_hdr(self) -> bool {
+        self != CaptureFormat::Srgb8
+    }
+}
+
+/// How an HDR backdrop is mapped to SDR before blurring, matching
+/// `BlurToneMapping`. See [`BlurWindow::set_tone_mapping`].
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum ToneMapping {
+    /// Clip everything brighter than SDR white.
+    #[default]
+    None = 0,
+    /// Reinhard: a soft roll-off of highlights that keeps hues.
+    Reinhard = 1,
+    /// The ACES filmic curve: more contrast, slightly desaturated highlights.
+    Aces = 2,
+}
+
 #[repr(i32)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum BlurErrorCode {
@@ -32,7 +425,43 @@ pub enum BlurErrorCode {
     Unknown = -99,
 }
 
+impl BlurErrorCode {
+    /// Codes this crate doesn't know map to `Unknown`.
+    pub(crate) fn from_raw(v: i32) -> Self {
+        match v {
+            0 => BlurErrorCode::Ok,
+            -1 => BlurErrorCode::NotInitialized,
+            -2 => BlurErrorCode::InvalidHandle,
+            -3 => BlurErrorCode::InvalidParameter,
+            -4 => BlurErrorCode::D3D11Failed,
+            -5 => BlurErrorCode::CaptureFailed,
+            _ => BlurErrorCode::Unknown,
+        }
+    }
+}
+
+/// Highest rate accepted by `blur_set_target_fps` (`BLUR_MAX_TARGET_FPS`).
+pub const MAX_TARGET_FPS: u32 = 1000;
+
+/// Longest interval accepted by `blur_set_capture_interval_ms`
+/// (`BLUR_MAX_CAPTURE_INTERVAL_MS`).
+pub const MAX_CAPTURE_INTERVAL_MS: u32 = 10000;
+
+/// Longest interval accepted by `blur_set_min_frame_interval_ms`
+/// (`BLUR_MAX_MIN_FRAME_INTERVAL_MS`).
+pub const MAX_MIN_FRAME_INTERVAL_MS: u32 = 1000;
+
+/// Largest radius accepted by `blur_set_kernel_radius` (`BLUR_MAX_KERNEL_RADIUS`)
+/// in the header these bindings match. The loaded library reports its own
+/// through [`BlurSystem::max_kernel_radius`], which windows validate against.
+pub const MAX_KERNEL_RADIUS: u32 = 32;
+
+/// Largest per-axis factor accepted by `blur_set_downsample_xy` (`BLUR_MAX_DOWNSAMPLE`).
+pub const MAX_DOWNSAMPLE: u32 = 8;
+
 #[repr(C)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BlurRect {
     pub left: i32,
     pub top: i32,
@@ -40,6 +469,136 @@ pub struct BlurRect {
     pub bottom: i32,
 }
 
+impl BlurRect {
+    /// True if the rect has no area.
+    pub fn is_empty(&self) -> bool {
+        self.right <= self.left || self.bottom <= self.top
+    }
+
+    /// Width and height (negative for an inverted rect).
+    pub fn size(&self) -> (i32, i32) {
+        (self.right - self.left, self.bottom - self.top)
+    }
+
+    /// Intersect with the virtual desktop (the bounding box of all monitors).
+    ///
+    /// A rect entirely off-screen comes back empty; check
+    /// [`is_empty`](BlurRect::is_empty) before using the result.
+    pub fn clamp_to_desktop(self) -> BlurRect {
+        let (x, y, w, h) = unsafe {
+            (
+                GetSystemMetrics(SM_XVIRTUALSCREEN),
+                GetSystemMetrics(SM_YVIRTUALSCREEN),
+                GetSystemMetrics(SM_CXVIRTUALSCREEN),
+                GetSystemMetrics(SM_CYVIRTUALSCREEN),
+            )
+        };
+        BlurRect {
+            left: self.left.max(x),
+            top: self.top.max(y),
+            right: self.right.min(x + w),
+            bottom: self.bottom.min(y + h),
+        }
+    }
+}
+
+/// Largest layer count accepted by `blur_set_tint_layers` (`BLUR_MAX_TINT_LAYERS`).
+pub const MAX_TINT_LAYERS: usize = 8;
+
+/// Most stages accepted by `blur_set_pipeline` (`BLUR_MAX_EFFECT_LAYERS`),
+/// the window's effect included.
+pub const MAX_EFFECT_LAYERS: usize = 4;
+
+/// Most regions accepted by `blur_set_region_effect` (`BLUR_MAX_REGION_EFFECTS`).
+pub const MAX_REGION_EFFECTS: usize = 8;
+
+/// Most frames kept by `blur_get_frame_history` (`BLUR_FRAME_HISTORY_SIZE`).
+pub const MAX_FRAME_HISTORY: usize = 1024;
+
+/// One monitor as filled in by `blur_get_monitor_info`; same layout as
+/// `BlurMonitorInfo`. See [`MonitorInfo`] for the safe form.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct MonitorInfoC {
+    pub bounds: BlurRect,
+    pub work_area: BlurRect,
+    pub dpi_x: u32,
+    pub dpi_y: u32,
+    pub primary: i32, // 1 = primary monitor
+}
+
+/// `BlurAdapterInfo`. See [`AdapterInfo`] for the safe form.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct AdapterInfoC {
+    pub vendor_id: u32,
+    pub device_id: u32,
+    pub dedicated_video_memory: u64,
+    pub driver_version: u64, // 4 x 16 bits, most significant first; 0 = unknown
+    pub software: i32,
+    pub active: i32, // 1 = the system's adapter
+}
+
+/// Timing of one presented frame. Same layout as `BlurFrameSample`.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FrameSample {
+    /// Index as passed to [`BlurWindow::on_frame`].
+    pub frame_index: u64,
+    /// CPU time spent capturing, processing and presenting the frame.
+    pub frame_time_ms: f32,
+    /// The window's FPS reading when the frame was presented, updated once
+    /// a second.
+    pub fps: f32,
+}
+
+/// RGBA color, each channel 0.0 to 1.0. Same layout as `BlurColor`.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl Color {
+    pub const TRANSPARENT: Color = Color::new(0.0, 0.0, 0.0, 0.0);
+
+    pub const fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Color { r, g, b, a }
+    }
+
+    pub const fn with_alpha(self, a: f32) -> Self {
+        Color { a, ..self }
+    }
+}
+
+// BlurRect and RECT share the same left/top/right/bottom layout.
+impl From<RECT> for BlurRect {
+    fn from(r: RECT) -> Self {
+        BlurRect {
+            left: r.left,
+            top: r.top,
+            right: r.right,
+            bottom: r.bottom,
+        }
+    }
+}
+
+impl From<BlurRect> for RECT {
+    fn from(r: BlurRect) -> Self {
+        RECT {
+            left: r.left,
+            top: r.top,
+            right: r.right,
+            bottom: r.bottom,
+        }
+    }
+}
+
 #[repr(C)]
 pub struct BlurSystemOptionsC {
     pub enable_logging: i32,     // 0 = false, 1 = true
@@ -53,8 +612,13 @@ pub struct BlurWindowOptionsC {
     pub bounds: BlurRect,
     pub top_most: i32,      // 0 = false, 1 = true
     pub click_through: i32, // 0 = false, 1 = true
+    pub manual_render: i32, // 0 = continuous loop, 1 = frames only via blur_render_once
+    pub child: i32,         // 1 = WS_CHILD of owner, bounds relative to its client area
+    pub start_hidden: i32,  // 1 = created hidden until blur_show
+    pub wallpaper: i32,     // 1 = behind the desktop icons, under the WorkerW
 }
 
+#[cfg(not(feature = "mock"))]
 #[link(name = "blurwindow")]
 extern "C" {
     pub fn blur_init(opts: *const BlurSystemOptionsC) -> BlurSystemHandle;
@@ -64,20 +628,152 @@ extern "C" {
         owner: HWND,
         opts: *const BlurWindowOptionsC,
     ) -> BlurWindowHandle;
+    pub fn blur_create_child_window(
+        sys: BlurSystemHandle,
+        owner: HWND,
+        relative_bounds: *const BlurRect,
+    ) -> BlurWindowHandle;
     pub fn blur_destroy_window(window: BlurWindowHandle);
     pub fn blur_start(window: BlurWindowHandle) -> BlurErrorCode;
     pub fn blur_stop(window: BlurWindowHandle) -> BlurErrorCode;
+    pub fn blur_render_once(window: BlurWindowHandle) -> BlurErrorCode;
+    pub fn blur_prewarm(window: BlurWindowHandle) -> BlurErrorCode;
     pub fn blur_set_preset(window: BlurWindowHandle, preset: BlurQualityPreset) -> BlurErrorCode;
+    /// Returns a `BlurQualityPreset` value; declared as `i32` so an
+    /// out-of-range value from the library can't create an invalid enum.
+    pub fn blur_get_active_preset(window: BlurWindowHandle) -> i32;
     pub fn blur_set_pipeline(window: BlurWindowHandle, json_config: *const c_char)
         -> BlurErrorCode;
     pub fn blur_set_bounds(window: BlurWindowHandle, bounds: *const BlurRect) -> BlurErrorCode;
+    pub fn blur_resize_swapchain(
+        window: BlurWindowHandle,
+        width: u32,
+        height: u32,
+    ) -> BlurErrorCode;
+    pub fn blur_set_buffer_count(window: BlurWindowHandle, count: u32) -> BlurErrorCode;
+    pub fn blur_get_ex_style(window: BlurWindowHandle) -> u32;
+    pub fn blur_set_ex_style(window: BlurWindowHandle, ex_style: u32) -> BlurErrorCode;
+    pub fn blur_set_region_effect(
+        window: BlurWindowHandle,
+        region: *const BlurRect,
+        effect_type: i32,
+    ) -> BlurErrorCode;
+    pub fn blur_set_active_region(
+        window: BlurWindowHandle,
+        region: *const BlurRect,
+    ) -> BlurErrorCode;
+    pub fn blur_begin_update(window: BlurWindowHandle) -> BlurErrorCode;
+    pub fn blur_end_update(window: BlurWindowHandle) -> BlurErrorCode;
+    pub fn blur_get_hwnd(window: BlurWindowHandle) -> HWND;
+    pub fn blur_is_blur_window(hwnd: HWND) -> i32;
     pub fn blur_get_fps(window: BlurWindowHandle) -> f32;
+    pub fn blur_set_cursor_follow(
+        window: BlurWindowHandle,
+        enabled: i32,
+        radius: f32,
+    ) -> BlurErrorCode;
+    pub fn blur_set_time_mode(window: BlurWindowHandle, mode: i32) -> BlurErrorCode;
+    pub fn blur_set_taskbar_relation(window: BlurWindowHandle, relation: i32) -> BlurErrorCode;
+    pub fn blur_set_time(window: BlurWindowHandle, seconds: f32) -> BlurErrorCode;
+    pub fn blur_set_target_fps(window: BlurWindowHandle, fps: i32) -> BlurErrorCode;
+    pub fn blur_set_capture_interval_ms(
+        window: BlurWindowHandle,
+        interval_ms: u32,
+    ) -> BlurErrorCode;
+    pub fn blur_set_min_frame_interval_ms(
+        window: BlurWindowHandle,
+        interval_ms: u32,
+    ) -> BlurErrorCode;
+    pub fn blur_get_display_refresh_hz(window: BlurWindowHandle) -> f32;
+    pub fn blur_get_frame_time(window: BlurWindowHandle) -> f32;
+    pub fn blur_get_latency_ms(window: BlurWindowHandle) -> f32;
+    pub fn blur_get_backend(window: BlurWindowHandle) -> i32;
+    pub fn blur_get_vram_usage(window: BlurWindowHandle) -> u64;
+    pub fn blur_get_frame_history(
+        window: BlurWindowHandle,
+        out: *mut FrameSample,
+        capacity: u32,
+    ) -> u32;
+    pub fn blur_get_capture_status(window: BlurWindowHandle) -> i32;
+    pub fn blur_get_capture_format(window: BlurWindowHandle) -> i32;
+    pub fn blur_get_capture_method(window: BlurWindowHandle) -> i32;
+    pub fn blur_set_preferred_capture_method(sys: BlurSystemHandle, method: i32) -> BlurErrorCode;
+    pub fn blur_get_capture_rect(
+        window: BlurWindowHandle,
+        out_rect: *mut BlurRect,
+    ) -> BlurErrorCode;
+    pub fn blur_set_debug_overlay(window: BlurWindowHandle, enabled: i32) -> BlurErrorCode;
+    pub fn blur_pause(window: BlurWindowHandle) -> BlurErrorCode;
+    pub fn blur_resume(window: BlurWindowHandle) -> BlurErrorCode;
+    pub fn blur_set_sync_to_owner_paint(window: BlurWindowHandle, enable: i32) -> BlurErrorCode;
+    pub fn blur_set_pause_when_occluded(window: BlurWindowHandle, enable: i32) -> BlurErrorCode;
+    pub fn blur_is_occluded(window: BlurWindowHandle) -> i32;
+    pub fn blur_freeze(window: BlurWindowHandle) -> BlurErrorCode;
+    pub fn blur_unfreeze(window: BlurWindowHandle) -> BlurErrorCode;
+    pub fn blur_show(window: BlurWindowHandle) -> BlurErrorCode;
+    pub fn blur_hide(window: BlurWindowHandle) -> BlurErrorCode;
+    pub fn blur_command(
+        window: BlurWindowHandle,
+        command: *const c_char,
+        args: *const c_char,
+    ) -> BlurErrorCode;
+    pub fn blur_is_debug_build() -> i32;
+    pub fn blur_is_dwm_enabled() -> i32;
+    pub fn blur_get_supported_algorithms(sys: BlurSystemHandle) -> u32;
+    pub fn blur_get_max_kernel_radius(sys: BlurSystemHandle) -> u32;
+    pub fn blur_validate_pipeline(
+        sys: BlurSystemHandle,
+        json_config: *const c_char,
+    ) -> BlurErrorCode;
+    pub fn blur_get_system_theme() -> i32;
+    pub fn blur_get_monitor_count() -> u32;
+    pub fn blur_get_monitor_info(index: u32, out: *mut MonitorInfoC) -> BlurErrorCode;
+    pub fn blur_get_adapter_info(
+        sys: BlurSystemHandle,
+        index: u32,
+        out: *mut AdapterInfoC,
+    ) -> BlurErrorCode;
+    pub fn blur_get_adapter_description(
+        sys: BlurSystemHandle,
+        index: u32,
+        buf: *mut c_char,
+        len: *mut u32,
+    ) -> BlurErrorCode;
+    pub fn blur_set_theme_changed_callback(
+        sys: BlurSystemHandle,
+        callback: BlurThemeChangedCallback,
+        user_data: *mut std::ffi::c_void,
+    ) -> BlurErrorCode;
+    pub fn blur_set_fullscreen_changed_callback(
+        sys: BlurSystemHandle,
+        callback: BlurFullscreenChangedCallback,
+        user_data: *mut std::ffi::c_void,
+    ) -> BlurErrorCode;
     pub fn blur_get_last_error() -> *const c_char;
+    pub fn blur_get_last_error_and_clear() -> *const c_char;
+    pub fn blur_get_last_error_code() -> i32;
 
     // Effect control
     pub fn blur_set_effect_type(window: BlurWindowHandle, effect_type: i32) -> BlurErrorCode;
     pub fn blur_set_strength(window: BlurWindowHandle, strength: f32) -> BlurErrorCode;
     pub fn blur_set_blur_param(window: BlurWindowHandle, param: f32) -> BlurErrorCode;
+    pub fn blur_set_kernel_radius(window: BlurWindowHandle, radius: u32) -> BlurErrorCode;
+    pub fn blur_set_edge_aa(window: BlurWindowHandle, samples: u32) -> BlurErrorCode;
+    pub fn blur_set_edge_feather(window: BlurWindowHandle, pixels: f32) -> BlurErrorCode;
+    pub fn blur_set_custom_shader(window: BlurWindowHandle, hlsl: *const c_char) -> BlurErrorCode;
+    pub fn blur_set_strength_gradient(
+        window: BlurWindowHandle,
+        top: f32,
+        bottom: f32,
+    ) -> BlurErrorCode;
+    pub fn blur_set_blur_xy(window: BlurWindowHandle, x: f32, y: f32) -> BlurErrorCode;
+    pub fn blur_set_downsample_xy(window: BlurWindowHandle, x: u32, y: u32) -> BlurErrorCode;
+    pub fn blur_set_temporal_mode(window: BlurWindowHandle, mode: i32) -> BlurErrorCode;
+    pub fn blur_set_direction(
+        window: BlurWindowHandle,
+        angle_degrees: f32,
+        strength: f32,
+    ) -> BlurErrorCode;
     pub fn blur_set_tint_color(
         window: BlurWindowHandle,
         r: f32,
@@ -85,12 +781,37 @@ extern "C" {
         b: f32,
         a: f32,
     ) -> BlurErrorCode;
+    pub fn blur_set_tint_layers(
+        window: BlurWindowHandle,
+        layers: *const Color,
+        count: u32,
+    ) -> BlurErrorCode;
+    pub fn blur_set_fallback_color(window: BlurWindowHandle, color: *const Color) -> BlurErrorCode;
+    pub fn blur_set_tone_mapping(window: BlurWindowHandle, mode: i32) -> BlurErrorCode;
+    pub fn blur_set_capture_source(window: BlurWindowHandle, source: HWND) -> BlurErrorCode;
+    pub fn blur_set_source_texture(
+        window: BlurWindowHandle,
+        shared_handle: *mut std::ffi::c_void,
+    ) -> BlurErrorCode;
+    pub fn blur_set_background_image(
+        window: BlurWindowHandle,
+        rgba: *const u8,
+        width: u32,
+        height: u32,
+    ) -> BlurErrorCode;
+    pub fn blur_sample_dominant_color(
+        window: BlurWindowHandle,
+        out_color: *mut Color,
+    ) -> BlurErrorCode;
 
     // Noise control
     pub fn blur_set_noise_intensity(window: BlurWindowHandle, intensity: f32) -> BlurErrorCode;
     pub fn blur_set_noise_scale(window: BlurWindowHandle, scale: f32) -> BlurErrorCode;
     pub fn blur_set_noise_speed(window: BlurWindowHandle, speed: f32) -> BlurErrorCode;
     pub fn blur_set_noise_type(window: BlurWindowHandle, noise_type: i32) -> BlurErrorCode;
+    pub fn blur_set_noise_blend_mode(window: BlurWindowHandle, mode: i32) -> BlurErrorCode;
+    pub fn blur_set_noise_color(window: BlurWindowHandle, color: *const Color) -> BlurErrorCode;
+    pub fn blur_set_noise_seed(window: BlurWindowHandle, seed: u32) -> BlurErrorCode;
 
     // Rain Effect control
     pub fn blur_set_rain_intensity(window: BlurWindowHandle, intensity: f32) -> BlurErrorCode;
@@ -109,6 +830,41 @@ extern "C" {
         callback: BlurClickCallback,
         user_data: *mut std::ffi::c_void,
     ) -> BlurErrorCode;
+
+    // Frame Callback
+    pub fn blur_set_frame_callback(
+        window: BlurWindowHandle,
+        callback: BlurFrameCallback,
+        user_data: *mut std::ffi::c_void,
+    ) -> BlurErrorCode;
+
+    // Owner Closed Callback
+    pub fn blur_set_owner_closed_callback(
+        window: BlurWindowHandle,
+        callback: BlurOwnerClosedCallback,
+        user_data: *mut std::ffi::c_void,
+    ) -> BlurErrorCode;
+
+    // Occlusion Changed Callback
+    pub fn blur_set_occlusion_changed_callback(
+        window: BlurWindowHandle,
+        callback: BlurOcclusionChangedCallback,
+        user_data: *mut std::ffi::c_void,
+    ) -> BlurErrorCode;
+
+    // Display Changed Callback
+    pub fn blur_set_display_changed_callback(
+        window: BlurWindowHandle,
+        callback: BlurDisplayChangedCallback,
+        user_data: *mut std::ffi::c_void,
+    ) -> BlurErrorCode;
+
+    // Power Changed Callback
+    pub fn blur_set_power_changed_callback(
+        window: BlurWindowHandle,
+        callback: BlurPowerChangedCallback,
+        user_data: *mut std::ffi::c_void,
+    ) -> BlurErrorCode;
 }
 
 /// Click callback function type
@@ -122,6 +878,163 @@ pub type BlurClickCallback = Option<
     ),
 >;
 
+/// Per-frame callback function type
+/// Parameters: frame index, frame time in milliseconds, user data
+pub type BlurFrameCallback = Option<
+    unsafe extern "C" fn(frame_index: u64, frame_time_ms: f32, user_data: *mut std::ffi::c_void),
+>;
+
+/// Owner-closed callback function type
+/// Parameters: user data
+pub type BlurOwnerClosedCallback = Option<unsafe extern "C" fn(user_data: *mut std::ffi::c_void)>;
+
+/// Occlusion-changed callback function type
+/// Parameters: occluded (1 = fully covered, 0 = revealed), user data
+pub type BlurOcclusionChangedCallback =
+    Option<unsafe extern "C" fn(occluded: i32, user_data: *mut std::ffi::c_void)>;
+
+/// Display-changed callback function type
+/// Parameters: user data
+pub type BlurDisplayChangedCallback =
+    Option<unsafe extern "C" fn(user_data: *mut std::ffi::c_void)>;
+
+/// Power-changed callback function type
+/// Parameters: user data
+pub type BlurPowerChangedCallback = Option<unsafe extern "C" fn(user_data: *mut std::ffi::c_void)>;
+
+/// Theme-changed callback function type
+/// Parameters: theme (0 = light, 1 = dark), user data
+pub type BlurThemeChangedCallback =
+    Option<unsafe extern "C" fn(theme: i32, user_data: *mut std::ffi::c_void)>;
+
+/// Fullscreen-changed callback function type
+/// Parameters: fullscreen (1 = a fullscreen app is in front, 0 = it left), user data
+pub type BlurFullscreenChangedCallback =
+    Option<unsafe extern "C" fn(fullscreen: i32, user_data: *mut std::ffi::c_void)>;
+
 // Safe wrapper implementation would go here...
 pub mod safe;
 pub use safe::*;
+
+pub mod state;
+pub use state::*;
+
+#[cfg(feature = "serde")]
+pub mod pipeline;
+#[cfg(feature = "serde")]
+pub use pipeline::*;
+
+#[cfg(feature = "serde")]
+pub mod profile;
+#[cfg(feature = "serde")]
+pub use profile::*;
+
+pub mod watchdog;
+pub use watchdog::*;
+
+pub mod animation;
+pub use animation::*;
+
+pub mod metrics;
+pub use metrics::*;
+
+pub mod owned_thread;
+pub use owned_thread::*;
+
+pub mod diff;
+pub use diff::*;
+
+pub mod history;
+pub use history::*;
+
+pub mod params;
+pub use params::*;
+
+pub mod ex_style;
+pub use ex_style::*;
+
+pub mod contrast;
+pub use contrast::*;
+
+pub mod conflict;
+pub use conflict::*;
+
+pub mod theme;
+pub use theme::*;
+
+pub mod monitor;
+pub use monitor::*;
+
+pub mod power;
+pub use power::*;
+
+pub mod look;
+pub use look::*;
+
+pub mod adapter;
+pub use adapter::*;
+
+pub(crate) mod registry;
+
+pub(crate) mod fullscreen;
+pub(crate) use fullscreen::FullscreenChangedFn;
+
+// Same signatures as the extern block, so callers' `unsafe` blocks stay valid
+#[cfg(feature = "mock")]
+#[allow(clippy::missing_safety_doc)]
+pub mod mock;
+#[cfg(feature = "mock")]
+pub use mock::*;
+
+#[cfg(feature = "control-server")]
+pub mod control;
+#[cfg(feature = "control-server")]
+pub use control::*;
+
+#[cfg(feature = "tauri-plugin")]
+pub mod tauri_plugin_blur;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rect_conversion_keeps_each_field() {
+        // Distinct values, so a swapped pair of fields shows up
+        let rect = RECT {
+            left: -1,
+            top: 2,
+            right: 30,
+            bottom: 400,
+        };
+        let blur: BlurRect = rect.into();
+        assert_eq!(
+            blur,
+            BlurRect {
+                left: -1,
+                top: 2,
+                right: 30,
+                bottom: 400
+            }
+        );
+        let back: RECT = blur.into();
+        assert_eq!(
+            (back.left, back.top, back.right, back.bottom),
+            (-1, 2, 30, 400)
+        );
+    }
+
+    #[test]
+    fn monitor_info_matches_the_c_layout() {
+        use std::mem::{align_of, offset_of, size_of};
+        // BlurMonitorInfo: two BlurRects, then uint32_t dpiX, dpiY and int32_t primary
+        assert_eq!(size_of::<BlurRect>(), 16);
+        assert_eq!(offset_of!(MonitorInfoC, bounds), 0);
+        assert_eq!(offset_of!(MonitorInfoC, work_area), 16);
+        assert_eq!(offset_of!(MonitorInfoC, dpi_x), 32);
+        assert_eq!(offset_of!(MonitorInfoC, dpi_y), 36);
+        assert_eq!(offset_of!(MonitorInfoC, primary), 40);
+        assert_eq!(size_of::<MonitorInfoC>(), 44);
+        assert_eq!(align_of::<MonitorInfoC>(), 4);
+    }
+}
@@ -0,0 +1,95 @@
+use super::*;
+
+/// A preset plus the few parameters an app usually overrides on top of it,
+/// applied together by [`BlurWindow::apply_look`].
+///
+/// With `serde`, the overrides may be left out, e.g.
+/// `{"base": "balanced", "strength": 0.6}`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Look {
+    pub base: BlurQualityPreset,
+    /// `None` keeps the current strength.
+    pub strength: Option<f32>,
+    /// `None` keeps the current tint.
+    pub tint: Option<Color>,
+    /// `None` keeps the current effect.
+    pub effect: Option<BlurEffectType>,
+    /// `None` keeps the current noise settings.
+    pub noise: Option<NoiseParams>,
+}
+
+impl Look {
+    /// `base` without overrides.
+    pub const fn new(base: BlurQualityPreset) -> Self {
+        Look {
+            base,
+            strength: None,
+            tint: None,
+            effect: None,
+            noise: None,
+        }
+    }
+
+    pub const fn with_strength(self, strength: f32) -> Self {
+        Look {
+            strength: Some(strength),
+            ..self
+        }
+    }
+
+    pub const fn with_tint(self, tint: Color) -> Self {
+        Look {
+            tint: Some(tint),
+            ..self
+        }
+    }
+
+    pub const fn with_effect(self, effect: BlurEffectType) -> Self {
+        Look {
+            effect: Some(effect),
+            ..self
+        }
+    }
+
+    pub const fn with_noise(self, noise: NoiseParams) -> Self {
+        Look {
+            noise: Some(noise),
+            ..self
+        }
+    }
+}
+
+impl From<BlurQualityPreset> for Look {
+    fn from(base: BlurQualityPreset) -> Self {
+        Look::new(base)
+    }
+}
+
+impl BlurWindow {
+    /// Switch to `look.base` with [`set_preset`](Self::set_preset), then apply
+    /// the overrides that are `Some`, as one [`batch`](Self::batch): no frame
+    /// shows the preset without its overrides. An override wins over a
+    /// [pinned](Self::pin) parameter.
+    ///
+    /// Stops at the first failing setter; the batch then leaves the window
+    /// as far as it got, like any other failed batch.
+    pub fn apply_look(&self, look: &Look) -> Result<(), BlurErrorCode> {
+        self.batch(|w| {
+            w.set_preset(look.base)?;
+            if let Some(effect) = look.effect {
+                w.set_effect_type(effect)?;
+            }
+            if let Some(strength) = look.strength {
+                w.set_strength(strength)?;
+            }
+            if let Some(tint) = look.tint {
+                w.set_tint_color(tint)?;
+            }
+            if let Some(noise) = &look.noise {
+                w.set_noise(noise)?;
+            }
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,124 @@
+use super::*;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::Duration;
+
+/// Point-in-time health data returned by
+/// [`BlurWindow::metrics`](crate::BlurWindow::metrics).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Metrics {
+    pub fps: f32,
+    /// CPU time spent capturing, processing and presenting the last frame.
+    pub frame_time_ms: f32,
+    /// See [`BlurWindow::latency_ms`](crate::BlurWindow::latency_ms); 0.0
+    /// before graphics are created.
+    pub latency_ms: f32,
+    pub backend: Backend,
+    /// See [`BlurWindow::capture_method`](crate::BlurWindow::capture_method).
+    pub capture_method: CaptureMethod,
+    /// Approximate GPU memory held by this window, see
+    /// [`BlurWindow::vram_usage_bytes`](crate::BlurWindow::vram_usage_bytes).
+    pub vram_bytes: u64,
+    pub running: bool,
+    pub paused: bool,
+    /// See [`BlurWindow::creation_duration`](crate::BlurWindow::creation_duration);
+    /// the system's share is [`BlurSystem::init_duration`](crate::BlurSystem::init_duration).
+    pub creation_duration: Duration,
+    /// See [`BlurWindow::uptime`](crate::BlurWindow::uptime).
+    pub uptime: Option<Duration>,
+    /// The system's [active adapter](crate::BlurSystem::active_adapter_info),
+    /// queried when the window was built; `None` if that failed.
+    pub adapter: Option<AdapterInfo>,
+}
+
+impl BlurWindow {
+    /// Timing of the last [`MAX_FRAME_HISTORY`] presented frames, oldest
+    /// first. The native side keeps them in a ring buffer filled by the
+    /// render loop and [`render_once`](Self::render_once), so this returns
+    /// a copy rather than a live view.
+    pub fn frame_history(&self) -> Vec<FrameSample> {
+        let mut samples = vec![FrameSample::default(); MAX_FRAME_HISTORY];
+        let count = unsafe {
+            blur_get_frame_history(self.handle, samples.as_mut_ptr(), samples.len() as u32)
+        };
+        samples.truncate(count as usize);
+        samples
+    }
+
+    /// Write [`frame_history`](Self::frame_history) to `path` as CSV with a
+    /// `frame_index,frame_time_ms,fps` header, e.g. to attach to a stutter
+    /// report. Overwrites an existing file.
+    pub fn export_frame_history_csv(&self, path: &Path) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        write_frame_history_csv(&mut out, &self.frame_history())?;
+        out.flush()
+    }
+}
+
+fn write_frame_history_csv(out: &mut impl Write, samples: &[FrameSample]) -> io::Result<()> {
+    writeln!(out, "frame_index,frame_time_ms,fps")?;
+    for sample in samples {
+        writeln!(
+            out,
+            "{},{:.3},{:.1}",
+            sample.frame_index, sample.frame_time_ms, sample.fps
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_has_a_header_and_one_row_per_sample() {
+        let samples = [
+            FrameSample {
+                frame_index: 7,
+                frame_time_ms: 16.6667,
+                fps: 59.94,
+            },
+            FrameSample {
+                frame_index: 8,
+                frame_time_ms: 4.0,
+                fps: 60.0,
+            },
+        ];
+        let mut out = Vec::new();
+        write_frame_history_csv(&mut out, &samples).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "frame_index,frame_time_ms,fps\n7,16.667,59.9\n8,4.000,60.0\n"
+        );
+    }
+
+    #[test]
+    fn csv_of_no_samples_is_the_header() {
+        let mut out = Vec::new();
+        write_frame_history_csv(&mut out, &[]).unwrap();
+        assert_eq!(out, b"frame_index,frame_time_ms,fps\n");
+    }
+
+    #[cfg(feature = "mock")]
+    #[test]
+    fn frame_history_keeps_the_newest_samples() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let window = WindowBuilder::new().build(&system).unwrap();
+        let frames = MAX_FRAME_HISTORY as u64 + 5;
+        for _ in 0..frames {
+            window.render_once().unwrap();
+        }
+
+        let history = window.frame_history();
+        assert_eq!(history.len(), MAX_FRAME_HISTORY);
+        assert_eq!(history.first().unwrap().frame_index, 5);
+        assert_eq!(history.last().unwrap().frame_index, frames - 1);
+        assert!(history
+            .windows(2)
+            .all(|pair| pair[1].frame_index == pair[0].frame_index + 1));
+    }
+}
@@ -0,0 +1,835 @@
+// Rust stand-ins for the native API, compiled instead of the extern block
+// when the `mock` feature is on. Nothing is rendered and the native library
+// is not linked; window calls are recorded per window so app tests can assert
+// what was applied.
+
+use super::*;
+use std::collections::{BTreeMap, VecDeque};
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// One native call made by a headless window, e.g. `blur_set_strength(0.5)`.
+///
+/// `function` is the C API name and `args` the arguments after the window
+/// handle, formatted with `{:?}` and separated by `, ` (pointers are
+/// dereferenced, callbacks show as `set` or `none`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedCall {
+    pub function: &'static str,
+    pub args: String,
+}
+
+impl fmt::Display for RecordedCall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}({})", self.function, self.args)
+    }
+}
+
+struct MockWindow {
+    calls: Vec<RecordedCall>,
+    started: bool,
+    active_preset: i32,
+    ex_style: u32,
+    fps: f32,
+    // One sample per render_once, the newest MAX_FRAME_HISTORY kept
+    frames: VecDeque<FrameSample>,
+    next_frame: u64,
+    owner_closed: Option<StoredOwnerClosedCallback>,
+}
+
+type StoredOwnerClosedCallback = (unsafe extern "C" fn(*mut std::ffi::c_void), usize);
+
+static WINDOWS: Mutex<BTreeMap<usize, MockWindow>> = Mutex::new(BTreeMap::new());
+static SYSTEM_CALLS: Mutex<Vec<RecordedCall>> = Mutex::new(Vec::new());
+// Code and message of the last error, then the message handed out by the
+// last clearing read, kept alive like the native thread-local buffer
+static LAST_ERROR: Mutex<(i32, Option<CString>)> = Mutex::new((0, None));
+static CLEARED_ERROR: Mutex<Option<CString>> = Mutex::new(None);
+static NEXT_WINDOW: AtomicUsize = AtomicUsize::new(1);
+static DEFAULT_PRESET: AtomicUsize = AtomicUsize::new(BlurQualityPreset::Balanced as usize);
+
+fn windows() -> std::sync::MutexGuard<'static, BTreeMap<usize, MockWindow>> {
+    WINDOWS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn with_window<R>(window: BlurWindowHandle, f: impl FnOnce(&mut MockWindow) -> R) -> Option<R> {
+    windows().get_mut(&(window.0 as usize)).map(f)
+}
+
+fn record(window: BlurWindowHandle, function: &'static str, args: String) -> BlurErrorCode {
+    with_window(window, |w| w.calls.push(RecordedCall { function, args }))
+        .map_or(BlurErrorCode::InvalidHandle, |()| BlurErrorCode::Ok)
+}
+
+fn system_calls() -> std::sync::MutexGuard<'static, Vec<RecordedCall>> {
+    SYSTEM_CALLS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn record_system(function: &'static str, args: String) {
+    system_calls().push(RecordedCall { function, args });
+}
+
+fn create(opts: Option<&BlurWindowOptionsC>) -> BlurWindowHandle {
+    // WS_EX_NOREDIRECTIONBITMAP, as for the DirectComposition backend
+    let mut ex_style = ExStyleFlags::NOREDIRECTIONBITMAP;
+    if let Some(opts) = opts {
+        ex_style.set(
+            ExStyleFlags::TOPMOST,
+            opts.top_most != 0 && opts.wallpaper == 0,
+        );
+        ex_style.set(
+            ExStyleFlags::TRANSPARENT,
+            opts.click_through != 0 || opts.wallpaper != 0,
+        );
+    }
+    let id = NEXT_WINDOW.fetch_add(1, Ordering::SeqCst);
+    windows().insert(
+        id,
+        MockWindow {
+            calls: Vec::new(),
+            started: false,
+            active_preset: DEFAULT_PRESET.load(Ordering::SeqCst) as i32,
+            ex_style: ex_style.bits(),
+            fps: 0.0,
+            frames: VecDeque::new(),
+            next_frame: 0,
+            owner_closed: None,
+        },
+    );
+    BlurWindowHandle(id as *mut std::ffi::c_void)
+}
+
+fn callback_arg<T>(callback: Option<T>) -> String {
+    if callback.is_some() { "set" } else { "none" }.to_string()
+}
+
+/// Setters that only record their arguments.
+macro_rules! recorded {
+    ($(fn $name:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            pub unsafe extern "C" fn $name(window: BlurWindowHandle, $($arg: $ty),*) -> BlurErrorCode {
+                let args: Vec<String> = vec![$(format!("{:?}", $arg)),*];
+                record(window, stringify!($name), args.join(", "))
+            }
+        )*
+    };
+}
+
+recorded! {
+    fn blur_stop();
+    fn blur_prewarm();
+    fn blur_resize_swapchain(width: u32, height: u32);
+    fn blur_set_buffer_count(count: u32);
+    fn blur_begin_update();
+    fn blur_end_update();
+    fn blur_set_cursor_follow(enabled: i32, radius: f32);
+    fn blur_set_time_mode(mode: i32);
+    fn blur_set_time(seconds: f32);
+    fn blur_set_target_fps(fps: i32);
+    fn blur_set_capture_interval_ms(interval_ms: u32);
+    fn blur_set_min_frame_interval_ms(interval_ms: u32);
+    fn blur_set_debug_overlay(enabled: i32);
+    fn blur_pause();
+    fn blur_resume();
+    fn blur_set_pause_when_occluded(enable: i32);
+    fn blur_set_sync_to_owner_paint(enable: i32);
+    fn blur_freeze();
+    fn blur_unfreeze();
+    fn blur_show();
+    fn blur_hide();
+    fn blur_set_effect_type(effect_type: i32);
+    fn blur_set_strength(strength: f32);
+    fn blur_set_blur_param(param: f32);
+    fn blur_set_kernel_radius(radius: u32);
+    fn blur_set_edge_aa(samples: u32);
+    fn blur_set_edge_feather(pixels: f32);
+    fn blur_set_tone_mapping(mode: i32);
+    fn blur_set_strength_gradient(top: f32, bottom: f32);
+    fn blur_set_blur_xy(x: f32, y: f32);
+    fn blur_set_downsample_xy(x: u32, y: u32);
+    fn blur_set_temporal_mode(mode: i32);
+    fn blur_set_direction(angle_degrees: f32, strength: f32);
+    fn blur_set_tint_color(r: f32, g: f32, b: f32, a: f32);
+    fn blur_set_capture_source(source: HWND);
+    fn blur_set_source_texture(shared_handle: *mut std::ffi::c_void);
+    fn blur_set_background_image(rgba: *const u8, width: u32, height: u32);
+    fn blur_set_noise_intensity(intensity: f32);
+    fn blur_set_noise_scale(scale: f32);
+    fn blur_set_noise_speed(speed: f32);
+    fn blur_set_noise_type(noise_type: i32);
+    fn blur_set_noise_blend_mode(mode: i32);
+    fn blur_set_noise_seed(seed: u32);
+    fn blur_set_rain_intensity(intensity: f32);
+    fn blur_set_rain_drop_speed(speed: f32);
+    fn blur_set_rain_refraction(strength: f32);
+    fn blur_set_rain_trail_length(length: f32);
+    fn blur_set_rain_drop_size(min_size: f32, max_size: f32);
+}
+
+pub unsafe extern "C" fn blur_init(opts: *const BlurSystemOptionsC) -> BlurSystemHandle {
+    match opts.as_ref() {
+        Some(opts) => {
+            DEFAULT_PRESET.store(opts.default_preset as usize, Ordering::SeqCst);
+            let log_path = (!opts.log_path.is_null()).then(|| CStr::from_ptr(opts.log_path));
+            let args = format!(
+                "{:?}, {:?}, {:?}",
+                opts.enable_logging, log_path, opts.default_preset
+            );
+            record_system("blur_init", args);
+        }
+        None => record_system("blur_init", "null".to_string()),
+    }
+    BlurSystemHandle(std::ptr::NonNull::<u8>::dangling().as_ptr().cast())
+}
+
+pub unsafe extern "C" fn blur_shutdown(_sys: BlurSystemHandle) {
+    record_system("blur_shutdown", String::new());
+}
+
+pub unsafe extern "C" fn blur_create_window(
+    _sys: BlurSystemHandle,
+    owner: HWND,
+    opts: *const BlurWindowOptionsC,
+) -> BlurWindowHandle {
+    record_system("blur_create_window", format!("{:?}", owner));
+    create(opts.as_ref())
+}
+
+pub unsafe extern "C" fn blur_create_child_window(
+    _sys: BlurSystemHandle,
+    owner: HWND,
+    relative_bounds: *const BlurRect,
+) -> BlurWindowHandle {
+    record_system(
+        "blur_create_child_window",
+        format!("{:?}, {:?}", owner, relative_bounds.as_ref()),
+    );
+    create(None)
+}
+
+pub unsafe extern "C" fn blur_destroy_window(window: BlurWindowHandle) {
+    windows().remove(&(window.0 as usize));
+}
+
+// Mock windows use their handle value as HWND
+pub unsafe extern "C" fn blur_get_hwnd(window: BlurWindowHandle) -> HWND {
+    match with_window(window, |_| ()) {
+        Some(()) => HWND(window.0),
+        None => HWND::default(),
+    }
+}
+
+pub unsafe extern "C" fn blur_is_blur_window(hwnd: HWND) -> i32 {
+    windows().contains_key(&(hwnd.0 as usize)) as i32
+}
+
+pub unsafe extern "C" fn blur_start(window: BlurWindowHandle) -> BlurErrorCode {
+    with_window(window, |w| w.started = true);
+    record(window, "blur_start", String::new())
+}
+
+pub unsafe extern "C" fn blur_render_once(window: BlurWindowHandle) -> BlurErrorCode {
+    with_window(window, |w| {
+        w.started = true;
+        if w.frames.len() == MAX_FRAME_HISTORY {
+            w.frames.pop_front();
+        }
+        w.frames.push_back(FrameSample {
+            frame_index: w.next_frame,
+            frame_time_ms: 0.0,
+            fps: w.fps,
+        });
+        w.next_frame += 1;
+    });
+    record(window, "blur_render_once", String::new())
+}
+
+pub unsafe extern "C" fn blur_set_preset(
+    window: BlurWindowHandle,
+    preset: BlurQualityPreset,
+) -> BlurErrorCode {
+    with_window(window, |w| w.active_preset = preset as i32);
+    record(window, "blur_set_preset", format!("{:?}", preset))
+}
+
+pub unsafe extern "C" fn blur_get_active_preset(window: BlurWindowHandle) -> i32 {
+    with_window(window, |w| w.active_preset).unwrap_or(BlurQualityPreset::Balanced as i32)
+}
+
+/// Compiles nothing: every snippet is accepted.
+pub unsafe extern "C" fn blur_set_custom_shader(
+    window: BlurWindowHandle,
+    hlsl: *const c_char,
+) -> BlurErrorCode {
+    let hlsl = if hlsl.is_null() {
+        "".into()
+    } else {
+        CStr::from_ptr(hlsl).to_string_lossy()
+    };
+    record(window, "blur_set_custom_shader", format!("{:?}", hlsl))
+}
+
+pub unsafe extern "C" fn blur_set_pipeline(
+    window: BlurWindowHandle,
+    json_config: *const c_char,
+) -> BlurErrorCode {
+    if json_config.is_null() {
+        return BlurErrorCode::InvalidParameter;
+    }
+    let json = CStr::from_ptr(json_config).to_string_lossy();
+    record(window, "blur_set_pipeline", format!("{:?}", json))
+}
+
+pub unsafe extern "C" fn blur_command(
+    window: BlurWindowHandle,
+    command: *const c_char,
+    args: *const c_char,
+) -> BlurErrorCode {
+    if command.is_null() {
+        return BlurErrorCode::InvalidParameter;
+    }
+    let command = CStr::from_ptr(command).to_string_lossy();
+    let args = if args.is_null() {
+        "".into()
+    } else {
+        CStr::from_ptr(args).to_string_lossy()
+    };
+    record(window, "blur_command", format!("{:?}, {:?}", command, args))
+}
+
+pub unsafe extern "C" fn blur_set_bounds(
+    window: BlurWindowHandle,
+    bounds: *const BlurRect,
+) -> BlurErrorCode {
+    match bounds.as_ref() {
+        Some(bounds) => record(window, "blur_set_bounds", format!("{:?}", bounds)),
+        None => BlurErrorCode::InvalidParameter,
+    }
+}
+
+pub unsafe extern "C" fn blur_get_ex_style(window: BlurWindowHandle) -> u32 {
+    with_window(window, |w| w.ex_style).unwrap_or(0)
+}
+
+/// Recorded, and sets `TOPMOST` like the native z-order change.
+pub unsafe extern "C" fn blur_set_taskbar_relation(
+    window: BlurWindowHandle,
+    relation: i32,
+) -> BlurErrorCode {
+    with_window(window, |w| w.ex_style |= ExStyleFlags::TOPMOST.bits());
+    record(
+        window,
+        "blur_set_taskbar_relation",
+        format!("{:?}", relation),
+    )
+}
+
+pub unsafe extern "C" fn blur_set_ex_style(
+    window: BlurWindowHandle,
+    ex_style: u32,
+) -> BlurErrorCode {
+    with_window(window, |w| w.ex_style = ex_style);
+    record(window, "blur_set_ex_style", format!("{:#x}", ex_style))
+}
+
+pub unsafe extern "C" fn blur_set_active_region(
+    window: BlurWindowHandle,
+    region: *const BlurRect,
+) -> BlurErrorCode {
+    record(
+        window,
+        "blur_set_active_region",
+        format!("{:?}", region.as_ref()),
+    )
+}
+
+pub unsafe extern "C" fn blur_set_region_effect(
+    window: BlurWindowHandle,
+    region: *const BlurRect,
+    effect_type: i32,
+) -> BlurErrorCode {
+    record(
+        window,
+        "blur_set_region_effect",
+        format!("{:?}, {}", region.as_ref(), effect_type),
+    )
+}
+
+pub unsafe extern "C" fn blur_set_tint_layers(
+    window: BlurWindowHandle,
+    layers: *const Color,
+    count: u32,
+) -> BlurErrorCode {
+    let layers = if layers.is_null() {
+        &[][..]
+    } else {
+        std::slice::from_raw_parts(layers, count as usize)
+    };
+    record(window, "blur_set_tint_layers", format!("{:?}", layers))
+}
+
+pub unsafe extern "C" fn blur_set_fallback_color(
+    window: BlurWindowHandle,
+    color: *const Color,
+) -> BlurErrorCode {
+    record(
+        window,
+        "blur_set_fallback_color",
+        format!("{:?}", color.as_ref()),
+    )
+}
+
+pub unsafe extern "C" fn blur_set_noise_color(
+    window: BlurWindowHandle,
+    color: *const Color,
+) -> BlurErrorCode {
+    record(
+        window,
+        "blur_set_noise_color",
+        format!("{:?}", color.as_ref()),
+    )
+}
+
+pub unsafe extern "C" fn blur_set_click_callback(
+    window: BlurWindowHandle,
+    callback: BlurClickCallback,
+    _user_data: *mut std::ffi::c_void,
+) -> BlurErrorCode {
+    record(window, "blur_set_click_callback", callback_arg(callback))
+}
+
+pub unsafe extern "C" fn blur_set_frame_callback(
+    window: BlurWindowHandle,
+    callback: BlurFrameCallback,
+    _user_data: *mut std::ffi::c_void,
+) -> BlurErrorCode {
+    record(window, "blur_set_frame_callback", callback_arg(callback))
+}
+
+/// Stored so [`BlurWindow::simulate_owner_closed`] can call it.
+pub unsafe extern "C" fn blur_set_owner_closed_callback(
+    window: BlurWindowHandle,
+    callback: BlurOwnerClosedCallback,
+    user_data: *mut std::ffi::c_void,
+) -> BlurErrorCode {
+    with_window(window, |w| {
+        w.owner_closed = callback.map(|f| (f, user_data as usize));
+    });
+    record(
+        window,
+        "blur_set_owner_closed_callback",
+        callback_arg(callback),
+    )
+}
+
+pub unsafe extern "C" fn blur_set_occlusion_changed_callback(
+    window: BlurWindowHandle,
+    callback: BlurOcclusionChangedCallback,
+    _user_data: *mut std::ffi::c_void,
+) -> BlurErrorCode {
+    record(
+        window,
+        "blur_set_occlusion_changed_callback",
+        callback_arg(callback),
+    )
+}
+
+pub unsafe extern "C" fn blur_set_display_changed_callback(
+    window: BlurWindowHandle,
+    callback: BlurDisplayChangedCallback,
+    _user_data: *mut std::ffi::c_void,
+) -> BlurErrorCode {
+    record(
+        window,
+        "blur_set_display_changed_callback",
+        callback_arg(callback),
+    )
+}
+
+pub unsafe extern "C" fn blur_set_power_changed_callback(
+    window: BlurWindowHandle,
+    callback: BlurPowerChangedCallback,
+    _user_data: *mut std::ffi::c_void,
+) -> BlurErrorCode {
+    record(
+        window,
+        "blur_set_power_changed_callback",
+        callback_arg(callback),
+    )
+}
+
+// Queries report an idle window; they are not recorded.
+
+pub unsafe extern "C" fn blur_get_fps(window: BlurWindowHandle) -> f32 {
+    with_window(window, |w| w.fps).unwrap_or(0.0)
+}
+
+pub unsafe extern "C" fn blur_get_display_refresh_hz(_window: BlurWindowHandle) -> f32 {
+    60.0
+}
+
+pub unsafe extern "C" fn blur_get_frame_time(_window: BlurWindowHandle) -> f32 {
+    0.0
+}
+
+pub unsafe extern "C" fn blur_get_latency_ms(_window: BlurWindowHandle) -> f32 {
+    0.0
+}
+
+pub unsafe extern "C" fn blur_get_backend(window: BlurWindowHandle) -> i32 {
+    match with_window(window, |w| w.started) {
+        Some(true) => Backend::DirectComposition as i32,
+        _ => Backend::None as i32,
+    }
+}
+
+/// Mid grey once the window has rendered.
+pub unsafe extern "C" fn blur_sample_dominant_color(
+    window: BlurWindowHandle,
+    out_color: *mut Color,
+) -> BlurErrorCode {
+    let Some(out_color) = out_color.as_mut() else {
+        return BlurErrorCode::InvalidParameter;
+    };
+    match with_window(window, |w| w.started) {
+        Some(true) => {
+            *out_color = Color::new(0.5, 0.5, 0.5, 1.0);
+            BlurErrorCode::Ok
+        }
+        Some(false) => BlurErrorCode::NotInitialized,
+        None => BlurErrorCode::InvalidHandle,
+    }
+}
+
+pub unsafe extern "C" fn blur_get_capture_status(_window: BlurWindowHandle) -> i32 {
+    CaptureStatus::Ok as i32
+}
+
+pub unsafe extern "C" fn blur_get_capture_method(window: BlurWindowHandle) -> i32 {
+    match with_window(window, |w| w.started) {
+        Some(true) => CaptureMethod::DesktopDuplication as i32,
+        _ => CaptureMethod::None as i32,
+    }
+}
+
+pub unsafe extern "C" fn blur_set_preferred_capture_method(
+    _sys: BlurSystemHandle,
+    method: i32,
+) -> BlurErrorCode {
+    if (0..=2).contains(&method) {
+        BlurErrorCode::Ok
+    } else {
+        BlurErrorCode::InvalidParameter
+    }
+}
+
+pub unsafe extern "C" fn blur_get_capture_format(_window: BlurWindowHandle) -> i32 {
+    CaptureFormat::Srgb8 as i32
+}
+
+pub unsafe extern "C" fn blur_get_capture_rect(
+    window: BlurWindowHandle,
+    out_rect: *mut BlurRect,
+) -> BlurErrorCode {
+    if out_rect.is_null() {
+        return BlurErrorCode::InvalidParameter;
+    }
+    // Nothing is ever captured
+    with_window(window, |_| BlurErrorCode::CaptureFailed).unwrap_or(BlurErrorCode::InvalidHandle)
+}
+
+pub unsafe extern "C" fn blur_is_occluded(_window: BlurWindowHandle) -> i32 {
+    0
+}
+
+pub unsafe extern "C" fn blur_get_vram_usage(_window: BlurWindowHandle) -> u64 {
+    0
+}
+
+pub unsafe extern "C" fn blur_get_frame_history(
+    window: BlurWindowHandle,
+    out: *mut FrameSample,
+    capacity: u32,
+) -> u32 {
+    with_window(window, |w| {
+        if out.is_null() {
+            return w.frames.len() as u32;
+        }
+        // The newest `capacity` samples, oldest first
+        let skip = w.frames.len().saturating_sub(capacity as usize);
+        let mut count = 0;
+        for (i, sample) in w.frames.iter().skip(skip).enumerate() {
+            *out.add(i) = *sample;
+            count += 1;
+        }
+        count
+    })
+    .unwrap_or(0)
+}
+
+pub unsafe extern "C" fn blur_is_debug_build() -> i32 {
+    0
+}
+
+pub unsafe extern "C" fn blur_is_dwm_enabled() -> i32 {
+    1
+}
+
+pub unsafe extern "C" fn blur_get_system_theme() -> i32 {
+    SystemTheme::Light as i32
+}
+
+/// Accepted, but a headless system never reports a theme switch.
+pub unsafe extern "C" fn blur_set_theme_changed_callback(
+    _sys: BlurSystemHandle,
+    _callback: BlurThemeChangedCallback,
+    _user_data: *mut std::ffi::c_void,
+) -> BlurErrorCode {
+    BlurErrorCode::Ok
+}
+
+/// Stored so [`BlurSystem::simulate_fullscreen_change`] can call it.
+pub unsafe extern "C" fn blur_set_fullscreen_changed_callback(
+    _sys: BlurSystemHandle,
+    callback: BlurFullscreenChangedCallback,
+    user_data: *mut std::ffi::c_void,
+) -> BlurErrorCode {
+    *fullscreen_callback() = callback.map(|f| (f, user_data as usize));
+    BlurErrorCode::Ok
+}
+
+type StoredFullscreenCallback = (unsafe extern "C" fn(i32, *mut std::ffi::c_void), usize);
+
+static FULLSCREEN_CALLBACK: Mutex<Option<StoredFullscreenCallback>> = Mutex::new(None);
+
+fn fullscreen_callback() -> std::sync::MutexGuard<'static, Option<StoredFullscreenCallback>> {
+    FULLSCREEN_CALLBACK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+pub unsafe extern "C" fn blur_get_supported_algorithms(_sys: BlurSystemHandle) -> u32 {
+    BlurAlgorithm::ALL
+        .into_iter()
+        .fold(TemporalMode::SUPPORT_BIT, |mask, a| mask | a.support_bit())
+}
+
+pub unsafe extern "C" fn blur_get_max_kernel_radius(sys: BlurSystemHandle) -> u32 {
+    if sys.0.is_null() {
+        0
+    } else {
+        MAX_KERNEL_RADIUS
+    }
+}
+
+/// A single 1920x1080 primary monitor at 100% scaling.
+pub unsafe extern "C" fn blur_get_monitor_count() -> u32 {
+    1
+}
+
+pub unsafe extern "C" fn blur_get_monitor_info(
+    index: u32,
+    out: *mut MonitorInfoC,
+) -> BlurErrorCode {
+    if index != 0 || out.is_null() {
+        return BlurErrorCode::InvalidParameter;
+    }
+    let bounds = BlurRect {
+        left: 0,
+        top: 0,
+        right: 1920,
+        bottom: 1080,
+    };
+    *out = MonitorInfoC {
+        bounds,
+        work_area: BlurRect {
+            bottom: 1032,
+            ..bounds
+        },
+        dpi_x: 96,
+        dpi_y: 96,
+        primary: 1,
+    };
+    BlurErrorCode::Ok
+}
+
+/// One hardware adapter, the system's.
+pub unsafe extern "C" fn blur_get_adapter_info(
+    sys: BlurSystemHandle,
+    index: u32,
+    out: *mut AdapterInfoC,
+) -> BlurErrorCode {
+    if sys.0.is_null() {
+        return BlurErrorCode::InvalidHandle;
+    }
+    if index != 0 || out.is_null() {
+        return BlurErrorCode::InvalidParameter;
+    }
+    *out = AdapterInfoC {
+        vendor_id: 0x1414,
+        device_id: 0,
+        dedicated_video_memory: 1 << 30,
+        driver_version: 0,
+        software: 0,
+        active: 1,
+    };
+    BlurErrorCode::Ok
+}
+
+pub unsafe extern "C" fn blur_get_adapter_description(
+    sys: BlurSystemHandle,
+    index: u32,
+    buf: *mut c_char,
+    len: *mut u32,
+) -> BlurErrorCode {
+    const NAME: &[u8] = b"Mock Adapter\0";
+    if sys.0.is_null() {
+        return BlurErrorCode::InvalidHandle;
+    }
+    if index != 0 || len.is_null() {
+        return BlurErrorCode::InvalidParameter;
+    }
+    let capacity = *len as usize;
+    *len = NAME.len() as u32;
+    if buf.is_null() || capacity < NAME.len() {
+        return BlurErrorCode::InvalidParameter;
+    }
+    std::ptr::copy_nonoverlapping(NAME.as_ptr() as *const c_char, buf, NAME.len());
+    BlurErrorCode::Ok
+}
+
+/// Accepts every string; the wrapper's own checks still run.
+pub unsafe extern "C" fn blur_validate_pipeline(
+    sys: BlurSystemHandle,
+    _json_config: *const c_char,
+) -> BlurErrorCode {
+    if sys.0.is_null() {
+        BlurErrorCode::InvalidHandle
+    } else {
+        BlurErrorCode::Ok
+    }
+}
+
+fn last_error_slot() -> std::sync::MutexGuard<'static, (i32, Option<CString>)> {
+    LAST_ERROR.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+pub unsafe extern "C" fn blur_get_last_error() -> *const c_char {
+    last_error_slot()
+        .1
+        .as_ref()
+        .map_or(std::ptr::null(), |message| message.as_ptr())
+}
+
+pub unsafe extern "C" fn blur_get_last_error_and_clear() -> *const c_char {
+    let message = std::mem::take(&mut *last_error_slot()).1;
+    let mut cleared = CLEARED_ERROR.lock().unwrap_or_else(|e| e.into_inner());
+    *cleared = message;
+    cleared
+        .as_ref()
+        .map_or(std::ptr::null(), |message| message.as_ptr())
+}
+
+pub unsafe extern "C" fn blur_get_last_error_code() -> i32 {
+    last_error_slot().0
+}
+
+impl BlurSystem {
+    /// A system for unit tests that needs neither a GPU nor the native
+    /// library. Windows it creates render nothing and record their native
+    /// calls, see [`BlurWindow::call_log`].
+    ///
+    /// With the `mock` feature every system is headless, so this is the same
+    /// as a successful [`BlurSystem::new`].
+    pub fn headless() -> BlurSystem {
+        let handle = unsafe { blur_init(std::ptr::null()) };
+        BlurSystem {
+            handle,
+            init_duration: Duration::ZERO,
+            theme_changed: Mutex::new(None),
+            fullscreen_changed: Mutex::new(None),
+            config: BlurSystemConfig {
+                logging: false,
+                log_path: None,
+                default_preset: BlurQualityPreset::Balanced,
+                capture_method: CapturePreference::Auto,
+            },
+        }
+    }
+}
+
+impl BlurSystem {
+    /// Report a fullscreen app coming to the foreground (or leaving) to the
+    /// handler registered with [`on_fullscreen_app_detected`](Self::on_fullscreen_app_detected),
+    /// as the native watcher would. Runs it on the calling thread.
+    pub fn simulate_fullscreen_change(&self, fullscreen: bool) {
+        let callback = *fullscreen_callback();
+        if let Some((f, user_data)) = callback {
+            unsafe { f(fullscreen as i32, user_data as *mut std::ffi::c_void) };
+        }
+    }
+}
+
+/// System-level native calls made so far by any system, oldest first:
+/// `blur_init`, `blur_shutdown` and window creation. Window calls are in
+/// [`BlurWindow::call_log`].
+pub fn recorded_calls() -> Vec<RecordedCall> {
+    system_calls().clone()
+}
+
+pub fn clear_recorded_calls() {
+    system_calls().clear();
+}
+
+/// Set the native last error, as if a native call had just failed with
+/// `code` and `message`, for testing how an app reports errors. Read it back
+/// with [`last_error`] or [`last_error_detail`].
+pub fn simulate_error(code: BlurErrorCode, message: &str) {
+    let message = CString::new(message).expect("error message contains a NUL byte");
+    *last_error_slot() = (code as i32, Some(message));
+}
+
+/// Calls recorded for `window`. Destroying a window drops its log, so only
+/// leaked windows, such as the one a `Watchdog` replaces, keep theirs.
+pub(crate) fn call_log_of(window: BlurWindowHandle) -> Vec<RecordedCall> {
+    with_window(window, |w| w.calls.clone()).unwrap_or_default()
+}
+
+/// Serializes tests that depend on process-wide mock state: the window
+/// registry (`pause_all` reaches every window), the last error and the
+/// fullscreen callback.
+#[cfg(test)]
+pub(crate) fn serial() -> std::sync::MutexGuard<'static, ()> {
+    static LOCK: Mutex<()> = Mutex::new(());
+    LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl BlurWindow {
+    /// Native calls this headless window has made so far, oldest first.
+    /// Queries (`blur_get_*`) are not included.
+    pub fn call_log(&self) -> Vec<RecordedCall> {
+        call_log_of(self.handle)
+    }
+
+    pub fn clear_call_log(&self) {
+        with_window(self.handle, |w| w.calls.clear());
+    }
+
+    /// Make [`get_fps`](Self::get_fps) report `fps` from now on, as if the
+    /// window rendered at that rate. Headless windows report 0.0 until then.
+    pub fn simulate_fps(&self, fps: f32) {
+        with_window(self.handle, |w| w.fps = fps);
+    }
+
+    /// Report the owner window as destroyed, as the native side would: runs
+    /// the callback registered for it on the calling thread.
+    pub fn simulate_owner_closed(&self) {
+        let callback = with_window(self.handle, |w| w.owner_closed).flatten();
+        if let Some((f, user_data)) = callback {
+            unsafe { f(user_data as *mut std::ffi::c_void) };
+        }
+    }
+}
@@ -0,0 +1,286 @@
+use super::*;
+use std::sync::{Arc, Mutex};
+use windows::Win32::Foundation::{BOOL, LPARAM, POINT, RECT};
+use windows::Win32::Graphics::Gdi::{
+    EnumDisplayMonitors, GetMonitorInfoW, MonitorFromPoint, MonitorFromRect, HDC, HMONITOR,
+    MONITORINFO, MONITOR_DEFAULTTONEAREST, MONITOR_DEFAULTTOPRIMARY,
+};
+use windows::Win32::UI::WindowsAndMessaging::GetCursorPos;
+
+/// Which monitor [`BlurSystem::create_fullscreen`] covers.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MonitorSelector {
+    /// The primary monitor, the one holding the taskbar's start button.
+    Primary,
+    /// The `n`th monitor in the order Windows enumerates them, starting at 0.
+    /// The order is stable while the display setup doesn't change but needn't
+    /// match the numbers in Display settings.
+    Index(u32),
+    /// The monitor under the mouse cursor, or the nearest one.
+    ContainingCursor,
+}
+
+impl MonitorSelector {
+    /// The selected monitor's bounds in physical screen coordinates, the
+    /// space [`WindowBuilder::bounds`] uses. `None` if the index is out of
+    /// range or the monitor can't be queried.
+    pub fn bounds(self) -> Option<BlurRect> {
+        let monitor = match self {
+            MonitorSelector::Primary => unsafe {
+                MonitorFromPoint(POINT { x: 0, y: 0 }, MONITOR_DEFAULTTOPRIMARY)
+            },
+            MonitorSelector::Index(n) => *enum_monitors()?.get(n as usize)?,
+            MonitorSelector::ContainingCursor => {
+                let mut cursor = POINT::default();
+                unsafe { GetCursorPos(&mut cursor) }.ok()?;
+                unsafe { MonitorFromPoint(cursor, MONITOR_DEFAULTTONEAREST) }
+            }
+        };
+        monitor_rect(monitor)
+    }
+}
+
+/// One monitor of the desktop, see [`BlurSystem::monitors`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MonitorInfo {
+    /// The whole monitor in physical screen coordinates, the space
+    /// [`WindowBuilder::bounds`] uses.
+    pub bounds: BlurRect,
+    /// `bounds` minus the taskbar and docked app bars.
+    pub work_area: BlurRect,
+    /// Effective DPI, 96 at 100% scaling.
+    pub dpi_x: u32,
+    pub dpi_y: u32,
+    pub primary: bool,
+}
+
+impl MonitorInfo {
+    /// The display scale factor, e.g. 1.5 at 150%.
+    pub fn scale(&self) -> f32 {
+        self.dpi_x as f32 / 96.0
+    }
+}
+
+impl From<MonitorInfoC> for MonitorInfo {
+    fn from(raw: MonitorInfoC) -> Self {
+        MonitorInfo {
+            bounds: raw.bounds,
+            work_area: raw.work_area,
+            dpi_x: raw.dpi_x,
+            dpi_y: raw.dpi_y,
+            primary: raw.primary != 0,
+        }
+    }
+}
+
+fn monitor_rect(monitor: HMONITOR) -> Option<BlurRect> {
+    if monitor.is_invalid() {
+        return None;
+    }
+    let mut info = MONITORINFO {
+        cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+        ..Default::default()
+    };
+    if !unsafe { GetMonitorInfoW(monitor, &mut info) }.as_bool() {
+        return None;
+    }
+    let r = info.rcMonitor;
+    Some(BlurRect {
+        left: r.left,
+        top: r.top,
+        right: r.right,
+        bottom: r.bottom,
+    })
+}
+
+/// Every monitor in enumeration order, `None` if Windows fails to list them.
+fn enum_monitors() -> Option<Vec<HMONITOR>> {
+    unsafe extern "system" fn push(
+        monitor: HMONITOR,
+        _hdc: HDC,
+        _clip: *mut RECT,
+        data: LPARAM,
+    ) -> BOOL {
+        (*(data.0 as *mut Vec<HMONITOR>)).push(monitor);
+        BOOL(1)
+    }
+
+    let mut monitors: Vec<HMONITOR> = Vec::new();
+    let listed = unsafe {
+        EnumDisplayMonitors(
+            HDC::default(),
+            None,
+            Some(push),
+            LPARAM(&mut monitors as *mut Vec<HMONITOR> as isize),
+        )
+    };
+    listed.as_bool().then_some(monitors)
+}
+
+impl BlurSystem {
+    /// Every monitor of the desktop, in the order [`MonitorSelector::Index`]
+    /// counts them, e.g. for an app's own placement UI.
+    ///
+    /// A snapshot: monitors added, removed or rearranged later aren't
+    /// reflected, so list them again from
+    /// [`BlurWindow::on_display_changed`]. A monitor unplugged while the
+    /// list is read ends it early.
+    pub fn monitors(&self) -> Vec<MonitorInfo> {
+        let count = unsafe { blur_get_monitor_count() };
+        let mut monitors = Vec::with_capacity(count as usize);
+        for index in 0..count {
+            let mut raw = MonitorInfoC::default();
+            if unsafe { blur_get_monitor_info(index, &mut raw) } != BlurErrorCode::Ok {
+                break;
+            }
+            monitors.push(raw.into());
+        }
+        monitors
+    }
+
+    /// Create a blur window covering a whole monitor, top-most and
+    /// click-through like [`WindowBuilder::new`]. For other options, build
+    /// one from [`MonitorSelector::bounds`] instead.
+    ///
+    /// The bounds are read once: the window doesn't follow the monitor when its
+    /// resolution or arrangement changes, or when it's unplugged. Build it with
+    /// [`WindowBuilder::auto_refit_on_display_change`] for that, or re-fit it
+    /// from [`BlurWindow::on_display_changed`]; either way a re-fit tracks the
+    /// monitor under the window, not `monitor`.
+    pub fn create_fullscreen(&self, monitor: MonitorSelector) -> Result<BlurWindow, String> {
+        let rect = monitor
+            .bounds()
+            .ok_or_else(|| format!("No monitor matches {:?}", monitor))?;
+        let (w, h) = rect.size();
+        WindowBuilder::new()
+            .bounds(rect.left, rect.top, w, h)
+            .build(self)
+    }
+}
+
+pub(crate) type DisplayChangedFn = Box<dyn Fn() + Send>;
+
+/// What runs on a display change: the builder's re-fit, then the handler.
+pub(crate) struct DisplayChanged {
+    refit: Option<Refit>,
+    handler: Option<DisplayChangedFn>,
+}
+
+struct Refit {
+    // Raw handle value; the handle itself isn't Send
+    handle: usize,
+    state: Arc<Mutex<WindowState>>,
+    resize_swapchain: bool,
+}
+
+impl Refit {
+    /// Fit the window to the monitor it overlaps most, resizing the swap
+    /// chain as [`BlurWindow::set_bounds`] does.
+    fn run(&self) {
+        let handle = BlurWindowHandle(self.handle as *mut _);
+        let old = self.state.lock().unwrap_or_else(|e| e.into_inner()).bounds;
+        let rect = RECT {
+            left: old.left,
+            top: old.top,
+            right: old.right,
+            bottom: old.bottom,
+        };
+        let Some(bounds) =
+            monitor_rect(unsafe { MonitorFromRect(&rect, MONITOR_DEFAULTTONEAREST) })
+        else {
+            return;
+        };
+        if bounds == old || unsafe { blur_set_bounds(handle, &bounds) } != BlurErrorCode::Ok {
+            return;
+        }
+        // Not held across the calls above, which may dispatch messages
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).bounds = bounds;
+        if self.resize_swapchain && bounds.size() != old.size() {
+            let (width, height) = bounds.size();
+            unsafe { blur_resize_swapchain(handle, width as u32, height as u32) };
+        }
+    }
+}
+
+unsafe extern "C" fn display_changed_trampoline(user_data: *mut std::ffi::c_void) {
+    let changed = &*(user_data as *const DisplayChanged);
+    // Unwinding into native code is undefined behavior
+    let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        if let Some(refit) = &changed.refit {
+            refit.run();
+        }
+        if let Some(handler) = &changed.handler {
+            handler();
+        }
+    }));
+}
+
+impl BlurWindow {
+    /// Call `f` after a monitor is added, removed or changes resolution or
+    /// arrangement, e.g. to move the window with [`set_bounds`](Self::set_bounds)
+    /// and [`MonitorSelector::bounds`]. Replaces any previous handler; a
+    /// [`WindowBuilder::auto_refit_on_display_change`] re-fit keeps running,
+    /// and has already moved the window when `f` is called.
+    ///
+    /// `f` runs on the window's thread while it dispatches messages (the
+    /// app's message loop, or [`OwnedWindowThread`]'s), so windows whose
+    /// thread never pumps aren't notified; neither are child windows. `f`
+    /// must not call `on_display_changed` or
+    /// [`off_display_changed`](Self::off_display_changed).
+    pub fn on_display_changed(&self, f: impl Fn() + Send + 'static) -> Result<(), BlurErrorCode> {
+        self.set_display_changed(Some(Box::new(f)))
+    }
+
+    /// Remove the handler installed by [`on_display_changed`](Self::on_display_changed),
+    /// keeping any automatic re-fit.
+    pub fn off_display_changed(&self) -> Result<(), BlurErrorCode> {
+        if self.options().auto_refit_on_display_change {
+            return self.set_display_changed(None);
+        }
+        let mut slot = self
+            .display_changed
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let code =
+            unsafe { blur_set_display_changed_callback(self.handle, None, std::ptr::null_mut()) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        *slot = None;
+        Ok(())
+    }
+
+    /// Register `handler` natively, together with the re-fit when the window
+    /// was built with it.
+    pub(crate) fn set_display_changed(
+        &self,
+        handler: Option<DisplayChangedFn>,
+    ) -> Result<(), BlurErrorCode> {
+        let options = self.options();
+        let refit = options.auto_refit_on_display_change.then(|| Refit {
+            handle: self.handle.0 as usize,
+            state: Arc::clone(&self.state),
+            resize_swapchain: options.auto_resize_swapchain,
+        });
+        let changed = Box::new(DisplayChanged { refit, handler });
+        let user_data = &*changed as *const DisplayChanged as *mut std::ffi::c_void;
+        let mut slot = self
+            .display_changed
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let code = unsafe {
+            blur_set_display_changed_callback(
+                self.handle,
+                Some(display_changed_trampoline),
+                user_data,
+            )
+        };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        // The native side no longer calls the old handler once the setter returns
+        *slot = Some(changed);
+        Ok(())
+    }
+}
@@ -0,0 +1,178 @@
+use super::*;
+use std::marker::PhantomData;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use windows::Win32::UI::WindowsAndMessaging::{
+    DispatchMessageW, PeekMessageW, TranslateMessage, MSG, PM_REMOVE,
+};
+
+type Command = Box<dyn FnOnce(&BlurWindow) + Send>;
+
+/// How long the owner thread waits for a command before pumping messages again.
+const PUMP_INTERVAL: Duration = Duration::from_millis(10);
+
+// The system handle points at the native singleton, and the borrow held by
+// `OwnedWindowThread` keeps it alive for as long as the thread runs. The
+// builder's owner HWND is only passed through to `blur_create_window`.
+struct Creation {
+    system: BlurSystemHandle,
+    builder: WindowBuilder,
+}
+unsafe impl Send for Creation {}
+
+impl Creation {
+    fn build(self) -> Result<BlurWindow, String> {
+        self.builder.build_on(self.system)
+    }
+}
+
+/// A [`BlurWindow`] living on a dedicated thread that owns its HWND and runs
+/// its message pump.
+///
+/// Win32 windows are thread-affine, so instead of touching the window from
+/// arbitrary threads, every call is sent over a channel and executed on the
+/// owner thread. Each method blocks until the owner thread has run it.
+/// Dropping the wrapper destroys the window on its own thread and joins it.
+pub struct OwnedWindowThread<'a> {
+    sender: Option<Sender<Command>>,
+    thread: Option<JoinHandle<()>>,
+    // Ties the wrapper to the system's lifetime without inheriting
+    // `BlurSystem`'s lack of `Sync`, so the wrapper itself is `Send + Sync`.
+    _system: PhantomData<fn() -> &'a BlurSystem>,
+}
+
+impl<'a> OwnedWindowThread<'a> {
+    /// Spawn the owner thread and build the window on it.
+    pub fn spawn(system: &'a BlurSystem, builder: WindowBuilder) -> Result<Self, String> {
+        let creation = Creation {
+            system: system.handle,
+            builder,
+        };
+        let (sender, receiver) = mpsc::channel::<Command>();
+        let (ready_tx, ready_rx) = mpsc::channel::<Result<(), String>>();
+
+        let thread = thread::Builder::new()
+            .name("blur-window".into())
+            .spawn(move || {
+                let window = match creation.build() {
+                    Ok(window) => {
+                        let _ = ready_tx.send(Ok(()));
+                        window
+                    }
+                    Err(e) => {
+                        let _ = ready_tx.send(Err(e));
+                        return;
+                    }
+                };
+
+                loop {
+                    pump_messages();
+                    match receiver.recv_timeout(PUMP_INTERVAL) {
+                        Ok(command) => command(&window),
+                        Err(RecvTimeoutError::Timeout) => {}
+                        // The wrapper was dropped; `window` is destroyed here
+                        Err(RecvTimeoutError::Disconnected) => break,
+                    }
+                }
+            })
+            .map_err(|e| format!("Failed to spawn window thread: {}", e))?;
+
+        match ready_rx.recv() {
+            Ok(Ok(())) => Ok(OwnedWindowThread {
+                sender: Some(sender),
+                thread: Some(thread),
+                _system: PhantomData,
+            }),
+            Ok(Err(e)) => {
+                let _ = thread.join();
+                Err(e)
+            }
+            Err(_) => {
+                let _ = thread.join();
+                Err("Window thread exited during creation".into())
+            }
+        }
+    }
+
+    /// Run `f` against the window on the owner thread and return its result.
+    ///
+    /// Returns `None` if the owner thread is gone (e.g. `f` panicked earlier).
+    pub fn with<R, F>(&self, f: F) -> Option<R>
+    where
+        R: Send + 'static,
+        F: FnOnce(&BlurWindow) -> R + Send + 'static,
+    {
+        let (reply_tx, reply_rx) = mpsc::channel();
+        let command: Command = Box::new(move |window| {
+            let _ = reply_tx.send(f(window));
+        });
+        self.sender.as_ref()?.send(command).ok()?;
+        reply_rx.recv().ok()
+    }
+
+    // A dead owner thread means the window is gone
+    fn call<F>(&self, f: F) -> Result<(), BlurErrorCode>
+    where
+        F: FnOnce(&BlurWindow) -> Result<(), BlurErrorCode> + Send + 'static,
+    {
+        self.with(f).unwrap_or(Err(BlurErrorCode::InvalidHandle))
+    }
+
+    pub fn start(&self) -> Result<(), BlurErrorCode> {
+        self.call(|w| w.start())
+    }
+
+    pub fn stop(&self) -> Result<(), BlurErrorCode> {
+        self.call(|w| w.stop())
+    }
+
+    pub fn set_strength(&self, strength: f32) -> Result<(), BlurErrorCode> {
+        self.call(move |w| w.set_strength(strength))
+    }
+
+    pub fn set_bounds(&self, bounds: BlurRect) -> Result<(), BlurErrorCode> {
+        self.call(move |w| w.set_bounds(bounds))
+    }
+
+    pub fn set_effect_type(&self, effect: BlurEffectType) -> Result<(), BlurErrorCode> {
+        self.call(move |w| w.set_effect_type(effect))
+    }
+
+    pub fn set_tint_color(&self, color: Color) -> Result<(), BlurErrorCode> {
+        self.call(move |w| w.set_tint_color(color))
+    }
+
+    pub fn set_noise(&self, noise: NoiseParams) -> Result<(), BlurErrorCode> {
+        self.call(move |w| w.set_noise(&noise))
+    }
+
+    pub fn apply_look(&self, look: Look) -> Result<(), BlurErrorCode> {
+        self.call(move |w| w.apply_look(&look))
+    }
+
+    pub fn snapshot(&self) -> Result<WindowState, BlurErrorCode> {
+        self.with(|w| w.snapshot())
+            .ok_or(BlurErrorCode::InvalidHandle)
+    }
+}
+
+impl Drop for OwnedWindowThread<'_> {
+    fn drop(&mut self) {
+        // Closing the channel ends the loop, which destroys the window on its thread
+        self.sender.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn pump_messages() {
+    let mut msg = MSG::default();
+    unsafe {
+        while PeekMessageW(&mut msg, HWND::default(), 0, 0, PM_REMOVE).as_bool() {
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+    }
+}
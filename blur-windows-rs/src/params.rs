@@ -0,0 +1,264 @@
+use super::*;
+
+/// A tunable parameter with a fixed valid range, see [`param_range`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ParamKind {
+    /// [`BlurWindow::set_strength`](crate::BlurWindow::set_strength).
+    Strength,
+    /// Effect parameter for Gaussian (sigma).
+    GaussianSigma,
+    /// Effect parameter for Box (radius in pixels).
+    BoxRadius,
+    /// Effect parameter for Kawase (iterations).
+    KawaseIterations,
+    /// Effect parameter for Radial (zoom amount).
+    RadialAmount,
+    /// [`BlurWindow::set_kernel_radius`](crate::BlurWindow::set_kernel_radius), 0 = derived.
+    KernelRadius,
+    NoiseIntensity,
+    NoiseScale,
+    NoiseSpeed,
+    /// Drop density of the rain effect.
+    RainIntensity,
+    /// [`BlurWindow::set_directional_blur`](crate::BlurWindow::set_directional_blur), 0 = off.
+    DirectionStrength,
+}
+
+impl ParamKind {
+    /// The kind behind [`BlurWindow::set_blur_param`](crate::BlurWindow::set_blur_param)
+    /// for `effect`, or `None` if the effect has no such parameter.
+    pub fn blur_param(effect: BlurEffectType) -> Option<ParamKind> {
+        match effect {
+            BlurEffectType::Gaussian => Some(ParamKind::GaussianSigma),
+            BlurEffectType::Box => Some(ParamKind::BoxRadius),
+            BlurEffectType::Kawase => Some(ParamKind::KawaseIterations),
+            BlurEffectType::Radial => Some(ParamKind::RadialAmount),
+            BlurEffectType::Rain => None,
+        }
+    }
+}
+
+/// Inclusive bounds and default of a [`ParamKind`], e.g. for configuring a slider.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ParamRange {
+    pub min: f32,
+    pub max: f32,
+    pub default: f32,
+}
+
+impl ParamRange {
+    pub fn clamp(&self, value: f32) -> f32 {
+        value.clamp(self.min, self.max)
+    }
+
+    pub fn contains(&self, value: f32) -> bool {
+        (self.min..=self.max).contains(&value)
+    }
+}
+
+/// Valid range of `param`. These mirror the native clamps, and the safe
+/// setters clamp through this table so cached state matches what renders.
+pub fn param_range(param: ParamKind) -> ParamRange {
+    let (min, max, default) = match param {
+        ParamKind::Strength => (0.0, 1.0, 1.0),
+        // Default is the Balanced preset's sigma
+        ParamKind::GaussianSigma => (0.1, 50.0, 5.0),
+        ParamKind::BoxRadius => (1.0, 32.0, 3.0),
+        ParamKind::KawaseIterations => (1.0, 10.0, 4.0),
+        ParamKind::RadialAmount => (0.01, 0.5, 0.15),
+        ParamKind::KernelRadius => (0.0, MAX_KERNEL_RADIUS as f32, 0.0),
+        ParamKind::NoiseIntensity => (0.0, 1.0, 0.0),
+        ParamKind::NoiseScale => (1.0, 1000.0, 100.0),
+        ParamKind::NoiseSpeed => (0.0, 100.0, 1.0),
+        ParamKind::RainIntensity => (0.0, 1.0, 0.5),
+        ParamKind::DirectionStrength => (0.0, 1.0, 0.0),
+    };
+    ParamRange { min, max, default }
+}
+
+/// Per-parameter starting point of a quality preset, see [`preset_baseline`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PresetBaseline {
+    pub strength: f32,
+    /// Kernel radius in pixels, 3 sigma of the preset's native blur.
+    pub kernel_radius: u32,
+    /// Resolution divisor on both axes, as for
+    /// [`BlurWindow::set_downsample`](crate::BlurWindow::set_downsample) and
+    /// pipeline stages (`StageParams::downsample`).
+    pub downsample: u32,
+}
+
+/// Baseline values for `preset`, applied by
+/// [`BlurWindow::reset_to_preset`](crate::BlurWindow::reset_to_preset).
+pub const fn preset_baseline(preset: BlurQualityPreset) -> PresetBaseline {
+    let (strength, kernel_radius, downsample) = match preset {
+        // Native sigma per preset: 8, 5, 3 and 2
+        BlurQualityPreset::High => (1.0, 24, 1),
+        BlurQualityPreset::Balanced => (1.0, 15, 1),
+        BlurQualityPreset::Performance => (1.0, 9, 2),
+        BlurQualityPreset::Minimal => (1.0, 6, 4),
+    };
+    PresetBaseline {
+        strength,
+        kernel_radius,
+        downsample,
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+
+    fn window(system: &BlurSystem) -> BlurWindow {
+        let window = WindowBuilder::new().build(system).unwrap();
+        window.clear_call_log();
+        window
+    }
+
+    // `function(args)` of the last native call `window` made
+    fn last_call(window: &BlurWindow) -> String {
+        window.call_log().last().unwrap().to_string()
+    }
+
+    #[test]
+    fn set_strength_clamps() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let window = window(&system);
+
+        window.set_strength(1.5).unwrap();
+        assert_eq!(last_call(&window), "blur_set_strength(1.0)");
+        assert_eq!(window.state().strength, 1.0);
+        window.set_strength(-0.5).unwrap();
+        assert_eq!(last_call(&window), "blur_set_strength(0.0)");
+        assert_eq!(window.state().strength, 0.0);
+    }
+
+    #[test]
+    fn set_strength_gradient_clamps_both_ends() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let window = window(&system);
+
+        window.set_strength_gradient(-1.0, 2.0).unwrap();
+        assert_eq!(last_call(&window), "blur_set_strength_gradient(0.0, 1.0)");
+        assert_eq!(window.state().strength_gradient, Some((0.0, 1.0)));
+    }
+
+    #[test]
+    fn set_blur_xy_clamps_both_axes() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let window = window(&system);
+
+        window.set_blur_xy(3.0, -3.0).unwrap();
+        assert_eq!(last_call(&window), "blur_set_blur_xy(1.0, 0.0)");
+        assert_eq!(window.state().blur_xy, Some((1.0, 0.0)));
+    }
+
+    #[test]
+    fn set_blur_param_clamps_to_the_effect_range() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let window = window(&system);
+
+        window.set_effect_type(BlurEffectType::Kawase).unwrap();
+        window.set_blur_param(25.0).unwrap();
+        assert_eq!(last_call(&window), "blur_set_blur_param(10.0)");
+        assert_eq!(window.state().blur_param, Some(10.0));
+
+        window.set_effect_type(BlurEffectType::Gaussian).unwrap();
+        window.set_blur_param(0.0).unwrap();
+        assert_eq!(last_call(&window), "blur_set_blur_param(0.1)");
+        assert_eq!(window.state().blur_param, Some(0.1));
+    }
+
+    #[test]
+    fn set_kernel_radius_rejects_out_of_range() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let window = window(&system);
+
+        window.set_kernel_radius(MAX_KERNEL_RADIUS).unwrap();
+        assert_eq!(
+            last_call(&window),
+            format!("blur_set_kernel_radius({})", MAX_KERNEL_RADIUS)
+        );
+        let rejected = window.set_kernel_radius(MAX_KERNEL_RADIUS + 1);
+        assert_eq!(rejected, Err(BlurErrorCode::InvalidParameter));
+        assert_eq!(window.call_log().len(), 1);
+        assert_eq!(window.state().kernel_radius, MAX_KERNEL_RADIUS);
+    }
+
+    #[test]
+    fn set_directional_blur_clamps_strength() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let window = window(&system);
+
+        window.set_directional_blur(90.0, 4.0).unwrap();
+        assert_eq!(last_call(&window), "blur_set_direction(90.0, 1.0)");
+        assert_eq!(window.state().direction_strength, 1.0);
+    }
+
+    #[test]
+    fn set_noise_clamps_intensity_scale_and_speed() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let window = window(&system);
+
+        let noise = NoiseParams {
+            intensity: 2.0,
+            scale: 0.0,
+            speed: 500.0,
+            ..Default::default()
+        };
+        window.set_noise(&noise).unwrap();
+        let calls: Vec<String> = window.call_log().iter().map(ToString::to_string).collect();
+        assert_eq!(
+            &calls[..3],
+            [
+                "blur_set_noise_intensity(1.0)",
+                "blur_set_noise_scale(1.0)",
+                "blur_set_noise_speed(100.0)",
+            ]
+        );
+        let cached = window.state().noise;
+        assert_eq!(
+            (cached.intensity, cached.scale, cached.speed),
+            (1.0, 1.0, 100.0)
+        );
+    }
+
+    #[test]
+    fn reset_to_preset_matches_the_baseline_table() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let window = window(&system);
+
+        for preset in [
+            BlurQualityPreset::High,
+            BlurQualityPreset::Balanced,
+            BlurQualityPreset::Performance,
+            BlurQualityPreset::Minimal,
+        ] {
+            window.set_strength(0.3).unwrap();
+            window.set_kernel_radius(2).unwrap();
+            window.set_downsample_xy(3, 1).unwrap();
+            window.reset_to_preset(preset).unwrap();
+
+            // Destructured so a new baseline field can't go unchecked
+            let PresetBaseline {
+                strength,
+                kernel_radius,
+                downsample,
+            } = preset_baseline(preset);
+            let state = window.state();
+            assert_eq!(state.preset, preset);
+            assert_eq!(state.active_preset, preset);
+            assert_eq!(state.strength, strength, "{:?}", preset);
+            assert_eq!(state.kernel_radius, kernel_radius, "{:?}", preset);
+            assert_eq!(state.downsample, (downsample, downsample), "{:?}", preset);
+        }
+    }
+}
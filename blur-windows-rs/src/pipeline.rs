@@ -0,0 +1,327 @@
+use super::*;
+use serde::{Deserialize, Serialize};
+
+/// Schema version written by [`PipelineConfig::from_state`].
+pub const PIPELINE_VERSION: u32 = 1;
+
+/// Pipeline JSON as consumed by `blur_set_pipeline`:
+///
+/// ```json
+/// {"version": 1, "pipeline": [{"type": "gaussian", "params": {"strength": 0.8, ...}}]}
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PipelineConfig {
+    pub version: u32,
+    pub pipeline: Vec<PipelineStage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PipelineStage {
+    #[serde(rename = "type")]
+    pub effect: BlurEffectType,
+    #[serde(default)]
+    pub params: StageParams,
+}
+
+/// Flat parameter object for a stage. The native parser only understands
+/// flat keys, so noise settings are not nested.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct StageParams {
+    pub strength: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub param: Option<f32>,
+    pub tint: [f32; 4],
+    pub noise_intensity: f32,
+    pub noise_scale: f32,
+    pub noise_speed: f32,
+    pub noise_type: NoiseType,
+    pub noise_blend_mode: NoiseBlendMode,
+    /// Explicit kernel radius in pixels (Gaussian and Box).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kernel_radius: Option<u32>,
+    /// Iteration count (Kawase only; the native side clamps to 1..=10).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passes: Option<u32>,
+    /// Resolution divisor for the blur input. Accepted by the schema but not
+    /// yet honored per stage; [`BlurWindow::set_downsample_xy`](crate::BlurWindow::set_downsample_xy)
+    /// downsamples the whole chain.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub downsample: Option<u32>,
+}
+
+impl Default for StageParams {
+    fn default() -> Self {
+        let noise = NoiseParams::default();
+        StageParams {
+            strength: 1.0,
+            param: None,
+            tint: [0.0; 4],
+            noise_intensity: noise.intensity,
+            noise_scale: noise.scale,
+            noise_speed: noise.speed,
+            noise_type: noise.noise_type,
+            noise_blend_mode: noise.blend_mode,
+            kernel_radius: None,
+            passes: None,
+            downsample: None,
+        }
+    }
+}
+
+/// Strongly-typed blur stage, converted into a [`PipelineStage`] by
+/// [`build`](Self::build).
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlurStage {
+    pub algorithm: BlurAlgorithm,
+    /// Kernel radius in pixels, 0 to derive it from the effect parameter.
+    /// Used by Gaussian and Box.
+    pub radius: u32,
+    /// Kawase iterations, at least 1. Ignored by Gaussian and Box.
+    pub passes: u32,
+    /// Resolution divisor, at least 1 (see [`StageParams::downsample`]).
+    pub downsample: u32,
+    /// Window-level values carried by the stage (strength, tint, noise).
+    pub params: StageParams,
+}
+
+impl BlurStage {
+    pub fn new(algorithm: BlurAlgorithm) -> Self {
+        BlurStage {
+            algorithm,
+            radius: 0,
+            passes: 1,
+            downsample: if algorithm == BlurAlgorithm::DualKawase {
+                2
+            } else {
+                1
+            },
+            params: StageParams::default(),
+        }
+    }
+
+    /// Like [`build`](Self::build), but first checks that `system`'s native
+    /// library implements the algorithm, instead of letting the pipeline keep
+    /// its previous effect, and that the radius is within its
+    /// [`max_kernel_radius`](BlurSystem::max_kernel_radius) rather than
+    /// [`MAX_KERNEL_RADIUS`].
+    pub fn build_for(self, system: &BlurSystem) -> Result<PipelineStage, String> {
+        if !system.supported_algorithms().contains(&self.algorithm) {
+            return Err(format!(
+                "Blur algorithm {:?} is not supported by the native library",
+                self.algorithm
+            ));
+        }
+        let max_radius = system.max_kernel_radius();
+        if self.radius > max_radius {
+            return Err(format!("Blur stage radius must be at most {}", max_radius));
+        }
+        self.build()
+    }
+
+    /// Validate and produce the stage in the form the native parser expects.
+    pub fn build(self) -> Result<PipelineStage, String> {
+        if self.passes < 1 {
+            return Err("Blur stage passes must be at least 1".into());
+        }
+        if self.downsample < 1 {
+            return Err("Blur stage downsample must be at least 1".into());
+        }
+        if self.radius > MAX_KERNEL_RADIUS {
+            return Err(format!(
+                "Blur stage radius must be at most {}",
+                MAX_KERNEL_RADIUS
+            ));
+        }
+
+        let effect = match self.algorithm {
+            BlurAlgorithm::Gaussian => BlurEffectType::Gaussian,
+            BlurAlgorithm::Box => BlurEffectType::Box,
+            BlurAlgorithm::Kawase | BlurAlgorithm::DualKawase => BlurEffectType::Kawase,
+        };
+        let is_kawase = effect == BlurEffectType::Kawase;
+        let params = StageParams {
+            kernel_radius: (!is_kawase && self.radius > 0).then_some(self.radius),
+            passes: is_kawase.then_some(self.passes),
+            downsample: (self.downsample > 1).then_some(self.downsample),
+            ..self.params
+        };
+        Ok(PipelineStage { effect, params })
+    }
+}
+
+/// Effects composited in order by
+/// [`BlurWindow::set_effect_stack`](crate::BlurWindow::set_effect_stack),
+/// e.g. a Gaussian blur, then rain, then a noise-only Box pass:
+///
+/// ```ignore
+/// let stack = EffectStack::new()
+///     .layer(BlurEffectType::Gaussian, StageParams::default())
+///     .layer(BlurEffectType::Rain, StageParams::default())
+///     .layer(BlurEffectType::Box, StageParams { noise_intensity: 0.1, ..Default::default() });
+/// ```
+///
+/// The first layer becomes the window's effect, which the window-level
+/// setters keep changing; each later layer blurs the result of the ones
+/// before it with only its own params. Rain layers use the default rain
+/// settings, since the rain setters drive the window's effect.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EffectStack {
+    pub layers: Vec<PipelineStage>,
+}
+
+impl EffectStack {
+    pub fn new() -> Self {
+        EffectStack::default()
+    }
+
+    /// Append a layer.
+    pub fn layer(mut self, effect: BlurEffectType, params: StageParams) -> Self {
+        self.layers.push(PipelineStage { effect, params });
+        self
+    }
+
+    /// Append a stage produced by [`BlurStage::build`].
+    pub fn stage(mut self, stage: PipelineStage) -> Self {
+        self.layers.push(stage);
+        self
+    }
+
+    /// The stack currently applied to a window: its effect, then its layers.
+    pub fn from_state(state: &WindowState) -> Self {
+        EffectStack {
+            layers: PipelineConfig::from_state(state).pipeline,
+        }
+    }
+
+    pub fn to_config(&self) -> PipelineConfig {
+        PipelineConfig {
+            version: PIPELINE_VERSION,
+            pipeline: self.layers.clone(),
+        }
+    }
+}
+
+impl PipelineConfig {
+    /// Pipeline reproducing the cached look of a window: its effect, then any
+    /// [`effect_layers`](WindowState::effect_layers).
+    pub fn from_state(state: &WindowState) -> Self {
+        let tint = state.tint;
+        let base = PipelineStage {
+            effect: state.effect,
+            params: StageParams {
+                strength: state.strength,
+                param: state.blur_param,
+                tint: [tint.r, tint.g, tint.b, tint.a],
+                noise_intensity: state.noise.intensity,
+                noise_scale: state.noise.scale,
+                noise_speed: state.noise.speed,
+                noise_type: state.noise.noise_type,
+                noise_blend_mode: state.noise.blend_mode,
+                kernel_radius: (state.kernel_radius > 0).then_some(state.kernel_radius),
+                passes: None,
+                downsample: None,
+            },
+        };
+        PipelineConfig {
+            version: PIPELINE_VERSION,
+            pipeline: std::iter::once(base)
+                .chain(state.effect_layers.iter().cloned())
+                .collect(),
+        }
+    }
+
+    /// Update `state` with what the native side applies for this pipeline:
+    /// the first stage sets the window's effect, the rest become its layers.
+    pub fn apply_to(&self, state: &mut WindowState) {
+        let Some(stage) = self.pipeline.first() else {
+            return;
+        };
+        state.effect_layers = self.pipeline[1..].to_vec();
+        let p = &stage.params;
+        state.effect = stage.effect;
+        state.strength = p.strength;
+        state.strength_gradient = None;
+        state.blur_xy = None;
+        state.blur_param = p.param;
+        if let Some(radius) = p.kernel_radius {
+            state.kernel_radius = radius;
+        }
+        if let (Some(passes), BlurEffectType::Kawase) = (p.passes, stage.effect) {
+            // Kawase's effect parameter is its iteration count
+            state.blur_param = Some(passes as f32);
+        }
+        state.tint = Color::new(p.tint[0], p.tint[1], p.tint[2], p.tint[3]);
+        state.noise = NoiseParams {
+            intensity: p.noise_intensity,
+            scale: p.noise_scale,
+            speed: p.noise_speed,
+            noise_type: p.noise_type,
+            blend_mode: p.noise_blend_mode,
+            // The native side keeps the color and seed across pipeline changes
+            color: state.noise.color,
+            seed: state.noise.seed,
+        };
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_pipeline_round_trips() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let window = WindowBuilder::new().build(&system).unwrap();
+        window.set_effect_type(BlurEffectType::Gaussian).unwrap();
+        window.set_strength(0.123_456_7).unwrap();
+        window.set_blur_param(2.718_281_7).unwrap();
+        window.set_kernel_radius(7).unwrap();
+        window
+            .set_tint_color(Color::new(0.1, 0.2, 0.3, 0.4))
+            .unwrap();
+        window
+            .set_noise(&NoiseParams {
+                intensity: 0.05,
+                scale: 2.5,
+                noise_type: NoiseType::Perlin,
+                ..Default::default()
+            })
+            .unwrap();
+        let state = window.snapshot();
+
+        let exported = window.export_pipeline().unwrap();
+        let mut parsed = state.clone();
+        serde_json::from_str::<PipelineConfig>(&exported)
+            .unwrap()
+            .apply_to(&mut parsed);
+        assert_eq!(parsed, state);
+
+        // Feeding it back changes nothing, so exporting again gives the same JSON
+        window.set_pipeline(&exported).unwrap();
+        assert_eq!(window.snapshot(), state);
+        assert_eq!(window.export_pipeline().unwrap(), exported);
+    }
+
+    #[test]
+    fn build_for_checks_the_radius_against_the_system() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        assert_eq!(system.max_kernel_radius(), MAX_KERNEL_RADIUS);
+
+        let stage = |radius| BlurStage {
+            radius,
+            ..BlurStage::new(BlurAlgorithm::Gaussian)
+        };
+        assert!(stage(system.max_kernel_radius()).build_for(&system).is_ok());
+        let error = stage(system.max_kernel_radius() + 1)
+            .build_for(&system)
+            .unwrap_err();
+        assert_eq!(
+            error,
+            format!("Blur stage radius must be at most {}", MAX_KERNEL_RADIUS)
+        );
+    }
+}
@@ -0,0 +1,192 @@
+use super::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use windows::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+/// Where the system draws its power from, see [`native_power_source`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PowerSource {
+    Ac,
+    Battery,
+}
+
+/// The current power source, from `GetSystemPowerStatus`. Machines without
+/// a battery, and those whose status is unknown, report [`PowerSource::Ac`].
+pub fn native_power_source() -> PowerSource {
+    let mut status = SYSTEM_POWER_STATUS::default();
+    // ACLineStatus: 0 offline, 1 online, 255 unknown
+    if unsafe { GetSystemPowerStatus(&mut status) }.is_ok() && status.ACLineStatus == 0 {
+        PowerSource::Battery
+    } else {
+        PowerSource::Ac
+    }
+}
+
+/// What a window does while the system runs on battery, see
+/// [`BlurWindow::set_battery_policy`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BatteryPolicy {
+    /// Render the same on battery as on AC.
+    #[default]
+    Ignore,
+    /// Switch to [`BlurQualityPreset::Minimal`], keeping every other
+    /// parameter.
+    MinimalPreset,
+    /// Pause rendering and hide the window.
+    Disable,
+}
+
+/// Registered natively while a policy other than `Ignore` is set.
+pub(crate) struct PowerChanged {
+    // Raw handle value; the handle itself isn't Send
+    handle: usize,
+    state: Arc<Mutex<WindowState>>,
+    policy: BatteryPolicy,
+    /// Whether the policy is currently applied.
+    degraded: AtomicBool,
+}
+
+impl PowerChanged {
+    /// Apply the policy on battery and undo it on AC, if not done already.
+    fn update(&self) -> Result<(), BlurErrorCode> {
+        let battery = native_power_source() == PowerSource::Battery;
+        if battery == self.degraded.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+        if battery {
+            self.degrade()
+        } else {
+            self.undo()
+        }
+    }
+
+    /// Natively only, except for `active_preset`: the cache keeps what the
+    /// app asked for, which `undo` puts back.
+    fn degrade(&self) -> Result<(), BlurErrorCode> {
+        let handle = BlurWindowHandle(self.handle as *mut _);
+        match self.policy {
+            BatteryPolicy::Ignore => {}
+            BatteryPolicy::MinimalPreset => {
+                check(unsafe { blur_set_preset(handle, BlurQualityPreset::Minimal) })?;
+                self.state().active_preset = BlurQualityPreset::Minimal;
+            }
+            BatteryPolicy::Disable => {
+                check(unsafe { blur_pause(handle) })?;
+                check(unsafe { blur_hide(handle) })?;
+            }
+        }
+        self.degraded.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn undo(&self) -> Result<(), BlurErrorCode> {
+        let handle = BlurWindowHandle(self.handle as *mut _);
+        // Not held across the calls below, which may dispatch messages
+        let (preset, paused, visible) = {
+            let state = self.state();
+            (state.preset, state.paused, state.visible)
+        };
+        match self.policy {
+            BatteryPolicy::Ignore => {}
+            BatteryPolicy::MinimalPreset => {
+                check(unsafe { blur_set_preset(handle, preset) })?;
+                self.state().active_preset = preset;
+            }
+            BatteryPolicy::Disable => {
+                if !paused {
+                    check(unsafe { blur_resume(handle) })?;
+                }
+                if visible {
+                    check(unsafe { blur_show(handle) })?;
+                }
+            }
+        }
+        self.degraded.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, WindowState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn check(code: BlurErrorCode) -> Result<(), BlurErrorCode> {
+    if code == BlurErrorCode::Ok {
+        Ok(())
+    } else {
+        Err(code)
+    }
+}
+
+unsafe extern "C" fn power_changed_trampoline(user_data: *mut std::ffi::c_void) {
+    let changed = &*(user_data as *const PowerChanged);
+    // Unwinding into native code is undefined behavior
+    let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        // Nobody to report to; the next power change tries again
+        let _ = changed.update();
+    }));
+}
+
+impl BlurWindow {
+    /// Degrade or stop the effect while the system runs on battery, and put
+    /// it back once it's plugged in. Applies right away if already on
+    /// battery; [`BatteryPolicy::Ignore`] undoes any degradation and stops
+    /// watching.
+    ///
+    /// The cached [`WindowState`] keeps what the app asked for, so
+    /// [`snapshot`](Self::snapshot) and [`restore`](Self::restore) are
+    /// unaffected; only [`WindowState::active_preset`] shows the minimal
+    /// preset. A [`set_preset`](Self::set_preset), [`show`](Self::show) or
+    /// [`resume`](Self::resume) on battery takes effect until the next
+    /// switch to AC.
+    ///
+    /// Power changes are noticed while the window's thread dispatches
+    /// messages, like [`on_display_changed`](Self::on_display_changed), so
+    /// windows whose thread never pumps, and child windows, only see the
+    /// power source at the time of this call.
+    pub fn set_battery_policy(&self, policy: BatteryPolicy) -> Result<(), BlurErrorCode> {
+        let mut slot = self.power_changed.lock().unwrap_or_else(|e| e.into_inner());
+        let changed = (policy != BatteryPolicy::Ignore).then(|| {
+            Box::new(PowerChanged {
+                handle: self.handle.0 as usize,
+                state: Arc::clone(&self.state),
+                policy,
+                degraded: AtomicBool::new(false),
+            })
+        });
+        let code = match &changed {
+            Some(changed) => unsafe {
+                blur_set_power_changed_callback(
+                    self.handle,
+                    Some(power_changed_trampoline),
+                    &**changed as *const PowerChanged as *mut std::ffi::c_void,
+                )
+            },
+            None => unsafe {
+                blur_set_power_changed_callback(self.handle, None, std::ptr::null_mut())
+            },
+        };
+        check(code)?;
+        // The native side no longer calls the old handler once the setter returns
+        if let Some(old) = std::mem::replace(&mut *slot, changed) {
+            if old.degraded.load(Ordering::SeqCst) {
+                old.undo()?;
+            }
+        }
+        match &*slot {
+            Some(changed) => changed.update(),
+            None => Ok(()),
+        }
+    }
+
+    /// The policy last passed to [`set_battery_policy`](Self::set_battery_policy).
+    pub fn battery_policy(&self) -> BatteryPolicy {
+        self.power_changed
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .map_or(BatteryPolicy::Ignore, |changed| changed.policy)
+    }
+}
@@ -0,0 +1,374 @@
+use super::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// Schema version written by [`BlurSystem::export_profile`].
+pub const PROFILE_VERSION: u32 = 1;
+
+/// A whole setup, the system options plus every window, saved as one file:
+///
+/// ```json
+/// {"version": 1, "system": {"logging": true, ...}, "windows": [{"options": {...}, "state": {...}}]}
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    pub version: u32,
+    pub system: BlurSystemConfig,
+    pub windows: Vec<WindowConfig>,
+}
+
+/// One window of a [`Profile`]: how it was created and its cached state.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WindowConfig {
+    pub options: WindowOptions,
+    pub state: WindowState,
+}
+
+impl Profile {
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize profile: {}", e))
+    }
+
+    pub fn from_json(json: &str) -> Result<Profile, String> {
+        let profile: Profile =
+            serde_json::from_str(json).map_err(|e| format!("Invalid profile: {}", e))?;
+        if profile.version != PROFILE_VERSION {
+            return Err(format!(
+                "Unsupported profile version {} (expected {})",
+                profile.version, PROFILE_VERSION
+            ));
+        }
+        Ok(profile)
+    }
+}
+
+impl BlurSystem {
+    /// Profile of this system and every live window, in creation order.
+    ///
+    /// Capture sources are not saved; child windows are saved but need their
+    /// owner to be rebuilt, see [`from_profile`](Self::from_profile).
+    pub fn export_profile(&self) -> Profile {
+        Profile {
+            version: PROFILE_VERSION,
+            system: self.config().clone(),
+            windows: registry::configs(),
+        }
+    }
+
+    /// Rebuild a system and its windows from `profile`, in profile order.
+    /// Windows that were running are started (and paused if they were).
+    ///
+    /// Windows are created without an owner, so a profile containing
+    /// [child](WindowBuilder::child) windows is rejected; recreate those with
+    /// a builder and [`BlurWindow::restore`] their state.
+    pub fn from_profile(profile: &Profile) -> Result<(BlurSystem, Vec<BlurWindow>), String> {
+        if profile.windows.iter().any(|w| w.options.child) {
+            return Err("Profile contains child windows, which need an owner".into());
+        }
+
+        let system = BlurSystemBuilder::from_config(&profile.system)?.build()?;
+        let mut windows = Vec::with_capacity(profile.windows.len());
+        for config in &profile.windows {
+            let bounds = config.state.bounds;
+            let window = WindowBuilder::new()
+                .bounds(
+                    bounds.left,
+                    bounds.top,
+                    bounds.right - bounds.left,
+                    bounds.bottom - bounds.top,
+                )
+                .options(config.options)
+                .build(&system)?;
+            let restore = |code| format!("Failed to restore window state: {:?}", code);
+            window.restore(&config.state).map_err(restore)?;
+            if config.state.running {
+                window.start().map_err(restore)?;
+                if config.state.paused {
+                    window.pause().map_err(restore)?;
+                }
+            }
+            windows.push(window);
+        }
+        Ok((system, windows))
+    }
+}
+
+/// Named looks a user can switch between at runtime, e.g. "Focus",
+/// "Cinematic" and "Subtle", saved as one file:
+///
+/// ```json
+/// {"version": 1, "profiles": {"Focus": {"options": {...}, "state": {...}}}}
+/// ```
+///
+/// Fill one from windows set up the way each look should be, with
+/// `WindowConfig { options: window.options(), state: window.snapshot() }`,
+/// then switch with [`BlurWindow::apply_profile`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileSet {
+    pub version: u32,
+    pub profiles: BTreeMap<String, WindowConfig>,
+}
+
+impl ProfileSet {
+    pub fn new() -> Self {
+        ProfileSet {
+            version: PROFILE_VERSION,
+            profiles: BTreeMap::new(),
+        }
+    }
+
+    /// Add or replace the profile called `name`, returning the one replaced.
+    pub fn insert(
+        &mut self,
+        name: impl Into<String>,
+        config: WindowConfig,
+    ) -> Option<WindowConfig> {
+        self.profiles.insert(name.into(), config)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<WindowConfig> {
+        self.profiles.remove(name)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&WindowConfig> {
+        self.profiles.get(name)
+    }
+
+    /// Profile names in alphabetical order, e.g. for a picker.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.profiles.keys().map(String::as_str)
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize profile set: {}", e))
+    }
+
+    pub fn from_json(json: &str) -> Result<ProfileSet, String> {
+        let set: ProfileSet =
+            serde_json::from_str(json).map_err(|e| format!("Invalid profile set: {}", e))?;
+        if set.version != PROFILE_VERSION {
+            return Err(format!(
+                "Unsupported profile set version {} (expected {})",
+                set.version, PROFILE_VERSION
+            ));
+        }
+        Ok(set)
+    }
+
+    pub fn load(path: &Path) -> Result<ProfileSet, String> {
+        let json = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        ProfileSet::from_json(&json)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        fs::write(path, self.to_json()?)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
+
+impl Default for ProfileSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `profile` with the parts that belong to the window rather than to its
+/// look (geometry, what it blurs, visibility) taken from `current`.
+fn profile_look(profile: &WindowState, current: &WindowState) -> WindowState {
+    WindowState {
+        bounds: current.bounds,
+        active_region: current.active_region,
+        cursor_follow: current.cursor_follow,
+        cursor_follow_radius: current.cursor_follow_radius,
+        capture_source: current.capture_source,
+        source_texture: current.source_texture,
+        visible: current.visible,
+        ..profile.clone()
+    }
+}
+
+impl BlurWindow {
+    /// Switch to the look saved as `name` in `profiles`; `InvalidParameter`
+    /// if there is none.
+    ///
+    /// Applies the profile's cached parameters like [`restore`](Self::restore),
+    /// except what belongs to the window rather than its look: bounds,
+    /// active region, cursor follow, capture source, source texture and
+    /// visibility stay as they are, as does whether it's running or paused.
+    /// The profile's creation options are ignored, since they can't change
+    /// after a window is built.
+    pub fn apply_profile(&self, profiles: &ProfileSet, name: &str) -> Result<(), BlurErrorCode> {
+        let config = profiles.get(name).ok_or(BlurErrorCode::InvalidParameter)?;
+        self.restore(&profile_look(&config.state, &self.snapshot()))
+    }
+
+    /// Like [`apply_profile`](Self::apply_profile), but blend strength and
+    /// tint over `duration` instead of jumping; everything else switches at
+    /// once. Tint layers aren't blended: with layers on either side the tint
+    /// switches at once too.
+    ///
+    /// Drive the returned handle's `tick` from your loop, as for
+    /// [`FadeHandle`]. The cached state holds the new profile throughout, so
+    /// snapshots taken mid-transition hold the final values.
+    pub fn apply_profile_animated(
+        &self,
+        profiles: &ProfileSet,
+        name: &str,
+        duration: Duration,
+    ) -> Result<ProfileTransition<'_>, BlurErrorCode> {
+        let config = profiles.get(name).ok_or(BlurErrorCode::InvalidParameter)?;
+        let from = self.snapshot();
+        let to = profile_look(&config.state, &from);
+        self.restore(&to)?;
+        let transition = ProfileTransition {
+            window: self,
+            duration,
+            elapsed: Duration::ZERO,
+            blend_tint: from.tint_layers.is_empty() && to.tint_layers.is_empty(),
+            from_strength: from.strength,
+            from_tint: from.tint,
+            to,
+        };
+        // Start from the old look; the first tick moves on
+        transition.push(0.0)?;
+        Ok(transition)
+    }
+}
+
+/// Drives a transition started by [`BlurWindow::apply_profile_animated`].
+///
+/// Nothing runs in the background: call [`tick`](Self::tick) from your own
+/// frame or event loop until it reports completion.
+pub struct ProfileTransition<'a> {
+    window: &'a BlurWindow,
+    duration: Duration,
+    elapsed: Duration,
+    blend_tint: bool,
+    from_strength: f32,
+    from_tint: Color,
+    to: WindowState,
+}
+
+impl ProfileTransition<'_> {
+    /// Advance the transition by `elapsed` (time since the previous tick).
+    /// Returns `true` once the new profile is fully applied.
+    pub fn tick(&mut self, elapsed: Duration) -> Result<bool, BlurErrorCode> {
+        self.elapsed += elapsed;
+        if self.is_complete() {
+            self.finish()?;
+            return Ok(true);
+        }
+        self.push(animation::ease(self.elapsed, self.duration))?;
+        Ok(false)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    /// Push the blend at `t` (0 = old look, 1 = new) to the native side,
+    /// leaving the cached state alone.
+    fn push(&self, t: f32) -> Result<(), BlurErrorCode> {
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+        let handle = self.window.handle;
+        let strength = lerp(self.from_strength, self.to.strength);
+        let code = unsafe { blur_set_strength(handle, strength) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        if self.blend_tint {
+            let (from, to) = (self.from_tint, self.to.tint);
+            let code = unsafe {
+                blur_set_tint_color(
+                    handle,
+                    lerp(from.r, to.r),
+                    lerp(from.g, to.g),
+                    lerp(from.b, to.b),
+                    lerp(from.a, to.a),
+                )
+            };
+            if code != BlurErrorCode::Ok {
+                return Err(code);
+            }
+        }
+        Ok(())
+    }
+
+    /// Re-apply the blended parameters exactly, including a strength
+    /// gradient or per-axis strength the blend flattened.
+    fn finish(&self) -> Result<(), BlurErrorCode> {
+        let to = &self.to;
+        animation::apply_strength(self.window, to.strength, to.strength_gradient, to.blur_xy)?;
+        if self.blend_tint {
+            self.window.set_tint_color(to.tint)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profile_json_round_trips() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let window = WindowBuilder::new()
+            .bounds(10, 20, 300, 200)
+            .build(&system)
+            .unwrap();
+        window.set_strength(0.35).unwrap();
+        window
+            .set_tint_color(Color {
+                r: 0.1,
+                g: 0.2,
+                b: 0.3,
+                a: 0.4,
+            })
+            .unwrap();
+
+        let profile = system.export_profile();
+        let json = profile.to_json().unwrap();
+        assert_eq!(Profile::from_json(&json).unwrap(), profile);
+    }
+
+    #[test]
+    fn export_profile_walks_the_live_windows() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let first = WindowBuilder::new().top_most(true).build(&system).unwrap();
+        let dropped = WindowBuilder::new().build(&system).unwrap();
+        let last = WindowBuilder::new().build(&system).unwrap();
+        last.set_strength(0.6).unwrap();
+        drop(dropped);
+
+        let profile = system.export_profile();
+        let configs: Vec<_> = [&first, &last]
+            .iter()
+            .map(|window| WindowConfig {
+                options: window.options(),
+                state: window.snapshot(),
+            })
+            .collect();
+        assert_eq!(profile.windows, configs);
+    }
+
+    #[test]
+    fn profile_with_another_version_is_rejected() {
+        let _serial = mock::serial();
+        let profile = Profile {
+            version: PROFILE_VERSION + 1,
+            system: BlurSystem::headless().config().clone(),
+            windows: Vec::new(),
+        };
+        let json = profile.to_json().unwrap();
+        assert!(Profile::from_json(&json).is_err());
+    }
+}
@@ -0,0 +1,123 @@
+// Every live BlurWindow in the process, so system-wide operations such as
+// BlurSystem::pause_all and export_profile can reach them. The native system is a singleton, so
+// one registry serves every BlurSystem.
+
+use super::*;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+struct Entry {
+    // Raw handle value; the handle itself isn't Send
+    handle: usize,
+    // Raw HWND value, 0 until the native window exists: it's created by the
+    // first start, render_once or prewarm, not with the handle
+    hwnd: isize,
+    state: Arc<Mutex<WindowState>>,
+    // Read by export_profile, which needs serde
+    #[cfg_attr(not(feature = "serde"), allow(dead_code))]
+    options: WindowOptions,
+}
+
+static WINDOWS: Mutex<Vec<Entry>> = Mutex::new(Vec::new());
+
+fn windows() -> MutexGuard<'static, Vec<Entry>> {
+    WINDOWS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+pub(crate) fn register(
+    handle: BlurWindowHandle,
+    state: &Arc<Mutex<WindowState>>,
+    options: WindowOptions,
+) {
+    let hwnd = unsafe { blur_get_hwnd(handle) };
+    windows().push(Entry {
+        handle: handle.0 as usize,
+        hwnd: hwnd.0 as isize,
+        state: Arc::clone(state),
+        options,
+    });
+}
+
+pub(crate) fn unregister(handle: BlurWindowHandle) {
+    windows().retain(|entry| entry.handle != handle.0 as usize);
+}
+
+/// Whether `hwnd` is the native window of a registered window. The native
+/// side is only asked for windows whose HWND isn't known yet; registered
+/// handles are live, since `Drop` and the watchdog unregister before a
+/// handle dies.
+pub(crate) fn contains_hwnd(hwnd: HWND) -> bool {
+    windows().iter_mut().any(|entry| {
+        if entry.hwnd == 0 {
+            let handle = BlurWindowHandle(entry.handle as *mut _);
+            entry.hwnd = unsafe { blur_get_hwnd(handle) }.0 as isize;
+        }
+        entry.hwnd == hwnd.0 as isize
+    })
+}
+
+/// Options and cached state of every registered window, oldest first.
+#[cfg(feature = "serde")]
+pub(crate) fn configs() -> Vec<WindowConfig> {
+    windows()
+        .iter()
+        .map(|entry| WindowConfig {
+            options: entry.options,
+            state: entry.state().clone(),
+        })
+        .collect()
+}
+
+/// Pause or resume every registered window, keeping their cached state in
+/// step. Tries every window and returns the first error.
+///
+/// The registry stays locked throughout, so a window being dropped on
+/// another thread waits instead of destroying its handle mid-call. Only
+/// `blur_pause` and `blur_resume` are called, which just set a flag natively
+/// and are safe from any thread.
+pub(crate) fn set_all_paused(paused: bool) -> Result<(), BlurErrorCode> {
+    set_paused_where(paused, |_| true).1
+}
+
+/// Pause the registered windows that aren't paused yet, returning their raw
+/// handles for [`resume`].
+pub(crate) fn pause_unpaused() -> Vec<usize> {
+    set_paused_where(true, |entry| !entry.state().paused).0
+}
+
+/// Resume the windows in `handles` that are still registered.
+pub(crate) fn resume(handles: &[usize]) {
+    let _ = set_paused_where(false, |entry| handles.contains(&entry.handle));
+}
+
+/// [`set_all_paused`] for the windows `select` picks. Returns the handles
+/// changed and the first error.
+fn set_paused_where(
+    paused: bool,
+    mut select: impl FnMut(&Entry) -> bool,
+) -> (Vec<usize>, Result<(), BlurErrorCode>) {
+    let mut changed = Vec::new();
+    let mut result = Ok(());
+    for entry in windows().iter().filter(|entry| select(entry)) {
+        let handle = BlurWindowHandle(entry.handle as *mut _);
+        let code = unsafe {
+            if paused {
+                blur_pause(handle)
+            } else {
+                blur_resume(handle)
+            }
+        };
+        if code == BlurErrorCode::Ok {
+            entry.state().paused = paused;
+            changed.push(entry.handle);
+        } else if result.is_ok() {
+            result = Err(code);
+        }
+    }
+    (changed, result)
+}
+
+impl Entry {
+    fn state(&self) -> MutexGuard<'_, WindowState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
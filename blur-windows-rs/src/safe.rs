@@ -1,98 +1,2835 @@
 use super::*;
 use std::ptr;
+use std::collections::HashSet;
 use std::ffi::CString;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
 use windows::Win32::Foundation::HWND;
 
+/// The native library's most recent error message, if any.
+///
+/// Reading clears it, so a message is reported once and never attributed to
+/// a later, unrelated failure.
+pub fn last_error() -> Option<String> {
+    unsafe {
+        let err = blur_get_last_error_and_clear();
+        if err.is_null() {
+            return None;
+        }
+        let message = std::ffi::CStr::from_ptr(err).to_string_lossy().into_owned();
+        if message.is_empty() {
+            None
+        } else {
+            Some(message)
+        }
+    }
+}
+
+/// The native library's most recent error as the code it came with plus
+/// its message, e.g. `(NotInitialized, "Blur system is not initialized")`
+/// after a failed window creation. Clears both, like [`last_error`].
+///
+/// Failures that don't set a message (such as a null handle) aren't
+/// reported here; they only show in the returned [`BlurErrorCode`].
+pub fn last_error_detail() -> Option<(BlurErrorCode, String)> {
+    // Read first: clearing the message resets the code
+    let code = BlurErrorCode::from_raw(unsafe { blur_get_last_error_code() });
+    let message = last_error()?;
+    Some((code, message))
+}
+
+/// The native last error as one message that keeps its code, e.g.
+/// `Blur system is not initialized (NotInitialized)`.
+fn last_error_message() -> Option<String> {
+    last_error_detail().map(|(code, message)| format!("{} ({:?})", message, code))
+}
+
+/// True if the loaded native library is a debug build (D3D11 debug layer,
+/// unoptimized shaders). Such builds are much slower and should not ship.
+pub fn native_is_debug_build() -> bool {
+    unsafe { blur_is_debug_build() != 0 }
+}
+
+/// True if desktop composition (DWM) is enabled, which blur windows need to
+/// render correctly. Always the case since Windows 8; it can be off on
+/// Windows 7 and in safe mode. [`BlurSystemBuilder::build`] checks it.
+pub fn native_dwm_enabled() -> bool {
+    unsafe { blur_is_dwm_enabled() != 0 }
+}
+
+/// Why [`BlurSystem::validate_pipeline`] or [`BlurWindow::set_pipeline`]
+/// rejected a pipeline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PipelineError {
+    pub code: BlurErrorCode,
+    /// What's wrong, e.g. `Pipeline stage 2 has unknown type "gausian"`.
+    pub message: String,
+}
+
+impl std::fmt::Display for PipelineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for PipelineError {}
+
+impl PipelineError {
+    fn invalid(message: String) -> Self {
+        PipelineError {
+            code: BlurErrorCode::InvalidParameter,
+            message,
+        }
+    }
+
+    /// A native failure that returned `code`, explained by the native last
+    /// error when there is one.
+    fn native(code: BlurErrorCode) -> Self {
+        match last_error_detail() {
+            // The code the message was reported with
+            Some((code, message)) => PipelineError { code, message },
+            None => PipelineError {
+                code,
+                message: format!("Pipeline rejected ({:?})", code),
+            },
+        }
+    }
+}
+
+/// The checks `validate_pipeline` and `set_pipeline` make before calling into
+/// the native library, which quietly turns an empty or non-object string into
+/// a default Gaussian blur.
+fn pipeline_json(json: &str) -> Result<CString, PipelineError> {
+    let trimmed = json.trim_start();
+    if trimmed.is_empty() {
+        return Err(PipelineError::invalid("Pipeline JSON is empty".into()));
+    }
+    if !trimmed.starts_with('{') {
+        return Err(PipelineError::invalid(
+            "Pipeline JSON must be an object".into(),
+        ));
+    }
+    #[cfg(feature = "serde")]
+    if let Err(e) = serde_json::from_str::<serde_json::Value>(json) {
+        return Err(PipelineError::invalid(format!(
+            "Invalid pipeline JSON: {}",
+            e
+        )));
+    }
+    CString::new(json)
+        .map_err(|_| PipelineError::invalid("Pipeline JSON contains a NUL byte".into()))
+}
+
+impl From<PipelineError> for BlurErrorCode {
+    fn from(e: PipelineError) -> Self {
+        e.code
+    }
+}
+
+/// Owner of the native blur system.
+///
+/// Not `Clone`: the native side is a singleton, and only one owner may shut it
+/// down. After [`shutdown`](Self::shutdown) the handle is nulled, so creating
+/// windows fails cleanly instead of reaching native code.
 pub struct BlurSystem {
-    handle: BlurSystemHandle,
+    /// Null once the system has been shut down.
+    pub(crate) handle: BlurSystemHandle,
+    pub(crate) init_duration: Duration,
+    pub(crate) config: BlurSystemConfig,
+    /// Handler registered with `on_theme_changed`, boxed twice so the native
+    /// side gets a thin pointer.
+    pub(crate) theme_changed: Mutex<Option<Box<ThemeChangedFn>>>,
+    /// Handler registered with `on_fullscreen_app_detected`, boxed twice
+    /// like `theme_changed`.
+    pub(crate) fullscreen_changed: Mutex<Option<Box<FullscreenChangedFn>>>,
 }
 
 impl BlurSystem {
     pub fn new() -> Result<Self, String> {
-        let options = BlurSystemOptionsC {
-            enable_logging: 1,
-            log_path: ptr::null(),
-            default_preset: BlurQualityPreset::Balanced,
-        };
-        
-        unsafe {
-            let handle = blur_init(&options);
-            if handle.0.is_null() {
-                let err = blur_get_last_error();
-                if !err.is_null() {
-                    let c_str = std::ffi::CStr::from_ptr(err);
-                    return Err(c_str.to_string_lossy().into_owned());
-                }
-                return Err("Failed to initialize blur system".into());
+        BlurSystemBuilder::new().build()
+    }
+
+    /// Initialize without blocking the calling (UI) thread.
+    ///
+    /// Only `blur_init` (D3D11 device creation, which can take tens of
+    /// milliseconds) is moved to tokio's blocking pool; everything else on
+    /// the returned system stays synchronous and fast.
+    #[cfg(feature = "async")]
+    pub async fn new_async() -> Result<Self, String> {
+        tokio::task::spawn_blocking(BlurSystem::new)
+            .await
+            .map_err(|e| format!("Blur system initialization task failed: {}", e))?
+    }
+
+    /// Whether `hwnd` is one of this process's blur windows, e.g. to skip them
+    /// when choosing a [capture source](BlurWindow::set_capture_source) or
+    /// following the foreground window, which would blur the app's own
+    /// output in a feedback loop.
+    ///
+    /// Windows of this crate are found in its window registry, which covers
+    /// every system and [`OwnedWindowThread`]. Others created by the native
+    /// library directly (through the C API) are found by the `"BlurWindow"`
+    /// window property it sets on every blur window, from `WM_NCCREATE` to
+    /// `WM_NCDESTROY`. Windows of other processes are never reported.
+    pub fn is_own_window(&self, hwnd: HWND) -> bool {
+        !hwnd.is_invalid()
+            && (registry::contains_hwnd(hwnd) || unsafe { blur_is_blur_window(hwnd) } != 0)
+    }
+
+    /// Copy of the native handle, for calling `extern` functions the safe API
+    /// doesn't wrap yet. Null after [`shutdown`](Self::shutdown).
+    ///
+    /// The handle stays owned by `self`: calling `blur_shutdown` on it (or
+    /// using it after `self` is dropped) leads to a double shutdown or
+    /// use-after-free when `Drop` runs.
+    pub fn raw_handle(&self) -> BlurSystemHandle {
+        self.handle
+    }
+
+    /// The options the system was built with.
+    pub fn config(&self) -> &BlurSystemConfig {
+        &self.config
+    }
+
+    /// How long `blur_init` (mostly D3D11 device creation) took.
+    pub fn init_duration(&self) -> Duration {
+        self.init_duration
+    }
+
+    /// Whether the system is still initialized (not yet shut down).
+    pub fn is_valid(&self) -> bool {
+        !self.handle.0.is_null()
+    }
+
+    /// Shut the native system down before `self` is dropped.
+    ///
+    /// Later window creation fails with a `NotInitialized` error, and calling
+    /// this again is a no-op. Windows created earlier should be dropped first.
+    pub fn shutdown(&mut self) {
+        if self.is_valid() {
+            unsafe {
+                blur_shutdown(self.handle);
             }
-            Ok(BlurSystem { handle })
+            self.handle = BlurSystemHandle(ptr::null_mut());
+        }
+    }
+
+    /// Blur algorithms the loaded native library implements. Empty after
+    /// [`shutdown`](Self::shutdown).
+    pub fn supported_algorithms(&self) -> Vec<BlurAlgorithm> {
+        if !self.is_valid() {
+            return Vec::new();
+        }
+        let mask = unsafe { blur_get_supported_algorithms(self.handle) };
+        BlurAlgorithm::ALL
+            .into_iter()
+            .filter(|algorithm| mask & algorithm.support_bit() != 0)
+            .collect()
+    }
+
+    /// Largest radius [`BlurWindow::set_kernel_radius`] accepts from the
+    /// loaded native library, [`MAX_KERNEL_RADIUS`] for the library these
+    /// bindings match. 0 after [`shutdown`](Self::shutdown).
+    pub fn max_kernel_radius(&self) -> u32 {
+        if !self.is_valid() {
+            return 0;
+        }
+        unsafe { blur_get_max_kernel_radius(self.handle) }
+    }
+
+    /// Whether the loaded native library implements
+    /// [`BlurWindow::set_temporal_mode`]. False after [`shutdown`](Self::shutdown).
+    pub fn supports_temporal_mode(&self) -> bool {
+        if !self.is_valid() {
+            return false;
+        }
+        let mask = unsafe { blur_get_supported_algorithms(self.handle) };
+        mask & TemporalMode::SUPPORT_BIT != 0
+    }
+
+    /// Check pipeline JSON as [`BlurWindow::set_pipeline`] would, without
+    /// applying it to any window, e.g. on every keystroke in an editor.
+    ///
+    /// Also rejects structured stages with a missing or unknown `type`, which
+    /// `set_pipeline` accepts and turns into a Gaussian blur.
+    pub fn validate_pipeline(&self, json: &str) -> Result<(), PipelineError> {
+        let c_json = pipeline_json(json)?;
+        let code = unsafe { blur_validate_pipeline(self.handle, c_json.as_ptr()) };
+        if code != BlurErrorCode::Ok {
+            return Err(PipelineError::native(code));
         }
+        Ok(())
     }
 
     pub fn create_window(&self, owner: HWND, x: i32, y: i32, w: i32, h: i32) -> Result<BlurWindow, String> {
+        WindowBuilder::new()
+            .owner(owner)
+            .bounds(x, y, w, h)
+            .build(self)
+    }
+
+    /// Blur behind `rect` of `owner` in one call: a child window clipped to the
+    /// rectangle, with `preset` applied and rendering started.
+    ///
+    /// `rect` is in `owner`'s client-area coordinates (physical pixels, origin
+    /// at the top-left of the client area), the same space a GUI toolkit uses
+    /// to lay out the control being blurred behind. Move it along with the
+    /// control via [`BlurWindow::set_bounds`].
+    #[cfg(feature = "raw-window-handle")]
+    pub fn blur_behind(
+        &self,
+        owner: &impl raw_window_handle::HasWindowHandle,
+        rect: BlurRect,
+        preset: BlurQualityPreset,
+    ) -> Result<BlurWindow, String> {
+        use raw_window_handle::RawWindowHandle;
+
+        let handle = owner
+            .window_handle()
+            .map_err(|e| format!("Owner window handle is unavailable: {}", e))?;
+        let hwnd = match handle.as_raw() {
+            RawWindowHandle::Win32(h) => HWND(h.hwnd.get() as *mut std::ffi::c_void),
+            _ => return Err("blur_behind requires a Win32 window".into()),
+        };
+
+        let window = WindowBuilder::new()
+            .owner(hwnd)
+            .bounds(
+                rect.left,
+                rect.top,
+                rect.right - rect.left,
+                rect.bottom - rect.top,
+            )
+            .child(true)
+            .build(self)?;
+        window
+            .set_preset(preset)
+            .map_err(|code| format!("Failed to apply preset: {:?}", code))?;
+        window
+            .start()
+            .map_err(|code| format!("Failed to start blur window: {:?}", code))?;
+        Ok(window)
+    }
+}
+
+/// Options of a [`BlurSystem`], as given to its [`BlurSystemBuilder`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct BlurSystemConfig {
+    pub logging: bool,
+    pub log_path: Option<String>,
+    pub default_preset: BlurQualityPreset,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub capture_method: CapturePreference,
+}
+
+/// Builder for systems that need non-default init options.
+#[derive(Clone)]
+pub struct BlurSystemBuilder {
+    logging: bool,
+    log_path: Option<CString>,
+    default_preset: BlurQualityPreset,
+    capture_method: CapturePreference,
+}
+
+impl BlurSystemBuilder {
+    pub fn new() -> Self {
+        BlurSystemBuilder {
+            logging: true,
+            log_path: None,
+            default_preset: BlurQualityPreset::Balanced,
+            capture_method: CapturePreference::Auto,
+        }
+    }
+
+    /// Enable or disable native logging.
+    ///
+    /// With the `no-logging` feature this is a no-op: logging stays off.
+    pub fn logging(mut self, enabled: bool) -> Self {
+        self.logging = enabled;
+        self
+    }
+
+    /// Write the native log to `path` instead of the console.
+    ///
+    /// Ignored when logging is disabled.
+    pub fn log_path(mut self, path: &str) -> Result<Self, String> {
+        let path = CString::new(path).map_err(|_| "Log path contains a NUL byte".to_string())?;
+        self.log_path = Some(path);
+        Ok(self)
+    }
+
+    pub fn default_preset(mut self, preset: BlurQualityPreset) -> Self {
+        self.default_preset = preset;
+        self
+    }
+
+    /// Override the automatic choice of capture method, to work around a
+    /// capture bug of one environment. [`CapturePreference::DesktopDuplication`]
+    /// captures the desktop even for windows with a
+    /// [capture source](BlurWindow::set_capture_source), which then blur
+    /// what's on screen behind them instead, at lower latency.
+    /// Windows.Graphics.Capture only captures a source window, so preferring
+    /// it leaves the desktop on Desktop Duplication. There is no GDI method.
+    ///
+    /// A preferred method that is unavailable or fails to start falls back to
+    /// the automatic choice; [`BlurWindow::capture_method`] reports the one in
+    /// use. Applies to every window of the system, when it creates graphics
+    /// or changes its capture source.
+    pub fn capture_method(mut self, preference: CapturePreference) -> Self {
+        self.capture_method = preference;
+        self
+    }
+
+    /// Builder with every option taken from `config`.
+    pub fn from_config(config: &BlurSystemConfig) -> Result<Self, String> {
+        let builder = BlurSystemBuilder::new()
+            .logging(config.logging)
+            .default_preset(config.default_preset)
+            .capture_method(config.capture_method);
+        match &config.log_path {
+            Some(path) => builder.log_path(path),
+            None => Ok(builder),
+        }
+    }
+
+    /// Fails without initializing anything when desktop composition is off
+    /// (see [`native_dwm_enabled`]), as blur windows would render incorrectly.
+    pub fn build(self) -> Result<BlurSystem, String> {
+        if !native_dwm_enabled() {
+            return Err("Desktop composition (DWM) is disabled, which blur windows need; \
+                        enable it (on Windows 7, switch to an Aero theme) or start outside safe mode"
+                .into());
+        }
+
+        // The feature check lives here so no code path can turn logging back on.
+        let logging = self.logging && cfg!(not(feature = "no-logging"));
+        let options = BlurSystemOptionsC {
+            enable_logging: logging as i32,
+            log_path: match (&self.log_path, logging) {
+                (Some(path), true) => path.as_ptr(),
+                _ => ptr::null(),
+            },
+            default_preset: self.default_preset,
+        };
+
+        let started = Instant::now();
+        let handle = unsafe { blur_init(&options) };
+        let init_duration = started.elapsed();
+        if handle.0.is_null() {
+            return Err(
+                last_error_message().unwrap_or_else(|| "Failed to initialize blur system".into())
+            );
+        }
+        // Set even for Auto: the native preference outlives a shut down system
+        unsafe { blur_set_preferred_capture_method(handle, self.capture_method as i32) };
+
+        #[cfg(all(feature = "log", not(feature = "no-logging"), not(debug_assertions)))]
+        if native_is_debug_build() {
+            log::warn!("blurwindow: a debug build of the native library is loaded in a release build; expect poor performance");
+        }
+
+        let config = BlurSystemConfig {
+            logging: self.logging,
+            log_path: self
+                .log_path
+                .map(|path| path.to_string_lossy().into_owned()),
+            default_preset: self.default_preset,
+            capture_method: self.capture_method,
+        };
+        Ok(BlurSystem {
+            handle,
+            init_duration,
+            config,
+            theme_changed: Mutex::new(None),
+            fullscreen_changed: Mutex::new(None),
+        })
+    }
+}
+
+impl Default for BlurSystemBuilder {
+    fn default() -> Self {
+        BlurSystemBuilder::new()
+    }
+}
+
+/// Creation options of a [`BlurWindow`], as given to its [`WindowBuilder`]
+/// (the owner and initial bounds aside).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct WindowOptions {
+    pub top_most: bool,
+    pub click_through: bool,
+    pub manual_render: bool,
+    pub clamp_to_desktop: bool,
+    pub child: bool,
+    pub auto_resize_swapchain: bool,
+    pub start_hidden: bool,
+    pub auto_refit_on_display_change: bool,
+    pub pause_when_occluded: bool,
+    pub sync_to_owner_paint: bool,
+    pub wallpaper_mode: bool,
+}
+
+/// Builder for windows that need non-default creation options.
+#[derive(Clone)]
+pub struct WindowBuilder {
+    owner: HWND,
+    bounds: BlurRect,
+    top_most: bool,
+    click_through: bool,
+    manual_render: bool,
+    clamp_to_desktop: bool,
+    child: bool,
+    auto_resize_swapchain: bool,
+    start_hidden: bool,
+    auto_refit_on_display_change: bool,
+    pause_when_occluded: bool,
+    sync_to_owner_paint: bool,
+    wallpaper_mode: bool,
+    background_image: Option<(Vec<u8>, u32, u32)>,
+}
+
+impl WindowBuilder {
+    pub fn new() -> Self {
+        WindowBuilder {
+            owner: HWND::default(),
+            bounds: BlurRect {
+                left: 0,
+                top: 0,
+                right: 400,
+                bottom: 300,
+            },
+            top_most: true,
+            click_through: true,
+            manual_render: false,
+            clamp_to_desktop: false,
+            child: false,
+            auto_resize_swapchain: true,
+            start_hidden: false,
+            auto_refit_on_display_change: false,
+            pause_when_occluded: false,
+            sync_to_owner_paint: false,
+            wallpaper_mode: false,
+            background_image: None,
+        }
+    }
+
+    pub fn owner(mut self, owner: HWND) -> Self {
+        self.owner = owner;
+        self
+    }
+
+    pub fn bounds(mut self, x: i32, y: i32, w: i32, h: i32) -> Self {
+        self.bounds = BlurRect {
+            left: x,
+            top: y,
+            right: x + w,
+            bottom: y + h,
+        };
+        self
+    }
+
+    /// Keep the window above ordinary windows (the default). It may then
+    /// cover the taskbar too; [`BlurWindow::set_taskbar_relation`] decides.
+    pub fn top_most(mut self, enable: bool) -> Self {
+        self.top_most = enable;
+        self
+    }
+
+    pub fn click_through(mut self, enable: bool) -> Self {
+        self.click_through = enable;
+        self
+    }
+
+    /// Create the window in manual render mode, where frames are produced
+    /// one at a time by [`BlurWindow::render_once`] instead of only by the
+    /// continuous loop. Useful for deterministic screenshots and golden-image tests.
+    pub fn manual_render(mut self, enable: bool) -> Self {
+        self.manual_render = enable;
+        self
+    }
+
+    /// Clip the bounds to the virtual desktop on creation and on every
+    /// [`BlurWindow::set_bounds`]. Partly off-screen windows otherwise tend
+    /// to render blank. Building then fails if nothing of the bounds is left;
+    /// without clamping, empty bounds are fine for a window sized later.
+    pub fn clamp_to_desktop(mut self, enable: bool) -> Self {
+        self.clamp_to_desktop = enable;
+        self
+    }
+
+    /// Embed the blur as a child window of `owner` instead of a top-level
+    /// overlay. It is clipped to and moves with the owner's client area.
+    ///
+    /// Bounds (here and in [`BlurWindow::set_bounds`]) are then relative to
+    /// the owner's client area, `top_most` and `clamp_to_desktop` are ignored,
+    /// and an owner is required. A child window can't be excluded from capture
+    /// on its own, so the owner's top-level window is excluded instead: the
+    /// whole app disappears from screenshots and screen sharing.
+    pub fn child(mut self, enable: bool) -> Self {
+        self.child = enable;
+        self
+    }
+
+    /// Resize the swap chain whenever [`BlurWindow::set_bounds`] changes the
+    /// window's size (on by default). Turn off to batch resizes during an
+    /// interactive drag and call [`BlurWindow::resize_swapchain`] at the end;
+    /// frames are stretched until then.
+    pub fn auto_resize_swapchain(mut self, enable: bool) -> Self {
+        self.auto_resize_swapchain = enable;
+        self
+    }
+
+    /// Create the window hidden, so parameters can be applied before anything
+    /// appears on screen; [`BlurWindow::show`] it when ready. A hidden window
+    /// still needs [`BlurWindow::start`] to begin rendering, so start it
+    /// before showing it or the first frames are blank.
+    pub fn start_hidden(mut self, enable: bool) -> Self {
+        self.start_hidden = enable;
+        self
+    }
+
+    /// Keep the window covering its monitor when displays come and go: after
+    /// a monitor is added, removed or changes resolution, the window is
+    /// re-fitted to the whole monitor it overlaps most (the nearest one if
+    /// its monitor was unplugged). Meant for windows covering a monitor, such
+    /// as those from [`BlurSystem::create_fullscreen`]; any other window
+    /// grows to fill its monitor too.
+    ///
+    /// The re-fit runs on the window's thread while it dispatches messages,
+    /// before any [`BlurWindow::on_display_changed`] handler. It follows the
+    /// window's position rather than the original [`MonitorSelector`], so a
+    /// `ContainingCursor` window stays where it is. Has no effect on child
+    /// windows, which aren't told about display changes.
+    pub fn auto_refit_on_display_change(mut self, enable: bool) -> Self {
+        self.auto_refit_on_display_change = enable;
+        self
+    }
+
+    /// Skip frames while the window is fully occluded, to save GPU time when
+    /// e.g. a maximized app covers the overlay. A partly covered window keeps
+    /// rendering, and rendering resumes within a fraction of a second once
+    /// any part of it is revealed. See [`BlurWindow::is_occluded`] for what
+    /// counts as covering.
+    ///
+    /// Independent of [`BlurWindow::pause`]: the window isn't reported as
+    /// paused while skipping, and [`BlurWindow::resume`] doesn't force it to
+    /// render.
+    pub fn pause_when_occluded(mut self, enable: bool) -> Self {
+        self.pause_when_occluded = enable;
+        self
+    }
+
+    /// Render once per `WM_PAINT` of the owner instead of at a fixed frame
+    /// rate, so the blur updates exactly when the app below it repaints and
+    /// wastes no frames in between. Only for windows with an [`owner`](Self::owner),
+    /// attached or [`child`](Self::child); building fails without one.
+    ///
+    /// Takes precedence over the target frame rate, whether from the preset
+    /// or [`BlurWindow::set_target_fps`]. The owner is subclassed, which only
+    /// works from its own thread, so build on the thread that created the
+    /// owner (not on an [`OwnedWindowThread`]). Paints of the owner's child
+    /// controls don't count.
+    pub fn sync_to_owner_paint(mut self, enable: bool) -> Self {
+        self.sync_to_owner_paint = enable;
+        self
+    }
+
+    /// Draw the window behind the desktop icons, as a live wallpaper, instead
+    /// of as an overlay. Bounds stay screen coordinates; cover a monitor with
+    /// [`MonitorSelector::bounds`].
+    ///
+    /// The native side sends Progman the undocumented `0x052C` message, which
+    /// makes Explorer create a `WorkerW` window between the wallpaper and the
+    /// icons, and parents the blur window under it when the window is created
+    /// (on the first [`BlurWindow::start`]). The window is then click-through
+    /// whatever [`click_through`](Self::click_through) says, can't be
+    /// [`top_most`](Self::top_most), and building fails with
+    /// [`child`](Self::child).
+    ///
+    /// It also can't be hidden from screen capture, so blurring the desktop
+    /// would mostly blur the window itself: pair it with
+    /// [`background_image`](Self::background_image),
+    /// [`BlurWindow::set_source_texture`] or [`BlurWindow::set_capture_source`].
+    /// An Explorer restart destroys the `WorkerW` and with it the window,
+    /// which then stops rendering as when an owner closes (see
+    /// [`BlurWindow::on_owner_closed`]); rebuild it. Wallpaper windows aren't
+    /// told about display changes, and without Explorer there is no `WorkerW`
+    /// and the window never appears.
+    pub fn wallpaper_mode(mut self, enable: bool) -> Self {
+        self.wallpaper_mode = enable;
+        self
+    }
+
+    /// Blur a still image instead of the screen behind the window, e.g. a
+    /// wallpaper for a preview or a screenshot for a golden-image test. `rgba`
+    /// holds `width * height` pixels of 4 bytes in R, G, B, A order, rows top
+    /// to bottom; building fails if its length doesn't match or a dimension
+    /// is 0 or above 16384. The image is stretched to fill the window.
+    ///
+    /// The image is uploaded once at creation and replaces capture for the
+    /// window's lifetime: the capture source, capture interval and
+    /// [`BlurWindow::freeze`] have no effect. Unlike
+    /// [`BlurWindow::set_source_texture`], which re-reads a texture the
+    /// caller keeps rendering into, the image is a copy that never changes;
+    /// a source texture takes precedence while set, and the image is blurred
+    /// again once it's cleared.
+    pub fn background_image(mut self, rgba: Vec<u8>, width: u32, height: u32) -> Self {
+        self.background_image = Some((rgba, width, height));
+        self
+    }
+
+    /// Apply every flag in `options`, keeping the owner and bounds.
+    pub fn options(self, options: WindowOptions) -> Self {
+        self.top_most(options.top_most)
+            .click_through(options.click_through)
+            .manual_render(options.manual_render)
+            .clamp_to_desktop(options.clamp_to_desktop)
+            .child(options.child)
+            .auto_resize_swapchain(options.auto_resize_swapchain)
+            .start_hidden(options.start_hidden)
+            .auto_refit_on_display_change(options.auto_refit_on_display_change)
+            .pause_when_occluded(options.pause_when_occluded)
+            .sync_to_owner_paint(options.sync_to_owner_paint)
+            .wallpaper_mode(options.wallpaper_mode)
+    }
+
+    pub fn build(self, system: &BlurSystem) -> Result<BlurWindow, String> {
+        self.build_on(system.handle)
+    }
+
+    pub(crate) fn build_on(self, system: BlurSystemHandle) -> Result<BlurWindow, String> {
+        if system.0.is_null() {
+            return Err(format!(
+                "Blur system has been shut down ({:?})",
+                BlurErrorCode::NotInitialized
+            ));
+        }
+        let clamp_to_desktop = self.clamp_to_desktop && !self.child;
+        let bounds = if clamp_to_desktop {
+            self.bounds.clamp_to_desktop()
+        } else {
+            self.bounds
+        };
+        if clamp_to_desktop && bounds.is_empty() {
+            return Err("Window bounds are empty or entirely off the desktop".into());
+        }
+        if self.wallpaper_mode && self.child {
+            return Err("Wallpaper windows can't be child windows".into());
+        }
+        if let Some((rgba, width, height)) = &self.background_image {
+            let expected = (*width as usize)
+                .checked_mul(*height as usize)
+                .and_then(|n| n.checked_mul(4));
+            if *width == 0 || *height == 0 || expected != Some(rgba.len()) {
+                return Err(format!(
+                    "Background image of {}x{} needs {} bytes of RGBA, got {}",
+                    width,
+                    height,
+                    expected.map_or_else(|| "too many".into(), |n| n.to_string()),
+                    rgba.len()
+                ));
+            }
+        }
+
         let opts = BlurWindowOptionsC {
-            owner,
-            bounds: BlurRect { left: x, top: y, right: x + w, bottom: y + h },
-            top_most: 1,
-            click_through: 1,
+            owner: self.owner,
+            bounds,
+            top_most: self.top_most as i32,
+            click_through: self.click_through as i32,
+            manual_render: self.manual_render as i32,
+            child: self.child as i32,
+            start_hidden: self.start_hidden as i32,
+            wallpaper: self.wallpaper_mode as i32,
         };
 
         unsafe {
-            let win_handle = blur_create_window(self.handle, owner, &opts);
+            let started = Instant::now();
+            let win_handle = blur_create_window(system, self.owner, &opts);
+            let creation_duration = started.elapsed();
             if win_handle.0.is_null() {
-                return Err("Failed to create blur window".into());
+                return Err(
+                    last_error_message().unwrap_or_else(|| "Failed to create blur window".into())
+                );
+            }
+            let state = Arc::new(Mutex::new(WindowState {
+                visible: !self.start_hidden,
+                ..WindowState::new(bounds)
+            }));
+            let options = WindowOptions {
+                top_most: self.top_most,
+                click_through: self.click_through,
+                manual_render: self.manual_render,
+                clamp_to_desktop: self.clamp_to_desktop,
+                child: self.child,
+                auto_resize_swapchain: self.auto_resize_swapchain,
+                start_hidden: self.start_hidden,
+                auto_refit_on_display_change: self.auto_refit_on_display_change,
+                pause_when_occluded: self.pause_when_occluded,
+                sync_to_owner_paint: self.sync_to_owner_paint,
+                wallpaper_mode: self.wallpaper_mode,
+            };
+            registry::register(win_handle, &state, options);
+            let window = BlurWindow {
+                handle: win_handle,
+                clamp_to_desktop,
+                auto_resize_swapchain: self.auto_resize_swapchain,
+                state,
+                fps_sample: None,
+                max_kernel_radius: blur_get_max_kernel_radius(system),
+                owner_closed: Mutex::new(None),
+                frame: Mutex::new(None),
+                pinned: Mutex::new(HashSet::new()),
+                creation_duration,
+                started_at: Arc::new(Mutex::new(None)),
+                adapter: active_adapter_info(system).ok(),
+                options,
+                display_changed: Mutex::new(None),
+                power_changed: Mutex::new(None),
+                occlusion_changed: Mutex::new(None),
+            };
+            if self.pause_when_occluded {
+                let code = blur_set_pause_when_occluded(win_handle, 1);
+                if code != BlurErrorCode::Ok {
+                    return Err(format!(
+                        "Failed to enable pausing when occluded ({:?})",
+                        code
+                    ));
+                }
+            }
+            if self.sync_to_owner_paint
+                && blur_set_sync_to_owner_paint(win_handle, 1) != BlurErrorCode::Ok
+            {
+                return Err(
+                    last_error_message().unwrap_or_else(|| "Failed to sync to owner paints".into())
+                );
+            }
+            if let Some((rgba, width, height)) = &self.background_image {
+                let code = blur_set_background_image(win_handle, rgba.as_ptr(), *width, *height);
+                if code != BlurErrorCode::Ok {
+                    return Err(last_error_message().unwrap_or_else(|| {
+                        format!("Failed to set the background image ({:?})", code)
+                    }));
+                }
             }
-            Ok(BlurWindow { handle: win_handle })
+            // Registered even without a handler, to keep the cache in step
+            // with the default stop
+            window
+                .set_owner_closed(None)
+                .map_err(|code| format!("Failed to watch for the owner closing ({:?})", code))?;
+            if self.auto_refit_on_display_change {
+                window
+                    .set_display_changed(None)
+                    .map_err(|code| format!("Failed to watch for display changes ({:?})", code))?;
+            }
+            Ok(window)
         }
     }
 }
 
+impl Default for WindowBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Drop for BlurSystem {
     fn drop(&mut self) {
-        unsafe {
-            blur_shutdown(self.handle);
-        }
+        self.shutdown();
     }
 }
 
+/// A blur overlay window.
+///
+/// Deliberately neither `Send` nor `Sync`. The native window is not
+/// thread-safe: the HWND belongs to the thread that first calls
+/// [`start`](Self::start) and is only pumped and destroyed correctly there,
+/// `SetBounds`/`SetPreset` expect the UI thread and take no lock, and
+/// concurrent `start`/`stop` race on the render thread handle. Only the effect
+/// setters are serialized by the native graphics mutex. To control a window
+/// from other threads, use [`OwnedWindowThread`].
 pub struct BlurWindow {
+    pub(crate) handle: BlurWindowHandle,
+    clamp_to_desktop: bool,
+    auto_resize_swapchain: bool,
+    /// Shared with the window registry, so `BlurSystem::pause_all` keeps
+    /// `paused` up to date, and with the display-change re-fit.
+    pub(crate) state: Arc<Mutex<WindowState>>,
+    /// Last value returned by `fps_throttled` and when it was sampled.
+    fps_sample: Option<(Instant, f32)>,
+    /// The system's `max_kernel_radius` when the window was built.
+    max_kernel_radius: u32,
+    /// What the owner-closed trampoline sees, registered when the window is
+    /// built and replaced by `on_owner_closed`.
+    owner_closed: Mutex<Option<Box<OwnerClosed>>>,
+    /// Handler registered with `on_frame`, boxed twice like `owner_closed`.
+    frame: Mutex<Option<Box<FrameFn>>>,
+    /// Handler registered with `on_occlusion_changed`, boxed twice like `owner_closed`.
+    occlusion_changed: Mutex<Option<Box<OcclusionChangedFn>>>,
+    /// Parameters re-applied after preset changes, see `pin`.
+    pinned: Mutex<HashSet<ParamKind>>,
+    creation_duration: Duration,
+    /// When the running effect was started, see `uptime`. Shared with the
+    /// owner-closed trampoline, which clears it on the default stop.
+    started_at: Arc<Mutex<Option<Instant>>>,
+    /// For `metrics`; the system's adapter doesn't change while it lives.
+    adapter: Option<AdapterInfo>,
+    options: WindowOptions,
+    /// Re-fit and handler registered for display changes, see `on_display_changed`.
+    pub(crate) display_changed: Mutex<Option<Box<DisplayChanged>>>,
+    /// Watcher registered by `set_battery_policy`.
+    pub(crate) power_changed: Mutex<Option<Box<PowerChanged>>>,
+}
+
+type OwnerClosedFn = Box<dyn Fn() + Send>;
+
+/// User data of the owner-closed trampoline: the handler, or without one
+/// what the default stop needs.
+struct OwnerClosed {
     handle: BlurWindowHandle,
+    state: Arc<Mutex<WindowState>>,
+    started_at: Arc<Mutex<Option<Instant>>>,
+    handler: Option<OwnerClosedFn>,
+}
+type FrameFn = Box<dyn Fn(u64, f32) + Send>;
+type OcclusionChangedFn = Box<dyn Fn(bool) + Send>;
+
+unsafe extern "C" fn frame_trampoline(
+    frame_index: u64,
+    frame_time_ms: f32,
+    user_data: *mut std::ffi::c_void,
+) {
+    let handler = &*(user_data as *const FrameFn);
+    // Unwinding into native code is undefined behavior
+    let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        handler(frame_index, frame_time_ms)
+    }));
+}
+
+unsafe extern "C" fn occlusion_changed_trampoline(occluded: i32, user_data: *mut std::ffi::c_void) {
+    let handler = &*(user_data as *const OcclusionChangedFn);
+    // Unwinding into native code is undefined behavior
+    let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| handler(occluded != 0)));
+}
+
+unsafe extern "C" fn owner_closed_trampoline(user_data: *mut std::ffi::c_void) {
+    let owner_closed = &*(user_data as *const OwnerClosed);
+    match &owner_closed.handler {
+        // Unwinding into native code is undefined behavior
+        Some(handler) => {
+            let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(handler));
+        }
+        // The native default, plus telling the cache. blur_stop only signals
+        // the loop when called from the render thread.
+        None => {
+            blur_stop(owner_closed.handle);
+            owner_closed
+                .state
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .running = false;
+            *owner_closed
+                .started_at
+                .lock()
+                .unwrap_or_else(|e| e.into_inner()) = None;
+        }
+    }
 }
 
 impl BlurWindow {
+    pub(crate) fn state(&self) -> MutexGuard<'_, WindowState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Copy of every parameter applied through this wrapper so far.
+    pub fn snapshot(&self) -> WindowState {
+        self.state().clone()
+    }
+
+    /// Re-apply a previously taken [`snapshot`](Self::snapshot).
+    pub fn restore(&self, state: &WindowState) -> Result<(), BlurErrorCode> {
+        self.set_preset(state.preset)?;
+        self.set_target_fps(state.target_fps)?;
+        self.set_capture_interval(state.capture_interval)?;
+        self.set_min_frame_interval(state.min_frame_interval)?;
+        self.set_buffer_count(state.buffer_count)?;
+        self.set_bounds(state.bounds)?;
+        self.set_active_region(state.active_region)?;
+        self.set_cursor_follow(state.cursor_follow, state.cursor_follow_radius)?;
+        // Switching effects resets the effect-specific parameter, so apply it afterwards
+        #[cfg(feature = "serde")]
+        self.set_effect_stack(&EffectStack::from_state(state))?;
+        #[cfg(not(feature = "serde"))]
+        self.set_effect_type(state.effect)?;
+        self.set_strength(state.strength)?;
+        if let Some((top, bottom)) = state.strength_gradient {
+            self.set_strength_gradient(top, bottom)?;
+        }
+        if let Some((x, y)) = state.blur_xy {
+            self.set_blur_xy(x, y)?;
+        }
+        if let Some(param) = state.blur_param {
+            self.set_blur_param(param)?;
+        }
+        self.set_kernel_radius(state.kernel_radius)?;
+        self.set_downsample_xy(state.downsample.0, state.downsample.1)?;
+        self.set_edge_aa(state.edge_aa)?;
+        self.set_edge_feather(state.edge_feather)?;
+        match &state.custom_shader {
+            Some(hlsl) => self.set_custom_shader(hlsl)?,
+            None => self.clear_custom_shader()?,
+        }
+        self.set_directional_blur(state.direction_angle, state.direction_strength)?;
+        self.set_tint_color(state.tint)?;
+        if !state.tint_layers.is_empty() {
+            self.set_tint_layers(&state.tint_layers)?;
+        }
+        self.set_fallback_color(state.fallback_color)?;
+        self.set_tone_mapping(state.tone_mapping)?;
+        self.set_capture_source(state.capture_source.map(|h| HWND(h as *mut _)))?;
+        self.set_source_texture(RawTextureHandle(state.source_texture.unwrap_or(0) as *mut _))?;
+        self.set_debug_overlay(state.debug_overlay)?;
+        self.set_noise(&state.noise)?;
+        self.set_time_mode(state.time_mode)?;
+        self.set_temporal_mode(state.temporal_mode)?;
+        self.clear_region_effects()?;
+        for region in &state.region_effects {
+            self.add_region_effect(region.rect, region.effect)?;
+        }
+        if let Some(relation) = state.taskbar_relation {
+            self.set_taskbar_relation(relation)?;
+        }
+        // Last, so a window shown here appears with everything else applied
+        if state.visible {
+            self.show()
+        } else {
+            self.hide()
+        }
+    }
+
     pub fn start(&self) -> Result<(), BlurErrorCode> {
         let code = unsafe { blur_start(self.handle) };
-        if code == BlurErrorCode::Ok { Ok(()) } else { Err(code) }
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().running = true;
+        // Starting a running window again doesn't restart the clock
+        self.started_at
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get_or_insert_with(Instant::now);
+        Ok(())
     }
 
     pub fn stop(&self) -> Result<(), BlurErrorCode> {
         let code = unsafe { blur_stop(self.handle) };
-        if code == BlurErrorCode::Ok { Ok(()) } else { Err(code) }
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().running = false;
+        *self.started_at.lock().unwrap_or_else(|e| e.into_inner()) = None;
+        Ok(())
+    }
+
+    /// How long the effect has been running since [`start`](Self::start),
+    /// or `None` while stopped. Time spent [paused](Self::pause) counts;
+    /// stopping resets it.
+    pub fn uptime(&self) -> Option<Duration> {
+        self.started_at
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .map(|started| started.elapsed())
+    }
+
+    /// Start the effect at strength 0 and ease it in to the cached strength
+    /// over `duration`.
+    ///
+    /// The caller must drive the returned handle's `tick` from their loop;
+    /// the cached strength is left at the target throughout, so snapshots
+    /// taken mid-fade hold the final value. If starting fails, the native
+    /// strength is put back to the target.
+    pub fn start_with_fade(&self, duration: Duration) -> Result<FadeHandle<'_>, BlurErrorCode> {
+        let target = self.snapshot();
+        let code = unsafe { blur_set_strength(self.handle, 0.0) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        if let Err(code) = self.start() {
+            // Put back what the cache still holds
+            let _ = animation::apply_strength(
+                self,
+                target.strength,
+                target.strength_gradient,
+                target.blur_xy,
+            );
+            return Err(code);
+        }
+        Ok(FadeHandle::new(self, duration, &target))
     }
 
+    /// Capture, process, and present exactly one frame.
+    ///
+    /// Requires a window built with [`WindowBuilder::manual_render`]; other
+    /// windows get `InvalidParameter`. This is a no-op while the continuous
+    /// loop started by [`start`](Self::start) is running.
+    ///
+    /// The first call sets up graphics and returns `NotInitialized` if that
+    /// fails; `CaptureFailed` means no frame could be captured.
+    pub fn render_once(&self) -> Result<(), BlurErrorCode> {
+        let code = unsafe { blur_render_once(self.handle) };
+        if code == BlurErrorCode::Ok {
+            Ok(())
+        } else {
+            Err(code)
+        }
+    }
+
+    /// Set up graphics and compile the shaders of the effect, pipeline layers
+    /// and [region effects](Self::add_region_effect) now, so the first frame
+    /// after [`start`](Self::start) or [`show`](Self::show) doesn't hitch.
+    /// Nothing is presented.
+    ///
+    /// Optional but recommended before `show`, e.g. during a loading screen;
+    /// it takes tens of milliseconds. Effects changed afterwards compile on
+    /// their first frame again, so call it once more after reconfiguring.
+    /// Like `start`, it creates the native window, so call it from the
+    /// thread that dispatches the window's messages. Returns `D3D11Failed`
+    /// if graphics can't be initialized.
+    pub fn prewarm(&self) -> Result<(), BlurErrorCode> {
+        let code = unsafe { blur_prewarm(self.handle) };
+        if code == BlurErrorCode::Ok {
+            Ok(())
+        } else {
+            Err(code)
+        }
+    }
+
+    /// Switch quality preset. [Pinned](Self::pin) parameters are re-applied
+    /// afterwards.
     pub fn set_preset(&self, preset: BlurQualityPreset) -> Result<(), BlurErrorCode> {
+        let saved = self.snapshot();
+        self.apply_preset(preset)?;
+        self.reapply_pinned(&saved)
+    }
+
+    fn apply_preset(&self, preset: BlurQualityPreset) -> Result<(), BlurErrorCode> {
         let code = unsafe { blur_set_preset(self.handle, preset) };
-        if code == BlurErrorCode::Ok { Ok(()) } else { Err(code) }
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        let mut state = self.state();
+        state.preset = preset;
+        state.active_preset = preset;
+        Ok(())
     }
 
-    pub fn set_pipeline(&self, json: &str) -> Result<(), BlurErrorCode> {
-        let c_json = CString::new(json).map_err(|_| BlurErrorCode::InvalidParameter)?;
-        let code = unsafe { blur_set_pipeline(self.handle, c_json.as_ptr()) };
-        if code == BlurErrorCode::Ok { Ok(()) } else { Err(code) }
+    /// Apply `preset` and put the parameters it governs back to its
+    /// [`preset_baseline`], discarding later tweaks. [`set_preset`](Self::set_preset)
+    /// alone leaves strength, kernel radius and downsampling as they are.
+    ///
+    /// The baseline `downsample` is applied on both axes. [Pinned](Self::pin)
+    /// parameters keep their values.
+    pub fn reset_to_preset(&self, preset: BlurQualityPreset) -> Result<(), BlurErrorCode> {
+        let baseline = preset_baseline(preset);
+        let saved = self.snapshot();
+        self.apply_preset(preset)?;
+        self.set_strength(baseline.strength)?;
+        self.set_kernel_radius(baseline.kernel_radius)?;
+        self.set_downsample_xy(baseline.downsample, baseline.downsample)?;
+        self.reapply_pinned(&saved)
     }
 
-    pub fn get_fps(&self) -> f32 {
-        unsafe { blur_get_fps(self.handle) }
+    /// Keep `param` at its current value across [`set_preset`](Self::set_preset)
+    /// and [`reset_to_preset`](Self::reset_to_preset), which re-apply pinned
+    /// parameters from the cache once the preset is in place. Pinning is a
+    /// wrapper-side setting and is not part of [`snapshot`](Self::snapshot).
+    ///
+    /// A pinned parameter with nothing cached to re-apply follows the preset:
+    /// an effect parameter for an effect other than the current one or still
+    /// at its default, and `RainIntensity`, which the wrapper doesn't cache.
+    pub fn pin(&self, param: ParamKind) {
+        self.pinned
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(param);
     }
-}
 
-impl Drop for BlurWindow {
-    fn drop(&mut self) {
-        unsafe {
-            blur_destroy_window(self.handle);
+    pub fn unpin(&self, param: ParamKind) {
+        self.pinned
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&param);
+    }
+
+    pub fn is_pinned(&self, param: ParamKind) -> bool {
+        self.pinned
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains(&param)
+    }
+
+    /// Restore the pinned parameters from `saved`, taken before a preset change.
+    fn reapply_pinned(&self, saved: &WindowState) -> Result<(), BlurErrorCode> {
+        let pinned = self
+            .pinned
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        // Seed None so re-applying noise doesn't restart its animation
+        let mut noise = NoiseParams {
+            seed: None,
+            ..self.state().noise
+        };
+        let mut noise_pinned = false;
+        for param in pinned {
+            match param {
+                ParamKind::Strength => match (saved.strength_gradient, saved.blur_xy) {
+                    (Some((top, bottom)), _) => self.set_strength_gradient(top, bottom)?,
+                    (None, Some((x, y))) => self.set_blur_xy(x, y)?,
+                    (None, None) => self.set_strength(saved.strength)?,
+                },
+                ParamKind::KernelRadius => self.set_kernel_radius(saved.kernel_radius)?,
+                ParamKind::DirectionStrength => {
+                    self.set_directional_blur(saved.direction_angle, saved.direction_strength)?
+                }
+                ParamKind::NoiseIntensity => noise.intensity = saved.noise.intensity,
+                ParamKind::NoiseScale => noise.scale = saved.noise.scale,
+                ParamKind::NoiseSpeed => noise.speed = saved.noise.speed,
+                ParamKind::RainIntensity => {}
+                kind => {
+                    if let (Some(param), true) = (
+                        saved.blur_param,
+                        ParamKind::blur_param(saved.effect) == Some(kind),
+                    ) {
+                        self.set_blur_param(param)?;
+                    }
+                }
+            }
+            noise_pinned |= matches!(
+                param,
+                ParamKind::NoiseIntensity | ParamKind::NoiseScale | ParamKind::NoiseSpeed
+            );
+        }
+        if noise_pinned {
+            self.set_noise(&noise)?;
         }
+        Ok(())
+    }
+
+    /// The preset last passed to [`set_preset`](Self::set_preset), from the cache.
+    pub fn requested_preset(&self) -> BlurQualityPreset {
+        self.state().preset
+    }
+
+    /// The preset the native renderer is actually using, which performance
+    /// logic may have changed from the [requested](Self::requested_preset)
+    /// one. Also refreshes [`WindowState::active_preset`].
+    pub fn active_preset(&self) -> Result<BlurQualityPreset, BlurErrorCode> {
+        let raw = unsafe { blur_get_active_preset(self.handle) };
+        let preset = BlurQualityPreset::from_raw(raw).ok_or(BlurErrorCode::Unknown)?;
+        self.state().active_preset = preset;
+        Ok(preset)
+    }
+
+    /// Move/resize the window (screen coordinates).
+    /// Returns `InvalidParameter` if the window was built with
+    /// [`WindowBuilder::clamp_to_desktop`] and `bounds` lies entirely off-screen.
+    ///
+    /// If the size changes, the swap chain is resized too unless
+    /// [`WindowBuilder::auto_resize_swapchain`] was turned off; moves alone
+    /// don't touch it.
+    pub fn set_bounds(&self, bounds: BlurRect) -> Result<(), BlurErrorCode> {
+        let bounds = if self.clamp_to_desktop {
+            bounds.clamp_to_desktop()
+        } else {
+            bounds
+        };
+        if self.clamp_to_desktop && bounds.is_empty() {
+            return Err(BlurErrorCode::InvalidParameter);
+        }
+        let old = self.bounds();
+        let code = unsafe { blur_set_bounds(self.handle, &bounds) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().bounds = bounds;
+        if self.auto_resize_swapchain && old.size() != bounds.size() {
+            self.resize_swapchain()?;
+        }
+        Ok(())
+    }
+
+    /// Resize the swap chain to the current [`bounds`](Self::bounds). Needed
+    /// after size changes when [`WindowBuilder::auto_resize_swapchain`] is off.
+    pub fn resize_swapchain(&self) -> Result<(), BlurErrorCode> {
+        let (width, height) = self.bounds().size();
+        let code = unsafe {
+            blur_resize_swapchain(self.handle, width.max(0) as u32, height.max(0) as u32)
+        };
+        if code == BlurErrorCode::Ok {
+            Ok(())
+        } else {
+            Err(code)
+        }
+    }
+
+    pub fn bounds(&self) -> BlurRect {
+        self.state().bounds
+    }
+
+    /// Blur only `region` (relative to the window's top-left corner) and leave
+    /// the rest fully transparent and click-through. `None` blurs the whole window.
+    ///
+    /// Unlike exclusions this is an inclusion region: everything outside it is hidden.
+    pub fn set_active_region(&self, region: Option<BlurRect>) -> Result<(), BlurErrorCode> {
+        let code = match region {
+            Some(r) => {
+                let bounds = self.bounds();
+                let (w, h) = (bounds.right - bounds.left, bounds.bottom - bounds.top);
+                if r.left < 0
+                    || r.top < 0
+                    || r.right > w
+                    || r.bottom > h
+                    || r.right <= r.left
+                    || r.bottom <= r.top
+                {
+                    return Err(BlurErrorCode::InvalidParameter);
+                }
+                unsafe { blur_set_active_region(self.handle, &r) }
+            }
+            None => unsafe { blur_set_active_region(self.handle, ptr::null()) },
+        };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().active_region = region;
+        Ok(())
+    }
+
+    /// Draw `effect` inside `rect` (relative to the window's top-left corner)
+    /// instead of the window's own effect and pipeline layers, e.g. a Gaussian
+    /// title bar over a rain body. Regions are drawn in the order they were
+    /// added, so a later one covers an earlier one where they overlap.
+    ///
+    /// The region's effect starts from the window's strength, tint and noise
+    /// when it's first drawn; later setters only change the window's own
+    /// effect. Each region costs about as much GPU time as the window's
+    /// effect. After a resize only the part of `rect` still inside the window
+    /// is drawn.
+    ///
+    /// Returns `InvalidParameter` for a rect that is empty or outside the
+    /// window, or beyond [`MAX_REGION_EFFECTS`] regions.
+    pub fn add_region_effect(
+        &self,
+        rect: BlurRect,
+        effect: BlurEffectType,
+    ) -> Result<(), BlurErrorCode> {
+        let bounds = self.bounds();
+        let (w, h) = (bounds.right - bounds.left, bounds.bottom - bounds.top);
+        if rect.left < 0
+            || rect.top < 0
+            || rect.right > w
+            || rect.bottom > h
+            || rect.right <= rect.left
+            || rect.bottom <= rect.top
+            || self.state().region_effects.len() >= MAX_REGION_EFFECTS
+        {
+            return Err(BlurErrorCode::InvalidParameter);
+        }
+        let code = unsafe { blur_set_region_effect(self.handle, &rect, effect as i32) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state()
+            .region_effects
+            .push(RegionEffect { rect, effect });
+        Ok(())
+    }
+
+    /// Remove every region added with [`add_region_effect`](Self::add_region_effect).
+    pub fn clear_region_effects(&self) -> Result<(), BlurErrorCode> {
+        let code = unsafe { blur_set_region_effect(self.handle, ptr::null(), 0) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().region_effects.clear();
+        Ok(())
     }
-}
 
-unsafe impl Send for BlurWindow {}
-unsafe impl Sync for BlurWindow {}
+    /// Run `f` as one transaction: region and bounds changes made inside it
+    /// show up together on the next frame instead of one call at a time, which
+    /// avoids a frame where only some of them are applied.
+    ///
+    /// No frames are rendered while `f` runs. Setters called outside a batch
+    /// apply immediately. Batches nest, and the batch is closed even if `f`
+    /// fails; `f`'s error takes precedence.
+    ///
+    /// If `f` leaves settings that [conflict](Self::validate_state), the
+    /// window is restored to its state before the batch, before any frame
+    /// shows them, and `InvalidParameter` is returned. Call `validate_state`
+    /// inside `f` to find out which combination was at fault. A conflict that
+    /// already existed before the batch is left alone.
+    pub fn batch<F>(&self, f: F) -> Result<(), BlurErrorCode>
+    where
+        F: FnOnce(&BlurWindow) -> Result<(), BlurErrorCode>,
+    {
+        // Closes the batch on unwind too; a batch left open would freeze rendering
+        struct Batch(BlurWindowHandle);
+        impl Batch {
+            fn end(self) -> BlurErrorCode {
+                let handle = self.0;
+                std::mem::forget(self);
+                unsafe { blur_end_update(handle) }
+            }
+        }
+        impl Drop for Batch {
+            fn drop(&mut self) {
+                unsafe {
+                    blur_end_update(self.0);
+                }
+            }
+        }
+
+        let code = unsafe { blur_begin_update(self.handle) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        let batch = Batch(self.handle);
+        let before = self.snapshot();
+        let result = f(self).and_then(|()| match self.validate_state() {
+            Err(conflict) if ConflictError::check(&before).is_ok() => {
+                self.restore(&before)?;
+                Err(conflict.into())
+            }
+            _ => Ok(()),
+        });
+        let code = batch.end();
+        result?;
+        if code == BlurErrorCode::Ok {
+            Ok(())
+        } else {
+            Err(code)
+        }
+    }
+
+    /// Turn the blur into a circle of `radius_px` centered on the mouse cursor,
+    /// updated every frame ("spotlight blur").
+    ///
+    /// While enabled this overrides [`set_active_region`](Self::set_active_region)
+    /// and the window is forced click-through, since the region always sits under
+    /// the cursor and would otherwise swallow every click. Both return to their
+    /// previous settings when disabled. The radius must be positive when enabling.
+    pub fn set_cursor_follow(&self, enabled: bool, radius_px: f32) -> Result<(), BlurErrorCode> {
+        let code = unsafe { blur_set_cursor_follow(self.handle, enabled as i32, radius_px) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        let mut state = self.state();
+        state.cursor_follow = enabled;
+        state.cursor_follow_radius = radius_px;
+        Ok(())
+    }
+
+    pub fn set_effect_type(&self, effect: BlurEffectType) -> Result<(), BlurErrorCode> {
+        let code = unsafe { blur_set_effect_type(self.handle, effect as i32) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        let mut state = self.state();
+        state.effect = effect;
+        state.blur_param = None;
+        Ok(())
+    }
+
+    /// Blend strength, 0.0 (transparent) to 1.0 (full blur); clamped to
+    /// [`param_range`]`(ParamKind::Strength)`. A shortcut for
+    /// [`set_blur_xy`](Self::set_blur_xy) with both axes at `strength`.
+    pub fn set_strength(&self, strength: f32) -> Result<(), BlurErrorCode> {
+        let strength = param_range(ParamKind::Strength).clamp(strength);
+        let code = unsafe { blur_set_strength(self.handle, strength) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        let mut state = self.state();
+        state.strength = strength;
+        state.strength_gradient = None;
+        state.blur_xy = None;
+        Ok(())
+    }
+
+    /// Blend strength varying linearly from `top` (top edge) to `bottom`
+    /// (bottom edge), e.g. stronger behind a title bar. Both are clamped to
+    /// the [`ParamKind::Strength`] range.
+    ///
+    /// A later [`set_strength`](Self::set_strength) replaces the gradient with
+    /// a uniform value; a [fade](Self::start_with_fade) keeps it. The Rain effect
+    /// blurs uniformly at the average of the two.
+    pub fn set_strength_gradient(&self, top: f32, bottom: f32) -> Result<(), BlurErrorCode> {
+        let range = param_range(ParamKind::Strength);
+        let (top, bottom) = (range.clamp(top), range.clamp(bottom));
+        let code = unsafe { blur_set_strength_gradient(self.handle, top, bottom) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        let mut state = self.state();
+        state.strength = top;
+        state.strength_gradient = Some((top, bottom));
+        state.blur_xy = None;
+        Ok(())
+    }
+
+    /// Separate horizontal and vertical strengths, each clamped to the
+    /// [`ParamKind::Strength`] range, e.g. `(1.0, 0.0)` for a horizontal
+    /// smear that keeps vertical edges sharp. The larger value becomes the
+    /// blend strength and the kernel reaches proportionally less far along
+    /// the other axis, so equal values are the same as
+    /// [`set_strength`](Self::set_strength).
+    ///
+    /// A later `set_strength` or [`set_strength_gradient`](Self::set_strength_gradient)
+    /// makes the blur isotropic again. Gaussian, Box and Kawase honor the
+    /// split; Radial and Rain blur evenly at the larger value.
+    pub fn set_blur_xy(&self, x: f32, y: f32) -> Result<(), BlurErrorCode> {
+        let range = param_range(ParamKind::Strength);
+        let (x, y) = (range.clamp(x), range.clamp(y));
+        let code = unsafe { blur_set_blur_xy(self.handle, x, y) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        let mut state = self.state();
+        state.strength = x.max(y);
+        state.strength_gradient = None;
+        state.blur_xy = (x != y).then_some((x, y));
+        Ok(())
+    }
+
+    /// Effect-specific parameter (sigma for Gaussian, radius for Box, iterations for Kawase),
+    /// clamped to the current effect's [`ParamKind::blur_param`] range.
+    pub fn set_blur_param(&self, param: f32) -> Result<(), BlurErrorCode> {
+        let effect = self.state().effect;
+        let param =
+            ParamKind::blur_param(effect).map_or(param, |kind| param_range(kind).clamp(param));
+        let code = unsafe { blur_set_blur_param(self.handle, param) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().blur_param = Some(param);
+        Ok(())
+    }
+
+    /// Blur kernel radius in pixels, overriding the one derived from
+    /// [`set_blur_param`](Self::set_blur_param); 0 goes back to the derived radius.
+    ///
+    /// The radius controls how far the blur spreads, while
+    /// [`set_strength`](Self::set_strength) controls how much of the blurred
+    /// image is blended over the original. Gaussian and Box only; other effects
+    /// ignore it. Returns `InvalidParameter` above the system's
+    /// [`max_kernel_radius`](BlurSystem::max_kernel_radius).
+    pub fn set_kernel_radius(&self, radius_px: u32) -> Result<(), BlurErrorCode> {
+        if radius_px > self.max_kernel_radius {
+            return Err(BlurErrorCode::InvalidParameter);
+        }
+        let code = unsafe { blur_set_kernel_radius(self.handle, radius_px) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().kernel_radius = radius_px;
+        Ok(())
+    }
+
+    /// Blur at the window's resolution divided by `factor` on both axes, the
+    /// same as [`set_downsample_xy`](Self::set_downsample_xy)`(factor, factor)`.
+    pub fn set_downsample(&self, factor: u32) -> Result<(), BlurErrorCode> {
+        self.set_downsample_xy(factor, factor)
+    }
+
+    /// Run the effect and its pipeline layers at the window's resolution
+    /// divided by `x` horizontally and `y` vertically, scaling the backdrop
+    /// down and the result back up; `(1, 1)` blurs at full resolution. Each
+    /// factor of 2 halves the work along that axis. Returns
+    /// `InvalidParameter` for 0 or above [`MAX_DOWNSAMPLE`] on either axis.
+    ///
+    /// The kernel works in downsampled pixels, so it reaches `x` and `y` times
+    /// as far on screen: lower [`set_kernel_radius`](Self::set_kernel_radius)
+    /// or the strength to keep the look. Unequal factors also stretch the
+    /// blur along the more downsampled axis, and above 2 fine detail can
+    /// shimmer as it moves. Text usually holds up better downsampled
+    /// horizontally than vertically, e.g. `(2, 1)`.
+    ///
+    /// Region effects, the [custom shader](Self::set_custom_shader), the
+    /// [edge feather](Self::set_edge_feather) and [edge AA](Self::set_edge_aa)
+    /// stay at full resolution.
+    pub fn set_downsample_xy(&self, x: u32, y: u32) -> Result<(), BlurErrorCode> {
+        if !(1..=MAX_DOWNSAMPLE).contains(&x) || !(1..=MAX_DOWNSAMPLE).contains(&y) {
+            return Err(BlurErrorCode::InvalidParameter);
+        }
+        let code = unsafe { blur_set_downsample_xy(self.handle, x, y) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().downsample = (x, y);
+        Ok(())
+    }
+
+    /// Anti-aliasing samples for mask and corner edges: 1 (off), 2, 4 or 8;
+    /// anything else returns `InvalidParameter`. More samples give smoother
+    /// edges at a GPU cost. Edge AA runs at the window's full resolution,
+    /// whatever [`set_downsample_xy`](Self::set_downsample_xy) or a pipeline
+    /// stage's `downsample` blurs at.
+    ///
+    /// Reserved for rounded corners and alpha masks, so it has no visible
+    /// effect yet: the active region and the cursor-follow circle are window
+    /// regions, which clip whole pixels.
+    pub fn set_edge_aa(&self, samples: u32) -> Result<(), BlurErrorCode> {
+        if !matches!(samples, 1 | 2 | 4 | 8) {
+            return Err(BlurErrorCode::InvalidParameter);
+        }
+        let code = unsafe { blur_set_edge_aa(self.handle, samples) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().edge_aa = samples;
+        Ok(())
+    }
+
+    /// Fade the blur out over `pixels` at the edges of the
+    /// [active region](Self::set_active_region), or of the window without
+    /// one, for a soft falloff instead of a hard cut; 0 turns it off.
+    /// Negative values count as 0, and a non-finite one returns
+    /// `InvalidParameter`.
+    ///
+    /// The requested width is cached as given; when rendering, it's capped at
+    /// half the area's smaller side so a small region never vanishes. The
+    /// feather applies to the finished effect and its layers, tint included,
+    /// and the debug overlay is drawn over it unfaded. The region clip stays
+    /// hard, but the fade has reached 0 by then. With cursor follow on, the
+    /// window edges are feathered rather than the circle.
+    pub fn set_edge_feather(&self, pixels: f32) -> Result<(), BlurErrorCode> {
+        if !pixels.is_finite() {
+            return Err(BlurErrorCode::InvalidParameter);
+        }
+        let pixels = pixels.max(0.0);
+        let code = unsafe { blur_set_edge_feather(self.handle, pixels) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().edge_feather = pixels;
+        Ok(())
+    }
+
+    /// Run an HLSL snippet over every finished frame, for effects the
+    /// built-in ones don't cover. The snippet defines the entry point
+    ///
+    /// ```hlsl
+    /// float4 CustomShade(float4 color, float2 uv, float time)
+    /// ```
+    ///
+    /// which gets the pixel's blurred, tinted color (premultiplied alpha), its
+    /// texture coordinate (0,0 top-left to 1,1 bottom-right) and the
+    /// animation time in seconds (see [`set_time_mode`](Self::set_time_mode)),
+    /// and returns its new premultiplied color. It may also read the globals
+    /// `blurredTexture` (`Texture2D`, the whole frame), `linearSampler`
+    /// (bilinear, clamped) and `resolution` (`float2`, in pixels). It runs
+    /// after [region effects](Self::add_region_effect) and before the
+    /// [edge feather](Self::set_edge_feather).
+    ///
+    /// The snippet is compiled for `ps_5_0` right away. If it doesn't
+    /// compile this returns `InvalidParameter`, [`last_error`] holds the
+    /// compiler's diagnostics verbatim (line numbers count from the start of
+    /// the snippet), and the previous snippet stays. A snippet with a NUL
+    /// byte also returns `InvalidParameter`. An empty one is the same as
+    /// [`clear_custom_shader`](Self::clear_custom_shader).
+    pub fn set_custom_shader(&self, hlsl: &str) -> Result<(), BlurErrorCode> {
+        let c_hlsl = CString::new(hlsl).map_err(|_| BlurErrorCode::InvalidParameter)?;
+        let code = unsafe { blur_set_custom_shader(self.handle, c_hlsl.as_ptr()) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().custom_shader = (!hlsl.is_empty()).then(|| hlsl.to_string());
+        Ok(())
+    }
+
+    /// Remove the snippet set by [`set_custom_shader`](Self::set_custom_shader).
+    pub fn clear_custom_shader(&self) -> Result<(), BlurErrorCode> {
+        let code = unsafe { blur_set_custom_shader(self.handle, ptr::null()) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().custom_shader = None;
+        Ok(())
+    }
+
+    /// Motion-style blur along `angle_deg` (counter-clockwise from pointing
+    /// right, normalized to 0..360) with `strength` clamped to 0.0..=1.0.
+    ///
+    /// Replaces rather than composes with the zoom blur of
+    /// [`BlurEffectType::Radial`], and only renders while that effect is active;
+    /// the setting is kept across effect switches. Strength 0 turns it off.
+    /// Returns `InvalidParameter` for a non-finite value.
+    pub fn set_directional_blur(&self, angle_deg: f32, strength: f32) -> Result<(), BlurErrorCode> {
+        if !angle_deg.is_finite() || !strength.is_finite() {
+            return Err(BlurErrorCode::InvalidParameter);
+        }
+        let angle = angle_deg.rem_euclid(360.0);
+        // rem_euclid rounds tiny negative angles up to exactly 360
+        let angle = if angle >= 360.0 { 0.0 } else { angle };
+        let strength = param_range(ParamKind::DirectionStrength).clamp(strength);
+        let code = unsafe { blur_set_direction(self.handle, angle, strength) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        let mut state = self.state();
+        state.direction_angle = angle;
+        state.direction_strength = strength;
+        Ok(())
+    }
+
+    pub fn set_tint_color(&self, color: Color) -> Result<(), BlurErrorCode> {
+        let code = unsafe { blur_set_tint_color(self.handle, color.r, color.g, color.b, color.a) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        let mut state = self.state();
+        state.tint = color;
+        state.tint_layers.clear();
+        Ok(())
+    }
+
+    /// Stack several tints, composited in order (first = bottom) over the blur.
+    ///
+    /// Replaces the single tint color; an empty slice clears the tint entirely.
+    /// Returns `InvalidParameter` for more than [`MAX_TINT_LAYERS`] layers or a
+    /// channel outside 0.0 to 1.0.
+    pub fn set_tint_layers(&self, layers: &[Color]) -> Result<(), BlurErrorCode> {
+        let in_range = |v: f32| (0.0..=1.0).contains(&v);
+        if layers.len() > MAX_TINT_LAYERS
+            || !layers
+                .iter()
+                .all(|c| in_range(c.r) && in_range(c.g) && in_range(c.b) && in_range(c.a))
+        {
+            return Err(BlurErrorCode::InvalidParameter);
+        }
+        let code =
+            unsafe { blur_set_tint_layers(self.handle, layers.as_ptr(), layers.len() as u32) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        let mut state = self.state();
+        if layers.is_empty() {
+            state.tint = Color::TRANSPARENT;
+        }
+        state.tint_layers = layers.to_vec();
+        Ok(())
+    }
+
+    /// Blur `color` instead of black where the capture comes back black.
+    ///
+    /// Desktop duplication returns black for DRM-protected surfaces (video
+    /// streaming apps, for example) and for parts of the window outside every
+    /// monitor, which makes the blur look broken. Pure-black capture pixels are
+    /// replaced with `color`, blended by its alpha; alpha 0 disables this.
+    /// Genuinely black desktop content can't be told apart and is replaced too.
+    ///
+    /// Returns `InvalidParameter` for a channel outside 0.0 to 1.0.
+    pub fn set_fallback_color(&self, color: Color) -> Result<(), BlurErrorCode> {
+        let code = unsafe { blur_set_fallback_color(self.handle, &color) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().fallback_color = color;
+        Ok(())
+    }
+
+    /// How an HDR backdrop is mapped to SDR before blurring, to keep it from
+    /// looking blown out. Only applies while [`capture_format`](Self::capture_format)
+    /// is HDR; on an SDR capture the mode is stored, nothing changes and `Ok`
+    /// is returned. Every capture is SDR today (Windows maps HDR desktops to
+    /// SDR itself), so for now this only takes effect once an HDR capture
+    /// path exists.
+    pub fn set_tone_mapping(&self, mode: ToneMapping) -> Result<(), BlurErrorCode> {
+        let code = unsafe { blur_set_tone_mapping(self.handle, mode as i32) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().tone_mapping = mode;
+        Ok(())
+    }
+
+    /// Blur the contents of `source` instead of the desktop behind the window,
+    /// or return to the desktop with `None`.
+    ///
+    /// The source is captured with Windows.Graphics.Capture and stretched over
+    /// the whole blur window. This needs Windows 10 1903 or later, and Windows
+    /// 10 draws a yellow capture border around the source. A minimized source
+    /// stops producing frames (the last one stays), DRM-protected content comes
+    /// back black, and an elevated window can't be captured from a
+    /// non-elevated process. Only top-level windows are supported.
+    ///
+    /// Before the window first renders the source is only stored, and the
+    /// desktop is used if window capture then fails. Returns `InvalidParameter`
+    /// if `source` isn't a window or is this window, and `CaptureFailed` if
+    /// capture could not be started (the previous source stays active).
+    pub fn set_capture_source(&self, source: Option<HWND>) -> Result<(), BlurErrorCode> {
+        let code = unsafe { blur_set_capture_source(self.handle, source.unwrap_or_default()) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().capture_source = source.map(|h| h.0 as isize);
+        Ok(())
+    }
+
+    pub fn capture_source(&self) -> Option<HWND> {
+        self.state().capture_source.map(|h| HWND(h as *mut _))
+    }
+
+    /// Blur a texture the caller renders (e.g. a game framebuffer) instead of
+    /// capturing the screen; a null handle returns to capture. The texture is
+    /// stretched to fill the window and re-read every frame, and while it's
+    /// set the capture source, capture interval and [`freeze`](Self::freeze)
+    /// have no effect.
+    ///
+    /// The texture must be shared (`D3D11_RESOURCE_MISC_SHARED` or
+    /// `D3D11_RESOURCE_MISC_SHARED_NTHANDLE`) on the system's adapter, be
+    /// `DXGI_FORMAT_B8G8R8A8_UNORM` with `D3D11_BIND_SHADER_RESOURCE`, and not
+    /// be multisampled; otherwise this returns `InvalidParameter` and the
+    /// previous source stays active. Keep the handle and texture alive until
+    /// another texture is set or the window is dropped, and flush writes to
+    /// it before the next frame: no keyed mutex is taken.
+    pub fn set_source_texture(&self, handle: RawTextureHandle) -> Result<(), BlurErrorCode> {
+        let code = unsafe { blur_set_source_texture(self.handle, handle.0) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().source_texture = (!handle.0.is_null()).then_some(handle.0 as isize);
+        Ok(())
+    }
+
+    pub fn source_texture(&self) -> Option<RawTextureHandle> {
+        self.state()
+            .source_texture
+            .map(|h| RawTextureHandle(h as *mut _))
+    }
+
+    /// Average color of the backdrop behind the window, e.g. for an adaptive
+    /// tint: `window.set_tint_color(window.dominant_color()?.with_alpha(0.3))`.
+    ///
+    /// The last captured frame is reduced to one pixel on the GPU and read
+    /// back, which stalls until the GPU catches up, so sample on a timer (a
+    /// few times per second at most) rather than every frame. Alpha is always
+    /// 1. Returns `NotInitialized` until a frame has been captured.
+    pub fn dominant_color(&self) -> Result<Color, BlurErrorCode> {
+        let mut color = Color::default();
+        let code = unsafe { blur_sample_dominant_color(self.handle, &mut color) };
+        if code == BlurErrorCode::Ok {
+            Ok(color)
+        } else {
+            Err(code)
+        }
+    }
+
+    /// Apply all noise settings at once. Intensity, scale and speed are
+    /// clamped to their [`param_range`]. Returns `InvalidParameter`, before
+    /// changing anything, for a color channel outside 0.0 to 1.0.
+    pub fn set_noise(&self, noise: &NoiseParams) -> Result<(), BlurErrorCode> {
+        if let Some(c) = noise.color {
+            if ![c.r, c.g, c.b, c.a].iter().all(|v| (0.0..=1.0).contains(v)) {
+                return Err(BlurErrorCode::InvalidParameter);
+            }
+        }
+        let noise = &NoiseParams {
+            intensity: param_range(ParamKind::NoiseIntensity).clamp(noise.intensity),
+            scale: param_range(ParamKind::NoiseScale).clamp(noise.scale),
+            speed: param_range(ParamKind::NoiseSpeed).clamp(noise.speed),
+            ..*noise
+        };
+        let codes = unsafe {
+            [
+                blur_set_noise_intensity(self.handle, noise.intensity),
+                blur_set_noise_scale(self.handle, noise.scale),
+                blur_set_noise_speed(self.handle, noise.speed),
+                blur_set_noise_type(self.handle, noise.noise_type as i32),
+                blur_set_noise_blend_mode(self.handle, noise.blend_mode as i32),
+                blur_set_noise_color(
+                    self.handle,
+                    noise
+                        .color
+                        .as_ref()
+                        .map_or(ptr::null(), |c| c as *const Color),
+                ),
+            ]
+        };
+        if let Some(&code) = codes.iter().find(|&&c| c != BlurErrorCode::Ok) {
+            return Err(code);
+        }
+        if let Some(seed) = noise.seed {
+            self.set_noise_seed(seed)?;
+        }
+        let mut state = self.state();
+        state.noise = NoiseParams {
+            seed: noise.seed.or(state.noise.seed),
+            ..*noise
+        };
+        Ok(())
+    }
+
+    /// Select the noise pattern. Also restarts the noise animation, so a fixed
+    /// seed with speed 0 renders the same grain every time (e.g. screenshots).
+    pub fn set_noise_seed(&self, seed: u32) -> Result<(), BlurErrorCode> {
+        let code = unsafe { blur_set_noise_seed(self.handle, seed) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().noise.seed = Some(seed);
+        Ok(())
+    }
+
+    /// Switch to a fresh random seed and return it. Calling this periodically
+    /// keeps zero-speed grain from looking like a static smudge on the screen.
+    pub fn reseed_noise(&self) -> Result<u32, BlurErrorCode> {
+        use std::hash::{BuildHasher, Hasher};
+        // RandomState is randomly keyed, which is plenty for picking a grain pattern
+        let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+        hasher.write_u128(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |d| d.as_nanos()),
+        );
+        let seed = hasher.finish() as u32;
+        self.set_noise_seed(seed)?;
+        Ok(seed)
+    }
+
+    /// Replace the effect pipeline from JSON, either the structured schema
+    /// ([`PipelineConfig`] with the `serde` feature) or a legacy object.
+    ///
+    /// An empty or whitespace-only string and a root that isn't a JSON
+    /// object (with `serde`, also malformed JSON) are rejected with
+    /// `InvalidParameter` and a message saying why, without calling into the
+    /// native library, which would otherwise quietly turn them into a default
+    /// Gaussian blur. The error converts into a [`BlurErrorCode`] with `?`.
+    pub fn set_pipeline(&self, json: &str) -> Result<(), PipelineError> {
+        let c_json = pipeline_json(json)?;
+        let code = unsafe { blur_set_pipeline(self.handle, c_json.as_ptr()) };
+        if code != BlurErrorCode::Ok {
+            return Err(PipelineError::native(code));
+        }
+        // Keep the cache in step when the JSON uses the structured schema
+        #[cfg(feature = "serde")]
+        if let Ok(config) = serde_json::from_str::<PipelineConfig>(json) {
+            config.apply_to(&mut self.state());
+        }
+        Ok(())
+    }
+
+    /// Run an experimental native command that has no typed wrapper yet,
+    /// e.g. `raw_command("effect.parameters", r#"{"iterations": 6}"#)`. See
+    /// `blur_command` in `c_api.h` for the commands the loaded library knows.
+    ///
+    /// Unstable and unversioned: commands may change or disappear in any
+    /// native release, so prefer a typed method once one exists. Effects of
+    /// a command bypass the cached state, so [`snapshot`](Self::snapshot)
+    /// and [`restore`](Self::restore) don't carry them. Returns
+    /// `InvalidParameter` for strings with interior NUL bytes and for
+    /// commands the native side rejects; [`last_error`] then says why.
+    pub fn raw_command(&self, cmd: &str, args: &str) -> Result<(), BlurErrorCode> {
+        let c_cmd = CString::new(cmd).map_err(|_| BlurErrorCode::InvalidParameter)?;
+        let c_args = CString::new(args).map_err(|_| BlurErrorCode::InvalidParameter)?;
+        let code = unsafe { blur_command(self.handle, c_cmd.as_ptr(), c_args.as_ptr()) };
+        if code == BlurErrorCode::Ok {
+            Ok(())
+        } else {
+            Err(code)
+        }
+    }
+
+    /// Composite `stack`'s layers in order, replacing the effect and any
+    /// previous layers. [`set_effect_type`](Self::set_effect_type) later
+    /// replaces only the first layer.
+    ///
+    /// Returns `InvalidParameter` for an empty stack or more than
+    /// [`MAX_EFFECT_LAYERS`] layers.
+    #[cfg(feature = "serde")]
+    pub fn set_effect_stack(&self, stack: &EffectStack) -> Result<(), BlurErrorCode> {
+        if stack.layers.is_empty() || stack.layers.len() > MAX_EFFECT_LAYERS {
+            return Err(BlurErrorCode::InvalidParameter);
+        }
+        let json = serde_json::to_string(&stack.to_config()).map_err(|_| BlurErrorCode::Unknown)?;
+        Ok(self.set_pipeline(&json)?)
+    }
+
+    /// Serialize the current look into the JSON accepted by [`set_pipeline`](Self::set_pipeline).
+    ///
+    /// Feeding the result back into `set_pipeline` reproduces the same parameters.
+    #[cfg(feature = "serde")]
+    pub fn export_pipeline(&self) -> Result<String, BlurErrorCode> {
+        let config = PipelineConfig::from_state(&self.state());
+        serde_json::to_string(&config).map_err(|_| BlurErrorCode::Unknown)
+    }
+
+    /// Copy of the native handle, for calling `extern` functions the safe API
+    /// doesn't wrap yet.
+    ///
+    /// The handle stays owned by `self`: calling `blur_destroy_window` on it
+    /// leads to a double free when `Drop` runs, and changes made through it
+    /// bypass the cached [`WindowState`].
+    pub fn raw_handle(&self) -> BlurWindowHandle {
+        self.handle
+    }
+
+    pub fn get_fps(&self) -> f32 {
+        unsafe { blur_get_fps(self.handle) }
+    }
+
+    /// Like [`get_fps`](Self::get_fps), but only queries the native side once
+    /// per `min_interval` and returns the cached value in between. Meant for
+    /// UIs that poll every frame but only need a few updates per second.
+    pub fn fps_throttled(&mut self, min_interval: Duration) -> f32 {
+        self.fps_throttled_at(min_interval, Instant::now())
+    }
+
+    // `fps_throttled` with the clock passed in, so tests can step it
+    fn fps_throttled_at(&mut self, min_interval: Duration, now: Instant) -> f32 {
+        if let Some((sampled_at, fps)) = self.fps_sample {
+            if now.saturating_duration_since(sampled_at) < min_interval {
+                return fps;
+            }
+        }
+        let fps = self.get_fps();
+        self.fps_sample = Some((now, fps));
+        fps
+    }
+
+    /// Cap the render loop at `fps` instead of the preset's rate (15 to 60
+    /// depending on preset); `None` follows the preset again. Has no effect
+    /// while the window is built with [`WindowBuilder::sync_to_owner_paint`].
+    ///
+    /// Returns `InvalidParameter` for 0 or above [`MAX_TARGET_FPS`].
+    pub fn set_target_fps(&self, fps: Option<u32>) -> Result<(), BlurErrorCode> {
+        let raw = match fps {
+            Some(fps) if fps == 0 || fps > MAX_TARGET_FPS => {
+                return Err(BlurErrorCode::InvalidParameter)
+            }
+            Some(fps) => fps as i32,
+            None => 0,
+        };
+        let code = unsafe { blur_set_target_fps(self.handle, raw) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().target_fps = fps;
+        Ok(())
+    }
+
+    /// Capture the backdrop at most once per `interval` and blur the last
+    /// capture in between, so animated effects keep the render rate while
+    /// capture costs less. Resizing forces a fresh capture. `Duration::ZERO`
+    /// captures every frame again.
+    ///
+    /// The interval is rounded down to whole milliseconds; returns
+    /// `InvalidParameter` above [`MAX_CAPTURE_INTERVAL_MS`].
+    pub fn set_capture_interval(&self, interval: Duration) -> Result<(), BlurErrorCode> {
+        let ms = interval.as_millis();
+        if ms > MAX_CAPTURE_INTERVAL_MS as u128 {
+            return Err(BlurErrorCode::InvalidParameter);
+        }
+        let code = unsafe { blur_set_capture_interval_ms(self.handle, ms as u32) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().capture_interval = Duration::from_millis(ms as u64);
+        Ok(())
+    }
+
+    /// Present at most one frame per `interval`, to bound GPU load on weak
+    /// hardware. A hard floor: it wins over [`set_target_fps`](Self::set_target_fps)
+    /// and the preset's rate, and also spaces out frames of windows built with
+    /// [`WindowBuilder::sync_to_owner_paint`]. [`render_once`](Self::render_once)
+    /// isn't throttled. `Duration::ZERO` removes the floor.
+    ///
+    /// The interval is rounded down to whole milliseconds; returns
+    /// `InvalidParameter` above [`MAX_MIN_FRAME_INTERVAL_MS`].
+    pub fn set_min_frame_interval(&self, interval: Duration) -> Result<(), BlurErrorCode> {
+        let ms = interval.as_millis();
+        if ms > MAX_MIN_FRAME_INTERVAL_MS as u128 {
+            return Err(BlurErrorCode::InvalidParameter);
+        }
+        let code = unsafe { blur_set_min_frame_interval_ms(self.handle, ms as u32) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().min_frame_interval = Duration::from_millis(ms as u64);
+        Ok(())
+    }
+
+    /// Swap chain buffers: 2 (the default) for the lowest
+    /// [latency](Self::latency_ms), 3 for smoother presentation; anything
+    /// else returns `InvalidParameter`.
+    ///
+    /// Frames are presented without waiting for vsync and the compositor
+    /// shows the newest on each refresh. With 2 buffers at most one finished
+    /// frame waits behind the one on screen, but a frame slower than a
+    /// refresh interval shows as a skip; with 3 the render thread keeps
+    /// working while two wait, evening out frame times for up to one more
+    /// refresh interval of latency. It matters most when the frame rate is
+    /// close to the refresh rate.
+    ///
+    /// Applied before the next frame by recreating the buffers in place, so
+    /// no other setting is lost and nothing needs re-applying. No effect with
+    /// [`Backend::Layered`].
+    pub fn set_buffer_count(&self, count: u32) -> Result<(), BlurErrorCode> {
+        if count != 2 && count != 3 {
+            return Err(BlurErrorCode::InvalidParameter);
+        }
+        let code = unsafe { blur_set_buffer_count(self.handle, count) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().buffer_count = count;
+        Ok(())
+    }
+
+    /// Refresh rate of the monitor showing the window, in Hz, or `None` if
+    /// Windows doesn't report one. When the window spans monitors with
+    /// different rates, the primary monitor's rate is used.
+    pub fn display_refresh_hz(&self) -> Option<f32> {
+        let hz = unsafe { blur_get_display_refresh_hz(self.handle) };
+        if hz > 0.0 {
+            Some(hz)
+        } else {
+            None
+        }
+    }
+
+    /// Set the target FPS to the display's refresh rate (see
+    /// [`display_refresh_hz`](Self::display_refresh_hz)), so high-refresh
+    /// monitors aren't held to the preset's cap.
+    ///
+    /// Returns `Unknown` if the refresh rate can't be determined. The rate is
+    /// sampled once; call again after the window moves to another monitor.
+    pub fn match_display_refresh(&self) -> Result<(), BlurErrorCode> {
+        let hz = self.display_refresh_hz().ok_or(BlurErrorCode::Unknown)?;
+        self.set_target_fps(Some((hz.round() as u32).clamp(1, MAX_TARGET_FPS)))
+    }
+
+    /// Choose whether noise and rain animate with the wall clock or only
+    /// through [`set_time`](Self::set_time).
+    ///
+    /// Manual mode enables frame-accurate capture: the animation holds still
+    /// until `set_time` moves it. Entering manual mode never moves it by itself.
+    pub fn set_time_mode(&self, mode: TimeMode) -> Result<(), BlurErrorCode> {
+        let code = unsafe { blur_set_time_mode(self.handle, mode as i32) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().time_mode = mode;
+        Ok(())
+    }
+
+    /// Blur only content that moves: each new capture is compared with the
+    /// previous one, and the window turns transparent where nothing changed,
+    /// so static content such as text shows through sharp while video or
+    /// scrolling gets blurred. [`TemporalMode::MotionWeighted`] fades the
+    /// blur in with the size of the change instead of all at once. Check
+    /// [`BlurSystem::supports_temporal_mode`] first.
+    ///
+    /// The frame rate and [`set_capture_interval`](Self::set_capture_interval)
+    /// set how far apart the compared captures are. Only live captures are
+    /// compared: with a [source texture](Self::set_source_texture), a
+    /// background image or a [frozen](Self::freeze) backdrop everything is
+    /// blurred, as on the first capture after the mode or the window size
+    /// changes. The comparison runs after the [custom shader](Self::set_custom_shader)
+    /// and before the [edge feather](Self::set_edge_feather).
+    ///
+    /// Costs two extra copies of the capture in GPU memory, 8 bytes per pixel
+    /// (16 for HDR captures), counted in [`vram_usage_bytes`](Self::vram_usage_bytes).
+    pub fn set_temporal_mode(&self, mode: TemporalMode) -> Result<(), BlurErrorCode> {
+        let code = unsafe { blur_set_temporal_mode(self.handle, mode as i32) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().temporal_mode = mode;
+        Ok(())
+    }
+
+    /// Set the animation clock in manual time mode; the next frame advances
+    /// animated effects by the difference from the previous value.
+    ///
+    /// Stepping backwards rewinds noise, but rain drops only move forward.
+    /// Ignored in [`TimeMode::Realtime`].
+    pub fn set_time(&self, seconds: f32) -> Result<(), BlurErrorCode> {
+        let code = unsafe { blur_set_time(self.handle, seconds) };
+        if code == BlurErrorCode::Ok {
+            Ok(())
+        } else {
+            Err(code)
+        }
+    }
+
+    /// Skip capture and rendering while keeping the render thread alive.
+    pub fn pause(&self) -> Result<(), BlurErrorCode> {
+        let code = unsafe { blur_pause(self.handle) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().paused = true;
+        Ok(())
+    }
+
+    pub fn resume(&self) -> Result<(), BlurErrorCode> {
+        let code = unsafe { blur_resume(self.handle) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().paused = false;
+        Ok(())
+    }
+
+    /// Keep blurring a snapshot of the backdrop, taken on the next frame,
+    /// instead of capturing the screen every frame. Cheaper, and nothing new
+    /// on screen is sampled while frozen.
+    ///
+    /// Moving the window with [`set_bounds`](Self::set_bounds) while frozen
+    /// keeps showing the stale capture; [`unfreeze`](Self::unfreeze) and freeze
+    /// again to take a new snapshot.
+    pub fn freeze(&self) -> Result<(), BlurErrorCode> {
+        let code = unsafe { blur_freeze(self.handle) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().frozen = true;
+        Ok(())
+    }
+
+    /// Return to live capture and drop the snapshot.
+    pub fn unfreeze(&self) -> Result<(), BlurErrorCode> {
+        let code = unsafe { blur_unfreeze(self.handle) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().frozen = false;
+        Ok(())
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.state().frozen
+    }
+
+    /// Show a window created with [`WindowBuilder::start_hidden`] or hidden
+    /// with [`hide`](Self::hide).
+    pub fn show(&self) -> Result<(), BlurErrorCode> {
+        let code = unsafe { blur_show(self.handle) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().visible = true;
+        Ok(())
+    }
+
+    /// Hide the window. The render loop keeps running, so showing it again
+    /// is immediate.
+    pub fn hide(&self) -> Result<(), BlurErrorCode> {
+        let code = unsafe { blur_hide(self.handle) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().visible = false;
+        Ok(())
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.state().visible
+    }
+
+    /// Draw FPS, frame time and backend in the top-left corner of the window.
+    ///
+    /// A development aid: the panel is rendered into the blur itself, so
+    /// leave it off in production builds.
+    pub fn set_debug_overlay(&self, enabled: bool) -> Result<(), BlurErrorCode> {
+        let code = unsafe { blur_set_debug_overlay(self.handle, enabled as i32) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        self.state().debug_overlay = enabled;
+        Ok(())
+    }
+
+    /// Why capture is failing, from the most recent capture attempt
+    /// (initialization, [`set_capture_source`](Self::set_capture_source) or a
+    /// rendered frame). Check it when `start` leaves the window blank or
+    /// `render_once` returns `CaptureFailed`, to tell the user e.g. that
+    /// screen capture is disabled by their administrator.
+    pub fn capture_status(&self) -> CaptureStatus {
+        CaptureStatus::from_raw(unsafe { blur_get_capture_status(self.handle) })
+    }
+
+    /// Pixel format of the backdrop the effects run on, to interpret tint and
+    /// other colors in the right space. Fails with `CaptureFailed` until the
+    /// first frame is rendered. See [`CaptureFormat`] for converting colors.
+    pub fn capture_format(&self) -> Result<CaptureFormat, BlurErrorCode> {
+        let v = unsafe { blur_get_capture_format(self.handle) };
+        if v < 0 {
+            Err(BlurErrorCode::from_raw(v))
+        } else {
+            Ok(CaptureFormat::from_raw(v))
+        }
+    }
+
+    /// How the backdrop is captured, the first thing to check in a
+    /// performance or compatibility report. The desktop is captured with
+    /// Desktop Duplication, the fast path; a window set with
+    /// [`set_capture_source`](Self::set_capture_source) with
+    /// Windows.Graphics.Capture, which needs Windows 10 1903 or later, costs
+    /// more per frame, and falls back to the desktop if it can't start when
+    /// graphics are created.
+    ///
+    /// The native library has no GDI fallback: if neither method works,
+    /// nothing is captured and this returns [`CaptureMethod::None`], with the
+    /// reason in [`capture_status`](Self::capture_status). `None` is also
+    /// returned before graphics are created. A source texture or background
+    /// image doesn't stop the capture, so its method is still reported.
+    pub fn capture_method(&self) -> Result<CaptureMethod, BlurErrorCode> {
+        let v = unsafe { blur_get_capture_method(self.handle) };
+        if v < 0 {
+            Err(BlurErrorCode::from_raw(v))
+        } else {
+            Ok(CaptureMethod::from_raw(v))
+        }
+    }
+
+    /// Screen rectangle the last frame was captured from, in physical
+    /// pixels and clipped to its monitor. When the blur looks offset from
+    /// the window, compare it with [`bounds`](Self::bounds): a smaller
+    /// rectangle means the window reaches past its monitor, while a matching
+    /// one means the bounds are DPI-scaled rather than physical, typically
+    /// because the process isn't per-monitor DPI aware.
+    ///
+    /// Fails with `CaptureFailed` before the first frame and while a window
+    /// ([`set_capture_source`](Self::set_capture_source)), source texture or
+    /// background image is blurred instead of the screen.
+    pub fn capture_rect(&self) -> Result<BlurRect, BlurErrorCode> {
+        let mut rect = BlurRect::default();
+        let code = unsafe { blur_get_capture_rect(self.handle, &mut rect) };
+        if code == BlurErrorCode::Ok {
+            Ok(rect)
+        } else {
+            Err(code)
+        }
+    }
+
+    /// Approximate GPU memory held by this window's textures and swap chain.
+    ///
+    /// Estimated from texture sizes, so it tracks settings that change the
+    /// footprint (window size, effect type) but is not a driver query and
+    /// excludes other windows and system-wide usage. Returns `NotInitialized`
+    /// before graphics are created (the first `start` or `render_once`).
+    pub fn vram_usage_bytes(&self) -> Result<u64, BlurErrorCode> {
+        unsafe {
+            if blur_get_backend(self.handle) == Backend::None as i32 {
+                return Err(BlurErrorCode::NotInitialized);
+            }
+            Ok(blur_get_vram_usage(self.handle))
+        }
+    }
+
+    /// How far the last frame lagged behind the desktop it blurred: the time
+    /// from the capture returning the backdrop to the frame being presented,
+    /// measured on the render thread. Use it to weigh
+    /// [`set_capture_interval`](Self::set_capture_interval) and the preset's
+    /// downsampling against responsiveness.
+    ///
+    /// It's a lower bound, accurate to well under 0.1 ms: the time the desktop
+    /// frame waited before the capture returned it and the wait for the
+    /// compositor to show the presented frame aren't included, which usually
+    /// add one to two display refresh intervals. With a capture interval the
+    /// frames in between re-blur an older capture, so the value climbs
+    /// through the interval and drops on each new capture. 0.0 before the
+    /// first frame and while the backdrop is a texture, an image or frozen.
+    /// Returns `NotInitialized` before graphics are created.
+    pub fn latency_ms(&self) -> Result<f32, BlurErrorCode> {
+        unsafe {
+            if blur_get_backend(self.handle) == Backend::None as i32 {
+                return Err(BlurErrorCode::NotInitialized);
+            }
+            Ok(blur_get_latency_ms(self.handle))
+        }
+    }
+
+    /// The options the window was created with.
+    pub fn options(&self) -> WindowOptions {
+        self.options
+    }
+
+    /// How long `blur_create_window` took. Graphics are set up later, on the
+    /// first [`start`](Self::start) or [`render_once`](Self::render_once), so
+    /// this is mostly window creation.
+    pub fn creation_duration(&self) -> Duration {
+        self.creation_duration
+    }
+
+    /// Health snapshot for periodic logging or telemetry.
+    ///
+    /// FPS, frame time and backend are queried from the native window;
+    /// `running` and `paused` come from the Rust-side cache.
+    pub fn metrics(&self) -> Metrics {
+        let (running, paused) = {
+            let state = self.state();
+            (state.running, state.paused)
+        };
+        unsafe {
+            Metrics {
+                fps: blur_get_fps(self.handle),
+                frame_time_ms: blur_get_frame_time(self.handle),
+                latency_ms: blur_get_latency_ms(self.handle),
+                backend: Backend::from_raw(blur_get_backend(self.handle)),
+                capture_method: CaptureMethod::from_raw(blur_get_capture_method(self.handle)),
+                vram_bytes: blur_get_vram_usage(self.handle),
+                running,
+                paused,
+                creation_duration: self.creation_duration,
+                uptime: self.uptime(),
+                adapter: self.adapter.clone(),
+            }
+        }
+    }
+
+    /// Call `f` once when the owner window is destroyed.
+    ///
+    /// Without a handler the window stops on its own when the owner goes
+    /// away, as if [`stop`](Self::stop) had been called. Registering one
+    /// replaces that default and nothing is stopped: `f` runs on the render
+    /// thread or on the thread that created the window, and `BlurWindow`
+    /// isn't `Send`, so forward the event over a channel and call `stop` (or
+    /// rebuild) on the window's thread. Calling this again replaces the
+    /// previous handler.
+    pub fn on_owner_closed(&self, f: impl Fn() + Send + 'static) -> Result<(), BlurErrorCode> {
+        self.set_owner_closed(Some(Box::new(f)))
+    }
+
+    /// Remove the handler installed by [`on_owner_closed`](Self::on_owner_closed),
+    /// restoring the default stop.
+    pub fn off_owner_closed(&self) -> Result<(), BlurErrorCode> {
+        self.set_owner_closed(None)
+    }
+
+    fn set_owner_closed(&self, handler: Option<OwnerClosedFn>) -> Result<(), BlurErrorCode> {
+        let owner_closed = Box::new(OwnerClosed {
+            handle: self.handle,
+            state: Arc::clone(&self.state),
+            started_at: Arc::clone(&self.started_at),
+            handler,
+        });
+        let user_data = &*owner_closed as *const OwnerClosed as *mut std::ffi::c_void;
+        let mut slot = self.owner_closed.lock().unwrap_or_else(|e| e.into_inner());
+        let code = unsafe {
+            blur_set_owner_closed_callback(self.handle, Some(owner_closed_trampoline), user_data)
+        };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        // The native side no longer calls the old handler once the setter returns
+        *slot = Some(owner_closed);
+        Ok(())
+    }
+
+    /// Call `f(frame_index, frame_time_ms)` after every presented frame, e.g.
+    /// to drive a custom performance HUD. Replaces any previous handler.
+    ///
+    /// `f` runs on the render thread at the frame rate (on the calling thread
+    /// for [`render_once`](Self::render_once)) and frame pacing waits for it,
+    /// so keep it cheap: hand the numbers off rather than doing work inline.
+    /// It must not call `on_frame` or [`off_frame`](Self::off_frame) itself.
+    /// A panic in `f` is caught and that frame's call is skipped.
+    pub fn on_frame(&self, f: impl Fn(u64, f32) + Send + 'static) -> Result<(), BlurErrorCode> {
+        let handler: Box<FrameFn> = Box::new(Box::new(f));
+        let user_data = &*handler as *const FrameFn as *mut std::ffi::c_void;
+        let mut slot = self.frame.lock().unwrap_or_else(|e| e.into_inner());
+        let code =
+            unsafe { blur_set_frame_callback(self.handle, Some(frame_trampoline), user_data) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        *slot = Some(handler);
+        Ok(())
+    }
+
+    /// Remove the handler installed by [`on_frame`](Self::on_frame).
+    pub fn off_frame(&self) -> Result<(), BlurErrorCode> {
+        let mut slot = self.frame.lock().unwrap_or_else(|e| e.into_inner());
+        let code = unsafe { blur_set_frame_callback(self.handle, None, ptr::null_mut()) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        *slot = None;
+        Ok(())
+    }
+
+    /// Whether the window is fully occluded right now: cloaked (e.g. on
+    /// another virtual desktop) or covered in full by the windows above it.
+    /// Layered and DirectComposition windows, like other blur overlays, may
+    /// be see-through and never count as covering.
+    pub fn is_occluded(&self) -> Result<bool, BlurErrorCode> {
+        let v = unsafe { blur_is_occluded(self.handle) };
+        if v < 0 {
+            Err(BlurErrorCode::from_raw(v))
+        } else {
+            Ok(v != 0)
+        }
+    }
+
+    /// Call `f` with `true` when the window becomes fully occluded and with
+    /// `false` when part of it is revealed again. Replaces any previous handler.
+    ///
+    /// The render loop checks a few times a second, so nothing is reported
+    /// while the window is stopped. `f` runs on the render thread and must not
+    /// call `on_occlusion_changed` or
+    /// [`off_occlusion_changed`](Self::off_occlusion_changed).
+    pub fn on_occlusion_changed(
+        &self,
+        f: impl Fn(bool) + Send + 'static,
+    ) -> Result<(), BlurErrorCode> {
+        let handler: Box<OcclusionChangedFn> = Box::new(Box::new(f));
+        let user_data = &*handler as *const OcclusionChangedFn as *mut std::ffi::c_void;
+        let mut slot = self
+            .occlusion_changed
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let code = unsafe {
+            blur_set_occlusion_changed_callback(
+                self.handle,
+                Some(occlusion_changed_trampoline),
+                user_data,
+            )
+        };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        *slot = Some(handler);
+        Ok(())
+    }
+
+    /// Remove the handler installed by [`on_occlusion_changed`](Self::on_occlusion_changed).
+    pub fn off_occlusion_changed(&self) -> Result<(), BlurErrorCode> {
+        let mut slot = self
+            .occlusion_changed
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let code =
+            unsafe { blur_set_occlusion_changed_callback(self.handle, None, ptr::null_mut()) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        *slot = None;
+        Ok(())
+    }
+}
+
+impl Drop for BlurWindow {
+    fn drop(&mut self) {
+        // First, so pause_all never reaches a destroyed handle
+        registry::unregister(self.handle);
+        unsafe {
+            blur_destroy_window(self.handle);
+        }
+    }
+}
+
+// The native system is a mutex-guarded singleton, so the handle can be moved
+// to (and shut down from) another thread.
+unsafe impl Send for BlurSystem {}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_own_window_recognizes_registered_windows() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let window = WindowBuilder::new().build(&system).unwrap();
+        // Mock windows use their handle value as HWND
+        let hwnd = HWND(window.handle.0);
+
+        assert!(system.is_own_window(hwnd));
+        assert!(!system.is_own_window(HWND::default()));
+        drop(window);
+        assert!(!system.is_own_window(hwnd));
+    }
+
+    #[test]
+    fn create_window_after_shutdown_is_not_initialized() {
+        let _serial = mock::serial();
+        let mut system = BlurSystem::headless();
+        system.shutdown();
+        mock::clear_recorded_calls();
+
+        assert_eq!(system.max_kernel_radius(), 0);
+        let error = system
+            .create_window(HWND::default(), 0, 0, 100, 100)
+            .err()
+            .unwrap();
+        assert!(error.contains("NotInitialized"), "{}", error);
+        let created = mock::recorded_calls()
+            .iter()
+            .any(|call| call.function.starts_with("blur_create"));
+        assert!(!created);
+    }
+
+    // The `blur_init` arguments of a system built by `builder`
+    fn init_args(builder: BlurSystemBuilder) -> String {
+        mock::clear_recorded_calls();
+        builder.build().unwrap();
+        let calls = mock::recorded_calls();
+        calls
+            .iter()
+            .find(|call| call.function == "blur_init")
+            .unwrap()
+            .args
+            .clone()
+    }
+
+    #[test]
+    fn builder_passes_logging_options_to_init() {
+        let _serial = mock::serial();
+        let to_file = BlurSystemBuilder::new()
+            .logging(true)
+            .log_path("blur.log")
+            .unwrap();
+        let expected = if cfg!(feature = "no-logging") {
+            "0, None, Balanced"
+        } else {
+            "1, Some(\"blur.log\"), Balanced"
+        };
+        assert_eq!(init_args(to_file), expected);
+
+        // A log path alone doesn't turn logging on
+        let disabled = BlurSystemBuilder::new()
+            .logging(false)
+            .log_path("blur.log")
+            .unwrap();
+        assert_eq!(init_args(disabled), "0, None, Balanced");
+    }
+
+    #[test]
+    fn fps_throttled_queries_once_per_interval() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let mut window = WindowBuilder::new().build(&system).unwrap();
+        let interval = Duration::from_millis(250);
+        let start = Instant::now();
+
+        window.simulate_fps(30.0);
+        assert_eq!(window.fps_throttled_at(interval, start), 30.0);
+        window.simulate_fps(60.0);
+        let cached = window.fps_throttled_at(interval, start + Duration::from_millis(249));
+        assert_eq!(cached, 30.0);
+        assert_eq!(window.fps_throttled_at(interval, start + interval), 60.0);
+    }
+
+    #[test]
+    fn last_error_is_reported_once() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let window = WindowBuilder::new().build(&system).unwrap();
+
+        mock::simulate_error(BlurErrorCode::InvalidParameter, "Strength out of range");
+        assert_eq!(last_error().as_deref(), Some("Strength out of range"));
+        window.set_strength(0.5).unwrap();
+        assert_eq!(last_error(), None);
+    }
+
+    #[test]
+    fn last_error_detail_pairs_code_and_message() {
+        let _serial = mock::serial();
+        let _ = last_error();
+        assert_eq!(last_error_detail(), None);
+
+        mock::simulate_error(
+            BlurErrorCode::NotInitialized,
+            "Blur system is not initialized",
+        );
+        let detail = last_error_detail();
+        assert_eq!(
+            detail,
+            Some((
+                BlurErrorCode::NotInitialized,
+                "Blur system is not initialized".to_string()
+            ))
+        );
+        // Both are cleared
+        assert_eq!(last_error_detail(), None);
+        assert_eq!(
+            unsafe { blur_get_last_error_code() },
+            BlurErrorCode::Ok as i32
+        );
+    }
+
+    #[test]
+    fn pinned_params_survive_a_preset_reset() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let window = WindowBuilder::new().build(&system).unwrap();
+        window.set_strength(0.4).unwrap();
+        window.set_kernel_radius(3).unwrap();
+
+        window.pin(ParamKind::Strength);
+        assert!(window.is_pinned(ParamKind::Strength));
+        window.reset_to_preset(BlurQualityPreset::High).unwrap();
+        assert_eq!(window.state().strength, 0.4);
+        assert_eq!(
+            window.state().kernel_radius,
+            preset_baseline(BlurQualityPreset::High).kernel_radius
+        );
+        assert_eq!(
+            window.call_log().last().unwrap().to_string(),
+            "blur_set_strength(0.4)"
+        );
+
+        window.unpin(ParamKind::Strength);
+        assert!(!window.is_pinned(ParamKind::Strength));
+        window.reset_to_preset(BlurQualityPreset::High).unwrap();
+        assert_eq!(
+            window.state().strength,
+            preset_baseline(BlurQualityPreset::High).strength
+        );
+    }
+
+    #[test]
+    fn set_pipeline_rejects_what_isnt_an_object() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let window = WindowBuilder::new().build(&system).unwrap();
+        window.clear_call_log();
+
+        let message = |json: &str| {
+            let error = window.set_pipeline(json).unwrap_err();
+            assert_eq!(error.code, BlurErrorCode::InvalidParameter);
+            error.message
+        };
+        for json in ["", "   ", "\n\t"] {
+            assert_eq!(message(json), "Pipeline JSON is empty", "{:?}", json);
+        }
+        for json in ["[]", "42", "\"gaussian\"", "null"] {
+            assert_eq!(
+                message(json),
+                "Pipeline JSON must be an object",
+                "{:?}",
+                json
+            );
+        }
+        #[cfg(feature = "serde")]
+        assert!(message("{\"version\": 1,").starts_with("Invalid pipeline JSON: "));
+        assert!(window.call_log().is_empty());
+
+        window.set_pipeline("  {}").unwrap();
+        assert_eq!(window.call_log().len(), 1);
+    }
+
+    #[test]
+    fn validate_pipeline_accepts_valid_json() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let json =
+            r#"{"version": 1, "pipeline": [{"type": "gaussian", "params": {"strength": 0.8}}]}"#;
+        assert_eq!(system.validate_pipeline(json), Ok(()));
+    }
+
+    #[test]
+    fn validate_pipeline_explains_malformed_json() {
+        let _serial = mock::serial();
+        let mut system = BlurSystem::headless();
+        let message = |json: &str| system.validate_pipeline(json).unwrap_err().message;
+
+        assert_eq!(message(" "), "Pipeline JSON is empty");
+        assert_eq!(message("[1, 2]"), "Pipeline JSON must be an object");
+        // With serde the JSON check already rejects it
+        #[cfg(not(feature = "serde"))]
+        assert_eq!(message("{\"a\": 1}\0"), "Pipeline JSON contains a NUL byte");
+        #[cfg(feature = "serde")]
+        assert!(message("{\"pipeline\": [").starts_with("Invalid pipeline JSON: "));
+
+        system.shutdown();
+        let rejected = system.validate_pipeline("{}").unwrap_err();
+        assert_eq!(rejected.code, BlurErrorCode::InvalidHandle);
+        assert_eq!(rejected.message, "Pipeline rejected (InvalidHandle)");
+        // A native message comes with the code it was reported with
+        mock::simulate_error(
+            BlurErrorCode::NotInitialized,
+            "Blur system is not initialized",
+        );
+        let rejected = system.validate_pipeline("{}").unwrap_err();
+        assert_eq!(rejected.code, BlurErrorCode::NotInitialized);
+        assert_eq!(rejected.message, "Blur system is not initialized");
+    }
+
+    #[test]
+    fn uptime_runs_from_start_to_stop() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let window = WindowBuilder::new().build(&system).unwrap();
+        assert_eq!(window.uptime(), None);
+
+        window.start().unwrap();
+        std::thread::sleep(Duration::from_millis(5));
+        // Neither pausing nor starting again resets the clock
+        window.pause().unwrap();
+        window.start().unwrap();
+        assert!(window.uptime().unwrap() >= Duration::from_millis(5));
+        assert!(window.metrics().uptime.is_some());
+
+        window.stop().unwrap();
+        assert_eq!(window.uptime(), None);
+    }
+
+    #[test]
+    fn owner_closing_stops_unless_handled() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let window = WindowBuilder::new().build(&system).unwrap();
+        window.start().unwrap();
+        window.clear_call_log();
+
+        window.simulate_owner_closed();
+        assert_eq!(window.call_log().last().unwrap().to_string(), "blur_stop()");
+        assert!(!window.metrics().running);
+        assert_eq!(window.uptime(), None);
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        window.start().unwrap();
+        window
+            .on_owner_closed(move || tx.send(()).unwrap())
+            .unwrap();
+        window.clear_call_log();
+        window.simulate_owner_closed();
+        rx.try_recv().unwrap();
+        assert!(window.call_log().is_empty());
+        assert!(window.metrics().running);
+
+        window.off_owner_closed().unwrap();
+        window.simulate_owner_closed();
+        assert!(!window.metrics().running);
+    }
+
+    #[test]
+    fn unclamped_windows_may_start_empty() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let window = WindowBuilder::new()
+            .bounds(0, 0, 0, 0)
+            .build(&system)
+            .unwrap();
+        assert!(window.state().bounds.is_empty());
+    }
+
+    #[test]
+    fn builder_rejects_a_log_path_with_nul() {
+        assert!(BlurSystemBuilder::new().log_path("blur\0.log").is_err());
+    }
+}
@@ -0,0 +1,208 @@
+use super::*;
+use std::time::Duration;
+
+/// Noise overlay settings applied together by
+/// [`BlurWindow::set_noise`](crate::BlurWindow::set_noise).
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NoiseParams {
+    /// 0.0 (off) to 1.0.
+    pub intensity: f32,
+    /// Spatial scale, 1.0 to 1000.0.
+    pub scale: f32,
+    /// Animation speed, 0.0 for static grain.
+    pub speed: f32,
+    pub noise_type: NoiseType,
+    pub blend_mode: NoiseBlendMode,
+    /// Multiplies the grain, independently of the tint; alpha fades from
+    /// monochrome (0) to the full color (1). `None` keeps the grain
+    /// monochrome.
+    pub color: Option<Color>,
+    /// Pattern seed; `None` keeps whatever seed the window already uses
+    /// (0 on a new window).
+    pub seed: Option<u32>,
+}
+
+impl Default for NoiseParams {
+    fn default() -> Self {
+        NoiseParams {
+            intensity: 0.0,
+            scale: 100.0,
+            speed: 1.0,
+            noise_type: NoiseType::White,
+            blend_mode: NoiseBlendMode::Additive,
+            color: None,
+            seed: None,
+        }
+    }
+}
+
+/// An effect drawn inside part of a window, see
+/// [`BlurWindow::add_region_effect`](crate::BlurWindow::add_region_effect).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegionEffect {
+    /// Relative to the window's top-left corner.
+    pub rect: BlurRect,
+    pub effect: BlurEffectType,
+}
+
+/// Rust-side cache of everything applied to a [`BlurWindow`](crate::BlurWindow)
+/// through the safe API.
+///
+/// The native library has no getters for most parameters, so this is the
+/// source of truth for [`snapshot`](crate::BlurWindow::snapshot) and
+/// [`restore`](crate::BlurWindow::restore).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowState {
+    /// Preset requested via [`set_preset`](crate::BlurWindow::set_preset).
+    pub preset: BlurQualityPreset,
+    /// Preset in effect as of the last [`set_preset`](crate::BlurWindow::set_preset)
+    /// or [`active_preset`](crate::BlurWindow::active_preset) call.
+    /// Informational only: [`restore`](crate::BlurWindow::restore) applies `preset`.
+    pub active_preset: BlurQualityPreset,
+    /// Frame rate cap overriding the preset's, `None` to follow the preset.
+    pub target_fps: Option<u32>,
+    /// Minimum time between backdrop captures, zero to capture every frame.
+    pub capture_interval: Duration,
+    /// Minimum time between presented frames, zero for no floor.
+    pub min_frame_interval: Duration,
+    /// Swap chain buffers, 2 or 3, see
+    /// [`set_buffer_count`](crate::BlurWindow::set_buffer_count).
+    pub buffer_count: u32,
+    /// Window bounds in screen coordinates (owner client-area coordinates for
+    /// [child](crate::WindowBuilder::child) windows).
+    pub bounds: BlurRect,
+    /// Blurred sub-region relative to the window, `None` for the full window.
+    pub active_region: Option<BlurRect>,
+    /// Whether the blur is a circle following the cursor (overrides `active_region`).
+    pub cursor_follow: bool,
+    /// Radius of the cursor-follow circle in pixels.
+    pub cursor_follow_radius: f32,
+    pub effect: BlurEffectType,
+    /// Stages applied after `effect`, set by
+    /// [`set_effect_stack`](crate::BlurWindow::set_effect_stack) or a
+    /// multi-stage [`set_pipeline`](crate::BlurWindow::set_pipeline).
+    #[cfg(feature = "serde")]
+    pub effect_layers: Vec<PipelineStage>,
+    /// Blend strength, 0.0 (transparent) to 1.0 (full blur).
+    pub strength: f32,
+    /// Top and bottom strength set via
+    /// [`set_strength_gradient`](crate::BlurWindow::set_strength_gradient),
+    /// `None` while `strength` applies uniformly (it then holds the top value).
+    pub strength_gradient: Option<(f32, f32)>,
+    /// Horizontal and vertical strength set via
+    /// [`set_blur_xy`](crate::BlurWindow::set_blur_xy), `None` while both
+    /// axes blur alike (`strength` then holds the larger of the two).
+    pub blur_xy: Option<(f32, f32)>,
+    /// Effect-specific parameter, `None` while the effect's own default is in use.
+    pub blur_param: Option<f32>,
+    /// Explicit kernel radius in pixels, 0 while derived from `blur_param`.
+    pub kernel_radius: u32,
+    /// Horizontal and vertical resolution divisor set via
+    /// [`set_downsample_xy`](crate::BlurWindow::set_downsample_xy), `(1, 1)`
+    /// at full resolution.
+    pub downsample: (u32, u32),
+    /// Edge anti-aliasing samples (1, 2, 4 or 8), see
+    /// [`set_edge_aa`](crate::BlurWindow::set_edge_aa).
+    pub edge_aa: u32,
+    /// Width of the fade at the blurred area's edges in pixels, 0 while off,
+    /// see [`set_edge_feather`](crate::BlurWindow::set_edge_feather).
+    pub edge_feather: f32,
+    /// HLSL snippet run over every frame, see
+    /// [`set_custom_shader`](crate::BlurWindow::set_custom_shader).
+    pub custom_shader: Option<String>,
+    /// Directional blur angle in degrees, 0 to 360.
+    pub direction_angle: f32,
+    /// Directional blur strength, 0.0 (off) to 1.0.
+    pub direction_strength: f32,
+    pub tint: Color,
+    /// Stacked tints, bottom first. When non-empty these replace `tint`.
+    pub tint_layers: Vec<Color>,
+    /// Effects drawn inside parts of the window, in the order they were added.
+    pub region_effects: Vec<RegionEffect>,
+    /// Color blurred in place of a black capture, alpha 0 while disabled.
+    pub fallback_color: Color,
+    /// Tone mapping for HDR captures, see
+    /// [`set_tone_mapping`](crate::BlurWindow::set_tone_mapping).
+    pub tone_mapping: ToneMapping,
+    /// Raw HWND value of the window blurred instead of the desktop, see
+    /// [`set_capture_source`](crate::BlurWindow::set_capture_source). Stored as
+    /// an integer so the state stays `Send`; `None` captures the desktop.
+    /// Not serialized, since handles don't outlive the session.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub capture_source: Option<isize>,
+    /// Raw shared-texture handle blurred instead of any capture, see
+    /// [`set_source_texture`](crate::BlurWindow::set_source_texture). Not
+    /// serialized, for the same reason as `capture_source`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub source_texture: Option<isize>,
+    pub noise: NoiseParams,
+    pub time_mode: TimeMode,
+    /// See [`set_temporal_mode`](crate::BlurWindow::set_temporal_mode).
+    pub temporal_mode: TemporalMode,
+    /// Whether the render loop was started. Informational only:
+    /// [`restore`](crate::BlurWindow::restore) does not start or stop the window.
+    pub running: bool,
+    /// Whether rendering is paused via [`pause`](crate::BlurWindow::pause).
+    pub paused: bool,
+    /// Whether the backdrop is frozen via [`freeze`](crate::BlurWindow::freeze).
+    pub frozen: bool,
+    /// Whether the window is shown, see [`show`](crate::BlurWindow::show).
+    pub visible: bool,
+    /// See [`set_taskbar_relation`](crate::BlurWindow::set_taskbar_relation);
+    /// `None` until it's called, leaving the z-order to
+    /// [`WindowBuilder::top_most`](crate::WindowBuilder::top_most).
+    pub taskbar_relation: Option<TaskbarRelation>,
+    /// Whether the native stats overlay is drawn, see
+    /// [`set_debug_overlay`](crate::BlurWindow::set_debug_overlay).
+    pub debug_overlay: bool,
+}
+
+impl WindowState {
+    pub(crate) fn new(bounds: BlurRect) -> Self {
+        WindowState {
+            preset: BlurQualityPreset::Balanced,
+            active_preset: BlurQualityPreset::Balanced,
+            target_fps: None,
+            capture_interval: Duration::ZERO,
+            min_frame_interval: Duration::ZERO,
+            buffer_count: 2,
+            bounds,
+            active_region: None,
+            cursor_follow: false,
+            cursor_follow_radius: 100.0,
+            effect: BlurEffectType::Gaussian,
+            #[cfg(feature = "serde")]
+            effect_layers: Vec::new(),
+            strength: 1.0,
+            strength_gradient: None,
+            blur_xy: None,
+            blur_param: None,
+            kernel_radius: 0,
+            downsample: (1, 1),
+            edge_aa: 1,
+            edge_feather: 0.0,
+            custom_shader: None,
+            direction_angle: 0.0,
+            direction_strength: 0.0,
+            tint: Color::TRANSPARENT,
+            tint_layers: Vec::new(),
+            region_effects: Vec::new(),
+            fallback_color: Color::TRANSPARENT,
+            tone_mapping: ToneMapping::None,
+            capture_source: None,
+            source_texture: None,
+            noise: NoiseParams::default(),
+            time_mode: TimeMode::Realtime,
+            temporal_mode: TemporalMode::Off,
+            running: false,
+            paused: false,
+            frozen: false,
+            visible: true,
+            taskbar_relation: None,
+            debug_overlay: false,
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use super::*;
+use std::sync::{Mutex, MutexGuard};
+use std::thread::{self, ThreadId};
+use tauri::plugin::{Builder, TauriPlugin};
+use tauri::{Manager, Runtime, State};
+
+/// Name the plugin registers under. Commands are invoked from the webview as
+/// `plugin:blur|start_blur`, and `blur:default` in a capability allows them all.
+pub const PLUGIN_NAME: &str = "blur";
+
+/// Bounds of the window created by `start_blur`, as `(x, y, width, height)`.
+const START_BOUNDS: (i32, i32, i32, i32) = (100, 100, 500, 400);
+
+/// Blur support for a Tauri app, registered with
+/// `tauri::Builder::default().plugin(blur_windows::tauri_plugin_blur::init())`.
+///
+/// Adds these commands:
+///
+/// - `start_blur { effectType? }` creates and starts a top-most blur window
+///   (the system is initialized on first use); does nothing if it's running.
+/// - `stop_blur` stops and destroys the window.
+/// - `update_blur_parameters { effectType?, strength?, param?, color? }`,
+///   with `color` as `[r, g, b, a]`.
+/// - `update_noise_parameters { intensity?, scale?, speed?, noiseType? }`.
+/// - `update_rain_parameters { intensity?, dropSpeed?, refraction?,
+///   trailLength?, minSize?, maxSize? }`; the drop size needs both bounds.
+/// - `get_blur_fps`, 0 while stopped.
+///
+/// The update commands do nothing while no window is running and stop at
+/// the first setter that fails.
+pub fn init<R: Runtime>() -> TauriPlugin<R> {
+    Builder::new(PLUGIN_NAME)
+        .invoke_handler(tauri::generate_handler![
+            start_blur,
+            stop_blur,
+            update_blur_parameters,
+            update_noise_parameters,
+            update_rain_parameters,
+            get_blur_fps
+        ])
+        .setup(|app, _api| {
+            app.manage(BlurState::default());
+            Ok(())
+        })
+        .build()
+}
+
+/// State managed by the plugin: the system and the window `start_blur`
+/// created, if any.
+pub struct BlurState {
+    // Declared before `system` so the window is destroyed first
+    window: Mutex<Option<BlurWindow>>,
+    system: Mutex<Option<BlurSystem>>,
+    owner: ThreadId,
+}
+
+impl Default for BlurState {
+    fn default() -> Self {
+        Self {
+            window: Mutex::new(None),
+            system: Mutex::new(None),
+            owner: thread::current().id(),
+        }
+    }
+}
+
+// BlurWindow is deliberately not Send (see its docs). The state is reachable
+// from any thread, so every access checks it is on `owner`, the thread that
+// created the state (the main thread, where the plugin's setup runs).
+unsafe impl Send for BlurState {}
+unsafe impl Sync for BlurState {}
+
+impl BlurState {
+    /// Run `f` on the running window, e.g. from app code that needs a setter
+    /// the commands don't cover. Returns `None` while stopped, or when called
+    /// from a thread other than the one that created the state.
+    pub fn with_window<T>(&self, f: impl FnOnce(&BlurWindow) -> T) -> Option<T> {
+        self.window().ok()?.as_ref().map(f)
+    }
+
+    fn window(&self) -> Result<MutexGuard<'_, Option<BlurWindow>>, String> {
+        self.check_thread()?;
+        Ok(self.window.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    fn system(&self) -> Result<MutexGuard<'_, Option<BlurSystem>>, String> {
+        self.check_thread()?;
+        Ok(self.system.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    fn check_thread(&self) -> Result<(), String> {
+        if thread::current().id() == self.owner {
+            Ok(())
+        } else {
+            Err("Blur state used off the thread that created it".to_string())
+        }
+    }
+}
+
+fn describe(code: BlurErrorCode) -> String {
+    format!("Blur call failed: {:?}", code)
+}
+
+fn check(code: BlurErrorCode) -> Result<(), String> {
+    if code == BlurErrorCode::Ok {
+        Ok(())
+    } else {
+        Err(describe(code))
+    }
+}
+
+#[tauri::command]
+fn start_blur(state: State<'_, BlurState>, effect_type: Option<i32>) -> Result<(), String> {
+    let mut system = state.system()?;
+    let mut window = state.window()?;
+    if window.is_some() {
+        return Ok(());
+    }
+
+    if system.is_none() {
+        *system = Some(BlurSystemBuilder::new().logging(true).build()?);
+    }
+    let (x, y, w, h) = START_BOUNDS;
+    let created = WindowBuilder::new()
+        .bounds(x, y, w, h)
+        .top_most(true)
+        .build(system.as_ref().unwrap())?;
+    created.start().map_err(describe)?;
+    if let Some(effect) = effect_type {
+        created
+            .set_effect_type(BlurEffectType::try_from(effect)?)
+            .map_err(describe)?;
+    }
+    *window = Some(created);
+    Ok(())
+}
+
+#[tauri::command]
+fn stop_blur(state: State<'_, BlurState>) -> Result<(), String> {
+    match state.window()?.take() {
+        // Dropping the window destroys it
+        Some(window) => window.stop().map_err(describe),
+        None => Ok(()),
+    }
+}
+
+#[tauri::command]
+fn update_blur_parameters(
+    state: State<'_, BlurState>,
+    effect_type: Option<i32>,
+    strength: Option<f32>,
+    param: Option<f32>,
+    color: Option<(f32, f32, f32, f32)>,
+) -> Result<(), String> {
+    let window = state.window()?;
+    let Some(window) = window.as_ref() else {
+        return Ok(());
+    };
+    if let Some(effect) = effect_type {
+        window
+            .set_effect_type(BlurEffectType::try_from(effect)?)
+            .map_err(describe)?;
+    }
+    if let Some(strength) = strength {
+        window.set_strength(strength).map_err(describe)?;
+    }
+    if let Some(param) = param {
+        window.set_blur_param(param).map_err(describe)?;
+    }
+    if let Some((r, g, b, a)) = color {
+        window
+            .set_tint_color(Color::new(r, g, b, a))
+            .map_err(describe)?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+fn update_noise_parameters(
+    state: State<'_, BlurState>,
+    intensity: Option<f32>,
+    scale: Option<f32>,
+    speed: Option<f32>,
+    noise_type: Option<i32>,
+) -> Result<(), String> {
+    let window = state.window()?;
+    let Some(window) = window.as_ref() else {
+        return Ok(());
+    };
+    // Seed None so updating the other settings doesn't restart the animation
+    let current = window.state().noise;
+    let noise = NoiseParams {
+        intensity: intensity.unwrap_or(current.intensity),
+        scale: scale.unwrap_or(current.scale),
+        speed: speed.unwrap_or(current.speed),
+        noise_type: match noise_type {
+            Some(t) => NoiseType::try_from(t)?,
+            None => current.noise_type,
+        },
+        seed: None,
+        ..current
+    };
+    window.set_noise(&noise).map_err(describe)
+}
+
+#[tauri::command]
+fn update_rain_parameters(
+    state: State<'_, BlurState>,
+    intensity: Option<f32>,
+    drop_speed: Option<f32>,
+    refraction: Option<f32>,
+    trail_length: Option<f32>,
+    min_size: Option<f32>,
+    max_size: Option<f32>,
+) -> Result<(), String> {
+    let window = state.window()?;
+    let Some(window) = window.as_ref() else {
+        return Ok(());
+    };
+    // The wrapper doesn't cache rain settings, so these go straight to the C API
+    let handle = window.handle;
+    unsafe {
+        if let Some(intensity) = intensity {
+            check(blur_set_rain_intensity(handle, intensity))?;
+        }
+        if let Some(speed) = drop_speed {
+            check(blur_set_rain_drop_speed(handle, speed))?;
+        }
+        if let Some(strength) = refraction {
+            check(blur_set_rain_refraction(handle, strength))?;
+        }
+        if let Some(length) = trail_length {
+            check(blur_set_rain_trail_length(handle, length))?;
+        }
+        if let (Some(min), Some(max)) = (min_size, max_size) {
+            check(blur_set_rain_drop_size(handle, min, max))?;
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+fn get_blur_fps(state: State<'_, BlurState>) -> f32 {
+    state.with_window(|window| window.get_fps()).unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_is_only_usable_on_its_own_thread() {
+        let state = BlurState::default();
+        assert!(state.window().is_ok());
+
+        thread::scope(|s| {
+            s.spawn(|| {
+                assert!(state.window().is_err());
+                assert!(state.system().is_err());
+                assert_eq!(state.with_window(|_| ()), None);
+            });
+        });
+    }
+}
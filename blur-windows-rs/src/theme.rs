@@ -0,0 +1,108 @@
+use super::*;
+
+/// Windows app theme (Settings > Personalization > Colors), matching
+/// `blur_get_system_theme`.
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum SystemTheme {
+    Light = 0,
+    Dark = 1,
+}
+
+impl SystemTheme {
+    pub(crate) fn from_raw(v: i32) -> Self {
+        if v == SystemTheme::Dark as i32 {
+            SystemTheme::Dark
+        } else {
+            SystemTheme::Light
+        }
+    }
+
+    /// Tint applied by [`BlurWindow::apply_theme_tint`]: the Windows 11
+    /// light and dark acrylic base colors at about half coverage (the
+    /// shader blends the tint by alpha squared).
+    pub fn default_tint(self) -> Color {
+        match self {
+            SystemTheme::Light => Color::new(0.953, 0.953, 0.953, 0.7),
+            SystemTheme::Dark => Color::new(0.125, 0.125, 0.125, 0.7),
+        }
+    }
+}
+
+/// The theme currently chosen for apps. Systems without dark mode report
+/// `Light`. Doesn't need a [`BlurSystem`].
+pub fn native_system_theme() -> SystemTheme {
+    SystemTheme::from_raw(unsafe { blur_get_system_theme() })
+}
+
+pub(crate) type ThemeChangedFn = Box<dyn Fn(SystemTheme) + Send>;
+
+unsafe extern "C" fn theme_changed_trampoline(theme: i32, user_data: *mut std::ffi::c_void) {
+    let handler = &*(user_data as *const ThemeChangedFn);
+    // Unwinding into native code is undefined behavior
+    let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        handler(SystemTheme::from_raw(theme))
+    }));
+}
+
+impl BlurSystem {
+    /// Call `f` with the new theme whenever the user switches between light
+    /// and dark, e.g. to [`apply_theme_tint`](BlurWindow::apply_theme_tint)
+    /// on every window. Replaces any previous handler.
+    ///
+    /// The native library watches for the switch from its own thread, so no
+    /// window or message loop is needed, and `f` runs on that thread.
+    /// [`BlurWindow`] is not `Send`, so forward the theme to the thread that
+    /// owns the windows (e.g. over a channel) rather than capturing them.
+    /// Accent color changes alone don't call `f`. `f` must not call
+    /// `on_theme_changed` or [`off_theme_changed`](Self::off_theme_changed).
+    pub fn on_theme_changed(
+        &self,
+        f: impl Fn(SystemTheme) + Send + 'static,
+    ) -> Result<(), BlurErrorCode> {
+        if !self.is_valid() {
+            return Err(BlurErrorCode::NotInitialized);
+        }
+        let handler: Box<ThemeChangedFn> = Box::new(Box::new(f));
+        let user_data = &*handler as *const ThemeChangedFn as *mut std::ffi::c_void;
+        let mut slot = self.theme_changed.lock().unwrap_or_else(|e| e.into_inner());
+        let code = unsafe {
+            blur_set_theme_changed_callback(self.handle, Some(theme_changed_trampoline), user_data)
+        };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        // The native side no longer calls the old handler once the setter returns
+        *slot = Some(handler);
+        Ok(())
+    }
+
+    /// Stop watching for theme switches and drop the handler.
+    pub fn off_theme_changed(&self) -> Result<(), BlurErrorCode> {
+        if !self.is_valid() {
+            return Ok(());
+        }
+        let mut slot = self.theme_changed.lock().unwrap_or_else(|e| e.into_inner());
+        let code =
+            unsafe { blur_set_theme_changed_callback(self.handle, None, std::ptr::null_mut()) };
+        if code != BlurErrorCode::Ok {
+            return Err(code);
+        }
+        *slot = None;
+        Ok(())
+    }
+}
+
+impl BlurWindow {
+    /// Replace the tint with `theme`'s [`default_tint`](SystemTheme::default_tint),
+    /// so the overlay matches native light or dark surfaces. Pass
+    /// [`native_system_theme`] to follow the system.
+    pub fn apply_theme_tint(&self, theme: SystemTheme) -> Result<(), BlurErrorCode> {
+        self.set_tint_color(theme.default_tint())
+    }
+}
@@ -0,0 +1,149 @@
+use super::*;
+
+/// Settings for [`Watchdog`].
+#[derive(Clone)]
+pub struct WatchdogConfig {
+    /// Options used to create (and later re-create) the window.
+    pub builder: WindowBuilder,
+    /// Number of consecutive `InvalidHandle` results before the window is re-created.
+    pub max_invalid_handle_errors: u32,
+}
+
+impl WatchdogConfig {
+    pub fn new(builder: WindowBuilder) -> Self {
+        WatchdogConfig {
+            builder,
+            max_invalid_handle_errors: 3,
+        }
+    }
+}
+
+/// Keeps a [`BlurWindow`] alive across native handle loss.
+///
+/// Some driver crashes leave the native window handle invalid, after which
+/// every call returns `InvalidHandle`. Routing calls through [`Watchdog::call`]
+/// counts those failures and, once the threshold is hit, creates a fresh
+/// window from the parent [`BlurSystem`] and re-applies the cached
+/// [`WindowState`].
+///
+/// The dead window is leaked rather than destroyed, since passing a broken
+/// handle to `blur_destroy_window` could crash the native side. It leaves the
+/// window registry, so [`BlurSystem::pause_all`] doesn't reach it either.
+pub struct Watchdog<'a> {
+    system: &'a BlurSystem,
+    config: WatchdogConfig,
+    window: BlurWindow,
+    invalid_handle_errors: u32,
+    recreations: u32,
+}
+
+impl<'a> Watchdog<'a> {
+    pub fn new(system: &'a BlurSystem, config: WatchdogConfig) -> Result<Self, String> {
+        let window = config.builder.clone().build(system)?;
+        Ok(Watchdog {
+            system,
+            config,
+            window,
+            invalid_handle_errors: 0,
+            recreations: 0,
+        })
+    }
+
+    pub fn window(&self) -> &BlurWindow {
+        &self.window
+    }
+
+    /// How many times the window has been re-created so far.
+    pub fn recreations(&self) -> u32 {
+        self.recreations
+    }
+
+    /// Run `f` against the window, re-creating it if the handle has died.
+    ///
+    /// When a re-creation happens the call is retried once on the new window.
+    pub fn call<T>(
+        &mut self,
+        f: impl Fn(&BlurWindow) -> Result<T, BlurErrorCode>,
+    ) -> Result<T, BlurErrorCode> {
+        match f(&self.window) {
+            Err(BlurErrorCode::InvalidHandle) => {
+                self.invalid_handle_errors += 1;
+                if self.invalid_handle_errors < self.config.max_invalid_handle_errors {
+                    return Err(BlurErrorCode::InvalidHandle);
+                }
+                self.recreate().map_err(|_| BlurErrorCode::InvalidHandle)?;
+                f(&self.window)
+            }
+            result => {
+                self.invalid_handle_errors = 0;
+                result
+            }
+        }
+    }
+
+    /// Replace the window with a new one carrying the same cached state.
+    pub fn recreate(&mut self) -> Result<&BlurWindow, String> {
+        let state = self.window.snapshot();
+        let window = self
+            .config
+            .builder
+            .clone()
+            .bounds(
+                state.bounds.left,
+                state.bounds.top,
+                state.bounds.right - state.bounds.left,
+                state.bounds.bottom - state.bounds.top,
+            )
+            .build(self.system)?;
+        window
+            .restore(&state)
+            .map_err(|e| format!("Failed to restore window state: {:?}", e))?;
+        if state.running {
+            window
+                .start()
+                .map_err(|e| format!("Failed to restart window: {:?}", e))?;
+        }
+        if state.paused {
+            window
+                .pause()
+                .map_err(|e| format!("Failed to re-pause window: {:?}", e))?;
+        }
+        if state.frozen {
+            // The new window snapshots the current backdrop
+            window
+                .freeze()
+                .map_err(|e| format!("Failed to re-freeze window: {:?}", e))?;
+        }
+
+        let dead = std::mem::replace(&mut self.window, window);
+        // Drop doesn't run, so unregister here or pause_all keeps calling
+        // into the dead handle
+        registry::unregister(dead.handle);
+        std::mem::forget(dead);
+        self.invalid_handle_errors = 0;
+        self.recreations += 1;
+        Ok(&self.window)
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recreate_unregisters_the_dead_window() {
+        let _serial = mock::serial();
+        let system = BlurSystem::headless();
+        let mut watchdog =
+            Watchdog::new(&system, WatchdogConfig::new(WindowBuilder::new())).unwrap();
+        let dead = watchdog.window().handle;
+
+        watchdog.recreate().unwrap();
+        system.pause_all().unwrap();
+
+        assert_eq!(watchdog.recreations(), 1);
+        assert!(watchdog.window().state().paused);
+        let dead_calls = mock::call_log_of(dead);
+        assert!(dead_calls.iter().all(|call| call.function != "blur_pause"));
+    }
+}
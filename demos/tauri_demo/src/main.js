@@ -13,7 +13,7 @@ function appendLog(msg) {
 async function startBlur() {
   try {
     const effectType = parseInt(document.getElementById("select-effect").value);
-    await invoke("start_blur", { effectType });
+    await invoke("plugin:blur|start_blur", { effectType });
     document.body.classList.add("running");
     statusText.textContent = "Running";
     appendLog(`Blur started (Effect: ${effectType}).`);
@@ -23,7 +23,7 @@ async function startBlur() {
 }
 
 async function stopBlur() {
-  await invoke("stop_blur");
+  await invoke("plugin:blur|stop_blur");
   document.body.classList.remove("running");
   statusText.textContent = "Stopped";
   appendLog("Blur stopped.");
@@ -44,7 +44,7 @@ async function updateBlurParams() {
   const colorHex = document.getElementById("color-tint").value;
   const alphaPercent = parseInt(document.getElementById("slider-alpha").value);
 
-  await invoke("update_blur_parameters", {
+  await invoke("plugin:blur|update_blur_parameters", {
     effectType: effect,
     strength: strength,
     param: param,
@@ -58,7 +58,7 @@ async function updateNoiseParams() {
   const speed = parseInt(document.getElementById("slider-noise-speed").value) / 10;
   const noiseType = parseInt(document.querySelector('input[name="noise-type"]:checked').value);
 
-  await invoke("update_noise_parameters", {
+  await invoke("plugin:blur|update_noise_parameters", {
     intensity,
     scale,
     speed,
@@ -72,7 +72,7 @@ async function updateRainParams() {
   const refraction = parseInt(document.getElementById("slider-rain-refraction").value) / 100;
   const trailLength = parseInt(document.getElementById("slider-rain-trail").value) / 100;
 
-  await invoke("update_rain_parameters", {
+  await invoke("plugin:blur|update_rain_parameters", {
     intensity,
     dropSpeed,
     refraction,
@@ -136,7 +136,7 @@ window.addEventListener("DOMContentLoaded", () => {
 
   setInterval(async () => {
     if (document.body.classList.contains("running")) {
-      const fps = await invoke("get_blur_fps");
+      const fps = await invoke("plugin:blur|get_blur_fps");
       fpsCounter.textContent = `${fps.toFixed(1)} FPS`;
     }
   }, 1000);
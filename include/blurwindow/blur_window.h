@@ -8,6 +8,7 @@
 #endif
 #include <string>
 #include <functional>
+#include <cstdint>
 
 namespace blurwindow {
 
@@ -26,9 +27,26 @@ public:
     /// Check if the blur window is running
     bool IsRunning() const;
 
-    /// Set the effect pipeline from JSON configuration
+    /// Capture, process, and present a single frame (manual render mode only)
+    /// @return true on success (no-op while the render loop is running)
+    bool RenderOnce();
+
+    /// Check if the window was created in manual render mode
+    bool IsManualRender() const;
+
+    /// Set up graphics and compile the shaders of every configured effect
+    /// without presenting, so the first frame after Start doesn't hitch.
+    /// Run again after changing effects. Optional.
+    /// @return false if graphics could not be initialized
+    bool Prewarm();
+
+    /// Most stages SetEffectPipeline accepts (the effect plus its layers)
+    static constexpr size_t MaxEffectLayers = 4;
+
+    /// Set the effect pipeline from JSON configuration. The first stage is the
+    /// window's effect; later stages are layers applied to its output in order.
     /// @param jsonConfig JSON configuration string
-    /// @return true on success
+    /// @return true on success, false for more than MaxEffectLayers stages
     bool SetEffectPipeline(const std::string& jsonConfig);
 
     /// Check if graphics subsystems are initialized
@@ -45,6 +63,25 @@ public:
     /// @param strength Blur strength (0.0 to 1.0)
     void SetBlurStrength(float strength);
 
+    /// Set blur strength interpolated from the top edge to the bottom edge
+    /// (replaced by the next SetBlurStrength)
+    void SetStrengthGradient(float top, float bottom);
+
+    /// Set separate horizontal and vertical blur strengths (0.0 to 1.0 each;
+    /// equal values are SetBlurStrength). Replaced by the next SetBlurStrength
+    /// or SetStrengthGradient; Radial and Rain blur isotropically at the larger.
+    void SetBlurXY(float x, float y);
+
+    /// Run the effect and its layers at the window's resolution divided by x
+    /// horizontally and y vertically (1, 1 = full resolution); region
+    /// effects and later passes stay at full resolution
+    void SetDownsampleXY(uint32_t x, uint32_t y);
+
+    /// Blur only the parts of the backdrop that changed between the last two
+    /// captures (MotionOnly), or more the more they changed (MotionWeighted);
+    /// static content shows through sharp. Keeps two extra copies of the capture.
+    void SetTemporalMode(TemporalMode mode);
+
     /// Set blur tint color
     /// @param r Red component (0.0 to 1.0)
     /// @param g Green component (0.0 to 1.0)
@@ -52,6 +89,37 @@ public:
     /// @param a Alpha component (0.0 to 1.0)
     void SetBlurColor(float r, float g, float b, float a);
 
+    /// Draw FPS, frame time and backend in the top-left corner (development aid)
+    void SetDebugOverlay(bool enabled);
+
+    /// Set the color shown where the capture is black (alpha 0 disables)
+    void SetFallbackColor(float r, float g, float b, float a);
+
+    /// Set how an HDR backdrop is mapped to SDR (0: none, 1: Reinhard, 2: ACES).
+    /// Stored only: every capture is SDR today.
+    void SetToneMapping(int mode);
+
+    /// Average color of the last captured backdrop (RGB 0-1, alpha 1).
+    /// False until a frame has been captured. Waits for the GPU; not for every frame.
+    bool SampleDominantColor(float outRgba[4]);
+
+    /// Blur a specific top-level window instead of the desktop (nullptr = desktop).
+    /// False if window capture could not be started; the previous source stays active.
+    bool SetCaptureSource(HWND source);
+
+    /// Blur a D3D11 shared texture instead of capturing (nullptr = capture again).
+    /// Needs DXGI_FORMAT_B8G8R8A8_UNORM with shader-resource binding; false if
+    /// the handle can't be opened or the texture doesn't qualify.
+    bool SetSourceTexture(HANDLE sharedHandle);
+
+    /// Blur a still image instead of capturing (width * height tightly packed
+    /// RGBA pixels, copied; nullptr = capture again). A source texture takes
+    /// precedence. False for a zero or oversized dimension.
+    bool SetBackgroundImage(const uint8_t* rgba, uint32_t width, uint32_t height);
+
+    /// Set stacked tint layers (count RGBA quadruples, bottom first; 0 clears the tint)
+    void SetTintLayers(const float* rgba, size_t count);
+
     /// Set noise intensity (0.0 to 1.0)
     void SetNoiseIntensity(float intensity);
 
@@ -64,12 +132,50 @@ public:
     /// Set noise type (0: White, 1: Sinusoid, 2: Grid, 3: Perlin, 4: Simplex, 5: Voronoi)
     void SetNoiseType(int type);
 
+    /// Set noise blend mode (0: Additive, 1: Overlay, 2: Multiply)
+    void SetNoiseBlendMode(int mode);
+
+    /// Tint the noise grain (1, 1, 1 for monochrome grain, the default)
+    void SetNoiseColor(float r, float g, float b);
+
+    /// Set the noise seed (restarts the noise animation; a fixed seed gives repeatable grain)
+    void SetNoiseSeed(uint32_t seed);
+
     /// Set the active effect type (0: Gaussian, 1: Box, 2: Kawase, 3: Radial)
     void SetEffectType(int type);
 
     /// Set blur-specific parameter (Sigma for Gaussian, Radius for Box, Iterations for Kawase)
     void SetBlurParam(float param);
 
+    /// Largest explicit kernel radius, in pixels
+    static constexpr int MaxKernelRadius = 32;
+
+    /// Set explicit kernel radius in pixels (0: derive from blur param)
+    void SetKernelRadius(int radius);
+
+    /// Fade the blur out over this many pixels at the edges of the active region
+    /// (the whole window without one); capped at half the region's smaller side, 0 = off
+    void SetEdgeFeather(float pixels);
+
+    /// Run an HLSL snippet defining float4 CustomShade(float4 color, float2 uv,
+    /// float time) over every finished frame (empty = none)
+    /// @param errors Receives the compiler's diagnostics on failure
+    /// @return false if the snippet doesn't compile or the system isn't initialized
+    bool SetCustomShader(const std::string& hlsl, std::string* errors = nullptr);
+
+    /// Set the anti-aliasing sample count for mask and corner edges (1, 2, 4, 8).
+    /// Stored only: no mask with soft edges exists yet.
+    void SetEdgeAA(uint32_t samples);
+
+    /// Set a directional blur (angle in degrees, strength 0-1; 0 turns it off).
+    /// Replaces the Radial effect's zoom blur; other effects ignore it.
+    void SetDirection(float angleDegrees, float strength);
+
+    /// Run an experimental command by name (unstable: commands may change or
+    /// go away in any release). False with a message in error for an unknown
+    /// command or arguments it rejects.
+    bool RunCommand(const std::string& command, const std::string& args, std::string& error);
+
     // --- Rain Effect Control ---
 
     /// Set rain effect intensity (0.0 to 1.0)
@@ -97,6 +203,52 @@ public:
     /// @param userData User data passed to callback
     void SetClickCallback(ClickCallback callback, void* userData);
 
+    /// Per-frame callback type
+    using FrameCallback = void(*)(uint64_t frameIndex, float frameTimeMs, void* userData);
+
+    /// Set a callback fired on the render thread after each presented frame (nullptr to remove)
+    void SetFrameCallback(FrameCallback callback, void* userData);
+
+    /// Owner-closed callback type
+    using OwnerClosedCallback = void(*)(void* userData);
+
+    /// Set a callback fired once when the owner window is destroyed
+    /// (nullptr restores the default, which stops rendering)
+    void SetOwnerClosedCallback(OwnerClosedCallback callback, void* userData);
+
+    /// Render once per WM_PAINT of the owner instead of at the target frame rate.
+    /// Subclasses the owner, so it must be called on the owner's thread; false
+    /// without an owner of the calling thread or if subclassing fails.
+    bool SetSyncToOwnerPaint(bool enable);
+
+    /// Skip frames while other windows fully cover this one (partly covered
+    /// windows keep rendering); checked a few times a second
+    void SetPauseWhenOccluded(bool enable);
+
+    /// Whether other windows fully cover this one, or it is cloaked
+    bool IsOccluded() const;
+
+    /// Occlusion-changed callback type (occluded: 1 when covered, 0 when revealed)
+    using OcclusionChangedCallback = void(*)(int32_t occluded, void* userData);
+
+    /// Set a callback fired on the render thread when the window becomes fully
+    /// covered or visible again (nullptr to remove)
+    void SetOcclusionChangedCallback(OcclusionChangedCallback callback, void* userData);
+
+    /// Display-changed callback type
+    using DisplayChangedCallback = void(*)(void* userData);
+
+    /// Set a callback fired on the window's thread when a monitor is added,
+    /// removed or changes resolution (WM_DISPLAYCHANGE; nullptr to remove)
+    void SetDisplayChangedCallback(DisplayChangedCallback callback, void* userData);
+
+    /// Power-changed callback type
+    using PowerChangedCallback = void(*)(void* userData);
+
+    /// Set a callback fired on the window's thread when the system switches
+    /// between AC and battery power (WM_POWERBROADCAST; nullptr to remove)
+    void SetPowerChangedCallback(PowerChangedCallback callback, void* userData);
+
     /// Enable/disable click-through
     /// @param enable true to enable click-through
     void SetClickThrough(bool enable);
@@ -105,6 +257,16 @@ public:
     /// @param enable true to enable always-on-top
     void SetTopMost(bool enable);
 
+    /// Keep the window above or below the taskbar; both make it top-most.
+    /// Applied now, or when the HWND is created (no effect on child windows)
+    void SetTaskbarRelation(TaskbarRelation relation);
+
+    /// Read the window's extended styles (WS_EX_*); false before the HWND exists
+    bool GetExStyle(uint32_t* exStyle) const;
+
+    /// Replace the window's extended styles; false before the HWND exists
+    bool SetExStyle(uint32_t exStyle);
+
     /// Update window bounds
     /// @param bounds New bounds (screen coordinates)
     void SetBounds(const RECT& bounds);
@@ -112,12 +274,135 @@ public:
     /// Get current bounds
     RECT GetBounds() const;
 
+    /// Resize the presentation surface (applied before the next frame)
+    void ResizeSwapchain(uint32_t width, uint32_t height);
+
+    /// Set how many buffers the swap chain cycles through, 2 or 3 (applied
+    /// before the next frame; no effect with the UpdateLayeredWindow backend)
+    void SetBufferCount(uint32_t count);
+
+    /// Restrict blur to a sub-region (window-relative coordinates)
+    /// @param region Region to blur, nullptr for the full window
+    /// @return false if the region is empty or outside the window
+    bool SetActiveRegion(const RECT* region);
+
+    /// Most effects AddRegionEffect keeps
+    static constexpr size_t MaxRegionEffects = 8;
+
+    /// Draw another effect inside a rect (window-relative coordinates) instead
+    /// of the window's effect and layers. Regions are drawn in the order they
+    /// were added, so a later one covers an earlier one where they overlap.
+    /// @param type Effect type as for SetEffectType
+    /// @return false for an unknown type, an empty rect or one outside the
+    ///         window, or more than MaxRegionEffects regions
+    bool AddRegionEffect(const RECT& rect, int type);
+
+    /// Remove every region added with AddRegionEffect
+    void ClearRegionEffects();
+
+    /// Hold region changes and frames until the matching EndUpdate (calls nest)
+    void BeginUpdate();
+
+    /// Close a BeginUpdate; the outermost call applies everything deferred.
+    /// Returns false without a matching BeginUpdate.
+    bool EndUpdate();
+
     /// Get the window handle
     HWND GetHWND() const;
 
+    /// Check whether hwnd is a blur window of this process (tagged with the
+    /// "BlurWindow" window property from creation until destruction)
+    static bool IsBlurWindow(HWND hwnd);
+
     /// Get current FPS
     float GetCurrentFPS() const;
 
+    /// Get the CPU time spent on the last frame in milliseconds
+    float GetFrameTimeMs() const;
+
+    /// Get the time from capturing the last frame's backdrop to presenting it, in milliseconds
+    float GetLatencyMs() const;
+
+    /// Get the active presentation backend (0: none, 1: DirectComposition, 2: UpdateLayeredWindow)
+    int GetBackend() const;
+
+    /// Center a circular blur region of the given radius on the cursor every frame
+    /// (forces click-through while enabled). Returns false for a non-positive radius.
+    bool SetCursorFollow(bool enabled, float radius);
+
+    /// Get approximate GPU memory held by this window's textures and surfaces, in bytes
+    uint64_t GetVideoMemoryUsage() const;
+
+    /// One presented frame, see GetFrameHistory
+    struct FrameSample {
+        uint64_t frameIndex;
+        float frameTimeMs;
+        float fps;  // FPS reading when the frame was presented
+    };
+
+    /// Most frames GetFrameHistory keeps
+    static constexpr size_t FrameHistorySize = 1024;
+
+    /// Copy the most recent frames (up to capacity) into out, oldest first.
+    /// Returns the number copied, or with out == nullptr the number kept.
+    uint32_t GetFrameHistory(FrameSample* out, uint32_t capacity) const;
+
+    /// Get why capture last failed (0: ok, 1: blocked by policy, 2: protected content
+    /// masked out, 3: device lost, 4: other failure)
+    int GetCaptureStatus() const;
+
+    /// Get the pixel format of the last rendered backdrop (-1: nothing captured yet,
+    /// 0: 8-bit sRGB, 1: FP16 scRGB, 2: 10-bit HDR10)
+    int GetCaptureFormat() const;
+
+    /// Get how the backdrop is captured (0: none, 1: Desktop Duplication,
+    /// 2: Windows.Graphics.Capture)
+    int GetCaptureMethod() const;
+
+    /// Physical screen rect the last frame was captured from, clipped to its
+    /// monitor. False before the first frame and while blurring a window, a
+    /// source texture or a background image.
+    bool GetCaptureRect(RECT* out) const;
+
+    /// Drive effect animation from SetTime (manual) instead of the wall clock
+    void SetTimeMode(bool manual);
+
+    /// Set the animation clock in seconds (manual time mode only)
+    void SetTime(float seconds);
+
+    /// Override the preset's frame rate cap (0: use the preset's rate again)
+    void SetTargetFPS(int fps);
+
+    /// Re-capture the backdrop at most every intervalMs, re-blurring the last
+    /// capture in between (0 = capture every frame)
+    void SetCaptureInterval(uint32_t intervalMs);
+
+    /// Never present frames closer together than intervalMs, whatever the
+    /// target frame rate or owner paints ask for (0 = no floor)
+    void SetMinFrameInterval(uint32_t intervalMs);
+
+    /// Get the refresh rate of the window's monitor in Hz (primary monitor if it spans several; 0 if unknown)
+    float GetDisplayRefreshHz() const;
+
+    /// Pause/resume rendering without stopping the render thread
+    void SetPaused(bool paused);
+
+    /// Check if rendering is paused
+    bool IsPaused() const;
+
+    /// Blur a one-time snapshot of the backdrop instead of capturing every frame
+    void SetFrozen(bool frozen);
+
+    /// Check if the backdrop is frozen
+    bool IsFrozen() const;
+
+    /// Show or hide the window (see WindowOptions::startHidden); rendering
+    /// continues while hidden
+    void SetVisible(bool visible);
+
+    /// Check if the window is shown
+    bool IsVisible() const;
+
     // Disable copy
     BlurWindow(const BlurWindow&) = delete;
     BlurWindow& operator=(const BlurWindow&) = delete;
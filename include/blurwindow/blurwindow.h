@@ -42,12 +42,29 @@ enum class QualityPreset {
     Minimal      ///< Minimum quality, lowest GPU load
 };
 
+/// Where a top-level window sits relative to the taskbar
+enum class TaskbarRelation {
+    Above,  ///< Covers the taskbar
+    Below   ///< Top-most, but under the taskbar
+};
+
+/// How the blur follows changes of the backdrop between captures
+enum class TemporalMode {
+    Off,            ///< Blur everything
+    MotionOnly,     ///< Blur only what changed; static content shows sharp
+    MotionWeighted  ///< Blur more the more a pixel changed
+};
+
 /// Window creation options
 struct WindowOptions {
     HWND owner = nullptr;       ///< Owner window handle
-    RECT bounds = {0, 0, 0, 0}; ///< Window bounds (screen coordinates)
-    bool topMost = true;        ///< Always on top
+    RECT bounds = {0, 0, 0, 0}; ///< Window bounds (screen coordinates; owner client coordinates if child)
+    bool topMost = true;        ///< Always on top (ignored for child windows)
     bool clickThrough = true;   ///< Click-through window
+    bool manualRender = false;  ///< Render frames only on explicit RenderOnce() calls
+    bool child = false;         ///< Create as a WS_CHILD of owner instead of a top-level overlay
+    bool startHidden = false;   ///< Create the window hidden until SetVisible(true)
+    bool wallpaper = false;     ///< Draw behind the desktop icons (bounds stay screen coordinates; not with child)
 };
 
 /// System initialization options
@@ -58,6 +75,12 @@ struct BlurSystemOptions {
     QualityPreset defaultPreset = QualityPreset::Balanced; ///< Default quality preset
 };
 
+/// Windows app theme (Settings > Personalization > Colors)
+enum class SystemTheme {
+    Light,
+    Dark
+};
+
 /// Main blur system class (singleton)
 class BLURWINDOW_API BlurSystem {
 public:
@@ -88,6 +111,31 @@ public:
     /// @return Unique pointer to BlurWindow, nullptr on failure
     std::unique_ptr<BlurWindow> CreateBlurWindow(HWND owner, const WindowOptions& opts);
 
+    /// Get the current app theme
+    static SystemTheme GetSystemTheme();
+
+    /// Theme change callback type
+    using ThemeChangedCallback = void(*)(SystemTheme theme, void* userData);
+
+    /// Call callback whenever the user switches between light and dark
+    /// (nullptr to stop). Runs on an internal watcher thread.
+    void SetThemeChangedCallback(ThemeChangedCallback callback, void* userData);
+
+    /// Fullscreen change callback type
+    using FullscreenChangedCallback = void(*)(bool fullscreen, void* userData);
+
+    /// Call callback when another process's window covering a whole monitor
+    /// comes to the foreground (true) and when it leaves (false); nullptr to
+    /// stop. Polled on an internal watcher thread, which runs the callback.
+    void SetFullscreenChangedCallback(FullscreenChangedCallback callback, void* userData);
+
+    /// Prefer a capture method for captures started from now on (0: automatic,
+    /// 1: Desktop Duplication, 2: Windows.Graphics.Capture)
+    void SetPreferredCaptureMethod(int method);
+
+    /// Get the preferred capture method
+    int GetPreferredCaptureMethod() const;
+
     // Disable copy/move
     BlurSystem(const BlurSystem&) = delete;
     BlurSystem& operator=(const BlurSystem&) = delete;
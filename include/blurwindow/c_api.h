@@ -39,6 +39,36 @@ typedef enum {
     BLUR_ERROR_UNKNOWN = -99             ///< An unexpected error occurred.
 } BlurErrorCode;
 
+/// Outcome of the most recent capture attempt, see blur_get_capture_status.
+typedef enum {
+    BLUR_CAPTURE_STATUS_OK = 0,                 ///< Capturing normally.
+    BLUR_CAPTURE_STATUS_BLOCKED_BY_POLICY = 1,  ///< Access denied (policy or secure desktop).
+    BLUR_CAPTURE_STATUS_PROTECTED_CONTENT = 2,  ///< Capturing, with DRM content blacked out.
+    BLUR_CAPTURE_STATUS_DEVICE_LOST = 3,        ///< GPU reset or removed, or display mode changed.
+    BLUR_CAPTURE_STATUS_FAILED = 4              ///< Any other failure.
+} BlurCaptureStatus;
+
+/// How the backdrop is captured, see blur_get_capture_method.
+typedef enum {
+    BLUR_CAPTURE_METHOD_NONE = 0,                      ///< No capture running.
+    BLUR_CAPTURE_METHOD_DESKTOP_DUPLICATION = 1,       ///< DXGI Desktop Duplication.
+    BLUR_CAPTURE_METHOD_WINDOWS_GRAPHICS_CAPTURE = 2   ///< Windows.Graphics.Capture.
+} BlurCaptureMethod;
+
+/// Pixel format of the captured backdrop, see blur_get_capture_format.
+typedef enum {
+    BLUR_CAPTURE_FORMAT_SRGB8 = 0,         ///< 8-bit BGRA, sRGB-encoded SDR.
+    BLUR_CAPTURE_FORMAT_RGBA16_FLOAT = 1,  ///< FP16 scRGB: linear, BT.709, 1.0 = 80 nits.
+    BLUR_CAPTURE_FORMAT_RGB10A2 = 2        ///< 10-bit HDR10: PQ-encoded, BT.2020.
+} BlurCaptureFormat;
+
+/// How an HDR backdrop is mapped to SDR, see blur_set_tone_mapping.
+typedef enum {
+    BLUR_TONE_MAPPING_NONE = 0,      ///< Clip values above SDR white.
+    BLUR_TONE_MAPPING_REINHARD = 1,  ///< Reinhard: soft roll-off, keeps hues.
+    BLUR_TONE_MAPPING_ACES = 2       ///< ACES filmic curve: more contrast.
+} BlurToneMapping;
+
 /// Rect structure for window bounds.
 typedef struct {
     int32_t left;
@@ -47,6 +77,14 @@ typedef struct {
     int32_t bottom;
 } BlurRect;
 
+/// RGBA color, each channel 0.0 to 1.0.
+typedef struct {
+    float r;
+    float g;
+    float b;
+    float a;
+} BlurColor;
+
 /// Global system configuration options.
 typedef struct {
     int32_t enableLogging;               ///< 0 = false, 1 = true.
@@ -60,6 +98,10 @@ typedef struct {
     BlurRect bounds;                     ///< Initial window position and size.
     int32_t topMost;                     ///< 1 to stay on top of other windows.
     int32_t clickThrough;                ///< 1 to allow mouse clicks to pass through.
+    int32_t manualRender;                ///< 1 to render only on blur_render_once (no free-running loop required).
+    int32_t child;                       ///< 1 to create a child of owner; bounds are then relative to owner's client area.
+    int32_t startHidden;                 ///< 1 to create the window hidden until blur_show.
+    int32_t wallpaper;                   ///< 1 to draw behind the desktop icons, see blur_create_window.
 } BlurWindowOptionsC;
 
 #ifndef BLURWINDOW_API
@@ -93,9 +135,39 @@ BLURWINDOW_API void blur_shutdown(BlurSystemHandle sys);
  * @param owner Parent window handle (HWND).
  * @param opts Window creation options.
  * @return Handle to the window, or NULL on failure.
+ * @note With opts->wallpaper the window is parented, when the HWND is created,
+ *       under the desktop's WorkerW: Progman is sent the undocumented 0x052C
+ *       message that makes Explorer create a WorkerW between the wallpaper
+ *       and the icons (a sibling after the icons' WorkerW, or on Windows 11
+ *       24H2 a child of Progman). The window then sits behind the icons and
+ *       every other window, is click-through whatever opts->clickThrough
+ *       says, and can't be top-most or a child (opts->child is rejected).
+ *       Its bounds stay screen coordinates. It can't be excluded from screen
+ *       capture, so blurring the desktop would capture the window itself:
+ *       give it a background image, source texture or capture source. The
+ *       window stops rendering like an owned window whose owner closed when
+ *       Explorer restarts (which destroys the WorkerW), and isn't told about
+ *       display changes. Without Explorer running, blur_start fails to
+ *       create the window.
  */
 BLURWINDOW_API BlurWindowHandle blur_create_window(BlurSystemHandle sys, void* owner, const BlurWindowOptionsC* opts);
 
+/**
+ * @brief Create a blur window embedded as a child of an existing window.
+ *
+ * The window is a WS_CHILD of owner, clipped to its client area, and moves with it.
+ * Unlike top-level windows, bounds (here and in blur_set_bounds) are in owner
+ * client-area coordinates. Because a child window cannot be excluded from
+ * capture on its own, the owner's top-level window is excluded instead, which
+ * also hides the whole app from screenshots and screen sharing.
+ *
+ * @param sys System handle.
+ * @param owner Parent HWND (required).
+ * @param relativeBounds Bounds relative to owner's client area.
+ * @return Window handle or NULL on failure.
+ */
+BLURWINDOW_API BlurWindowHandle blur_create_child_window(BlurSystemHandle sys, void* owner, const BlurRect* relativeBounds);
+
 /**
  * @brief Destroy a blur window.
  * @param window Window handle.
@@ -124,14 +196,53 @@ BLURWINDOW_API BlurErrorCode blur_stop(BlurWindowHandle window);
  */
 BLURWINDOW_API BlurErrorCode blur_set_preset(BlurWindowHandle window, BlurQualityPreset preset);
 
+/**
+ * @brief Get the quality preset currently in effect for a window.
+ *
+ * This is the preset the renderer is using, which performance logic may set
+ * differently from the one last passed to blur_set_preset.
+ *
+ * @param window Window handle.
+ * @return The effective preset, or BLUR_PRESET_BALANCED for a NULL handle.
+ */
+BLURWINDOW_API BlurQualityPreset blur_get_active_preset(BlurWindowHandle window);
+
+/** Most stages accepted by blur_set_pipeline */
+#define BLUR_MAX_EFFECT_LAYERS 4
+
 /**
  * @brief Set the effect pipeline configuration using a JSON string.
+ *
+ * The first stage of a structured pipeline is the window's effect, which
+ * later setters (blur_set_strength, blur_set_effect_type, ...) change. Each
+ * further stage is a layer applied to the previous result in order, e.g. a
+ * Gaussian blur followed by a rain overlay; layers use only their own stage
+ * params and are kept by blur_set_effect_type. A pipeline with one stage
+ * removes all layers.
+ *
  * @param window Window handle.
  * @param json_config JSON configuration.
- * @return BLUR_OK on success.
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER for an empty
+ *         (or whitespace-only) string, a root that isn't a JSON object, more
+ *         than BLUR_MAX_EFFECT_LAYERS stages or a stage that can't be created.
  */
 BLURWINDOW_API BlurErrorCode blur_set_pipeline(BlurWindowHandle window, const char* json_config);
 
+/**
+ * @brief Check a pipeline JSON string without applying it.
+ *
+ * Runs the checks blur_set_pipeline does, and also rejects structured stages
+ * whose type is missing or unknown, which blur_set_pipeline would quietly
+ * turn into a Gaussian blur. Touches no window and creates no GPU resources,
+ * so it is cheap enough to call on every keystroke in an editor.
+ *
+ * @param sys System handle.
+ * @param json_config JSON configuration.
+ * @return BLUR_OK if the pipeline is valid, BLUR_ERROR_INVALID_PARAMETER with
+ *         a message for blur_get_last_error otherwise.
+ */
+BLURWINDOW_API BlurErrorCode blur_validate_pipeline(BlurSystemHandle sys, const char* json_config);
+
 /**
  * @brief Update the window bounds.
  * @param window Window handle.
@@ -140,6 +251,167 @@ BLURWINDOW_API BlurErrorCode blur_set_pipeline(BlurWindowHandle window, const ch
  */
 BLURWINDOW_API BlurErrorCode blur_set_bounds(BlurWindowHandle window, const BlurRect* bounds);
 
+/**
+ * @brief Recreate the presentation surface at a new size.
+ *
+ * blur_set_bounds resizes the window and the render target but not the swap
+ * chain (or layered-window surface), so frames are stretched until this is
+ * called with the new size. The resize happens before the next frame.
+ *
+ * @param window Window handle.
+ * @param width New width in pixels.
+ * @param height New height in pixels.
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER for a zero size.
+ */
+BLURWINDOW_API BlurErrorCode blur_resize_swapchain(BlurWindowHandle window, uint32_t width, uint32_t height);
+
+/**
+ * @brief Set how many buffers the swap chain cycles through.
+ *
+ * Frames are presented without waiting for vsync, and the compositor shows
+ * the newest one on each refresh. With 2 buffers at most one finished frame
+ * waits behind the one on screen, which gives the lowest latency
+ * (blur_get_latency_ms), but a frame that takes longer than a refresh
+ * interval is seen as a skipped frame. With 3 the render thread can finish
+ * a frame while two are queued, which smooths out uneven frame times at the
+ * cost of up to one more refresh interval of latency. The difference is
+ * largest when the frame rate is close to the display's refresh rate.
+ *
+ * The buffers are recreated in place before the next frame, so no other
+ * setting is lost; the count is kept when graphics are recreated. It has no
+ * effect with the UpdateLayeredWindow backend (blur_get_backend returns 2).
+ * The default is 2.
+ *
+ * @param window Window handle.
+ * @param count 2 or 3.
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER for any other count.
+ * @note A failed change is logged and leaves the previous count in place.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_buffer_count(BlurWindowHandle window, uint32_t count);
+
+/**
+ * @brief Get the window's extended styles (WS_EX_* bits).
+ * @param window Window handle.
+ * @return The styles, or 0 for a NULL handle or before the window is created
+ *         (by the first blur_start or blur_render_once).
+ */
+BLURWINDOW_API uint32_t blur_get_ex_style(BlurWindowHandle window);
+
+/**
+ * @brief Replace the window's extended styles (WS_EX_* bits).
+ *
+ * Safe to toggle at runtime: WS_EX_TRANSPARENT (click-through), WS_EX_TOPMOST
+ * (applied through the z-order), WS_EX_NOACTIVATE. WS_EX_TOOLWINDOW takes
+ * effect the next time the window is shown. WS_EX_NOREDIRECTIONBITMAP and
+ * WS_EX_LAYERED are chosen for the presentation backend at creation: changing
+ * them afterwards breaks presentation until the window is recreated.
+ *
+ * @param window Window handle.
+ * @param exStyle New styles (read them with blur_get_ex_style and modify).
+ * @return BLUR_OK on success, BLUR_ERROR_NOT_INITIALIZED before the window is created.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_ex_style(BlurWindowHandle window, uint32_t exStyle);
+
+/**
+ * @brief Keep the window above or below the taskbar.
+ *
+ * Both relations make the window top-most (WS_EX_TOPMOST), so it stays above
+ * ordinary windows whatever BlurWindowOptions.topMost said. Below places it
+ * under the lowest taskbar, on every monitor, so the taskbar stays visible
+ * and clickable over a fullscreen overlay. The z-order is set when called
+ * (or when the window is created, if it doesn't exist yet): a top-most
+ * window raised later, or a taskbar recreated by an Explorer restart, may
+ * end up on the other side until this is called again. A later
+ * blur_set_ex_style that clears WS_EX_TOPMOST undoes it. No effect on child
+ * windows.
+ *
+ * @param window Window handle.
+ * @param relation 0: Above, 1: Below.
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER for an unknown relation.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_taskbar_relation(BlurWindowHandle window, int32_t relation);
+
+/**
+ * @brief Capture, process, and present exactly one frame.
+ * @param window Window handle (must be created with manualRender = 1).
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER if the window is not in manual render mode,
+ *         BLUR_ERROR_NOT_INITIALIZED if graphics could not be set up, BLUR_ERROR_CAPTURE_FAILED
+ *         if no frame could be captured.
+ * @note This is a no-op (returns BLUR_OK) while the continuous loop started by blur_start is running.
+ */
+BLURWINDOW_API BlurErrorCode blur_render_once(BlurWindowHandle window);
+
+/**
+ * @brief Compile the shaders of every configured effect ahead of the first frame.
+ *
+ * Graphics are otherwise set up by the first blur_start or blur_render_once,
+ * whose frame then takes much longer than the rest. This does that work, and
+ * runs each effect, pipeline layer and region effect once off-screen so the
+ * driver compiles them too, without presenting anything. Optional, but worth
+ * calling during a loading screen before blur_show or blur_start; call it
+ * again after changing effects.
+ *
+ * @param window Window handle.
+ * @return BLUR_OK on success, BLUR_ERROR_D3D11_FAILED if graphics could not be
+ *         initialized.
+ * @note Creates the window's HWND like blur_start, so call it from the thread
+ *       that pumps its messages. Takes tens of milliseconds.
+ */
+BLURWINDOW_API BlurErrorCode blur_prewarm(BlurWindowHandle window);
+
+/**
+ * @brief Restrict the blur to a sub-region of the window.
+ * @param window Window handle.
+ * @param region Rectangle relative to the window's top-left corner (NULL for the full window).
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER if the region is empty or outside the window.
+ * @note Outside the region the window is fully transparent and click-through.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_active_region(BlurWindowHandle window, const BlurRect* region);
+
+/** Most region effects a window keeps, see blur_set_region_effect */
+#define BLUR_MAX_REGION_EFFECTS 8
+
+/**
+ * @brief Draw a different effect inside a rectangle, e.g. Gaussian in a title
+ *        bar over a rain body.
+ *
+ * Inside the rectangle the window shows the given effect applied to the
+ * backdrop, instead of its own effect and pipeline layers. Regions are drawn
+ * in the order they were added, so a later region covers an earlier one where
+ * they overlap. Each region's effect starts from the window's strength, tint
+ * and noise settings when it is first drawn; later setters only change the
+ * window's own effect. After a resize only the part of a region still inside
+ * the window is drawn.
+ *
+ * @param window Window handle.
+ * @param region Rectangle relative to the window's top-left corner, or NULL to
+ *        remove every region effect.
+ * @param effect_type Effect type as for blur_set_effect_type.
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER for an unknown type,
+ *         a region that is empty or outside the window, or more than
+ *         BLUR_MAX_REGION_EFFECTS regions.
+ * @note Each region renders its effect over the whole window, so every region
+ *       costs about as much GPU time as the window's own effect.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_region_effect(BlurWindowHandle window, const BlurRect* region, int32_t effect_type);
+
+/**
+ * @brief Start a batch of changes that should appear in the same frame.
+ * @param window Window handle.
+ * @return BLUR_OK on success.
+ * @note Until the matching blur_end_update, region changes are deferred and no
+ *       frames are rendered. Batches nest; only the outermost end applies them.
+ *       Outside a batch, every setter applies immediately.
+ */
+BLURWINDOW_API BlurErrorCode blur_begin_update(BlurWindowHandle window);
+
+/**
+ * @brief Close a batch opened with blur_begin_update.
+ * @param window Window handle.
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER without a matching blur_begin_update.
+ */
+BLURWINDOW_API BlurErrorCode blur_end_update(BlurWindowHandle window);
+
 // --- Effect Management ---
 
 /**
@@ -152,12 +424,74 @@ BLURWINDOW_API BlurErrorCode blur_set_effect_type(BlurWindowHandle window, int32
 
 /**
  * @brief Set the overall blend strength.
+ *
+ * A shortcut for blur_set_blur_xy with both axes at strength.
+ *
  * @param window Window handle.
  * @param strength 0.0 (transparent) to 1.0 (full blur).
  * @return BLUR_OK on success.
  */
 BLURWINDOW_API BlurErrorCode blur_set_strength(BlurWindowHandle window, float strength);
 
+/**
+ * @brief Vary the blend strength linearly from the top of the window to the bottom.
+ *
+ * A later blur_set_strength (or a pipeline stage's strength) replaces the
+ * gradient with a uniform value. The Rain effect blurs uniformly at the
+ * average of the two values.
+ *
+ * @param window Window handle.
+ * @param top Strength at the top edge, clamped to 0.0-1.0.
+ * @param bottom Strength at the bottom edge, clamped to 0.0-1.0.
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER for a non-finite value.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_strength_gradient(BlurWindowHandle window, float top, float bottom);
+
+/**
+ * @brief Set separate horizontal and vertical blur strengths.
+ *
+ * The larger value becomes the blend strength, and the kernel reaches
+ * proportionally less far along the other axis: (1, 0) smears horizontally
+ * and keeps vertical detail sharp. Equal values are the same as
+ * blur_set_strength, which resets both axes to its value, as does
+ * blur_set_strength_gradient. Gaussian, Box and Kawase honor the split;
+ * Radial and Rain blur isotropically at the larger value.
+ *
+ * @param window Window handle.
+ * @param x Horizontal strength, clamped to 0.0-1.0.
+ * @param y Vertical strength, clamped to 0.0-1.0.
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER for a non-finite value.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_blur_xy(BlurWindowHandle window, float x, float y);
+
+/** Largest per-axis factor accepted by blur_set_downsample_xy. */
+#define BLUR_MAX_DOWNSAMPLE 8
+
+/**
+ * @brief Blur at a reduced resolution, divided separately per axis.
+ *
+ * The backdrop is scaled down bilinearly by x horizontally and y vertically,
+ * the effect and its pipeline layers run on that, and the result is scaled
+ * back up to the window's size. Each step of 2 on an axis halves the pixels
+ * the effect processes along it. The kernel works in downsampled pixels, so
+ * it reaches x and y times as far on screen; lower the kernel radius or
+ * strength to keep the look. Unequal factors also stretch the blur along
+ * the more downsampled axis, and above 2 fine detail such as text can
+ * shimmer as it moves, since the bilinear step skips pixels. Text usually
+ * survives horizontal downsampling better than vertical, e.g. (2, 1).
+ *
+ * Region effects, the custom shader, the edge feather, the debug overlay and
+ * edge AA (blur_set_edge_aa) all stay at full resolution. Equal factors of 1
+ * turn downsampling off.
+ *
+ * @param window Window handle.
+ * @param x Horizontal divisor, 1 to BLUR_MAX_DOWNSAMPLE.
+ * @param y Vertical divisor, 1 to BLUR_MAX_DOWNSAMPLE.
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER for 0 or more than
+ *         BLUR_MAX_DOWNSAMPLE on either axis.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_downsample_xy(BlurWindowHandle window, uint32_t x, uint32_t y);
+
 /**
  * @brief Set the primary parameter for the active effect.
  * @param window Window handle.
@@ -166,6 +500,139 @@ BLURWINDOW_API BlurErrorCode blur_set_strength(BlurWindowHandle window, float st
  */
 BLURWINDOW_API BlurErrorCode blur_set_blur_param(BlurWindowHandle window, float param);
 
+/** Largest kernel radius accepted by blur_set_kernel_radius. */
+#define BLUR_MAX_KERNEL_RADIUS 32
+
+/**
+ * @brief Set the blur kernel radius in pixels explicitly.
+ *
+ * Overrides the radius derived from blur_set_blur_param (Gaussian: 3 * sigma).
+ * This controls how far the blur spreads; blur_set_strength still controls how
+ * much of the blurred image is blended over the original.
+ * Applies to Gaussian and Box; other effects ignore it.
+ *
+ * @param window Window handle.
+ * @param radius Radius in pixels, 0 to return to the derived radius.
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER above BLUR_MAX_KERNEL_RADIUS.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_kernel_radius(BlurWindowHandle window, uint32_t radius);
+
+/**
+ * @brief Set the anti-aliasing sample count for mask and corner edges.
+ *
+ * Reserved for rounded corners and alpha masks: it is stored, but nothing
+ * renders with it yet. The masks that exist today (the active region and the
+ * cursor-follow circle) are window regions, which clip whole pixels and
+ * can't be smoothed. Edge AA will run at the window's full resolution
+ * whatever downsample the window (blur_set_downsample_xy) or a pipeline
+ * stage blurs at.
+ *
+ * @param window Window handle.
+ * @param samples 1 (off), 2, 4 or 8.
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER for any other count.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_edge_aa(BlurWindowHandle window, uint32_t samples);
+
+/**
+ * @brief Fade the blur out towards the edges of the blurred area.
+ *
+ * The window's alpha ramps from 0 at the edge to full over the given width,
+ * measured inward from the active region (blur_set_active_region), or from
+ * the window edges without one. The width is capped at half the area's
+ * smaller side when a frame is rendered, so shrinking the region never
+ * leaves it fully transparent.
+ *
+ * Order of operations: the effect and its layers (tint included), region
+ * effects, the custom shader and the temporal mask (blur_set_temporal_mode)
+ * run first, then the feather, then the debug overlay, which stays opaque. The
+ * region clip itself stays hard, but since the fade already reaches 0 there
+ * it isn't visible. While cursor follow is on, the window edges are
+ * feathered, not the circle. Masks and rounded corners don't exist yet.
+ *
+ * @param window Window handle.
+ * @param pixels Feather width in pixels; negative values are treated as 0 (off).
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER for a non-finite value.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_edge_feather(BlurWindowHandle window, float pixels);
+
+/**
+ * @brief Run a custom HLSL snippet over every finished frame.
+ *
+ * The snippet must define the entry point
+ *
+ *     float4 CustomShade(float4 color, float2 uv, float time)
+ *
+ * which receives the blurred, tinted color of the pixel (premultiplied
+ * alpha), its texture coordinate (0,0 top-left to 1,1 bottom-right) and the
+ * animation time in seconds (see blur_set_time_mode), and returns the
+ * pixel's new premultiplied color. The snippet may also read these globals:
+ *
+ *     Texture2D blurredTexture;   // The whole blurred frame
+ *     SamplerState linearSampler; // Bilinear, clamped
+ *     float2 resolution;          // Output size in pixels
+ *
+ * It is compiled for ps_5_0 and runs after region effects, before the edge
+ * feather and the debug overlay. Line numbers in compiler messages count
+ * from the start of the snippet.
+ *
+ * @param window Window handle.
+ * @param hlsl Null-terminated HLSL source; NULL or "" removes the pass.
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER if the snippet
+ *         doesn't compile (blur_get_last_error then holds the compiler's
+ *         diagnostics verbatim), BLUR_ERROR_NOT_INITIALIZED before blur_init.
+ * @note A snippet that fails to compile leaves the previous one in place.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_custom_shader(BlurWindowHandle window, const char* hlsl);
+
+/** Modes for blur_set_temporal_mode. */
+#define BLUR_TEMPORAL_OFF             0  /**< Blur everything */
+#define BLUR_TEMPORAL_MOTION_ONLY     1  /**< Blur only what changed */
+#define BLUR_TEMPORAL_MOTION_WEIGHTED 2  /**< Blur more the more it changed */
+
+/**
+ * @brief Blur only the parts of the backdrop that move.
+ *
+ * Each new capture is compared with the one before it, pixel by pixel with a
+ * few pixels of margin. With BLUR_TEMPORAL_MOTION_ONLY the window turns
+ * transparent wherever nothing changed, so static content shows through
+ * sharp and only changing content (video, scrolling, animation) is blurred.
+ * BLUR_TEMPORAL_MOTION_WEIGHTED fades the blur in with the size of the
+ * change instead, fully blurred from a quarter of the full color range.
+ *
+ * The window's own frame rate and capture interval set how far apart the
+ * compared captures are; frames between captures keep the last comparison.
+ * Only live desktop captures are compared: with a source texture, a
+ * background image or a frozen backdrop the whole window is blurred, as it is
+ * on the first capture after the mode (or the window's size) changes.
+ *
+ * Costs two extra copies of the capture in GPU memory (8 bytes per pixel for
+ * 8-bit captures, 16 for HDR ones), one copy per new capture and a 9-tap
+ * comparison per pixel, counted in blur_get_vram_usage. Check
+ * BLUR_ALGORITHM_TEMPORAL in blur_get_supported_algorithms first.
+ *
+ * @param window Window handle.
+ * @param mode One of the BLUR_TEMPORAL_* values.
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER for an unknown mode.
+ * @note If the comparison pass can't be created the mode falls back to
+ *       BLUR_TEMPORAL_OFF, with a warning in the log.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_temporal_mode(BlurWindowHandle window, int32_t mode);
+
+/**
+ * @brief Set a directional (motion-style) blur.
+ *
+ * While strength is above 0 the Radial effect smears along the given direction
+ * instead of zooming toward its center; it replaces the radial blur rather than
+ * composing with it. Other effects ignore this setting, which is kept and
+ * applied when switching to Radial.
+ *
+ * @param window Window handle.
+ * @param angleDegrees Direction, counter-clockwise from pointing right; normalized to [0, 360).
+ * @param strength 0.0 (off, plain radial blur) to 1.0 (longest smear); clamped.
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER for a non-finite value.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_direction(BlurWindowHandle window, float angleDegrees, float strength);
+
 /**
  * @brief Set the tint color.
  * @param r Red (0-1).
@@ -176,6 +643,136 @@ BLURWINDOW_API BlurErrorCode blur_set_blur_param(BlurWindowHandle window, float
  */
 BLURWINDOW_API BlurErrorCode blur_set_tint_color(BlurWindowHandle window, float r, float g, float b, float a);
 
+/** Largest layer count accepted by blur_set_tint_layers. */
+#define BLUR_MAX_TINT_LAYERS 8
+
+/**
+ * @brief Set a stack of tint layers, composited in order over the blur.
+ *
+ * Replaces any color set with blur_set_tint_color. Each layer's alpha acts
+ * as its strength, exactly as for a single tint.
+ *
+ * @param window Window handle.
+ * @param layers Array of colors, bottom layer first (may be NULL if count is 0).
+ * @param count Number of layers, 0 to clear the tint.
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER if count exceeds
+ *         BLUR_MAX_TINT_LAYERS or a channel is outside 0-1.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_tint_layers(BlurWindowHandle window, const BlurColor* layers, uint32_t count);
+
+/**
+ * @brief Set the color blurred in place of a black backdrop.
+ *
+ * Desktop duplication returns black for DRM-protected surfaces (video
+ * players, streaming apps) and for parts of the window outside every
+ * monitor. Capture pixels that are pure black are replaced with this color
+ * before blurring, blended by its alpha. Genuinely black desktop content is
+ * indistinguishable from protected content and is replaced too.
+ *
+ * @param window Window handle.
+ * @param color Fallback color, or NULL to disable (same as alpha 0).
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER if a channel is outside 0-1.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_fallback_color(BlurWindowHandle window, const BlurColor* color);
+
+/**
+ * @brief Set how an HDR backdrop is tone-mapped to SDR before blurring.
+ *
+ * Only applies while blur_get_capture_format reports an HDR format; with an
+ * SDR capture the mode is stored and nothing changes. Every capture is SDR
+ * today, as Windows maps HDR desktops to SDR itself, so this is a no-op until
+ * an HDR capture path exists.
+ *
+ * @param window Window handle.
+ * @param mode A BlurToneMapping value.
+ * @return BLUR_OK on success (also for an SDR capture),
+ *         BLUR_ERROR_INVALID_PARAMETER for an unknown mode.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_tone_mapping(BlurWindowHandle window, int32_t mode);
+
+/**
+ * @brief Blur a specific window instead of the desktop behind the blur window.
+ *
+ * The source is captured with Windows.Graphics.Capture and stretched over the
+ * whole blur window, whatever its position. Caveats:
+ * - Requires Windows 10 version 1903 or later.
+ * - Windows 10 draws a yellow capture border around the source window.
+ * - A minimized source produces no new frames; the last frame is kept.
+ * - DRM-protected content in the source is captured as black.
+ * - An elevated window cannot be captured from a non-elevated process.
+ * - Only top-level windows can be captured.
+ *
+ * Before the first blur_start or blur_render_once the source is only stored;
+ * if window capture then fails, the desktop is captured instead.
+ *
+ * @param window Window handle.
+ * @param source HWND of the window to capture, or NULL for the desktop.
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER if source is not a
+ *         window or is the blur window itself, BLUR_ERROR_CAPTURE_FAILED if
+ *         capture of the source could not be started.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_capture_source(BlurWindowHandle window, void* source);
+
+/**
+ * @brief Blur a texture supplied by the caller instead of capturing the screen.
+ *
+ * Useful for engine integration: the caller renders into a texture (e.g. a
+ * game framebuffer) and the window blurs that. The texture is stretched to
+ * fill the window and read again every frame, so updating its contents is
+ * enough; while it is set, capture, blur_set_capture_source, the capture
+ * interval and freezing have no effect.
+ *
+ * Texture requirements:
+ * - Created with D3D11_RESOURCE_MISC_SHARED or D3D11_RESOURCE_MISC_SHARED_NTHANDLE
+ *   on the same adapter as the blur system.
+ * - Format DXGI_FORMAT_B8G8R8A8_UNORM, D3D11_BIND_SHADER_RESOURCE, not multisampled.
+ *
+ * The handle and texture must stay valid until another texture or NULL is
+ * set, or the window is destroyed. No keyed mutex is acquired: the caller
+ * must have flushed its writes before the next frame is rendered.
+ *
+ * @param window Window handle.
+ * @param sharedHandle Shared handle of the texture, or NULL to capture the screen again.
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER if the handle
+ *         can't be opened or the texture doesn't meet the requirements; the
+ *         previous source then stays active.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_source_texture(BlurWindowHandle window, void* sharedHandle);
+
+/**
+ * @brief Blur a still image instead of capturing the screen.
+ *
+ * The image is copied, so the buffer can be freed once the call returns. It
+ * is stretched to fill the window like a source texture, and capture,
+ * blur_set_capture_source, the capture interval and freezing have no effect
+ * while it is set. A source texture (blur_set_source_texture) takes
+ * precedence; the image is used again once the texture is cleared.
+ *
+ * @param window Window handle.
+ * @param rgba width * height pixels, 4 bytes each in R, G, B, A order, rows
+ *             top to bottom without padding; NULL to capture the screen again.
+ * @param width Image width in pixels (1 to 16384).
+ * @param height Image height in pixels (1 to 16384).
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER for a size out
+ *         of range or if the texture can't be created; the previous source
+ *         then stays active.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_background_image(BlurWindowHandle window, const uint8_t* rgba, uint32_t width, uint32_t height);
+
+/**
+ * @brief Get the average color of the backdrop behind the window.
+ *
+ * Reduces the last captured frame to a single pixel on the GPU and reads it
+ * back, which stalls until the GPU has caught up. Call it on a timer (a few
+ * times per second at most) rather than every frame.
+ *
+ * @param window Window handle.
+ * @param outColor Receives the color; RGB in 0-1, alpha always 1.
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER if outColor is NULL,
+ *         BLUR_ERROR_NOT_INITIALIZED before the first frame has been captured.
+ */
+BLURWINDOW_API BlurErrorCode blur_sample_dominant_color(BlurWindowHandle window, BlurColor* outColor);
+
 // --- Noise Control ---
 
 /**
@@ -210,6 +807,39 @@ BLURWINDOW_API BlurErrorCode blur_set_noise_speed(BlurWindowHandle window, float
  */
 BLURWINDOW_API BlurErrorCode blur_set_noise_type(BlurWindowHandle window, int32_t type);
 
+/**
+ * @brief Set how the noise is composited onto the blurred image.
+ * @param window Window handle.
+ * @param mode 0: Additive (brightens/darkens evenly), 1: Overlay (boosts contrast), 2: Multiply (scales existing color).
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER for an unknown mode.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_noise_blend_mode(BlurWindowHandle window, int32_t mode);
+
+/**
+ * @brief Tint the noise grain independently of the tint color.
+ *
+ * The grain is multiplied by the color before it is blended, e.g. blue grain
+ * over a neutral blur. Alpha sets how much of the color is applied: 0 keeps
+ * the grain monochrome, 1 tints it fully. Dark colors weaken the grain as
+ * well as tinting it.
+ *
+ * @param window Window handle.
+ * @param color Noise color, or NULL for monochrome grain (the default).
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER if a channel is outside 0-1.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_noise_color(BlurWindowHandle window, const BlurColor* color);
+
+/**
+ * @brief Select the noise pattern by seed.
+ * @param window Window handle.
+ * @param seed Any value; the default is 0.
+ * @return BLUR_OK on success.
+ * @note Also restarts the noise animation clock, so a fixed seed with speed 0
+ *       (or manual time mode) renders identical grain every time. Reseeding
+ *       periodically varies grain that would otherwise be static at speed 0.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_noise_seed(BlurWindowHandle window, uint32_t seed);
+
 // --- Rain Effect Control ---
 
 /**
@@ -278,6 +908,105 @@ BLURWINDOW_API BlurErrorCode blur_set_click_callback(
     void* userData
 );
 
+/**
+ * @brief Callback type for presented frames.
+ * @param frameIndex Number of frames presented before this one.
+ * @param frameTimeMs Time spent producing this frame, in milliseconds.
+ * @param userData User data passed to blur_set_frame_callback.
+ */
+typedef void (*BlurFrameCallback)(uint64_t frameIndex, float frameTimeMs, void* userData);
+
+/**
+ * @brief Set a callback fired after every presented frame.
+ * @param window Window handle.
+ * @param callback Function to call (NULL to remove).
+ * @param userData User data passed to callback.
+ * @return BLUR_OK on success.
+ * @note The callback runs on the render thread at the frame rate (or on the
+ *       caller's thread for blur_render_once); frame pacing waits for it, so
+ *       keep it cheap. Once this function returns, a replaced callback is
+ *       never called again.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_frame_callback(
+    BlurWindowHandle window,
+    BlurFrameCallback callback,
+    void* userData
+);
+
+/**
+ * @brief Callback type for the owner window being destroyed.
+ * @param userData User data passed to blur_set_owner_closed_callback.
+ */
+typedef void (*BlurOwnerClosedCallback)(void* userData);
+
+/**
+ * @brief Set a callback fired once when the window's owner is destroyed.
+ * @param window Window handle.
+ * @param callback Function to call (NULL restores the default behavior).
+ * @param userData User data passed to callback.
+ * @return BLUR_OK on success.
+ * @note Without a callback the render loop stops itself when the owner goes
+ *       away, as if blur_stop had been called. With a callback nothing is
+ *       stopped automatically. The callback runs on the render thread or on
+ *       the window's thread, and once this function returns a replaced
+ *       callback is never called again.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_owner_closed_callback(
+    BlurWindowHandle window,
+    BlurOwnerClosedCallback callback,
+    void* userData
+);
+
+/**
+ * @brief Callback type for display configuration changes.
+ * @param userData User data passed to blur_set_display_changed_callback.
+ */
+typedef void (*BlurDisplayChangedCallback)(void* userData);
+
+/**
+ * @brief Set a callback fired when a monitor is added or removed, or changes
+ *        resolution or arrangement (WM_DISPLAYCHANGE).
+ * @param window Window handle.
+ * @param callback Function to call (NULL to remove).
+ * @param userData User data passed to callback.
+ * @return BLUR_OK on success.
+ * @note The window keeps its bounds; the callback may move it, e.g. with
+ *       blur_set_bounds. It runs on the window's thread while that thread
+ *       dispatches messages. Child windows (BlurWindowOptions.child) are never
+ *       notified. Once this function returns, a replaced callback is never
+ *       called again.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_display_changed_callback(
+    BlurWindowHandle window,
+    BlurDisplayChangedCallback callback,
+    void* userData
+);
+
+/**
+ * @brief Callback type for power status changes.
+ * @param userData User data passed to blur_set_power_changed_callback.
+ */
+typedef void (*BlurPowerChangedCallback)(void* userData);
+
+/**
+ * @brief Set a callback fired when the power status changes, e.g. when the
+ *        system switches between AC and battery power (WM_POWERBROADCAST with
+ *        PBT_APMPOWERSTATUSCHANGE).
+ * @param window Window handle.
+ * @param callback Function to call (NULL to remove).
+ * @param userData User data passed to callback.
+ * @return BLUR_OK on success.
+ * @note Battery level changes fire it too; query GetSystemPowerStatus for the
+ *       new state. Runs on the window's thread like the display-changed
+ *       callback, and child windows are never notified. Once this function
+ *       returns, a replaced callback is never called again.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_power_changed_callback(
+    BlurWindowHandle window,
+    BlurPowerChangedCallback callback,
+    void* userData
+);
+
 // --- Utility ---
 
 /**
@@ -287,18 +1016,667 @@ BLURWINDOW_API BlurErrorCode blur_set_click_callback(
  */
 BLURWINDOW_API void* blur_get_hwnd(BlurWindowHandle window);
 
+/**
+ * @brief Check whether a native window is a blur window of this process.
+ *
+ * For apps handling many windows, e.g. to skip their own blur windows when
+ * picking a capture source (blur_set_capture_source) or following the
+ * foreground window, which would otherwise blur their own output.
+ *
+ * Every blur window is tagged with the window property "BlurWindow"
+ * (SetPropW, nonzero value) from WM_NCCREATE until WM_NCDESTROY, so the tag
+ * also holds while the window is being created or destroyed. Windows of
+ * other processes are never reported, even if they carry the same property.
+ *
+ * @param hwnd Any window handle, or NULL.
+ * @return 1 for a blur window, 0 otherwise (including destroyed handles).
+ */
+BLURWINDOW_API int32_t blur_is_blur_window(void* hwnd);
+
 /**
  * @brief Get current FPS for the window.
  * @return Current frames per second.
  */
 BLURWINDOW_API float blur_get_fps(BlurWindowHandle window);
 
+/**
+ * @brief Make the blur a circle that follows the mouse cursor ("spotlight").
+ *
+ * While enabled, the render loop re-centers a circular window region on the
+ * cursor every frame, overriding blur_set_active_region. The window is forced
+ * click-through so it never captures the cursor it follows; the original
+ * click-through setting and active region return when disabled.
+ *
+ * @param window Window handle.
+ * @param enabled 1 to follow the cursor, 0 to stop.
+ * @param radius Circle radius in pixels (must be > 0 when enabling).
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER for a bad radius.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_cursor_follow(BlurWindowHandle window, int32_t enabled, float radius);
+
+/**
+ * @brief Choose the clock that animated effects (noise, rain) follow.
+ *
+ * In realtime mode (0, default) animation advances with the wall clock. In
+ * manual mode (1) it only advances when blur_set_time is called, by the
+ * difference from the previously set time, enabling frame-accurate capture.
+ * Entering manual mode does not move the animation; only later blur_set_time
+ * calls do. Stepping backwards rewinds noise; rain drops only ever move forward.
+ *
+ * @param window Window handle.
+ * @param mode 0: Realtime, 1: Manual.
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER for an unknown mode.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_time_mode(BlurWindowHandle window, int32_t mode);
+
+/**
+ * @brief Set the animation clock for manual time mode.
+ * @param window Window handle.
+ * @param seconds Animation time in seconds. Ignored in realtime mode.
+ * @return BLUR_OK on success.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_time(BlurWindowHandle window, float seconds);
+
+/** Highest frame rate accepted by blur_set_target_fps. */
+#define BLUR_MAX_TARGET_FPS 1000
+
+/**
+ * @brief Override the frame rate cap set by the quality preset.
+ * @param window Window handle.
+ * @param fps Frames per second (1 to BLUR_MAX_TARGET_FPS), or 0 to follow the preset again.
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER if out of range.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_target_fps(BlurWindowHandle window, int32_t fps);
+
+/**
+ * @brief Render once per owner repaint instead of at a fixed frame rate.
+ *
+ * For owned and child windows: the owner window is subclassed and every
+ * WM_PAINT it receives triggers one frame, so the blur only updates when the
+ * app below it does. While enabled this takes precedence over the target
+ * frame rate (preset or blur_set_target_fps), which applies again once
+ * disabled. Paints of the owner's child controls don't count.
+ *
+ * @param window Window handle.
+ * @param enable Non-zero to sync to owner paints.
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER when enabling for a
+ *         window without an owner, or from a thread other than the owner's
+ *         (subclassing only works there).
+ */
+BLURWINDOW_API BlurErrorCode blur_set_sync_to_owner_paint(BlurWindowHandle window, int32_t enable);
+
+/** Longest interval accepted by blur_set_capture_interval_ms. */
+#define BLUR_MAX_CAPTURE_INTERVAL_MS 10000
+
+/**
+ * @brief Capture the backdrop less often than frames are rendered.
+ *
+ * Frames between captures re-blur the last capture, so animated effects keep
+ * their frame rate while capture cost drops. Useful for mostly static
+ * backdrops on battery; moving content behind the window lags by up to the
+ * interval.
+ *
+ * @param window Window handle.
+ * @param intervalMs Minimum time between captures in milliseconds
+ *        (up to BLUR_MAX_CAPTURE_INTERVAL_MS), or 0 to capture every frame.
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER if out of range.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_capture_interval_ms(BlurWindowHandle window, uint32_t intervalMs);
+
+/** Longest interval accepted by blur_set_min_frame_interval_ms. */
+#define BLUR_MAX_MIN_FRAME_INTERVAL_MS 1000
+
+/**
+ * @brief Never render frames closer together than an interval.
+ *
+ * A hard floor on frame time, e.g. to keep a thin laptop's GPU from heating
+ * up and throttling: where the target frame rate (preset or
+ * blur_set_target_fps) or owner paints (blur_set_sync_to_owner_paint) would
+ * render more often, the interval wins. Together with the target frame rate
+ * it bounds both ends of the frame rate range. blur_render_once isn't
+ * throttled.
+ *
+ * @param window Window handle.
+ * @param intervalMs Minimum time between frames in milliseconds (up to
+ *        BLUR_MAX_MIN_FRAME_INTERVAL_MS), or 0 for no floor.
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER if out of range.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_min_frame_interval_ms(BlurWindowHandle window, uint32_t intervalMs);
+
+/**
+ * @brief Get the refresh rate of the monitor the window is on.
+ *
+ * If the window spans several monitors, the primary monitor's rate is returned.
+ *
+ * @param window Window handle.
+ * @return Refresh rate in Hz, 0.0 if unknown, or -1.0 if the handle is invalid.
+ */
+BLURWINDOW_API float blur_get_display_refresh_hz(BlurWindowHandle window);
+
+/**
+ * @brief Get the time spent producing the last frame.
+ * @return Frame time in milliseconds, or -1.0 if the handle is invalid.
+ */
+BLURWINDOW_API float blur_get_frame_time(BlurWindowHandle window);
+
+/**
+ * @brief Get how far the last frame lagged behind the desktop it blurred.
+ *
+ * Measured on the render thread from the moment the capture returned the
+ * backdrop to the moment Present returned. With a capture interval
+ * (blur_set_capture_interval_ms) the frames in between re-blur an older
+ * capture, so the value rises through the interval and drops on each new
+ * capture.
+ *
+ * @param window Window handle.
+ * @return Latency in milliseconds, 0.0 before the first frame or while the
+ *         backdrop is a texture, an image or frozen, or -1.0 if the handle is invalid.
+ * @note A lower bound, accurate to the system timer (well under 0.1 ms). It
+ *       leaves out the time the desktop frame waited before capture returned
+ *       it and the wait for the compositor to show the presented frame,
+ *       together typically one to two display refresh intervals more.
+ */
+BLURWINDOW_API float blur_get_latency_ms(BlurWindowHandle window);
+
+/**
+ * @brief Draw a stats panel (FPS, frame time, backend) in the window's top-left corner.
+ * @param window Window handle.
+ * @param enabled 1 to show, 0 to hide.
+ * @return BLUR_OK on success.
+ * @note Intended for development; the panel is part of the rendered output
+ *       and is not meant to be shipped enabled.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_debug_overlay(BlurWindowHandle window, int32_t enabled);
+
+/**
+ * @brief Get the presentation backend in use.
+ * @return 0: not initialized, 1: DirectComposition, 2: UpdateLayeredWindow, -1: invalid handle.
+ */
+BLURWINDOW_API int32_t blur_get_backend(BlurWindowHandle window);
+
+/**
+ * @brief Get why capture is failing, e.g. after BLUR_ERROR_CAPTURE_FAILED.
+ *
+ * Reflects the most recent capture attempt: initialization, a capture source
+ * change, or a rendered frame. BLUR_CAPTURE_STATUS_BLOCKED_BY_POLICY means the
+ * OS denied access, typically because screen capture is disabled by group
+ * policy; it is also reported while the secure desktop (UAC prompt, lock
+ * screen) is shown, and clears once a frame is captured again.
+ *
+ * @param window Window handle.
+ * @return A BlurCaptureStatus value, or -1 if the handle is invalid.
+ */
+BLURWINDOW_API int32_t blur_get_capture_status(BlurWindowHandle window);
+
+/**
+ * @brief Get the pixel format of the backdrop the effects run on.
+ *
+ * Tint colors and other color parameters are applied in this format's space.
+ * Desktop and window capture always deliver BLUR_CAPTURE_FORMAT_SRGB8, also
+ * on HDR displays, where Windows maps the desktop to SDR before handing it
+ * over; a source texture (blur_set_source_texture) must be 8-bit too. The
+ * HDR formats are reserved for captures that keep the HDR signal: there,
+ * color parameters are linear (scRGB) or PQ-encoded (HDR10), so sRGB tint
+ * values must be converted first.
+ *
+ * @param window Window handle.
+ * @return A BlurCaptureFormat value, BLUR_ERROR_CAPTURE_FAILED if no frame has
+ *         been rendered yet, or BLUR_ERROR_INVALID_HANDLE.
+ */
+BLURWINDOW_API int32_t blur_get_capture_format(BlurWindowHandle window);
+
+/**
+ * @brief Get how the window captures its backdrop.
+ *
+ * The desktop is captured with DXGI Desktop Duplication, which copies whole
+ * monitor frames on the GPU and is the fast path. A window set with
+ * blur_set_capture_source is captured with Windows.Graphics.Capture, which
+ * needs Windows 10 1903 or later and is slower; if it can't start when
+ * graphics are created, the desktop is captured instead. This build has no
+ * GDI (BitBlt) fallback: when neither method works, nothing is captured,
+ * BLUR_CAPTURE_METHOD_NONE is reported and blur_get_capture_status says why.
+ * NONE is also reported before graphics are created (the first blur_start
+ * or blur_render_once). A source texture or background image replaces the
+ * capture without stopping it, so the method is still reported.
+ *
+ * @param window Window handle.
+ * @return A BlurCaptureMethod value, or BLUR_ERROR_INVALID_HANDLE.
+ */
+BLURWINDOW_API int32_t blur_get_capture_method(BlurWindowHandle window);
+
+/** Preferences for blur_set_preferred_capture_method. */
+#define BLUR_CAPTURE_PREFER_AUTO                     0  /**< DXGI for the desktop, WGC for a capture source */
+#define BLUR_CAPTURE_PREFER_DESKTOP_DUPLICATION      1  /**< DXGI, also for a capture source */
+#define BLUR_CAPTURE_PREFER_WINDOWS_GRAPHICS_CAPTURE 2  /**< WGC wherever it's available */
+
+/**
+ * @brief Override the automatic choice of capture method.
+ *
+ * For working around a capture bug of one environment, e.g. a driver that
+ * breaks Windows.Graphics.Capture. BLUR_CAPTURE_PREFER_DESKTOP_DUPLICATION
+ * captures the desktop even for windows with a capture source
+ * (blur_set_capture_source), so those blur what's on screen behind them
+ * instead of the source window, at lower latency.
+ * BLUR_CAPTURE_PREFER_WINDOWS_GRAPHICS_CAPTURE can only capture a source
+ * window, so the desktop keeps using Desktop Duplication.
+ *
+ * A preferred method that is unavailable or fails to start falls back to the
+ * automatic choice, with a warning in the log; blur_get_capture_method
+ * reports the method actually used. There is no GDI method to prefer in this
+ * build.
+ *
+ * Applies to every window of the system, to captures started after the
+ * call: when graphics are created (the first blur_start or blur_render_once)
+ * and by blur_set_capture_source. Set it before creating windows; running
+ * captures are kept.
+ *
+ * @param sys System handle.
+ * @param method One of the BLUR_CAPTURE_PREFER_* values.
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER for an unknown
+ *         method, or BLUR_ERROR_INVALID_HANDLE.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_preferred_capture_method(BlurSystemHandle sys, int32_t method);
+
+/**
+ * @brief Get the screen rectangle the last frame was captured from.
+ *
+ * In physical pixels, clipped to the monitor being duplicated; the window
+ * bounds are captured as given. A rectangle smaller than the bounds means the
+ * window extends past its monitor. One matching the bounds while the blur
+ * still looks offset means the bounds are DPI-scaled rather than physical,
+ * typically because the process isn't per-monitor DPI aware.
+ *
+ * @param window Window handle.
+ * @param outRect Receives the rectangle in screen coordinates.
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER if outRect is NULL,
+ *         BLUR_ERROR_CAPTURE_FAILED before the first frame and while a window
+ *         (blur_set_capture_source), source texture or background image is
+ *         blurred instead of the screen.
+ */
+BLURWINDOW_API BlurErrorCode blur_get_capture_rect(BlurWindowHandle window, BlurRect* outRect);
+
+/**
+ * @brief Get the approximate GPU memory used by this window.
+ *
+ * Sums the textures and swap chain buffers this window allocated (capture
+ * copy, effect intermediates, output, presentation surfaces). It is an
+ * estimate from texture dimensions, not a driver query, and does not include
+ * system-wide or other windows' usage.
+ *
+ * @param window Window handle.
+ * @return Bytes, or 0 if the handle is invalid or graphics are not initialized.
+ */
+BLURWINDOW_API uint64_t blur_get_vram_usage(BlurWindowHandle window);
+
+/** Most frames kept by blur_get_frame_history. */
+#define BLUR_FRAME_HISTORY_SIZE 1024
+
+/// One presented frame, see blur_get_frame_history.
+typedef struct {
+    uint64_t frameIndex;  ///< Index as passed to the frame callback.
+    float frameTimeMs;    ///< CPU time to capture, process and present it.
+    float fps;            ///< The window's FPS reading (updated every second) when it was presented.
+} BlurFrameSample;
+
+/**
+ * @brief Get the timing of recently presented frames, e.g. to profile stutter.
+ *
+ * The window keeps its last BLUR_FRAME_HISTORY_SIZE presented frames, from
+ * the render loop and blur_render_once alike.
+ *
+ * @param window Window handle.
+ * @param out Receives the most recent frames, oldest first (NULL to count them).
+ * @param capacity Number of entries out can hold.
+ * @return Frames written, or with out == NULL the number kept; 0 if the handle is invalid.
+ */
+BLURWINDOW_API uint32_t blur_get_frame_history(BlurWindowHandle window, BlurFrameSample* out, uint32_t capacity);
+
+/**
+ * @brief Pause rendering while keeping the render loop alive.
+ * @param window Window handle.
+ * @return BLUR_OK on success.
+ */
+BLURWINDOW_API BlurErrorCode blur_pause(BlurWindowHandle window);
+
+/**
+ * @brief Resume rendering after blur_pause.
+ * @param window Window handle.
+ * @return BLUR_OK on success.
+ */
+BLURWINDOW_API BlurErrorCode blur_resume(BlurWindowHandle window);
+
+/**
+ * @brief Skip frames while the window is fully occluded.
+ *
+ * The render loop checks a few times a second whether the windows above
+ * cover every pixel of this one, or whether it is cloaked (e.g. on another
+ * virtual desktop), and renders again once any part is revealed. A partly
+ * covered window keeps rendering. Layered and DirectComposition windows may
+ * be see-through, so they never count as covering. Independent of
+ * blur_pause.
+ *
+ * @param window Window handle.
+ * @param enable Non-zero to skip occluded frames.
+ * @return BLUR_OK on success.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_pause_when_occluded(BlurWindowHandle window, int32_t enable);
+
+/**
+ * @brief Check whether the window is fully occluded right now, by the same
+ *        rules as blur_set_pause_when_occluded.
+ * @param window Window handle.
+ * @return 1 if occluded, 0 if not, or BLUR_ERROR_INVALID_HANDLE.
+ */
+BLURWINDOW_API int32_t blur_is_occluded(BlurWindowHandle window);
+
+/**
+ * @brief Callback type for occlusion changes.
+ * @param occluded 1 when the window became fully occluded, 0 when revealed.
+ * @param userData User data passed to blur_set_occlusion_changed_callback.
+ */
+typedef void (*BlurOcclusionChangedCallback)(int32_t occluded, void* userData);
+
+/**
+ * @brief Set a callback fired when the window becomes fully occluded or is
+ *        revealed again.
+ * @param window Window handle.
+ * @param callback Function to call (NULL to remove).
+ * @param userData User data passed to callback.
+ * @return BLUR_OK on success.
+ * @note Occlusion is checked by the render loop, so nothing is reported while
+ *       the window is stopped. The callback runs on the render thread, and
+ *       once this function returns a replaced callback is never called again.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_occlusion_changed_callback(
+    BlurWindowHandle window,
+    BlurOcclusionChangedCallback callback,
+    void* userData
+);
+
+/**
+ * @brief Stop capturing and keep blurring a snapshot of the backdrop.
+ * @param window Window handle.
+ * @return BLUR_OK on success.
+ * @note The snapshot is taken on the next rendered frame. Effects and animated
+ *       noise keep rendering on top of it, but the screen is no longer
+ *       captured. Moving or resizing a frozen window (blur_set_bounds) keeps
+ *       showing the stale snapshot; unfreeze and freeze again to retake it.
+ */
+BLURWINDOW_API BlurErrorCode blur_freeze(BlurWindowHandle window);
+
+/**
+ * @brief Resume live capture after blur_freeze and release the snapshot.
+ * @param window Window handle.
+ * @return BLUR_OK on success.
+ */
+BLURWINDOW_API BlurErrorCode blur_unfreeze(BlurWindowHandle window);
+
+/**
+ * @brief Show a window created with startHidden, or hidden with blur_hide.
+ * @param window Window handle.
+ * @return BLUR_OK on success.
+ * @note A hidden window still needs blur_start to render. Start it hidden,
+ *       apply every parameter, then show it so the first visible frame
+ *       already has the final look.
+ */
+BLURWINDOW_API BlurErrorCode blur_show(BlurWindowHandle window);
+
+/**
+ * @brief Hide the window. The render loop keeps running while hidden.
+ * @param window Window handle.
+ * @return BLUR_OK on success.
+ */
+BLURWINDOW_API BlurErrorCode blur_hide(BlurWindowHandle window);
+
+/**
+ * @brief Run an experimental native command that has no dedicated function yet.
+ *
+ * Unstable and unversioned: commands and their arguments may change or be
+ * removed in any release, so prefer a dedicated function once one exists.
+ * Currently understood:
+ * - "effect.parameters": args is a JSON object handed to the active effect,
+ *   e.g. {"iterations": 6} for Kawase.
+ *
+ * @param window Window handle.
+ * @param command Command name.
+ * @param args Command arguments; NULL is the same as "".
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER for a NULL or
+ *         unknown command or arguments it rejects (see blur_get_last_error).
+ */
+BLURWINDOW_API BlurErrorCode blur_command(BlurWindowHandle window, const char* command, const char* args);
+
+/**
+ * @brief Check whether the library is a debug build.
+ *
+ * Debug builds enable the D3D11 debug layer and unoptimized shaders, which
+ * are much slower and should not ship.
+ *
+ * @return 1 for a debug build, 0 for release.
+ */
+BLURWINDOW_API int32_t blur_is_debug_build(void);
+
+/**
+ * @brief Check whether desktop composition (DWM) is enabled.
+ *
+ * Both presentation backends need it; without composition blur windows
+ * render incorrectly. Always on since Windows 8, but it can be off on
+ * Windows 7 (basic theme, some remote sessions) and in safe mode. Needs no
+ * blur system.
+ *
+ * @return 1 if composition is enabled, 0 if not or if DWM can't be queried.
+ */
+BLURWINDOW_API int32_t blur_is_dwm_enabled(void);
+
+/** Bits returned by blur_get_supported_algorithms. */
+#define BLUR_ALGORITHM_GAUSSIAN    (1u << 0)
+#define BLUR_ALGORITHM_KAWASE      (1u << 1)
+#define BLUR_ALGORITHM_BOX         (1u << 2)
+#define BLUR_ALGORITHM_RADIAL      (1u << 3)
+#define BLUR_ALGORITHM_RAIN        (1u << 4)
+#define BLUR_ALGORITHM_DUAL_KAWASE (1u << 5)  /**< Kawase on a downsampled image */
+#define BLUR_ALGORITHM_TEMPORAL    (1u << 6)  /**< blur_set_temporal_mode */
+
+/**
+ * @brief Get the effects this build of the library implements.
+ *
+ * Requesting an effect missing from the mask, through blur_set_effect_type or
+ * a pipeline, leaves the current effect in place. BLUR_ALGORITHM_TEMPORAL is
+ * not an effect but the temporal modes, which combine with any of them.
+ *
+ * @param sys System handle.
+ * @return Bitmask of BLUR_ALGORITHM_* values, 0 for an invalid handle.
+ */
+BLURWINDOW_API uint32_t blur_get_supported_algorithms(BlurSystemHandle sys);
+
+/**
+ * @brief Get the largest kernel radius this build accepts.
+ *
+ * blur_set_kernel_radius rejects larger radii and pipeline stages clamp their
+ * "kernelRadius" to it. Prefer it over BLUR_MAX_KERNEL_RADIUS when the
+ * library may be a different build than the header.
+ *
+ * @param sys System handle.
+ * @return Radius in pixels, 0 for an invalid handle.
+ */
+BLURWINDOW_API uint32_t blur_get_max_kernel_radius(BlurSystemHandle sys);
+
+// --- System Theme ---
+
+/// Windows app theme, see blur_get_system_theme.
+typedef enum {
+    BLUR_THEME_LIGHT = 0,
+    BLUR_THEME_DARK = 1
+} BlurSystemTheme;
+
+/**
+ * @brief Get the app theme chosen in Settings > Personalization > Colors.
+ * @return BLUR_THEME_LIGHT or BLUR_THEME_DARK (light on systems without dark mode).
+ * @note Doesn't need an initialized system.
+ */
+BLURWINDOW_API BlurSystemTheme blur_get_system_theme(void);
+
+/**
+ * @brief Callback type for theme switches.
+ * @param theme The new theme.
+ * @param userData User data passed to blur_set_theme_changed_callback.
+ */
+typedef void (*BlurThemeChangedCallback)(BlurSystemTheme theme, void* userData);
+
+/**
+ * @brief Set a callback fired when the user switches between light and dark.
+ * @param sys System handle.
+ * @param callback Function to call (NULL stops watching).
+ * @param userData User data passed to callback.
+ * @return BLUR_OK on success.
+ * @note The library watches WM_SETTINGCHANGE from a hidden window on its own
+ *       thread, so no blur window or message loop is needed; the callback
+ *       runs on that thread. Once this function returns a replaced callback
+ *       is never called again. Don't call it from the callback. blur_shutdown
+ *       stops watching.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_theme_changed_callback(
+    BlurSystemHandle sys,
+    BlurThemeChangedCallback callback,
+    void* userData
+);
+
+// --- Monitors ---
+
+/// One monitor, see blur_get_monitor_info.
+typedef struct {
+    BlurRect bounds;    ///< The whole monitor, in physical screen coordinates.
+    BlurRect workArea;  ///< The bounds minus the taskbar and docked app bars.
+    uint32_t dpiX;      ///< Effective DPI, 96 at 100% scaling.
+    uint32_t dpiY;
+    int32_t primary;    ///< 1 for the primary monitor.
+} BlurMonitorInfo;
+
+/**
+ * @brief Count the monitors attached to the desktop.
+ * @return Number of monitors.
+ * @note Doesn't need an initialized system. Monitors come and go, so the
+ *       count may be stale by the time blur_get_monitor_info is called.
+ */
+BLURWINDOW_API uint32_t blur_get_monitor_count(void);
+
+/**
+ * @brief Describe one monitor.
+ * @param index Position in the order Windows enumerates monitors, from 0.
+ *        Stable while the display setup doesn't change, but needn't match
+ *        the numbers in Display settings.
+ * @param out Receives the monitor's description.
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER for a NULL out or
+ *         an index past the last monitor.
+ * @note Doesn't need an initialized system.
+ */
+BLURWINDOW_API BlurErrorCode blur_get_monitor_info(uint32_t index, BlurMonitorInfo* out);
+
+// --- Adapters ---
+
+/// One graphics adapter, see blur_get_adapter_info.
+typedef struct {
+    uint32_t vendorId;              ///< PCI vendor ID, e.g. 0x10DE for NVIDIA.
+    uint32_t deviceId;              ///< PCI device ID.
+    uint64_t dedicatedVideoMemory;  ///< Dedicated video memory, in bytes.
+    uint64_t driverVersion;         ///< User-mode driver version as four 16-bit parts, most significant first; 0 if unknown.
+    int32_t software;               ///< 1 for a software adapter such as the Microsoft Basic Render Driver.
+    int32_t active;                 ///< 1 for the adapter the system renders on.
+} BlurAdapterInfo;
+
+/**
+ * @brief Describe one graphics adapter, e.g. for a bug report.
+ * @param sys System handle.
+ * @param index Position in the order DXGI enumerates adapters, from 0. The
+ *        adapter driving the primary monitor comes first.
+ * @param out Receives the adapter's description.
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER for a NULL out or
+ *         an index past the last adapter.
+ * @note active is only set once the system is initialized.
+ */
+BLURWINDOW_API BlurErrorCode blur_get_adapter_info(BlurSystemHandle sys, uint32_t index, BlurAdapterInfo* out);
+
+/**
+ * @brief Get a graphics adapter's name, e.g. "NVIDIA GeForce RTX 3060".
+ *
+ * Set *len to the capacity of buf in bytes. On return *len holds the size
+ * the name needs in UTF-8, including the terminating null, whether or not it
+ * fit. Pass a NULL buf (with any *len) to query the size first.
+ *
+ * @param sys System handle.
+ * @param index Adapter index, as for blur_get_adapter_info.
+ * @param buf Receives the null-terminated name; may be NULL.
+ * @param len In: capacity of buf. Out: size needed.
+ * @return BLUR_OK on success, BLUR_ERROR_INVALID_PARAMETER for a NULL len, an
+ *         index past the last adapter, or a NULL or too small buf, which is
+ *         then left untouched.
+ */
+BLURWINDOW_API BlurErrorCode blur_get_adapter_description(
+    BlurSystemHandle sys,
+    uint32_t index,
+    char* buf,
+    uint32_t* len
+);
+
+// --- Fullscreen Detection ---
+
+/**
+ * @brief Callback type for fullscreen changes.
+ * @param fullscreen 1 when a fullscreen app came to the foreground, 0 when it left.
+ * @param userData User data passed to blur_set_fullscreen_changed_callback.
+ */
+typedef void (*BlurFullscreenChangedCallback)(int32_t fullscreen, void* userData);
+
+/**
+ * @brief Set a callback fired when a fullscreen app of another process comes
+ *        to the foreground and when it leaves, e.g. to pause every blur
+ *        window while a game runs.
+ *
+ * An app counts as fullscreen when its foreground window covers a whole
+ * monitor, which includes exclusive and borderless fullscreen games as well
+ * as videos and presentations. The host process's own windows never count.
+ *
+ * @param sys System handle.
+ * @param callback Function to call (NULL stops watching).
+ * @param userData User data passed to callback.
+ * @return BLUR_OK on success.
+ * @note The foreground window is polled twice a second on a library thread,
+ *       which runs the callback; an app already fullscreen when watching
+ *       starts is reported right away. Once this function returns a replaced
+ *       callback is never called again. Don't call it from the callback.
+ *       blur_shutdown stops watching.
+ */
+BLURWINDOW_API BlurErrorCode blur_set_fullscreen_changed_callback(
+    BlurSystemHandle sys,
+    BlurFullscreenChangedCallback callback,
+    void* userData
+);
+
 /**
  * @brief Get the last error string.
  * @return Static error string pointer.
  */
 BLURWINDOW_API const char* blur_get_last_error(void);
 
+/**
+ * @brief Get the last error string and clear it, so each error is reported once.
+ * @return Error string pointer, valid until the next call to this function.
+ *         Empty string if no error occurred since the previous read.
+ */
+BLURWINDOW_API const char* blur_get_last_error_and_clear(void);
+
+/**
+ * @brief Get the error code that accompanied the last error string.
+ *
+ * Functions returning a handle report NULL on failure; this gives the reason
+ * (e.g. BLUR_ERROR_NOT_INITIALIZED for blur_create_window before blur_init).
+ * Read it before blur_get_last_error_and_clear, which resets it.
+ *
+ * @return A BlurErrorCode value, BLUR_OK if no error message is pending.
+ * @note Failures that set no message (such as a NULL handle) leave the
+ *       previous code in place.
+ */
+BLURWINDOW_API int32_t blur_get_last_error_code(void);
+
 /**
  * @brief Enable or disable detailed logging.
  * @param sys System handle.
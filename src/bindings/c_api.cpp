@@ -2,11 +2,54 @@
 #include "blurwindow/blurwindow.h"
 #include "blurwindow/blur_window.h"
 #include "../effects/RainEffect.h"
+#include "../core/SubsystemFactory.h"
+#include "../config/ConfigManager.h"
+#include <algorithm>
+#include <cmath>
 #include <string>
+#include <vector>
+#include <dwmapi.h>
+#include <dxgi.h>
+#include <shellscalingapi.h>
+#include <wrl/client.h>
+
+static_assert(BLUR_MAX_EFFECT_LAYERS == blurwindow::BlurWindow::MaxEffectLayers,
+    "BLUR_MAX_EFFECT_LAYERS must match BlurWindow::MaxEffectLayers");
+static_assert(BLUR_MAX_REGION_EFFECTS == blurwindow::BlurWindow::MaxRegionEffects,
+    "BLUR_MAX_REGION_EFFECTS must match BlurWindow::MaxRegionEffects");
+static_assert(BLUR_FRAME_HISTORY_SIZE == blurwindow::BlurWindow::FrameHistorySize,
+    "BLUR_FRAME_HISTORY_SIZE must match BlurWindow::FrameHistorySize");
+static_assert(BLUR_MAX_KERNEL_RADIUS == blurwindow::BlurWindow::MaxKernelRadius,
+    "BLUR_MAX_KERNEL_RADIUS must match BlurWindow::MaxKernelRadius");
+static_assert(sizeof(BlurFrameSample) == sizeof(blurwindow::BlurWindow::FrameSample),
+    "BlurFrameSample must match BlurWindow::FrameSample");
 
 using namespace blurwindow;
 
 static std::string g_lastError;
+static std::string g_reportedError;  // Backing storage for blur_get_last_error_and_clear
+static BlurErrorCode g_lastErrorCode = BLUR_OK;  // Returned (or implied) alongside g_lastError
+
+// Record a failure for blur_get_last_error and blur_get_last_error_code
+static BlurErrorCode ReportError(BlurErrorCode code, const char* message) {
+    g_lastErrorCode = code;
+    g_lastError = message;
+    return code;
+}
+
+// The system is a singleton, so there is a single theme callback to forward to
+static BlurThemeChangedCallback g_themeCallback = nullptr;
+
+static void ForwardThemeChanged(SystemTheme theme, void* userData) {
+    g_themeCallback(theme == SystemTheme::Dark ? BLUR_THEME_DARK : BLUR_THEME_LIGHT, userData);
+}
+
+// Same for the fullscreen callback
+static BlurFullscreenChangedCallback g_fullscreenCallback = nullptr;
+
+static void ForwardFullscreenChanged(bool fullscreen, void* userData) {
+    g_fullscreenCallback(fullscreen ? 1 : 0, userData);
+}
 
 extern "C" {
 
@@ -20,7 +63,7 @@ BLURWINDOW_API BlurSystemHandle blur_init(const BlurSystemOptionsC* opts) {
     }
 
     if (!BlurSystem::Instance().Initialize(options)) {
-        g_lastError = "Failed to initialize blur system";
+        ReportError(BLUR_ERROR_D3D11_FAILED, "Failed to initialize blur system");
         return nullptr;
     }
 
@@ -35,7 +78,7 @@ BLURWINDOW_API void blur_shutdown(BlurSystemHandle sys) {
 
 BLURWINDOW_API BlurWindowHandle blur_create_window(BlurSystemHandle sys, void* owner, const BlurWindowOptionsC* opts) {
     if (!sys || !opts) {
-        g_lastError = "Invalid parameters";
+        ReportError(BLUR_ERROR_INVALID_PARAMETER, "Invalid parameters");
         return nullptr;
     }
 
@@ -47,16 +90,49 @@ BLURWINDOW_API BlurWindowHandle blur_create_window(BlurSystemHandle sys, void* o
     options.bounds.bottom = opts->bounds.bottom;
     options.topMost = (opts->topMost != 0);
     options.clickThrough = (opts->clickThrough != 0);
+    options.manualRender = (opts->manualRender != 0);
+    options.child = (opts->child != 0);
+    options.startHidden = (opts->startHidden != 0);
+    options.wallpaper = (opts->wallpaper != 0);
+
+    if (options.child && !options.owner) {
+        ReportError(BLUR_ERROR_INVALID_PARAMETER, "Child windows require an owner");
+        return nullptr;
+    }
+    if (options.child && options.wallpaper) {
+        ReportError(BLUR_ERROR_INVALID_PARAMETER, "Wallpaper windows can't be child windows");
+        return nullptr;
+    }
+
+    if (!BlurSystem::Instance().IsInitialized()) {
+        ReportError(BLUR_ERROR_NOT_INITIALIZED, "Blur system is not initialized");
+        return nullptr;
+    }
 
     auto window = BlurSystem::Instance().CreateBlurWindow(options.owner, options);
     if (!window) {
-        g_lastError = "Failed to create blur window";
+        ReportError(BLUR_ERROR_UNKNOWN, "Failed to create blur window");
         return nullptr;
     }
 
     return reinterpret_cast<BlurWindowHandle>(window.release());
 }
 
+BLURWINDOW_API BlurWindowHandle blur_create_child_window(BlurSystemHandle sys, void* owner, const BlurRect* relativeBounds) {
+    if (!relativeBounds) {
+        ReportError(BLUR_ERROR_INVALID_PARAMETER, "Invalid parameters");
+        return nullptr;
+    }
+
+    BlurWindowOptionsC opts = {};
+    opts.owner = owner;
+    opts.bounds = *relativeBounds;
+    opts.topMost = 0;
+    opts.clickThrough = 1;
+    opts.child = 1;
+    return blur_create_window(sys, owner, &opts);
+}
+
 BLURWINDOW_API void blur_destroy_window(BlurWindowHandle window) {
     if (window) {
         delete reinterpret_cast<BlurWindow*>(window);
@@ -79,6 +155,33 @@ BLURWINDOW_API BlurErrorCode blur_stop(BlurWindowHandle window) {
     return BLUR_OK;
 }
 
+BLURWINDOW_API BlurErrorCode blur_render_once(BlurWindowHandle window) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    if (!w->IsManualRender()) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Window was not created in manual render mode");
+    }
+    if (!w->RenderOnce()) {
+        // RenderOnce sets graphics up on first use; tell that apart from a failed capture
+        if (!w->IsInitialized()) {
+            return ReportError(BLUR_ERROR_NOT_INITIALIZED, "Failed to initialize graphics");
+        }
+        return ReportError(BLUR_ERROR_CAPTURE_FAILED, "Failed to render frame");
+    }
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_prewarm(BlurWindowHandle window) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    if (!w->Prewarm()) {
+        return ReportError(BLUR_ERROR_D3D11_FAILED, "Failed to initialize graphics");
+    }
+    return BLUR_OK;
+}
+
 BLURWINDOW_API BlurErrorCode blur_set_preset(BlurWindowHandle window, BlurQualityPreset preset) {
     if (!window) return BLUR_ERROR_INVALID_HANDLE;
     
@@ -87,14 +190,66 @@ BLURWINDOW_API BlurErrorCode blur_set_preset(BlurWindowHandle window, BlurQualit
     return BLUR_OK;
 }
 
+BLURWINDOW_API BlurQualityPreset blur_get_active_preset(BlurWindowHandle window) {
+    if (!window) {
+        ReportError(BLUR_ERROR_INVALID_HANDLE, "Invalid window handle");
+        return BLUR_PRESET_BALANCED;
+    }
+
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    return static_cast<BlurQualityPreset>(w->GetPreset());
+}
+
+// The parser turns anything it can't read into a default Gaussian blur,
+// so catch the common mistakes before it runs
+static const char* CheckPipelineRoot(const std::string& json) {
+    size_t start = json.find_first_not_of(" \t\r\n");
+    if (start == std::string::npos) return "Pipeline JSON is empty";
+    if (json[start] != '{') return "Pipeline JSON must be an object";
+    return nullptr;
+}
+
 BLURWINDOW_API BlurErrorCode blur_set_pipeline(BlurWindowHandle window, const char* json_config) {
     if (!window) return BLUR_ERROR_INVALID_HANDLE;
     if (!json_config) return BLUR_ERROR_INVALID_PARAMETER;
-    
+
+    std::string json(json_config);
+    if (const char* problem = CheckPipelineRoot(json)) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, problem);
+    }
+
     auto* w = reinterpret_cast<BlurWindow*>(window);
-    if (!w->SetEffectPipeline(json_config)) {
-        g_lastError = "Failed to set effect pipeline";
-        return BLUR_ERROR_INVALID_PARAMETER;
+    if (!w->SetEffectPipeline(json)) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Failed to set effect pipeline");
+    }
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_validate_pipeline(BlurSystemHandle sys, const char* json_config) {
+    if (!sys) return BLUR_ERROR_INVALID_HANDLE;
+    if (!json_config) return BLUR_ERROR_INVALID_PARAMETER;
+
+    std::string json(json_config);
+    if (const char* problem = CheckPipelineRoot(json)) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, problem);
+    }
+
+    // The same parse SetEffectPipeline starts with; a legacy object has no
+    // stages and is always accepted
+    auto config = ConfigManager::ParsePipelineJson(json);
+    if (config.effects.size() > BlurWindow::MaxEffectLayers) {
+        std::string message = "Pipeline has " + std::to_string(config.effects.size()) +
+            " stages, at most " + std::to_string(BlurWindow::MaxEffectLayers) + " are supported";
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, message.c_str());
+    }
+    for (size_t i = 0; i < config.effects.size(); i++) {
+        const std::string& type = config.effects[i].type;
+        // Only allocates; GPU resources wait for Initialize
+        if (!ConfigManager::CreateEffect(type)) {
+            std::string message = "Pipeline stage " + std::to_string(i + 1) +
+                (type.empty() ? " has no type" : " has unknown type \"" + type + "\"");
+            return ReportError(BLUR_ERROR_INVALID_PARAMETER, message.c_str());
+        }
     }
     return BLUR_OK;
 }
@@ -105,8 +260,7 @@ BLURWINDOW_API BlurErrorCode blur_set_bounds(BlurWindowHandle window, const Blur
     
     // Validate bounds
     if (bounds->right <= bounds->left || bounds->bottom <= bounds->top) {
-        g_lastError = "Invalid bounds: width and height must be positive";
-        return BLUR_ERROR_INVALID_PARAMETER;
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Invalid bounds: width and height must be positive");
     }
     
     auto* w = reinterpret_cast<BlurWindow*>(window);
@@ -115,6 +269,94 @@ BLURWINDOW_API BlurErrorCode blur_set_bounds(BlurWindowHandle window, const Blur
     return BLUR_OK;
 }
 
+BLURWINDOW_API BlurErrorCode blur_resize_swapchain(BlurWindowHandle window, uint32_t width, uint32_t height) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    if (width == 0 || height == 0) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Invalid swapchain size: width and height must be positive");
+    }
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->ResizeSwapchain(width, height);
+    return BLUR_OK;
+}
+
+BLURWINDOW_API uint32_t blur_get_ex_style(BlurWindowHandle window) {
+    if (!window) return 0;
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    uint32_t exStyle = 0;
+    return w->GetExStyle(&exStyle) ? exStyle : 0;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_ex_style(BlurWindowHandle window, uint32_t exStyle) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    if (!w->SetExStyle(exStyle)) {
+        return ReportError(BLUR_ERROR_NOT_INITIALIZED, "Window has not been created yet");
+    }
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_taskbar_relation(BlurWindowHandle window, int32_t relation) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    if (relation < 0 || relation > 1) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Invalid taskbar relation");
+    }
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetTaskbarRelation(static_cast<TaskbarRelation>(relation));
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_active_region(BlurWindowHandle window, const BlurRect* region) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    if (!region) {
+        w->SetActiveRegion(nullptr);
+        return BLUR_OK;
+    }
+
+    RECT r = {region->left, region->top, region->right, region->bottom};
+    if (!w->SetActiveRegion(&r)) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Invalid active region: must be non-empty and inside the window");
+    }
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_region_effect(BlurWindowHandle window, const BlurRect* region, int32_t effect_type) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    if (!region) {
+        w->ClearRegionEffects();
+        return BLUR_OK;
+    }
+
+    RECT r = {region->left, region->top, region->right, region->bottom};
+    if (!w->AddRegionEffect(r, effect_type)) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER,
+            "Invalid region effect: needs a known type, a non-empty region inside the window and at most "
+            "BLUR_MAX_REGION_EFFECTS regions");
+    }
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_begin_update(BlurWindowHandle window) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->BeginUpdate();
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_end_update(BlurWindowHandle window) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    if (!w->EndUpdate()) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "blur_end_update called without a matching blur_begin_update");
+    }
+    return BLUR_OK;
+}
+
 BLURWINDOW_API BlurErrorCode blur_set_noise_intensity(BlurWindowHandle window, float intensity) {
     if (!window) return BLUR_ERROR_INVALID_HANDLE;
     auto* w = reinterpret_cast<BlurWindow*>(window);
@@ -136,6 +378,93 @@ BLURWINDOW_API BlurErrorCode blur_set_strength(BlurWindowHandle window, float st
     return BLUR_OK;
 }
 
+BLURWINDOW_API BlurErrorCode blur_set_strength_gradient(BlurWindowHandle window, float top, float bottom) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    if (!std::isfinite(top) || !std::isfinite(bottom)) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Strength gradient must be finite");
+    }
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetStrengthGradient(std::clamp(top, 0.0f, 1.0f), std::clamp(bottom, 0.0f, 1.0f));
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_blur_xy(BlurWindowHandle window, float x, float y) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    if (!std::isfinite(x) || !std::isfinite(y)) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Blur strengths must be finite");
+    }
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetBlurXY(x, y);
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_buffer_count(BlurWindowHandle window, uint32_t count) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    if (count != 2 && count != 3) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Buffer count must be 2 or 3");
+    }
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetBufferCount(count);
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_temporal_mode(BlurWindowHandle window, int32_t mode) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    if (mode < BLUR_TEMPORAL_OFF || mode > BLUR_TEMPORAL_MOTION_WEIGHTED) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Invalid temporal mode");
+    }
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetTemporalMode(static_cast<TemporalMode>(mode));
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_downsample_xy(BlurWindowHandle window, uint32_t x, uint32_t y) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    if (x == 0 || y == 0) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Downsample factors must be at least 1");
+    }
+    if (x > BLUR_MAX_DOWNSAMPLE || y > BLUR_MAX_DOWNSAMPLE) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Downsample factor exceeds BLUR_MAX_DOWNSAMPLE");
+    }
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetDownsampleXY(x, y);
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_edge_aa(BlurWindowHandle window, uint32_t samples) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    if (samples != 1 && samples != 2 && samples != 4 && samples != 8) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Edge AA samples must be 1, 2, 4 or 8");
+    }
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetEdgeAA(samples);
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_edge_feather(BlurWindowHandle window, float pixels) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    if (!std::isfinite(pixels)) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Edge feather must be finite");
+    }
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetEdgeFeather((std::max)(pixels, 0.0f));
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_custom_shader(BlurWindowHandle window, const char* hlsl) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    if (!BlurSystem::Instance().IsInitialized()) {
+        return ReportError(BLUR_ERROR_NOT_INITIALIZED, "Blur system is not initialized");
+    }
+
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    std::string errors;
+    if (!w->SetCustomShader(hlsl ? hlsl : "", &errors)) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, errors.c_str());
+    }
+    return BLUR_OK;
+}
+
 BLURWINDOW_API BlurErrorCode blur_set_blur_param(BlurWindowHandle window, float param) {
     if (!window) return BLUR_ERROR_INVALID_HANDLE;
     auto* w = reinterpret_cast<BlurWindow*>(window);
@@ -143,6 +472,29 @@ BLURWINDOW_API BlurErrorCode blur_set_blur_param(BlurWindowHandle window, float
     return BLUR_OK;
 }
 
+BLURWINDOW_API BlurErrorCode blur_set_kernel_radius(BlurWindowHandle window, uint32_t radius) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    if (radius > BLUR_MAX_KERNEL_RADIUS) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Kernel radius exceeds BLUR_MAX_KERNEL_RADIUS");
+    }
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetKernelRadius(static_cast<int>(radius));
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_direction(BlurWindowHandle window, float angleDegrees, float strength) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    if (!std::isfinite(angleDegrees) || !std::isfinite(strength)) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Direction angle and strength must be finite");
+    }
+
+    float angle = std::fmod(angleDegrees, 360.0f);
+    if (angle < 0.0f) angle += 360.0f;
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetDirection(angle, std::clamp(strength, 0.0f, 1.0f));
+    return BLUR_OK;
+}
+
 BLURWINDOW_API BlurErrorCode blur_set_tint_color(BlurWindowHandle window, float r, float g, float b, float a) {
     if (!window) return BLUR_ERROR_INVALID_HANDLE;
     auto* w = reinterpret_cast<BlurWindow*>(window);
@@ -150,6 +502,105 @@ BLURWINDOW_API BlurErrorCode blur_set_tint_color(BlurWindowHandle window, float
     return BLUR_OK;
 }
 
+BLURWINDOW_API BlurErrorCode blur_set_tint_layers(BlurWindowHandle window, const BlurColor* layers, uint32_t count) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    if (count > BLUR_MAX_TINT_LAYERS || (count > 0 && !layers)) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Invalid tint layer count");
+    }
+
+    float rgba[BLUR_MAX_TINT_LAYERS * 4];
+    for (uint32_t i = 0; i < count; i++) {
+        const float channels[4] = { layers[i].r, layers[i].g, layers[i].b, layers[i].a };
+        for (int c = 0; c < 4; c++) {
+            // Written as a negated range check so NaN is rejected too
+            if (!(channels[c] >= 0.0f && channels[c] <= 1.0f)) {
+                return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Tint layer channel out of range");
+            }
+            rgba[i * 4 + c] = channels[c];
+        }
+    }
+
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetTintLayers(rgba, count);
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_fallback_color(BlurWindowHandle window, const BlurColor* color) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    if (!color) {
+        w->SetFallbackColor(0.0f, 0.0f, 0.0f, 0.0f);
+        return BLUR_OK;
+    }
+
+    const float channels[4] = { color->r, color->g, color->b, color->a };
+    for (float c : channels) {
+        if (!(c >= 0.0f && c <= 1.0f)) {
+            return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Fallback color channel out of range");
+        }
+    }
+    w->SetFallbackColor(color->r, color->g, color->b, color->a);
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_tone_mapping(BlurWindowHandle window, int32_t mode) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    if (mode < BLUR_TONE_MAPPING_NONE || mode > BLUR_TONE_MAPPING_ACES) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Unknown tone mapping mode");
+    }
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetToneMapping(mode);
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_capture_source(BlurWindowHandle window, void* source) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    HWND hwnd = static_cast<HWND>(source);
+    if (hwnd && (!IsWindow(hwnd) || hwnd == w->GetHWND())) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Capture source is not a window or is the blur window itself");
+    }
+    if (!w->SetCaptureSource(hwnd)) {
+        return ReportError(BLUR_ERROR_CAPTURE_FAILED, "Failed to start capture of the source window");
+    }
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_source_texture(BlurWindowHandle window, void* sharedHandle) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    if (!w->SetSourceTexture(static_cast<HANDLE>(sharedHandle))) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Shared texture could not be opened or is not a BGRA8 shader resource");
+    }
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_background_image(BlurWindowHandle window, const uint8_t* rgba, uint32_t width, uint32_t height) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    if (!w->SetBackgroundImage(rgba, width, height)) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Background image size out of range or texture creation failed");
+    }
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_sample_dominant_color(BlurWindowHandle window, BlurColor* outColor) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    if (!outColor) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "outColor is NULL");
+    }
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    float rgba[4];
+    if (!w->SampleDominantColor(rgba)) {
+        return ReportError(BLUR_ERROR_NOT_INITIALIZED, "No captured frame to sample yet");
+    }
+    outColor->r = rgba[0];
+    outColor->g = rgba[1];
+    outColor->b = rgba[2];
+    outColor->a = rgba[3];
+    return BLUR_OK;
+}
+
 BLURWINDOW_API BlurErrorCode blur_set_noise_scale(BlurWindowHandle window, float scale) {
     if (!window) return BLUR_ERROR_INVALID_HANDLE;
     auto* w = reinterpret_cast<BlurWindow*>(window);
@@ -171,12 +622,51 @@ BLURWINDOW_API BlurErrorCode blur_set_noise_type(BlurWindowHandle window, int32_
     return BLUR_OK;
 }
 
+BLURWINDOW_API BlurErrorCode blur_set_noise_blend_mode(BlurWindowHandle window, int32_t mode) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    if (mode < 0 || mode > 2) return BLUR_ERROR_INVALID_PARAMETER;
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetNoiseBlendMode(mode);
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_noise_color(BlurWindowHandle window, const BlurColor* color) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    if (!color) {
+        w->SetNoiseColor(1.0f, 1.0f, 1.0f);
+        return BLUR_OK;
+    }
+
+    const float channels[4] = { color->r, color->g, color->b, color->a };
+    for (float c : channels) {
+        if (!(c >= 0.0f && c <= 1.0f)) {
+            return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Noise color channel out of range");
+        }
+    }
+    // Alpha fades from monochrome (white) grain to the full color
+    const float a = color->a;
+    w->SetNoiseColor(1.0f + (color->r - 1.0f) * a, 1.0f + (color->g - 1.0f) * a, 1.0f + (color->b - 1.0f) * a);
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_noise_seed(BlurWindowHandle window, uint32_t seed) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetNoiseSeed(seed);
+    return BLUR_OK;
+}
+
 BLURWINDOW_API void* blur_get_hwnd(BlurWindowHandle window) {
     if (!window) return nullptr;
     auto* w = reinterpret_cast<BlurWindow*>(window);
     return w->GetHWND();
 }
 
+BLURWINDOW_API int32_t blur_is_blur_window(void* hwnd) {
+    return BlurWindow::IsBlurWindow(static_cast<HWND>(hwnd)) ? 1 : 0;
+}
+
 BLURWINDOW_API float blur_get_fps(BlurWindowHandle window) {
     if (!window) return -1.0f;
     
@@ -184,10 +674,446 @@ BLURWINDOW_API float blur_get_fps(BlurWindowHandle window) {
     return w->GetCurrentFPS();
 }
 
+BLURWINDOW_API BlurErrorCode blur_set_cursor_follow(BlurWindowHandle window, int32_t enabled, float radius) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    if (!w->SetCursorFollow(enabled != 0, radius)) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Cursor follow radius must be positive");
+    }
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_time_mode(BlurWindowHandle window, int32_t mode) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    if (mode < 0 || mode > 1) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Invalid time mode");
+    }
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetTimeMode(mode == 1);
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_time(BlurWindowHandle window, float seconds) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetTime(seconds);
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_target_fps(BlurWindowHandle window, int32_t fps) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    if (fps < 0 || fps > BLUR_MAX_TARGET_FPS) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Target FPS out of range");
+    }
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetTargetFPS(fps);
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_sync_to_owner_paint(BlurWindowHandle window, int32_t enable) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    if (!w->SetSyncToOwnerPaint(enable != 0)) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Owner paint sync needs an owner window of the calling thread");
+    }
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_capture_interval_ms(BlurWindowHandle window, uint32_t intervalMs) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    if (intervalMs > BLUR_MAX_CAPTURE_INTERVAL_MS) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Capture interval out of range");
+    }
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetCaptureInterval(intervalMs);
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_min_frame_interval_ms(BlurWindowHandle window, uint32_t intervalMs) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    if (intervalMs > BLUR_MAX_MIN_FRAME_INTERVAL_MS) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Minimum frame interval out of range");
+    }
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetMinFrameInterval(intervalMs);
+    return BLUR_OK;
+}
+
+BLURWINDOW_API float blur_get_display_refresh_hz(BlurWindowHandle window) {
+    if (!window) return -1.0f;
+
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    return w->GetDisplayRefreshHz();
+}
+
+BLURWINDOW_API float blur_get_frame_time(BlurWindowHandle window) {
+    if (!window) return -1.0f;
+
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    return w->GetFrameTimeMs();
+}
+
+BLURWINDOW_API float blur_get_latency_ms(BlurWindowHandle window) {
+    if (!window) return -1.0f;
+
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    return w->GetLatencyMs();
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_debug_overlay(BlurWindowHandle window, int32_t enabled) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetDebugOverlay(enabled != 0);
+    return BLUR_OK;
+}
+
+BLURWINDOW_API int32_t blur_get_backend(BlurWindowHandle window) {
+    if (!window) return -1;
+
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    return w->GetBackend();
+}
+
+BLURWINDOW_API int32_t blur_get_capture_status(BlurWindowHandle window) {
+    if (!window) return -1;
+
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    return w->GetCaptureStatus();
+}
+
+BLURWINDOW_API int32_t blur_get_capture_method(BlurWindowHandle window) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    return w->GetCaptureMethod();
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_preferred_capture_method(BlurSystemHandle sys, int32_t method) {
+    if (!sys) return BLUR_ERROR_INVALID_HANDLE;
+    if (method < BLUR_CAPTURE_PREFER_AUTO || method > BLUR_CAPTURE_PREFER_WINDOWS_GRAPHICS_CAPTURE) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Unknown capture method");
+    }
+
+    BlurSystem::Instance().SetPreferredCaptureMethod(method);
+    return BLUR_OK;
+}
+
+BLURWINDOW_API int32_t blur_get_capture_format(BlurWindowHandle window) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    int format = w->GetCaptureFormat();
+    if (format < 0) return ReportError(BLUR_ERROR_CAPTURE_FAILED, "No frame has been captured yet");
+    return format;
+}
+
+BLURWINDOW_API BlurErrorCode blur_get_capture_rect(BlurWindowHandle window, BlurRect* outRect) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    if (!outRect) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "outRect is NULL");
+    }
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    RECT rect;
+    if (!w->GetCaptureRect(&rect)) {
+        return ReportError(BLUR_ERROR_CAPTURE_FAILED, "No screen region has been captured");
+    }
+    outRect->left = rect.left;
+    outRect->top = rect.top;
+    outRect->right = rect.right;
+    outRect->bottom = rect.bottom;
+    return BLUR_OK;
+}
+
+BLURWINDOW_API uint64_t blur_get_vram_usage(BlurWindowHandle window) {
+    if (!window) return 0;
+
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    return w->GetVideoMemoryUsage();
+}
+
+BLURWINDOW_API uint32_t blur_get_frame_history(BlurWindowHandle window, BlurFrameSample* out, uint32_t capacity) {
+    if (!window) return 0;
+
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    return w->GetFrameHistory(reinterpret_cast<BlurWindow::FrameSample*>(out), capacity);
+}
+
+BLURWINDOW_API BlurErrorCode blur_pause(BlurWindowHandle window) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetPaused(true);
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_resume(BlurWindowHandle window) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetPaused(false);
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_pause_when_occluded(BlurWindowHandle window, int32_t enable) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetPauseWhenOccluded(enable != 0);
+    return BLUR_OK;
+}
+
+BLURWINDOW_API int32_t blur_is_occluded(BlurWindowHandle window) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    return w->IsOccluded() ? 1 : 0;
+}
+
+BLURWINDOW_API BlurErrorCode blur_freeze(BlurWindowHandle window) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetFrozen(true);
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_unfreeze(BlurWindowHandle window) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetFrozen(false);
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_show(BlurWindowHandle window) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetVisible(true);
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_hide(BlurWindowHandle window) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetVisible(false);
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_command(BlurWindowHandle window, const char* command, const char* args) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    if (!command) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "command is NULL");
+    }
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    std::string error;
+    if (!w->RunCommand(command, args ? args : "", error)) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, error.c_str());
+    }
+    return BLUR_OK;
+}
+
+BLURWINDOW_API int32_t blur_is_debug_build(void) {
+#ifdef _DEBUG
+    return 1;
+#else
+    return 0;
+#endif
+}
+
+BLURWINDOW_API int32_t blur_is_dwm_enabled(void) {
+    BOOL enabled = FALSE;
+    return SUCCEEDED(DwmIsCompositionEnabled(&enabled)) && enabled ? 1 : 0;
+}
+
+BLURWINDOW_API uint32_t blur_get_supported_algorithms(BlurSystemHandle sys) {
+    if (!sys) return 0;
+
+    struct { EffectType type; uint32_t bit; } effects[] = {
+        { EffectType::Gaussian, BLUR_ALGORITHM_GAUSSIAN },
+        { EffectType::Kawase,   BLUR_ALGORITHM_KAWASE },
+        { EffectType::Box,      BLUR_ALGORITHM_BOX },
+        { EffectType::Radial,   BLUR_ALGORITHM_RADIAL },
+        { EffectType::Rain,     BLUR_ALGORITHM_RAIN },
+    };
+
+    // Creating an effect only allocates it; GPU resources wait for Initialize
+    uint32_t mask = 0;
+    for (const auto& effect : effects) {
+        if (SubsystemFactory::CreateEffect(effect.type)) mask |= effect.bit;
+    }
+    // BLUR_ALGORITHM_DUAL_KAWASE stays clear: blur stages always run at full resolution
+    mask |= BLUR_ALGORITHM_TEMPORAL;
+    return mask;
+}
+
+BLURWINDOW_API uint32_t blur_get_max_kernel_radius(BlurSystemHandle sys) {
+    if (!sys) return 0;
+    return static_cast<uint32_t>(BlurWindow::MaxKernelRadius);
+}
+
+BLURWINDOW_API BlurSystemTheme blur_get_system_theme(void) {
+    return BlurSystem::GetSystemTheme() == SystemTheme::Dark ? BLUR_THEME_DARK : BLUR_THEME_LIGHT;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_theme_changed_callback(
+    BlurSystemHandle sys,
+    BlurThemeChangedCallback callback,
+    void* userData
+) {
+    if (!sys) return BLUR_ERROR_INVALID_HANDLE;
+    // Stopped first so the watcher thread never reads g_themeCallback while it changes
+    BlurSystem::Instance().SetThemeChangedCallback(nullptr, nullptr);
+    g_themeCallback = callback;
+    if (callback) {
+        BlurSystem::Instance().SetThemeChangedCallback(ForwardThemeChanged, userData);
+    }
+    return BLUR_OK;
+}
+
+static std::vector<HMONITOR> EnumMonitors() {
+    std::vector<HMONITOR> monitors;
+    EnumDisplayMonitors(nullptr, nullptr, [](HMONITOR monitor, HDC, LPRECT, LPARAM data) -> BOOL {
+        reinterpret_cast<std::vector<HMONITOR>*>(data)->push_back(monitor);
+        return TRUE;
+    }, reinterpret_cast<LPARAM>(&monitors));
+    return monitors;
+}
+
+static BlurRect ToBlurRect(const RECT& r) {
+    return { r.left, r.top, r.right, r.bottom };
+}
+
+BLURWINDOW_API uint32_t blur_get_monitor_count(void) {
+    return static_cast<uint32_t>(EnumMonitors().size());
+}
+
+BLURWINDOW_API BlurErrorCode blur_get_monitor_info(uint32_t index, BlurMonitorInfo* out) {
+    if (!out) return BLUR_ERROR_INVALID_PARAMETER;
+
+    auto monitors = EnumMonitors();
+    if (index >= monitors.size()) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Monitor index out of range");
+    }
+    MONITORINFO info = {};
+    info.cbSize = sizeof(info);
+    if (!GetMonitorInfoW(monitors[index], &info)) {
+        // Unplugged since it was enumerated
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Monitor index out of range");
+    }
+    UINT dpiX = 96, dpiY = 96;
+    if (FAILED(GetDpiForMonitor(monitors[index], MDT_EFFECTIVE_DPI, &dpiX, &dpiY))) {
+        dpiX = dpiY = 96;
+    }
+
+    out->bounds = ToBlurRect(info.rcMonitor);
+    out->workArea = ToBlurRect(info.rcWork);
+    out->dpiX = dpiX;
+    out->dpiY = dpiY;
+    out->primary = (info.dwFlags & MONITORINFOF_PRIMARY) ? 1 : 0;
+    return BLUR_OK;
+}
+
+// The index-th adapter in DXGI order, or null past the last one
+static Microsoft::WRL::ComPtr<IDXGIAdapter1> GetAdapter(uint32_t index) {
+    Microsoft::WRL::ComPtr<IDXGIFactory1> factory;
+    Microsoft::WRL::ComPtr<IDXGIAdapter1> adapter;
+    if (SUCCEEDED(CreateDXGIFactory1(IID_PPV_ARGS(factory.GetAddressOf())))) {
+        factory->EnumAdapters1(index, adapter.GetAddressOf());
+    }
+    return adapter;
+}
+
+// Whether desc is the adapter the system's device was created on
+static bool IsActiveAdapter(const DXGI_ADAPTER_DESC1& desc) {
+    Microsoft::WRL::ComPtr<IDXGIDevice> dxgiDevice;
+    Microsoft::WRL::ComPtr<IDXGIAdapter> adapter;
+    DXGI_ADAPTER_DESC active = {};
+    ID3D11Device* device = BlurSystem::Instance().GetDevice();
+    return device
+        && SUCCEEDED(device->QueryInterface(IID_PPV_ARGS(dxgiDevice.GetAddressOf())))
+        && SUCCEEDED(dxgiDevice->GetAdapter(adapter.GetAddressOf()))
+        && SUCCEEDED(adapter->GetDesc(&active))
+        && active.AdapterLuid.LowPart == desc.AdapterLuid.LowPart
+        && active.AdapterLuid.HighPart == desc.AdapterLuid.HighPart;
+}
+
+BLURWINDOW_API BlurErrorCode blur_get_adapter_info(BlurSystemHandle sys, uint32_t index, BlurAdapterInfo* out) {
+    if (!sys) return BLUR_ERROR_INVALID_HANDLE;
+    if (!out) return BLUR_ERROR_INVALID_PARAMETER;
+
+    auto adapter = GetAdapter(index);
+    DXGI_ADAPTER_DESC1 desc = {};
+    if (!adapter || FAILED(adapter->GetDesc1(&desc))) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Adapter index out of range");
+    }
+    // Only answered for IDXGIDevice, with the user-mode driver's version
+    LARGE_INTEGER driverVersion = {};
+    if (FAILED(adapter->CheckInterfaceSupport(__uuidof(IDXGIDevice), &driverVersion))) {
+        driverVersion.QuadPart = 0;
+    }
+
+    out->vendorId = desc.VendorId;
+    out->deviceId = desc.DeviceId;
+    out->dedicatedVideoMemory = desc.DedicatedVideoMemory;
+    out->driverVersion = static_cast<uint64_t>(driverVersion.QuadPart);
+    out->software = (desc.Flags & DXGI_ADAPTER_FLAG_SOFTWARE) ? 1 : 0;
+    out->active = IsActiveAdapter(desc) ? 1 : 0;
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_get_adapter_description(
+    BlurSystemHandle sys,
+    uint32_t index,
+    char* buf,
+    uint32_t* len
+) {
+    if (!sys) return BLUR_ERROR_INVALID_HANDLE;
+    if (!len) return BLUR_ERROR_INVALID_PARAMETER;
+
+    auto adapter = GetAdapter(index);
+    DXGI_ADAPTER_DESC1 desc = {};
+    if (!adapter || FAILED(adapter->GetDesc1(&desc))) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Adapter index out of range");
+    }
+    // Size includes the terminating null
+    int needed = WideCharToMultiByte(CP_UTF8, 0, desc.Description, -1, nullptr, 0, nullptr, nullptr);
+    if (needed <= 0) {
+        return ReportError(BLUR_ERROR_UNKNOWN, "Failed to convert the adapter description");
+    }
+    uint32_t capacity = *len;
+    *len = static_cast<uint32_t>(needed);
+    if (!buf || capacity < *len) {
+        return ReportError(BLUR_ERROR_INVALID_PARAMETER, "Buffer too small for the adapter description");
+    }
+    WideCharToMultiByte(CP_UTF8, 0, desc.Description, -1, buf, needed, nullptr, nullptr);
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_fullscreen_changed_callback(
+    BlurSystemHandle sys,
+    BlurFullscreenChangedCallback callback,
+    void* userData
+) {
+    if (!sys) return BLUR_ERROR_INVALID_HANDLE;
+    // Stopped first so the watcher thread never reads g_fullscreenCallback while it changes
+    BlurSystem::Instance().SetFullscreenChangedCallback(nullptr, nullptr);
+    g_fullscreenCallback = callback;
+    if (callback) {
+        BlurSystem::Instance().SetFullscreenChangedCallback(ForwardFullscreenChanged, userData);
+    }
+    return BLUR_OK;
+}
+
 BLURWINDOW_API const char* blur_get_last_error(void) {
     return g_lastError.c_str();
 }
 
+BLURWINDOW_API const char* blur_get_last_error_and_clear(void) {
+    g_reportedError.swap(g_lastError);
+    g_lastError.clear();
+    g_lastErrorCode = BLUR_OK;
+    return g_reportedError.c_str();
+}
+
+BLURWINDOW_API int32_t blur_get_last_error_code(void) {
+    return g_lastErrorCode;
+}
+
 BLURWINDOW_API void blur_enable_logging(BlurSystemHandle sys, int32_t enable, const char* path) {
     // TODO: Implement logging control
     (void)sys;
@@ -208,6 +1134,61 @@ BLURWINDOW_API BlurErrorCode blur_set_click_callback(
     return BLUR_OK;
 }
 
+BLURWINDOW_API BlurErrorCode blur_set_frame_callback(
+    BlurWindowHandle window,
+    BlurFrameCallback callback,
+    void* userData
+) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetFrameCallback(callback, userData);
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_owner_closed_callback(
+    BlurWindowHandle window,
+    BlurOwnerClosedCallback callback,
+    void* userData
+) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetOwnerClosedCallback(callback, userData);
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_display_changed_callback(
+    BlurWindowHandle window,
+    BlurDisplayChangedCallback callback,
+    void* userData
+) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetDisplayChangedCallback(callback, userData);
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_power_changed_callback(
+    BlurWindowHandle window,
+    BlurPowerChangedCallback callback,
+    void* userData
+) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetPowerChangedCallback(callback, userData);
+    return BLUR_OK;
+}
+
+BLURWINDOW_API BlurErrorCode blur_set_occlusion_changed_callback(
+    BlurWindowHandle window,
+    BlurOcclusionChangedCallback callback,
+    void* userData
+) {
+    if (!window) return BLUR_ERROR_INVALID_HANDLE;
+    auto* w = reinterpret_cast<BlurWindow*>(window);
+    w->SetOcclusionChangedCallback(callback, userData);
+    return BLUR_OK;
+}
+
 // --- Rain Effect Control ---
 
 BLURWINDOW_API BlurErrorCode blur_set_rain_intensity(BlurWindowHandle window, float intensity) {
@@ -1,5 +1,6 @@
 #include "ICaptureSubsystem.h"
 #include "../core/Logger.h"
+#include "../core/GpuMemory.h"
 #include <dxgi1_2.h>
 #include <d3d11.h>
 #include <wrl/client.h>
@@ -39,6 +40,7 @@ public:
         HRESULT hr = m_device->QueryInterface(IID_PPV_ARGS(dxgiDevice.GetAddressOf()));
         if (FAILED(hr)) {
             LOG_ERROR("Failed to query IDXGIDevice from D3D11 device (0x%08X).", hr);
+            m_status = CaptureStatus::Failed;
             return false;
         }
 
@@ -46,6 +48,7 @@ public:
         hr = dxgiDevice->GetAdapter(m_adapter.GetAddressOf());
         if (FAILED(hr)) {
             LOG_ERROR("Failed to get adapter from DXGI device (0x%08X).", hr);
+            m_status = CaptureStatus::Failed;
             return false;
         }
 
@@ -59,6 +62,7 @@ public:
         }
 
         LOG_ERROR("No monitors found to capture.");
+        m_status = CaptureStatus::Failed;
         return false;
     }
 
@@ -105,15 +109,19 @@ public:
             LOG_WARN("DXGI Desktop Duplication access lost. Reinitializing...");
             m_initialized = false;
             InitializeDuplicationForMonitor(m_currentMonitorIndex);
+            // Stays DeviceLost until the next frame arrives, even if reinitialized
+            if (m_status == CaptureStatus::Ok) m_status = CaptureStatus::DeviceLost;
             return false;
         }
 
         if (FAILED(hr)) {
             LOG_ERROR("AcquireNextFrame failed (0x%08X).", hr);
+            m_status = CaptureStatusFromHResult(hr);
             return false;
         }
 
         m_frameAcquired = true;
+        m_status = frameInfo.ProtectedContentMaskedOut ? CaptureStatus::ProtectedContent : CaptureStatus::Ok;
 
         // Get texture from resource
         ComPtr<ID3D11Texture2D> desktopTexture;
@@ -168,6 +176,12 @@ public:
         srcBox.right = (std::min)(m_outputWidth, (UINT)srcBox.right);
         srcBox.bottom = (std::min)(m_outputHeight, (UINT)srcBox.bottom);
 
+        m_lastCaptureRect = {
+            monInfo.bounds.left + static_cast<LONG>(srcBox.left), monInfo.bounds.top + static_cast<LONG>(srcBox.top),
+            monInfo.bounds.left + static_cast<LONG>(srcBox.right), monInfo.bounds.top + static_cast<LONG>(srcBox.bottom)
+        };
+        m_hasLastCaptureRect = true;
+
         // Copy region from desktop texture
         m_context->CopySubresourceRegion(
             m_cachedTexture.Get(), 0,
@@ -187,6 +201,7 @@ public:
         }
         
         m_cachedTexture.Reset();
+        m_hasLastCaptureRect = false;
         m_duplication.Reset();
         m_context.Reset();
         m_adapter.Reset();
@@ -199,6 +214,25 @@ public:
         m_selfHwnd = hwnd;
     }
 
+    // Desktop duplication only captures the desktop
+    bool SetSourceWindow(HWND source) override {
+        return source == nullptr;
+    }
+
+    CaptureStatus GetStatus() const override {
+        return m_status;
+    }
+
+    bool GetLastCaptureRect(RECT* out) const override {
+        if (!m_hasLastCaptureRect) return false;
+        *out = m_lastCaptureRect;
+        return true;
+    }
+
+    uint64_t GetVideoMemoryUsage() const override {
+        return TextureBytes(m_cachedTexture.Get());
+    }
+
 private:
     void EnumerateMonitors() {
         m_monitors.clear();
@@ -262,6 +296,8 @@ private:
         // Create desktop duplication
         hr = output1->DuplicateOutput(m_device, m_duplication.GetAddressOf());
         if (FAILED(hr)) {
+            LOG_ERROR("DuplicateOutput failed for monitor %d (0x%08X).", monitorIndex, hr);
+            m_status = CaptureStatusFromHResult(hr);
             return false;
         }
 
@@ -273,6 +309,7 @@ private:
 
         m_currentMonitorIndex = monitorIndex;
         m_initialized = true;
+        m_status = CaptureStatus::Ok;
         return true;
     }
 
@@ -302,10 +339,13 @@ private:
 
     bool m_initialized = false;
     bool m_frameAcquired = false;
+    CaptureStatus m_status = CaptureStatus::Ok;
     UINT m_outputWidth = 0;
     UINT m_outputHeight = 0;
     int m_cachedWidth = 0;
     int m_cachedHeight = 0;
+    RECT m_lastCaptureRect = {};
+    bool m_hasLastCaptureRect = false;
 
     HWND m_selfHwnd = nullptr;
 };
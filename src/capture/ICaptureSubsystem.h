@@ -2,11 +2,61 @@
 
 #include <d3d11.h>
 #include <wrl/client.h>
+#include <cstdint>
 
 namespace blurwindow {
 
 using Microsoft::WRL::ComPtr;
 
+/// Why capture is failing (or degraded), for reporting to the user
+enum class CaptureStatus {
+    Ok,                 // Capturing normally
+    BlockedByPolicy,    // Access denied: disabled by policy, or the secure desktop is shown
+    ProtectedContent,   // Capturing, but DRM-protected content is blacked out
+    DeviceLost,         // The GPU was reset or removed, or the display mode changed
+    Failed              // Any other failure
+};
+
+/// Map a failed capture HRESULT to a status
+inline CaptureStatus CaptureStatusFromHResult(HRESULT hr) {
+    switch (hr) {
+        case E_ACCESSDENIED:
+            return CaptureStatus::BlockedByPolicy;
+        case DXGI_ERROR_ACCESS_LOST:
+        case DXGI_ERROR_DEVICE_REMOVED:
+        case DXGI_ERROR_DEVICE_RESET:
+        case DXGI_ERROR_SESSION_DISCONNECTED:
+            return CaptureStatus::DeviceLost;
+        default:
+            return CaptureStatus::Failed;
+    }
+}
+
+/// Pixel format of the texture handed to the effects, see blur_get_capture_format
+enum class CaptureFormat {
+    Unknown = -1,     // Nothing captured yet
+    Srgb8 = 0,        // 8-bit BGRA/RGBA, sRGB-encoded SDR
+    Rgba16Float = 1,  // FP16 scRGB: linear, BT.709 primaries, 1.0 = SDR white (80 nits)
+    Rgb10A2 = 2       // 10-bit HDR10: PQ-encoded, BT.2020 primaries
+};
+
+/// Map a backdrop texture format to a capture format
+inline CaptureFormat CaptureFormatFromDxgi(DXGI_FORMAT format) {
+    switch (format) {
+        case DXGI_FORMAT_B8G8R8A8_UNORM:
+        case DXGI_FORMAT_B8G8R8A8_UNORM_SRGB:
+        case DXGI_FORMAT_R8G8B8A8_UNORM:
+        case DXGI_FORMAT_R8G8B8A8_UNORM_SRGB:
+            return CaptureFormat::Srgb8;
+        case DXGI_FORMAT_R16G16B16A16_FLOAT:
+            return CaptureFormat::Rgba16Float;
+        case DXGI_FORMAT_R10G10B10A2_UNORM:
+            return CaptureFormat::Rgb10A2;
+        default:
+            return CaptureFormat::Unknown;
+    }
+}
+
 /// Abstract interface for capture subsystems
 class ICaptureSubsystem {
 public:
@@ -29,6 +79,24 @@ public:
     /// Set self window handle for self-capture avoidance
     /// @param hwnd Self window handle
     virtual void SetSelfWindow(HWND hwnd) = 0;
+
+    /// Capture a specific top-level window instead of the desktop
+    /// @param source Window to capture, or nullptr for the desktop
+    /// @return false if this subsystem cannot capture the given source
+    virtual bool SetSourceWindow(HWND source) = 0;
+
+    /// Status of the most recent Initialize, SetSourceWindow or CaptureFrame
+    /// call (a frame with nothing new keeps the previous status)
+    virtual CaptureStatus GetStatus() const = 0;
+
+    /// Physical screen rect the last captured frame was copied from, after
+    /// clipping to its monitor. False before the first frame and for window
+    /// capture, which has no screen region.
+    virtual bool GetLastCaptureRect(RECT* out) const = 0;
+
+    /// Approximate GPU memory held by capture buffers owned by this subsystem, in bytes
+    /// (the duplicated desktop surface belongs to the OS and is not counted)
+    virtual uint64_t GetVideoMemoryUsage() const = 0;
 };
 
 } // namespace blurwindow
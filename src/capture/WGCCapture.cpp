@@ -0,0 +1,262 @@
+#include "ICaptureSubsystem.h"
+#include "../core/Logger.h"
+#include "../core/GpuMemory.h"
+#include "../core/ShaderLoader.h"
+#include "../core/FullscreenRenderer.h"
+#include <d3d11.h>
+#include <dxgi.h>
+#include <wrl/client.h>
+#include <memory>
+#include <cstring>
+
+#include <winrt/Windows.Foundation.h>
+#include <winrt/Windows.Graphics.Capture.h>
+#include <winrt/Windows.Graphics.DirectX.Direct3D11.h>
+#include <windows.graphics.capture.interop.h>
+#include <windows.graphics.directx.direct3d11.interop.h>
+
+using Microsoft::WRL::ComPtr;
+
+namespace blurwindow {
+
+namespace wgc = winrt::Windows::Graphics::Capture;
+namespace d3d = winrt::Windows::Graphics::DirectX::Direct3D11;
+
+// Scales the source window's frame to the blur window's size
+static const char* g_ScalePS = R"(
+Texture2D inputTexture : register(t0);
+SamplerState linearSampler : register(s0);
+
+float4 main(float4 position : SV_Position, float2 texcoord : TEXCOORD0) : SV_Target {
+    float4 color = inputTexture.Sample(linearSampler, texcoord);
+    color.a = 1.0f;
+    return color;
+}
+)";
+
+/// Windows.Graphics.Capture of a single top-level window. The captured window
+/// is stretched over the whole blur window; the capture region only sets the
+/// output size.
+class WGCCapture : public ICaptureSubsystem {
+public:
+    WGCCapture() = default;
+    ~WGCCapture() override { Shutdown(); }
+
+    bool Initialize(ID3D11Device* device) override {
+        if (!device) return false;
+
+        // WinRT activation needs COM on this thread; an existing STA is fine too
+        try {
+            winrt::init_apartment(winrt::apartment_type::multi_threaded);
+        } catch (const winrt::hresult_error&) {
+        }
+
+        if (!wgc::GraphicsCaptureSession::IsSupported()) {
+            LOG_ERROR("Windows.Graphics.Capture is not supported on this system.");
+            m_status = CaptureStatus::Failed;
+            return false;
+        }
+
+        m_device = device;
+        m_device->GetImmediateContext(m_context.GetAddressOf());
+
+        ComPtr<IDXGIDevice> dxgiDevice;
+        HRESULT hr = m_device->QueryInterface(IID_PPV_ARGS(dxgiDevice.GetAddressOf()));
+        if (FAILED(hr)) {
+            LOG_ERROR("Failed to query IDXGIDevice from D3D11 device (0x%08X).", hr);
+            return false;
+        }
+
+        winrt::com_ptr<::IInspectable> inspectable;
+        hr = CreateDirect3D11DeviceFromDXGIDevice(dxgiDevice.Get(), inspectable.put());
+        if (FAILED(hr)) {
+            LOG_ERROR("Failed to create WinRT Direct3D device (0x%08X).", hr);
+            return false;
+        }
+        m_winrtDevice = inspectable.as<d3d::IDirect3DDevice>();
+
+        if (!ShaderLoader::CompilePixelShader(device, g_ScalePS, strlen(g_ScalePS), "main", m_scalePS.GetAddressOf())) return false;
+        if (!m_fullscreenRenderer.Initialize(device)) return false;
+
+        D3D11_SAMPLER_DESC samplerDesc = {};
+        samplerDesc.Filter = D3D11_FILTER_MIN_MAG_MIP_LINEAR;
+        samplerDesc.AddressU = samplerDesc.AddressV = samplerDesc.AddressW = D3D11_TEXTURE_ADDRESS_CLAMP;
+        if (FAILED(m_device->CreateSamplerState(&samplerDesc, m_sampler.GetAddressOf()))) return false;
+
+        m_initialized = true;
+        LOG_INFO("WGC capture initialized.");
+        return true;
+    }
+
+    bool SetSourceWindow(HWND source) override {
+        if (!m_initialized || !source) return false;
+        StopSession();
+
+        try {
+            auto interop = winrt::get_activation_factory<wgc::GraphicsCaptureItem, IGraphicsCaptureItemInterop>();
+            wgc::GraphicsCaptureItem item{ nullptr };
+            winrt::check_hresult(interop->CreateForWindow(
+                source, winrt::guid_of<wgc::GraphicsCaptureItem>(), winrt::put_abi(item)));
+
+            m_item = item;
+            m_sourceSize = item.Size();
+            m_framePool = wgc::Direct3D11CaptureFramePool::CreateFreeThreaded(
+                m_winrtDevice, winrt::Windows::Graphics::DirectX::DirectXPixelFormat::B8G8R8A8UIntNormalized,
+                2, m_sourceSize);
+            m_session = m_framePool.CreateCaptureSession(m_item);
+            m_session.StartCapture();
+        } catch (const winrt::hresult_error& e) {
+            LOG_ERROR("Failed to start window capture (0x%08X).", static_cast<uint32_t>(e.code()));
+            m_status = CaptureStatusFromHResult(e.code());
+            StopSession();
+            return false;
+        }
+
+        m_status = CaptureStatus::Ok;
+
+        LOG_INFO("Capturing window %p (%dx%d).", source, m_sourceSize.Width, m_sourceSize.Height);
+        return true;
+    }
+
+    bool CaptureFrame(const RECT& region, ID3D11Texture2D** outTexture) override {
+        if (!m_initialized || !outTexture || !m_framePool) return false;
+
+        int width = region.right - region.left;
+        int height = region.bottom - region.top;
+        if (width <= 0 || height <= 0) return false;
+
+        wgc::Direct3D11CaptureFrame frame{ nullptr };
+        try {
+            frame = m_framePool.TryGetNextFrame();
+        } catch (const winrt::hresult_error& e) {
+            m_status = CaptureStatusFromHResult(e.code());
+            frame = nullptr;
+        }
+
+        if (!frame) {
+            // Nothing new (source idle or minimized): reuse the last frame
+            if (m_cachedTexture && m_cachedWidth == width && m_cachedHeight == height) {
+                *outTexture = m_cachedTexture.Get();
+                return true;
+            }
+            return false;
+        }
+
+        auto contentSize = frame.ContentSize();
+        if (contentSize.Width != m_sourceSize.Width || contentSize.Height != m_sourceSize.Height) {
+            // The source was resized; later frames use the new size
+            m_sourceSize = contentSize;
+            m_framePool.Recreate(m_winrtDevice,
+                winrt::Windows::Graphics::DirectX::DirectXPixelFormat::B8G8R8A8UIntNormalized, 2, m_sourceSize);
+        }
+
+        ComPtr<ID3D11Texture2D> sourceTexture;
+        auto access = frame.Surface().as<::Windows::Graphics::DirectX::Direct3D11::IDirect3DDxgiInterfaceAccess>();
+        if (FAILED(access->GetInterface(IID_PPV_ARGS(sourceTexture.GetAddressOf())))) return false;
+
+        if (!EnsureOutput(width, height)) return false;
+
+        ComPtr<ID3D11ShaderResourceView> sourceSRV;
+        if (FAILED(m_device->CreateShaderResourceView(sourceTexture.Get(), nullptr, sourceSRV.GetAddressOf()))) return false;
+
+        m_fullscreenRenderer.SetViewport(m_context.Get(), width, height);
+        m_context->PSSetShader(m_scalePS.Get(), nullptr, 0);
+        ID3D11ShaderResourceView* srv = sourceSRV.Get();
+        m_context->PSSetShaderResources(0, 1, &srv);
+        m_context->PSSetSamplers(0, 1, m_sampler.GetAddressOf());
+        m_context->OMSetRenderTargets(1, m_cachedRTV.GetAddressOf(), nullptr);
+        m_fullscreenRenderer.DrawFullscreen(m_context.Get());
+
+        ID3D11ShaderResourceView* nullSRV = nullptr;
+        m_context->PSSetShaderResources(0, 1, &nullSRV);
+        ID3D11RenderTargetView* nullRTV = nullptr;
+        m_context->OMSetRenderTargets(1, &nullRTV, nullptr);
+
+        m_status = CaptureStatus::Ok;
+        *outTexture = m_cachedTexture.Get();
+        return true;
+    }
+
+    void Shutdown() override {
+        StopSession();
+        m_cachedRTV.Reset();
+        m_cachedTexture.Reset();
+        m_sampler.Reset();
+        m_scalePS.Reset();
+        m_winrtDevice = nullptr;
+        m_context.Reset();
+        m_device = nullptr;
+        m_initialized = false;
+    }
+
+    // The blur window is not part of another window's content
+    void SetSelfWindow(HWND) override {}
+
+    CaptureStatus GetStatus() const override {
+        return m_status;
+    }
+
+    bool GetLastCaptureRect(RECT*) const override {
+        return false;
+    }
+
+    uint64_t GetVideoMemoryUsage() const override {
+        return TextureBytes(m_cachedTexture.Get());
+    }
+
+private:
+    void StopSession() {
+        if (m_session) m_session.Close();
+        if (m_framePool) m_framePool.Close();
+        m_session = nullptr;
+        m_framePool = nullptr;
+        m_item = nullptr;
+    }
+
+    bool EnsureOutput(int width, int height) {
+        if (m_cachedTexture && m_cachedWidth == width && m_cachedHeight == height) return true;
+
+        D3D11_TEXTURE2D_DESC desc = {};
+        desc.Width = width;
+        desc.Height = height;
+        desc.MipLevels = 1;
+        desc.ArraySize = 1;
+        desc.Format = DXGI_FORMAT_B8G8R8A8_UNORM;
+        desc.SampleDesc.Count = 1;
+        desc.Usage = D3D11_USAGE_DEFAULT;
+        desc.BindFlags = D3D11_BIND_SHADER_RESOURCE | D3D11_BIND_RENDER_TARGET;
+
+        m_cachedRTV.Reset();
+        if (FAILED(m_device->CreateTexture2D(&desc, nullptr, m_cachedTexture.ReleaseAndGetAddressOf()))) return false;
+        if (FAILED(m_device->CreateRenderTargetView(m_cachedTexture.Get(), nullptr, m_cachedRTV.GetAddressOf()))) return false;
+        m_cachedWidth = width;
+        m_cachedHeight = height;
+        return true;
+    }
+
+    ID3D11Device* m_device = nullptr;
+    ComPtr<ID3D11DeviceContext> m_context;
+    bool m_initialized = false;
+    CaptureStatus m_status = CaptureStatus::Ok;
+
+    d3d::IDirect3DDevice m_winrtDevice{ nullptr };
+    wgc::GraphicsCaptureItem m_item{ nullptr };
+    wgc::Direct3D11CaptureFramePool m_framePool{ nullptr };
+    wgc::GraphicsCaptureSession m_session{ nullptr };
+    winrt::Windows::Graphics::SizeInt32 m_sourceSize{ 0, 0 };
+
+    FullscreenRenderer m_fullscreenRenderer;
+    ComPtr<ID3D11PixelShader> m_scalePS;
+    ComPtr<ID3D11SamplerState> m_sampler;
+    ComPtr<ID3D11Texture2D> m_cachedTexture;
+    ComPtr<ID3D11RenderTargetView> m_cachedRTV;
+    int m_cachedWidth = 0;
+    int m_cachedHeight = 0;
+};
+
+// Factory function
+std::unique_ptr<ICaptureSubsystem> CreateWGCCapture() {
+    return std::make_unique<WGCCapture>();
+}
+
+} // namespace blurwindow
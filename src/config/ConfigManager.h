@@ -6,6 +6,7 @@
 #include <vector>
 #include <fstream>
 #include <sstream>
+#include <cstdlib>
 
 namespace blurwindow {
 
@@ -84,6 +85,49 @@ public:
         return nullptr;
     }
 
+    /// Read a numeric value for "key" from a flat JSON object
+    static bool GetFloat(const std::string& json, const char* key, float& out) {
+        std::string quoted = std::string("\"") + key + "\"";
+        size_t keyPos = json.find(quoted);
+        if (keyPos == std::string::npos) return false;
+
+        size_t colon = json.find(':', keyPos + quoted.size());
+        if (colon == std::string::npos) return false;
+
+        const char* start = json.c_str() + colon + 1;
+        char* end = nullptr;
+        float value = std::strtof(start, &end);
+        if (end == start) return false;
+
+        out = value;
+        return true;
+    }
+
+    /// Read a fixed-length numeric array for "key" from a flat JSON object
+    static bool GetFloatArray(const std::string& json, const char* key, float* out, size_t count) {
+        std::string quoted = std::string("\"") + key + "\"";
+        size_t keyPos = json.find(quoted);
+        if (keyPos == std::string::npos) return false;
+
+        size_t open = json.find('[', keyPos + quoted.size());
+        size_t close = json.find(']', keyPos + quoted.size());
+        if (open == std::string::npos || close == std::string::npos || close < open) return false;
+
+        float values[16] = {};
+        if (count > 16) return false;
+        const char* cursor = json.c_str() + open + 1;
+        for (size_t i = 0; i < count; i++) {
+            char* end = nullptr;
+            values[i] = std::strtof(cursor, &end);
+            if (end == cursor) return false;
+            cursor = end;
+            while (*cursor == ' ' || *cursor == ',') cursor++;
+        }
+
+        for (size_t i = 0; i < count; i++) out[i] = values[i];
+        return true;
+    }
+
     static PipelineConfig ParsePipelineJson(const std::string& json) {
         PipelineConfig config;
         
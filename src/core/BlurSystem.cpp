@@ -1,7 +1,10 @@
 #include "blurwindow/blur_window.h"
 #include "Logger.h"
+#include "ThemeWatcher.h"
+#include "FullscreenWatcher.h"
 #include <d3d11.h>
 #include <wrl/client.h>
+#include <atomic>
 #include <vector>
 #include <mutex>
 
@@ -85,6 +88,9 @@ public:
     }
 
     void Shutdown() {
+        m_themeWatcher.SetCallback(nullptr, nullptr);
+        m_fullscreenWatcher.SetCallback(nullptr, nullptr);
+
         std::lock_guard<std::mutex> lock(m_mutex);
         
         m_windows.clear();
@@ -118,9 +124,26 @@ public:
         );
     }
 
+    void SetThemeChangedCallback(BlurSystem::ThemeChangedCallback callback, void* userData) {
+        m_themeWatcher.SetCallback(callback, userData);
+    }
+
+    void SetFullscreenChangedCallback(BlurSystem::FullscreenChangedCallback callback, void* userData) {
+        m_fullscreenWatcher.SetCallback(callback, userData);
+    }
+
+    void SetPreferredCaptureMethod(int method) {
+        m_preferredCaptureMethod = method;
+    }
+
+    int GetPreferredCaptureMethod() const {
+        return m_preferredCaptureMethod;
+    }
+
 private:
     std::mutex m_mutex;
     bool m_initialized = false;
+    std::atomic<int> m_preferredCaptureMethod{0};
     BlurSystemOptions m_options{};
     
     ComPtr<ID3D11Device> m_device;
@@ -128,6 +151,8 @@ private:
     D3D_FEATURE_LEVEL m_featureLevel = D3D_FEATURE_LEVEL_11_0;
     
     std::vector<BlurWindow*> m_windows;
+    ThemeWatcher m_themeWatcher;
+    FullscreenWatcher m_fullscreenWatcher;
 };
 
 // Singleton instance
@@ -170,4 +195,24 @@ std::unique_ptr<BlurWindow> BlurSystem::CreateBlurWindow(HWND owner, const Windo
     return window;
 }
 
+SystemTheme BlurSystem::GetSystemTheme() {
+    return ReadSystemTheme();
+}
+
+void BlurSystem::SetThemeChangedCallback(ThemeChangedCallback callback, void* userData) {
+    m_impl->SetThemeChangedCallback(callback, userData);
+}
+
+void BlurSystem::SetFullscreenChangedCallback(FullscreenChangedCallback callback, void* userData) {
+    m_impl->SetFullscreenChangedCallback(callback, userData);
+}
+
+void BlurSystem::SetPreferredCaptureMethod(int method) {
+    m_impl->SetPreferredCaptureMethod(method);
+}
+
+int BlurSystem::GetPreferredCaptureMethod() const {
+    return m_impl->GetPreferredCaptureMethod();
+}
+
 } // namespace blurwindow
@@ -3,11 +3,22 @@
 #include "Logger.h"
 #include "SubsystemFactory.h"
 #include "FullscreenRenderer.h"
+#include "DebugOverlay.h"
+#include "GpuMemory.h"
+#include "ShaderLoader.h"
+#include "../config/ConfigManager.h"
 #include "../effects/RainEffect.h"
+#include <algorithm>
 #include <atomic>
 #include <chrono>
+#include <cmath>
+#include <condition_variable>
+#include <cstring>
 #include <mutex>
-#include <d3d11.h>
+#include <vector>
+#include <d3d11_1.h>
+#include <commctrl.h>
+#include <dwmapi.h>
 #include <wrl/client.h>
 #include <windowsx.h>  // for GET_X_LPARAM, GET_Y_LPARAM
 
@@ -15,6 +26,157 @@ using Microsoft::WRL::ComPtr;
 
 namespace blurwindow {
 
+// Window property tagging every blur window for the whole life of its HWND,
+// see blur_is_blur_window
+static const wchar_t* g_BlurWindowProp = L"BlurWindow";
+
+// Replaces pixels the capture left black (protected content, area outside
+// every monitor) with the fallback color before the effect runs
+static const char* g_FallbackPS = R"(
+Texture2D inputTexture : register(t0);
+SamplerState pointSampler : register(s0);
+
+cbuffer FallbackParams : register(b0) {
+    float4 fallbackColor;
+};
+
+float4 main(float4 position : SV_Position, float2 texcoord : TEXCOORD0) : SV_Target {
+    float4 color = inputTexture.Sample(pointSampler, texcoord);
+    if (max(color.r, max(color.g, color.b)) <= 1.0f / 255.0f) {
+        color.rgb = lerp(color.rgb, fallbackColor.rgb, fallbackColor.a);
+    }
+    color.a = 1.0f;
+    return color;
+}
+)";
+
+// Fades the finished frame out towards the edges of the blurred area. Drawn
+// with a blend state that multiplies the target by the returned alpha, which
+// keeps the premultiplied output valid.
+static const char* g_FeatherPS = R"(
+cbuffer FeatherParams : register(b0) {
+    float4 featherRect;  // left, top, right, bottom in pixels
+    float feather;
+    float3 featherPadding;
+};
+
+float4 main(float4 position : SV_Position, float2 texcoord : TEXCOORD0) : SV_Target {
+    float2 p = position.xy;
+    float d = min(min(p.x - featherRect.x, featherRect.z - p.x), min(p.y - featherRect.y, featherRect.w - p.y));
+    return float4(0.0f, 0.0f, 0.0f, saturate(d / feather));
+}
+)";
+
+// Scales between the window's resolution and the downsampled one the effect
+// chain runs at; drawn with a bilinear sampler.
+static const char* g_ResamplePS = R"(
+Texture2D inputTexture : register(t0);
+SamplerState linearSampler : register(s0);
+
+float4 main(float4 position : SV_Position, float2 texcoord : TEXCOORD0) : SV_Target {
+    return inputTexture.Sample(linearSampler, texcoord);
+}
+)";
+
+// Scales the output's alpha by how much the backdrop changed between the
+// last two captures, taking the largest change around each pixel so the
+// blur covers the edges of moving content. Drawn with the feather's
+// multiplying blend state.
+static const char* g_TemporalPS = R"(
+Texture2D currentTexture : register(t0);
+Texture2D previousTexture : register(t1);
+SamplerState pointSampler : register(s0);
+
+cbuffer TemporalParams : register(b0) {
+    float2 texelSize;
+    float weighted;  // 1: MotionWeighted, 0: MotionOnly
+    float temporalPadding;
+};
+
+float4 main(float4 position : SV_Position, float2 texcoord : TEXCOORD0) : SV_Target {
+    float motion = 0.0f;
+    [unroll] for (int y = -1; y <= 1; y++) {
+        [unroll] for (int x = -1; x <= 1; x++) {
+            float2 uv = texcoord + float2(x, y) * 2.0f * texelSize;
+            float3 d = abs(currentTexture.Sample(pointSampler, uv).rgb - previousTexture.Sample(pointSampler, uv).rgb);
+            motion = max(motion, max(d.r, max(d.g, d.b)));
+        }
+    }
+    // A quarter of the full range blurs fully; below 4/255 counts as still
+    float amount = weighted > 0.5f ? saturate(motion * 4.0f) : smoothstep(4.0f / 255.0f, 8.0f / 255.0f, motion);
+    return float4(0.0f, 0.0f, 0.0f, amount);
+}
+)";
+
+// Wraps a blur_set_custom_shader snippet, which defines
+// float4 CustomShade(float4 color, float2 uv, float time). The #line makes
+// compiler messages count lines from the start of the snippet.
+static const char* g_CustomShaderPrologue = R"(
+Texture2D blurredTexture : register(t0);
+SamplerState linearSampler : register(s0);
+
+cbuffer CustomShaderParams : register(b0) {
+    float2 resolution;
+    float customShaderTime;
+    float customShaderPadding;
+};
+#line 1 "custom_shader"
+)";
+
+static const char* g_CustomShaderEpilogue = R"(
+#line 1 "custom_shader_main"
+float4 main(float4 position : SV_Position, float2 texcoord : TEXCOORD0) : SV_Target {
+    return CustomShade(blurredTexture.Sample(linearSampler, texcoord), texcoord, customShaderTime);
+}
+)";
+
+// Longest the render loop waits for an owner paint before re-checking
+// whether it should stop
+static constexpr auto OWNER_PAINT_WAIT = std::chrono::milliseconds(100);
+
+// How often the render loop re-checks occlusion while someone is interested
+static constexpr auto OCCLUSION_POLL_INTERVAL = std::chrono::milliseconds(250);
+
+static bool IsCloaked(HWND hwnd) {
+    BOOL cloaked = FALSE;
+    return SUCCEEDED(DwmGetWindowAttribute(hwnd, DWMWA_CLOAKED, &cloaked, sizeof(cloaked))) && cloaked;
+}
+
+// True when hwnd is cloaked (e.g. on another virtual desktop) or the windows
+// above its top-level window cover every pixel of it. Windows that may be
+// see-through (layered, DirectComposition like ours) never count as covering.
+static bool IsWindowOccluded(HWND hwnd) {
+    if (IsCloaked(hwnd)) return true;
+    RECT rect;
+    if (!GetWindowRect(hwnd, &rect) || IsRectEmpty(&rect)) return false;
+
+    HRGN uncovered = CreateRectRgnIndirect(&rect);
+    HRGN cover = CreateRectRgn(0, 0, 0, 0);
+    bool occluded = false;
+    if (uncovered && cover) {
+        const LONG seeThrough = WS_EX_LAYERED | WS_EX_TRANSPARENT | WS_EX_NOREDIRECTIONBITMAP;
+        HWND root = GetAncestor(hwnd, GA_ROOT);
+        for (HWND above = GetWindow(root ? root : hwnd, GW_HWNDPREV); above; above = GetWindow(above, GW_HWNDPREV)) {
+            if (!IsWindowVisible(above) || IsIconic(above) || IsCloaked(above)) continue;
+            if (GetWindowLongW(above, GWL_EXSTYLE) & seeThrough) continue;
+            // The window rect includes the invisible resize borders
+            RECT aboveRect;
+            if (FAILED(DwmGetWindowAttribute(above, DWMWA_EXTENDED_FRAME_BOUNDS, &aboveRect, sizeof(aboveRect))) &&
+                !GetWindowRect(above, &aboveRect)) {
+                continue;
+            }
+            SetRectRgn(cover, aboveRect.left, aboveRect.top, aboveRect.right, aboveRect.bottom);
+            if (CombineRgn(uncovered, uncovered, cover, RGN_DIFF) == NULLREGION) {
+                occluded = true;
+                break;
+            }
+        }
+    }
+    if (cover) DeleteObject(cover);
+    if (uncovered) DeleteObject(uncovered);
+    return occluded;
+}
+
 class BlurWindow::Impl {
 public:
     Impl(HWND owner, const WindowOptions& opts)
@@ -29,11 +191,13 @@ public:
         // Don't initialize graphics in constructor
         // Only determine rendering mode
         m_useDirectComp = ShouldUseDirectComposition();
+        m_visible = !opts.startHidden;
         LOG_INFO("BlurWindow created (DirectComp: %d)", m_useDirectComp);
     }
 
     ~Impl() {
         Stop();
+        ReleaseOwnerPaintHook();
         ShutdownGraphics();
         DestroyBlurWindow();
     }
@@ -56,7 +220,12 @@ public:
         if (m_running.exchange(true)) {
             return;
         }
+        // A loop that stopped itself (owner closed) still needs joining
+        if (m_renderThread.joinable()) {
+            m_renderThread.join();
+        }
         
+        m_lastAnimationUpdate = std::chrono::high_resolution_clock::now();
         m_renderThread = std::thread([this]() {
             RenderLoop();
         });
@@ -65,6 +234,11 @@ public:
 
     void Stop() {
         m_running = false;
+        // Called from a callback on the render thread: the loop exits by itself
+        // and is joined by the next Start() or the destructor
+        if (m_renderThread.get_id() == std::this_thread::get_id()) {
+            return;
+        }
         if (m_renderThread.joinable()) {
             m_renderThread.join();
         }
@@ -74,6 +248,112 @@ public:
         return m_running;
     }
 
+    bool IsManualRender() const {
+        return m_options.manualRender;
+    }
+
+    bool RenderOnce() {
+        if (!m_options.manualRender) return false;
+
+        // The continuous loop already presents frames; don't race it
+        if (m_running) return true;
+        // Deferred until blur_end_update
+        if (m_updateDepth > 0) return true;
+
+        if (!m_hwnd) {
+            CreateBlurWindow();
+        }
+
+        if (!m_graphicsInitialized) {
+            if (!InitializeGraphicsBasics() || !InitializeSubsystems()) {
+                LOG_ERROR("RenderOnce: initialization failed.");
+                return false;
+            }
+        }
+
+        ProcessPendingResize();
+
+        using clock = std::chrono::high_resolution_clock;
+        auto frameStart = clock::now();
+        {
+            std::lock_guard<std::mutex> lock(m_graphicsMutex);
+            if (!m_capture || !m_effect || !m_presenter) return false;
+
+            ID3D11Texture2D* capturedTexture = nullptr;
+            if (!AcquireBackdrop(&capturedTexture)) {
+                LOG_WARN("RenderOnce: capture returned no frame.");
+                return false;
+            }
+            RenderFrame(capturedTexture);
+        }
+        NotifyFrame(std::chrono::duration<float, std::milli>(clock::now() - frameStart).count());
+        return true;
+    }
+
+    // Set up graphics and run every pass the current settings use once, into
+    // a layer target instead of the window, so shaders are compiled (by us
+    // and by the driver) and intermediate textures sized before the first
+    // presented frame
+    bool Prewarm() {
+        if (!m_hwnd) {
+            CreateBlurWindow();
+        }
+
+        if (!m_graphicsInitialized) {
+            if (!InitializeGraphicsBasics() || !InitializeSubsystems()) {
+                LOG_ERROR("Prewarm: initialization failed.");
+                return false;
+            }
+        }
+
+        ProcessPendingResize();
+
+        auto started = std::chrono::steady_clock::now();
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        if (!m_effect || !EnsureLayerTargets()) return false;
+        if (m_fallbackColor[3] > 0.0f) EnsureFallbackPass();
+        if (m_edgeFeather > 0.0f) EnsureFeatherPass();
+        if (m_temporalMode != TemporalMode::Off) EnsureTemporalPass();
+
+        // Layer target 1 stands in for the backdrop; its content doesn't matter.
+        // The chain allocates at the size it runs at, so warm it up there.
+        ID3D11ShaderResourceView* chainInput = m_layerSRVs[1].Get();
+        ID3D11RenderTargetView* chainOutput = m_layerRTVs[0].Get();
+        uint32_t chainWidth = m_width, chainHeight = m_height;
+        if (m_downsample[0] > 1 || m_downsample[1] > 1) {
+            DownsampledSize(&chainWidth, &chainHeight);
+            if (EnsureResamplePass() && EnsureDownsampleTargets(chainWidth, chainHeight)) {
+                chainInput = m_downsampleSRVs[1].Get();
+                chainOutput = m_downsampleRTVs[0].Get();
+            } else {
+                chainWidth = m_width;
+                chainHeight = m_height;
+            }
+        }
+        std::vector<IBlurEffect*> effects = { m_effect.get() };
+        for (auto& layer : m_layers) effects.push_back(layer.get());
+        size_t chainLength = effects.size();
+        for (auto& region : m_regionEffects) {
+            if (region.effect || CreateRegionEffect(region)) effects.push_back(region.effect.get());
+        }
+        for (size_t i = 0; i < effects.size(); i++) {
+            bool inChain = i < chainLength;
+            ID3D11ShaderResourceView* nullSRVs[2] = { nullptr, nullptr };
+            m_context->PSSetShaderResources(0, 2, nullSRVs);
+            if (!effects[i]->Apply(m_context.Get(), inChain ? chainInput : m_layerSRVs[1].Get(),
+                                   inChain ? chainOutput : m_layerRTVs[0].Get(),
+                                   inChain ? chainWidth : m_width, inChain ? chainHeight : m_height)) {
+                LOG_WARN("Prewarm: effect %s failed to apply", effects[i]->GetName());
+            }
+        }
+        // Writes the output texture, which nothing presents until the next frame
+        ApplyCustomShader();
+        m_context->Flush();
+        LOG_INFO("Prewarm: %zu effects in %.1f ms", effects.size(),
+            std::chrono::duration<double, std::milli>(std::chrono::steady_clock::now() - started).count());
+        return true;
+    }
+
     void SetPreset(QualityPreset preset) {
         m_preset = preset;
         UpdatePresetSettings();
@@ -95,6 +375,32 @@ public:
         SetWindowLongPtrW(m_hwnd, GWL_EXSTYLE, exStyle);
     }
 
+    // Returns false until the HWND exists
+    bool GetExStyle(uint32_t* exStyle) const {
+        if (!m_hwnd) return false;
+        *exStyle = static_cast<uint32_t>(GetWindowLongPtrW(m_hwnd, GWL_EXSTYLE));
+        return true;
+    }
+
+    bool SetExStyle(uint32_t exStyle) {
+        if (!m_hwnd) return false;
+
+        LONG_PTR old = GetWindowLongPtrW(m_hwnd, GWL_EXSTYLE);
+        SetWindowLongPtrW(m_hwnd, GWL_EXSTYLE, static_cast<LONG_PTR>(exStyle));
+
+        // WS_EX_TOPMOST can only be changed through the z-order
+        HWND insertAfter = nullptr;
+        UINT flags = SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE | SWP_FRAMECHANGED;
+        if ((old ^ static_cast<LONG_PTR>(exStyle)) & WS_EX_TOPMOST) {
+            insertAfter = (exStyle & WS_EX_TOPMOST) ? HWND_TOPMOST : HWND_NOTOPMOST;
+        } else {
+            flags |= SWP_NOZORDER;
+        }
+        SetWindowPos(m_hwnd, insertAfter, 0, 0, 0, 0, flags);
+        LOG_INFO("SetExStyle: 0x%08X -> 0x%08X", static_cast<uint32_t>(old), exStyle);
+        return true;
+    }
+
     void SetTopMost(bool enable) {
         if (!m_hwnd) return;
         
@@ -105,14 +411,23 @@ public:
         );
     }
 
+    void SetTaskbarRelation(TaskbarRelation relation) {
+        m_taskbarRelation = static_cast<int>(relation);
+        ApplyTaskbarRelation();
+    }
+
     void SetBounds(const RECT& bounds) {
         // Window操作は即時実行（UIスレッドから呼ばれることが期待される）
         int newWidth = bounds.right - bounds.left;
         int newHeight = bounds.bottom - bounds.top;
         
         if (m_hwnd) {
+            // Wallpaper bounds are screen coordinates, the window's position
+            // is relative to the WorkerW
+            POINT origin = { bounds.left, bounds.top };
+            if (m_options.wallpaper) ScreenToClient(m_wallpaperHost, &origin);
             SetWindowPos(m_hwnd, nullptr,
-                bounds.left, bounds.top,
+                origin.x, origin.y,
                 newWidth, newHeight,
                 SWP_NOZORDER | SWP_NOACTIVATE
             );
@@ -130,195 +445,1154 @@ public:
         return m_options.bounds;
     }
 
-    HWND GetHWND() const {
-        return m_hwnd;
+    // Like SetBounds, the presenter itself is resized on the render thread
+    void ResizeSwapchain(uint32_t width, uint32_t height) {
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        m_pendingSwapchainWidth = width;
+        m_pendingSwapchainHeight = height;
+        m_swapchainResizeRequested = true;
     }
 
-    float GetCurrentFPS() const {
-        return m_currentFPS;
+    // Applied before the next frame, like ResizeSwapchain
+    void SetBufferCount(uint32_t count) {
+        m_bufferCount = count;
+        m_bufferCountRequested = true;
+        LOG_INFO("SetBufferCount: %u", count);
     }
 
-    bool SetEffectPipeline(const std::string& jsonConfig) {
-        // Simple dispatcher based on JSON type field for now
-        EffectType type = EffectType::Gaussian;
-        if (jsonConfig.find("\"kawase\"") != std::string::npos) type = EffectType::Kawase;
-        else if (jsonConfig.find("\"box\"") != std::string::npos) type = EffectType::Box;
-        else if (jsonConfig.find("\"radial\"") != std::string::npos) type = EffectType::Radial;
-        else if (jsonConfig.find("\"rain\"") != std::string::npos) type = EffectType::Rain;
+    bool SetActiveRegion(const RECT* region) {
+        if (!region) {
+            {
+                // Also read by the render thread's edge feather
+                std::lock_guard<std::mutex> lock(m_graphicsMutex);
+                m_hasActiveRegion = false;
+            }
+            ApplyActiveRegion();
+            return true;
+        }
 
-        LOG_INFO("SetEffectPipeline: detected type=%d from config", static_cast<int>(type));
+        LONG width = m_options.bounds.right - m_options.bounds.left;
+        LONG height = m_options.bounds.bottom - m_options.bounds.top;
+        if (region->right <= region->left || region->bottom <= region->top ||
+            region->left < 0 || region->top < 0 ||
+            region->right > width || region->bottom > height) {
+            LOG_WARN("SetActiveRegion: region outside %ldx%ld window", width, height);
+            return false;
+        }
 
-        auto newEffect = SubsystemFactory::CreateEffect(type);
-        if (newEffect && newEffect->Initialize(m_device)) {
+        {
             std::lock_guard<std::mutex> lock(m_graphicsMutex);
-            // Preserve current strength and apply to new effect
-            newEffect->SetStrength(m_currentStrength);
-            newEffect->SetNoiseIntensity(m_noiseIntensity);
-            newEffect->SetNoiseScale(m_noiseScale);
-            newEffect->SetNoiseSpeed(m_noiseSpeed);
-            newEffect->SetNoiseType(m_noiseType);
-            newEffect->SetColor(m_tintColor[0], m_tintColor[1], m_tintColor[2], m_tintColor[3]);
-            m_effect = std::move(newEffect);
-            m_graphicsInitialized = (m_capture && m_effect && m_presenter);
-            LOG_INFO("SetEffectPipeline: new effect initialized successfully");
-            return true;
+            m_activeRegion = *region;
+            m_hasActiveRegion = true;
         }
-        LOG_ERROR("SetEffectPipeline: failed to create or initialize effect type=%d", static_cast<int>(type));
-        return false;
+        ApplyActiveRegion();
+        return true;
     }
 
-    void SetBlurStrength(float strength) {
-        std::lock_guard<std::mutex> lock(m_graphicsMutex);
-        m_currentStrength = strength;
-        LOG_INFO("SetBlurStrength: %.2f", strength);
-        if (m_effect) {
-            m_effect->SetStrength(strength);
+    struct RegionEffect {
+        RECT rect;  // Relative to the window's top-left corner
+        EffectType type;
+        std::unique_ptr<IBlurEffect> effect;  // Created on first draw
+        bool failed;  // Creation failed on the current device
+    };
+
+    bool AddRegionEffect(const RECT& rect, int type) {
+        if (type < 0 || type > static_cast<int>(EffectType::Rain)) {
+            LOG_WARN("AddRegionEffect: unknown effect type %d", type);
+            return false;
+        }
+        LONG width = m_options.bounds.right - m_options.bounds.left;
+        LONG height = m_options.bounds.bottom - m_options.bounds.top;
+        if (rect.right <= rect.left || rect.bottom <= rect.top ||
+            rect.left < 0 || rect.top < 0 ||
+            rect.right > width || rect.bottom > height) {
+            LOG_WARN("AddRegionEffect: region outside %ldx%ld window", width, height);
+            return false;
         }
-    }
 
-    void SetBlurColor(float r, float g, float b, float a) {
         std::lock_guard<std::mutex> lock(m_graphicsMutex);
-        m_tintColor[0] = r; m_tintColor[1] = g; m_tintColor[2] = b; m_tintColor[3] = a;
-        if (m_effect) {
-            m_effect->SetColor(r, g, b, a);
+        if (m_regionEffects.size() >= BlurWindow::MaxRegionEffects) {
+            LOG_WARN("AddRegionEffect: at most %zu region effects are supported", BlurWindow::MaxRegionEffects);
+            return false;
         }
+        m_regionEffects.push_back({rect, static_cast<EffectType>(type), nullptr, false});
+        LOG_INFO("AddRegionEffect: type=%d at (%ld, %ld)-(%ld, %ld)", type, rect.left, rect.top, rect.right, rect.bottom);
+        return true;
     }
 
-    void SetEffectType(int type) {
+    void ClearRegionEffects() {
         std::lock_guard<std::mutex> lock(m_graphicsMutex);
-        SetEffectTypeInternal(type);
+        m_regionEffects.clear();
+        LOG_INFO("ClearRegionEffects");
     }
-    
-    // Internal version without lock (must be called with m_graphicsMutex held)
-    void SetEffectTypeInternal(int type) {
-        EffectType effectType = static_cast<EffectType>(type);
-        LOG_DEBUG("SetEffectTypeInternal: switching to type {}", type);
-        
-        auto newEffect = SubsystemFactory::CreateEffect(effectType);
-        if (!newEffect) {
-            LOG_ERROR("SetEffectTypeInternal: CreateEffect failed for type {}", type);
-            return;
-        }
-        
-        if (!newEffect->Initialize(m_device)) {
-            LOG_ERROR("SetEffectTypeInternal: Initialize failed, m_device={}", (void*)m_device);
-            return;
-        }
-        
-        newEffect->SetStrength(m_currentStrength);
-        newEffect->SetNoiseIntensity(m_noiseIntensity);
-        newEffect->SetNoiseScale(m_noiseScale);
-        newEffect->SetNoiseSpeed(m_noiseSpeed);
-        newEffect->SetNoiseType(m_noiseType);
-        newEffect->SetColor(m_tintColor[0], m_tintColor[1], m_tintColor[2], m_tintColor[3]);
-        m_effect = std::move(newEffect);
-        LOG_INFO("SetEffectTypeInternal: Successfully switched to type {}", type);
+
+    void BeginUpdate() {
+        ++m_updateDepth;
     }
-    
-    // Helper: Ensure RainEffect is active (must be called with m_graphicsMutex held)
-    RainEffect* EnsureRainEffect() {
-        auto* rain = dynamic_cast<RainEffect*>(m_effect.get());
-        if (!rain) {
-            LOG_INFO("EnsureRainEffect: Current effect is not RainEffect, switching...");
-            SetEffectTypeInternal(static_cast<int>(EffectType::Rain));
-            rain = dynamic_cast<RainEffect*>(m_effect.get());
-            if (!rain) {
-                LOG_ERROR("EnsureRainEffect: Failed to switch to RainEffect");
-            } else {
-                LOG_INFO("EnsureRainEffect: Successfully switched to RainEffect");
-            }
+
+    bool EndUpdate() {
+        int depth = m_updateDepth.load();
+        do {
+            if (depth <= 0) return false;
+        } while (!m_updateDepth.compare_exchange_weak(depth, depth - 1));
+
+        if (depth == 1 && m_regionDeferred.exchange(false)) {
+            ApplyActiveRegion();
         }
-        return rain;
+        return true;
     }
 
-    void SetBlurParam(float param) {
-        std::lock_guard<std::mutex> lock(m_graphicsMutex);
-        if (m_effect) {
-            // This is a bit of a hack since IBlurEffect doesn't have a generic SetParam
-            // We'll need to check the effect type or add SetParam to IBlurEffect
-            // For now, let's assume SetParameters can handle a simple float-check
-            char buffer[64];
-            snprintf(buffer, sizeof(buffer), "{\"param\": %.2f}", param);
-            m_effect->SetParameters(buffer);
+    bool SetCursorFollow(bool enabled, float radius) {
+        if (enabled && !(radius > 0.0f)) {
+            LOG_WARN("SetCursorFollow: invalid radius %.2f", radius);
+            return false;
         }
+
+        m_cursorRadius = radius;
+        m_cursorFollow = enabled;
+        m_cursorRegionDirty = true;
+
+        // The region sits under the cursor, so the window must never take mouse input
+        if (m_hwnd) {
+            LONG_PTR exStyle = GetWindowLongPtrW(m_hwnd, GWL_EXSTYLE);
+            if (enabled || m_options.clickThrough) exStyle |= WS_EX_TRANSPARENT;
+            else exStyle &= ~static_cast<LONG_PTR>(WS_EX_TRANSPARENT);
+            SetWindowLongPtrW(m_hwnd, GWL_EXSTYLE, exStyle);
+        }
+
+        // Without a render loop, nobody else will restore the normal region
+        if (!enabled && !m_running) {
+            m_cursorRegionDirty = false;
+            ApplyActiveRegion();
+        }
+        LOG_INFO("SetCursorFollow: %d (radius %.1f)", enabled, radius);
+        return true;
     }
 
-    void SetNoiseIntensity(float intensity) {
-        std::lock_guard<std::mutex> lock(m_graphicsMutex);
-        m_noiseIntensity = intensity;
-        if (m_effect) m_effect->SetNoiseIntensity(intensity);
+    HWND GetHWND() const {
+        return m_hwnd;
     }
 
-    void SetNoiseScale(float scale) {
-        std::lock_guard<std::mutex> lock(m_graphicsMutex);
-        m_noiseScale = scale;
-        if (m_effect) m_effect->SetNoiseScale(scale);
+    float GetCurrentFPS() const {
+        return m_currentFPS;
     }
 
-    void SetNoiseSpeed(float speed) {
-        std::lock_guard<std::mutex> lock(m_graphicsMutex);
-        m_noiseSpeed = speed;
-        if (m_effect) m_effect->SetNoiseSpeed(speed);
+    float GetFrameTimeMs() const {
+        return m_frameTimeMs;
     }
 
-    void SetNoiseType(int type) {
+    float GetLatencyMs() const {
+        return m_latencyMs;
+    }
+
+    int GetBackend() const {
         std::lock_guard<std::mutex> lock(m_graphicsMutex);
-        m_noiseType = type;
-        if (m_effect) m_effect->SetNoiseType(type);
+        if (!m_presenter) return 0;
+        return m_useDirectComp ? 1 : 2;
     }
 
-    // --- Rain Effect Control ---
+    int GetCaptureStatus() const {
+        return static_cast<int>(m_captureStatus.load());
+    }
 
-    void SetRainIntensity(float intensity) {
-        std::lock_guard<std::mutex> lock(m_graphicsMutex);
-        // Auto-enable RainEffect if intensity > 0
-        RainEffect* rain = (intensity > 0) ? EnsureRainEffect() : dynamic_cast<RainEffect*>(m_effect.get());
-        if (rain) rain->SetRainIntensity(intensity);
+    int GetCaptureFormat() const {
+        return static_cast<int>(m_captureFormat.load());
     }
 
-    void SetRainDropSpeed(float speed) {
+    // 0: none, 1: Desktop Duplication, 2: Windows.Graphics.Capture
+    int GetCaptureMethod() const {
         std::lock_guard<std::mutex> lock(m_graphicsMutex);
-        RainEffect* rain = EnsureRainEffect();
-        if (rain) rain->SetDropSpeed(speed);
+        if (!m_capture) return 0;
+        return m_captureType == CaptureType::WGC ? 2 : 1;
     }
 
-    void SetRainRefraction(float strength) {
+    // Nothing is captured from the screen while a texture or image replaces it
+    bool GetCaptureRect(RECT* out) const {
         std::lock_guard<std::mutex> lock(m_graphicsMutex);
-        RainEffect* rain = EnsureRainEffect();
-        if (rain) rain->SetRefractionStrength(strength);
+        if (m_sourceTexture || m_backgroundImage || !m_capture) return false;
+        return m_capture->GetLastCaptureRect(out);
     }
 
-    void SetRainTrailLength(float length) {
+    uint64_t GetVideoMemoryUsage() const {
         std::lock_guard<std::mutex> lock(m_graphicsMutex);
-        RainEffect* rain = EnsureRainEffect();
-        if (rain) rain->SetTrailLength(length);
+        uint64_t total = TextureBytes(m_outputTexture.Get()) + TextureBytes(m_frozenTexture.Get()) +
+                         TextureBytes(m_fallbackTexture.Get()) + TextureBytes(m_sampleTexture.Get());
+        if (m_capture) total += m_capture->GetVideoMemoryUsage();
+        if (m_effect) total += m_effect->GetVideoMemoryUsage();
+        for (const auto& layer : m_layers) total += layer->GetVideoMemoryUsage();
+        for (const auto& region : m_regionEffects) {
+            if (region.effect) total += region.effect->GetVideoMemoryUsage();
+        }
+        for (const auto& texture : m_layerTextures) total += TextureBytes(texture.Get());
+        for (const auto& texture : m_downsampleTextures) total += TextureBytes(texture.Get());
+        for (const auto& texture : m_temporalTextures) total += TextureBytes(texture.Get());
+        if (m_presenter) total += m_presenter->GetVideoMemoryUsage();
+        return total;
     }
 
-    void SetRainDropSize(float minSize, float maxSize) {
+    void SetTimeMode(bool manual) {
         std::lock_guard<std::mutex> lock(m_graphicsMutex);
-        RainEffect* rain = EnsureRainEffect();
-        if (rain) rain->SetDropSizeRange(minSize, maxSize);
+        if (manual && !m_manualTime) {
+            // The current value is the baseline; only later changes animate
+            m_appliedTimeSeconds = m_manualTimeSeconds;
+        } else if (!manual && m_manualTime) {
+            // Don't count the time spent in manual mode as one huge step
+            m_lastAnimationUpdate = std::chrono::high_resolution_clock::now();
+        }
+        m_manualTime = manual;
+        LOG_INFO("SetTimeMode: %s", manual ? "manual" : "realtime");
     }
 
-    // --- Click Callback ---
-    void SetClickCallback(BlurWindow::ClickCallback callback, void* userData) {
-        m_clickCallback = callback;
-        m_clickUserData = userData;
-        LOG_INFO("SetClickCallback: callback=%p, userData=%p", (void*)callback, userData);
+    void SetTime(float seconds) {
+        m_manualTimeSeconds = seconds;
     }
 
-    bool IsInitialized() const {
-        return m_graphicsInitialized;
+    void SetTargetFPS(int fps) {
+        m_targetFpsOverride = fps;
+        LOG_INFO("SetTargetFPS: %d", fps);
     }
 
-    bool Initialize() {
-        if (m_graphicsInitialized) return true;
-        
-        LOG_INFO("Manual initialization requested.");
-        if (!m_hwnd) CreateBlurWindow();
-        bool success = InitializeGraphicsBasics() && InitializeSubsystems();
-        if (success && m_capture && m_hwnd) {
-            m_capture->SetSelfWindow(m_hwnd);
+    // 0 captures every frame
+    void SetCaptureInterval(uint32_t intervalMs) {
+        m_captureIntervalMs = intervalMs;
+        LOG_INFO("SetCaptureInterval: %u ms", intervalMs);
+    }
+
+    // 0: no floor beyond the target frame rate
+    void SetMinFrameInterval(uint32_t intervalMs) {
+        m_minFrameIntervalMs = intervalMs;
+        LOG_INFO("SetMinFrameInterval: %u ms", intervalMs);
+    }
+
+    // Refresh rate of the monitor showing the window. When the window spans
+    // several monitors, the primary monitor's rate is used.
+    float GetDisplayRefreshHz() const {
+        RECT rect = CaptureRect();
+        int monitorCount = 0;
+        EnumDisplayMonitors(nullptr, &rect, [](HMONITOR, HDC, LPRECT, LPARAM data) -> BOOL {
+            ++*reinterpret_cast<int*>(data);
+            return TRUE;
+        }, reinterpret_cast<LPARAM>(&monitorCount));
+
+        HMONITOR monitor = (monitorCount > 1)
+            ? MonitorFromPoint(POINT{0, 0}, MONITOR_DEFAULTTOPRIMARY)
+            : MonitorFromRect(&rect, MONITOR_DEFAULTTOPRIMARY);
+
+        MONITORINFOEXW info = {};
+        info.cbSize = sizeof(info);
+        if (!GetMonitorInfoW(monitor, &info)) return 0.0f;
+
+        DEVMODEW mode = {};
+        mode.dmSize = sizeof(mode);
+        if (!EnumDisplaySettingsW(info.szDevice, ENUM_CURRENT_SETTINGS, &mode)) return 0.0f;
+
+        // 0 and 1 mean "hardware default" rather than a real rate
+        return mode.dmDisplayFrequency > 1 ? static_cast<float>(mode.dmDisplayFrequency) : 0.0f;
+    }
+
+    void SetPaused(bool paused) {
+        m_paused = paused;
+        LOG_INFO("SetPaused: %d", paused);
+    }
+
+    bool IsPaused() const {
+        return m_paused;
+    }
+
+    // Subclasses the owner, so it must run on the owner's thread (where a
+    // window is normally created anyway)
+    bool SetSyncToOwnerPaint(bool enable) {
+        if (!enable) {
+            ReleaseOwnerPaintHook();
+            LOG_INFO("SetSyncToOwnerPaint: 0");
+            return true;
+        }
+        if (m_ownerPaintHook) return true;
+        HWND owner = m_options.owner;
+        if (!owner || GetWindowThreadProcessId(owner, nullptr) != GetCurrentThreadId()) {
+            LOG_ERROR("SetSyncToOwnerPaint: needs an owner window of the calling thread.");
+            return false;
+        }
+
+        auto* hook = new OwnerPaintHook{ {}, this, true };
+        if (!SetWindowSubclass(owner, OwnerSubclassProc, reinterpret_cast<UINT_PTR>(this),
+                               reinterpret_cast<DWORD_PTR>(hook))) {
+            LOG_ERROR("SetSyncToOwnerPaint: failed to subclass the owner window.");
+            delete hook;
+            return false;
+        }
+        m_ownerPaintHook = hook;
+        m_syncToOwnerPaint = true;
+        LOG_INFO("SetSyncToOwnerPaint: 1");
+        return true;
+    }
+
+    void SetPauseWhenOccluded(bool enable) {
+        m_pauseWhenOccluded = enable;
+        LOG_INFO("SetPauseWhenOccluded: %d", enable);
+    }
+
+    bool IsOccluded() const {
+        return m_hwnd && IsWindowOccluded(m_hwnd);
+    }
+
+    void SetOcclusionChangedCallback(BlurWindow::OcclusionChangedCallback callback, void* userData) {
+        std::lock_guard<std::mutex> lock(m_occlusionMutex);
+        m_occlusionCallback = callback;
+        m_occlusionUserData = userData;
+        m_occlusionWatched = callback != nullptr;
+    }
+
+    void SetFrozen(bool frozen) {
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        m_frozen = frozen;
+        // The snapshot itself is taken by the next frame (see AcquireBackdrop)
+        if (!frozen) m_frozenTexture.Reset();
+        LOG_INFO("SetFrozen: %d", frozen);
+    }
+
+    bool IsFrozen() const {
+        return m_frozen;
+    }
+
+    // Before the HWND exists (it is created by the first Start), only decides
+    // whether it is created visible
+    void SetVisible(bool visible) {
+        m_visible = visible;
+        if (m_hwnd) ShowWindow(m_hwnd, visible ? SW_SHOWNOACTIVATE : SW_HIDE);
+    }
+
+    bool IsVisible() const {
+        return m_visible;
+    }
+
+    static EffectType EffectTypeFromName(const std::string& name) {
+        if (name == "kawase" || name == "Kawase") return EffectType::Kawase;
+        if (name == "box" || name == "Box") return EffectType::Box;
+        if (name == "radial" || name == "Radial") return EffectType::Radial;
+        if (name == "rain" || name == "Rain") return EffectType::Rain;
+        return EffectType::Gaussian;
+    }
+
+    // The first stage becomes the window's effect, which the window-level
+    // setters keep changing; later stages are layers applied in order to its
+    // output, each with only its own stage params.
+    bool SetEffectPipeline(const std::string& jsonConfig) {
+        EffectType type = EffectType::Gaussian;
+        std::string stageParams;
+        std::vector<std::unique_ptr<IBlurEffect>> layers;
+
+        // Structured form: {"version": 1, "pipeline": [{"type": ..., "params": {...}}]}
+        auto config = ConfigManager::ParsePipelineJson(jsonConfig);
+        if (config.effects.size() > BlurWindow::MaxEffectLayers) {
+            LOG_ERROR("SetEffectPipeline: %zu stages, at most %zu are supported",
+                config.effects.size(), BlurWindow::MaxEffectLayers);
+            return false;
+        }
+        if (!config.effects.empty()) {
+            type = EffectTypeFromName(config.effects[0].type);
+            stageParams = config.effects[0].params;
+            for (size_t i = 1; i < config.effects.size(); i++) {
+                auto layer = SubsystemFactory::CreateEffect(EffectTypeFromName(config.effects[i].type));
+                if (!layer || !layer->Initialize(m_device)) {
+                    LOG_ERROR("SetEffectPipeline: failed to create layer %zu (%s)", i, config.effects[i].type.c_str());
+                    return false;
+                }
+                ApplyLayerParams(config.effects[i].params, layer.get());
+                layers.push_back(std::move(layer));
+            }
+        } else {
+            // Simple dispatcher based on JSON type field
+            if (jsonConfig.find("\"kawase\"") != std::string::npos) type = EffectType::Kawase;
+            else if (jsonConfig.find("\"box\"") != std::string::npos) type = EffectType::Box;
+            else if (jsonConfig.find("\"radial\"") != std::string::npos) type = EffectType::Radial;
+            else if (jsonConfig.find("\"rain\"") != std::string::npos) type = EffectType::Rain;
+        }
+
+        LOG_INFO("SetEffectPipeline: detected type=%d from config", static_cast<int>(type));
+
+        auto newEffect = SubsystemFactory::CreateEffect(type);
+        if (newEffect && newEffect->Initialize(m_device)) {
+            std::lock_guard<std::mutex> lock(m_graphicsMutex);
+            if (!stageParams.empty()) {
+                ApplyStageParams(stageParams, newEffect.get());
+            }
+            // Preserve current strength and apply to new effect
+            newEffect->SetStrengthGradient(m_currentStrength, m_strengthBottom);
+            newEffect->SetNoiseIntensity(m_noiseIntensity);
+            newEffect->SetNoiseScale(m_noiseScale);
+            newEffect->SetNoiseSpeed(m_noiseSpeed);
+            newEffect->SetNoiseType(m_noiseType);
+            newEffect->SetNoiseBlendMode(m_noiseBlendMode);
+            newEffect->SetNoiseColor(m_noiseColor[0], m_noiseColor[1], m_noiseColor[2]);
+            newEffect->SetKernelRadius(m_kernelRadius);
+            newEffect->SetNoiseSeed(m_noiseSeed);
+            newEffect->SetDirection(m_directionAngle, m_directionStrength);
+            newEffect->SetAxisScale(m_axisScale[0], m_axisScale[1]);
+            newEffect->SetColor(m_tintColor[0], m_tintColor[1], m_tintColor[2], m_tintColor[3]);
+            m_effect = std::move(newEffect);
+            m_layers = std::move(layers);
+            m_graphicsInitialized = (m_capture && m_effect && m_presenter);
+            LOG_INFO("SetEffectPipeline: new effect initialized successfully");
+            return true;
+        }
+        LOG_ERROR("SetEffectPipeline: failed to create or initialize effect type=%d", static_cast<int>(type));
+        return false;
+    }
+
+    // Window-level values carried in a pipeline stage's "params" object
+    // (must be called with m_graphicsMutex held)
+    void ApplyStageParams(const std::string& params, IBlurEffect* effect) {
+        float value = 0.0f;
+        if (ConfigManager::GetFloat(params, "strength", value)) {
+            m_currentStrength = m_strengthBottom = value;
+            m_axisScale[0] = m_axisScale[1] = 1.0f;
+        }
+        if (ConfigManager::GetFloat(params, "noiseIntensity", value)) m_noiseIntensity = value;
+        if (ConfigManager::GetFloat(params, "noiseScale", value)) m_noiseScale = value;
+        if (ConfigManager::GetFloat(params, "noiseSpeed", value)) m_noiseSpeed = value;
+        if (ConfigManager::GetFloat(params, "noiseType", value)) m_noiseType = static_cast<int>(value);
+        if (ConfigManager::GetFloat(params, "noiseBlendMode", value)) m_noiseBlendMode = static_cast<int>(value);
+        ConfigManager::GetFloatArray(params, "tint", m_tintColor, 4);
+        if (ConfigManager::GetFloat(params, "kernelRadius", value)) {
+            m_kernelRadius = std::clamp(static_cast<int>(value), 0, BlurWindow::MaxKernelRadius);
+        }
+        ApplyEffectParams(params, effect);
+    }
+
+    // A layer's stage params go straight to its effect; window-level values
+    // are left alone
+    static void ApplyLayerParams(const std::string& params, IBlurEffect* effect) {
+        float value = 0.0f;
+        if (ConfigManager::GetFloat(params, "strength", value)) effect->SetStrength(value);
+        if (ConfigManager::GetFloat(params, "noiseIntensity", value)) effect->SetNoiseIntensity(value);
+        if (ConfigManager::GetFloat(params, "noiseScale", value)) effect->SetNoiseScale(value);
+        if (ConfigManager::GetFloat(params, "noiseSpeed", value)) effect->SetNoiseSpeed(value);
+        if (ConfigManager::GetFloat(params, "noiseType", value)) effect->SetNoiseType(static_cast<int>(value));
+        if (ConfigManager::GetFloat(params, "noiseBlendMode", value)) effect->SetNoiseBlendMode(static_cast<int>(value));
+        float tint[4];
+        if (ConfigManager::GetFloatArray(params, "tint", tint, 4)) effect->SetColor(tint[0], tint[1], tint[2], tint[3]);
+        if (ConfigManager::GetFloat(params, "kernelRadius", value)) {
+            effect->SetKernelRadius(std::clamp(static_cast<int>(value), 0, BlurWindow::MaxKernelRadius));
+        }
+        ApplyEffectParams(params, effect);
+    }
+
+    // Stage params only the effect itself understands
+    static void ApplyEffectParams(const std::string& params, IBlurEffect* effect) {
+        float value = 0.0f;
+        if (ConfigManager::GetFloat(params, "passes", value)) {
+            // Only iterative effects (Kawase) understand this key
+            char buffer[64];
+            snprintf(buffer, sizeof(buffer), "{\"iterations\": %.0f}", value);
+            effect->SetParameters(buffer);
+        }
+        if (ConfigManager::GetFloat(params, "param", value)) {
+            char buffer[64];
+            snprintf(buffer, sizeof(buffer), "{\"param\": %.9g}", value);
+            effect->SetParameters(buffer);
+        }
+    }
+
+    void SetBlurStrength(float strength) {
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        m_currentStrength = strength;
+        m_strengthBottom = strength;
+        m_axisScale[0] = m_axisScale[1] = 1.0f;
+        LOG_INFO("SetBlurStrength: %.2f", strength);
+        if (m_effect) {
+            m_effect->SetStrength(strength);
+            m_effect->SetAxisScale(1.0f, 1.0f);
+        }
+    }
+
+    void SetStrengthGradient(float top, float bottom) {
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        m_currentStrength = top;
+        m_strengthBottom = bottom;
+        m_axisScale[0] = m_axisScale[1] = 1.0f;
+        if (m_effect) {
+            m_effect->SetStrengthGradient(top, bottom);
+            m_effect->SetAxisScale(1.0f, 1.0f);
+        }
+    }
+
+    // The stronger axis sets the blend strength and the kernel reaches
+    // proportionally less far along the other, so x == y is SetBlurStrength(x).
+    void SetBlurXY(float x, float y) {
+        x = std::clamp(x, 0.0f, 1.0f);
+        y = std::clamp(y, 0.0f, 1.0f);
+        float strength = std::max(x, y);
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        m_currentStrength = strength;
+        m_strengthBottom = strength;
+        m_axisScale[0] = strength > 0.0f ? x / strength : 1.0f;
+        m_axisScale[1] = strength > 0.0f ? y / strength : 1.0f;
+        LOG_INFO("SetBlurXY: %.2f, %.2f", x, y);
+        if (m_effect) {
+            m_effect->SetStrength(strength);
+            m_effect->SetAxisScale(m_axisScale[0], m_axisScale[1]);
+        }
+    }
+
+    void SetDownsampleXY(uint32_t x, uint32_t y) {
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        m_downsample[0] = x;
+        m_downsample[1] = y;
+        LOG_INFO("SetDownsampleXY: %u, %u", x, y);
+    }
+
+    void SetTemporalMode(TemporalMode mode) {
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        m_temporalMode = mode;
+        // Each mode starts over from the next two captures
+        ReleaseTemporalTargets();
+        LOG_INFO("SetTemporalMode: %d", static_cast<int>(mode));
+    }
+
+    void SetBlurColor(float r, float g, float b, float a) {
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        m_tintColor[0] = r; m_tintColor[1] = g; m_tintColor[2] = b; m_tintColor[3] = a;
+        if (m_effect) {
+            m_effect->SetColor(r, g, b, a);
+        }
+    }
+
+    void SetDebugOverlay(bool enabled) {
+        m_debugOverlayEnabled = enabled;
+    }
+
+    void SetFallbackColor(float r, float g, float b, float a) {
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        m_fallbackColor[0] = r; m_fallbackColor[1] = g; m_fallbackColor[2] = b; m_fallbackColor[3] = a;
+    }
+
+    void SetToneMapping(int mode) {
+        m_toneMapping = mode;
+        LOG_INFO("SetToneMapping: %d", mode);
+    }
+
+    // Average color of the last captured backdrop: the capture is reduced to
+    // 1x1 through its mip chain and read back, which waits for the GPU.
+    // RGB in 0..1, alpha always 1. False until a frame has been captured.
+    bool SampleDominantColor(float outRgba[4]) {
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        ID3D11Texture2D* source = m_lastCapturedTexture;
+        if (!m_graphicsInitialized || !source) return false;
+
+        D3D11_TEXTURE2D_DESC sourceDesc;
+        source->GetDesc(&sourceDesc);
+        D3D11_TEXTURE2D_DESC desc = {};
+        if (m_sampleTexture) m_sampleTexture->GetDesc(&desc);
+        if (!m_sampleTexture || desc.Width != sourceDesc.Width || desc.Height != sourceDesc.Height) {
+            desc = {};
+            desc.Width = sourceDesc.Width;
+            desc.Height = sourceDesc.Height;
+            desc.MipLevels = 0;  // Full chain
+            desc.ArraySize = 1;
+            desc.Format = DXGI_FORMAT_B8G8R8A8_UNORM;
+            desc.SampleDesc.Count = 1;
+            desc.Usage = D3D11_USAGE_DEFAULT;
+            desc.BindFlags = D3D11_BIND_SHADER_RESOURCE | D3D11_BIND_RENDER_TARGET;
+            desc.MiscFlags = D3D11_RESOURCE_MISC_GENERATE_MIPS;
+
+            D3D11_TEXTURE2D_DESC stagingDesc = {};
+            stagingDesc.Width = stagingDesc.Height = 1;
+            stagingDesc.MipLevels = stagingDesc.ArraySize = 1;
+            stagingDesc.Format = DXGI_FORMAT_B8G8R8A8_UNORM;
+            stagingDesc.SampleDesc.Count = 1;
+            stagingDesc.Usage = D3D11_USAGE_STAGING;
+            stagingDesc.CPUAccessFlags = D3D11_CPU_ACCESS_READ;
+
+            m_sampleSRV.Reset();
+            if (FAILED(m_device->CreateTexture2D(&desc, nullptr, m_sampleTexture.ReleaseAndGetAddressOf())) ||
+                FAILED(m_device->CreateShaderResourceView(m_sampleTexture.Get(), nullptr, m_sampleSRV.GetAddressOf())) ||
+                FAILED(m_device->CreateTexture2D(&stagingDesc, nullptr, m_sampleStaging.ReleaseAndGetAddressOf()))) {
+                LOG_ERROR("SampleDominantColor: failed to create sampling textures.");
+                m_sampleTexture.Reset();
+                return false;
+            }
+            m_sampleTexture->GetDesc(&desc);
+        }
+
+        m_context->CopySubresourceRegion(m_sampleTexture.Get(), 0, 0, 0, 0, source, 0, nullptr);
+        m_context->GenerateMips(m_sampleSRV.Get());
+        m_context->CopySubresourceRegion(m_sampleStaging.Get(), 0, 0, 0, 0, m_sampleTexture.Get(), desc.MipLevels - 1, nullptr);
+
+        D3D11_MAPPED_SUBRESOURCE mapped;
+        if (FAILED(m_context->Map(m_sampleStaging.Get(), 0, D3D11_MAP_READ, 0, &mapped))) return false;
+        const uint8_t* bgra = static_cast<const uint8_t*>(mapped.pData);
+        outRgba[0] = bgra[2] / 255.0f;
+        outRgba[1] = bgra[1] / 255.0f;
+        outRgba[2] = bgra[0] / 255.0f;
+        outRgba[3] = 1.0f;
+        m_context->Unmap(m_sampleStaging.Get(), 0);
+        return true;
+    }
+
+    // nullptr captures the desktop. Before graphics exist the source is only
+    // stored; afterwards the current capture is kept if the new one fails.
+    bool SetCaptureSource(HWND source) {
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        if (!m_graphicsInitialized) {
+            m_captureSource = source;
+            return true;
+        }
+
+        CaptureType type;
+        auto capture = CreateCaptureFor(source, &type);
+        if (!capture) return false;
+
+        if (m_capture) m_capture->Shutdown();
+        m_capture = std::move(capture);
+        m_captureType = type;
+        m_captureSource = source;
+        m_capturedSRV.Reset();
+        m_lastCapturedTexture = nullptr;
+        LOG_INFO("SetCaptureSource: %p", source);
+        return true;
+    }
+
+    // nullptr returns to screen capture. The texture is opened on the shared
+    // device, which exists before graphics do, so it's validated right away.
+    bool SetSourceTexture(HANDLE sharedHandle) {
+        ComPtr<ID3D11Texture2D> texture;
+        if (sharedHandle) {
+            ID3D11Device* device = BlurSystem::Instance().GetDevice();
+            if (!device) return false;
+            // Legacy shared handles first, then NT handles (D3D11_RESOURCE_MISC_SHARED_NTHANDLE)
+            HRESULT hr = device->OpenSharedResource(sharedHandle, IID_PPV_ARGS(texture.GetAddressOf()));
+            if (FAILED(hr)) {
+                ComPtr<ID3D11Device1> device1;
+                if (SUCCEEDED(device->QueryInterface(IID_PPV_ARGS(device1.GetAddressOf())))) {
+                    hr = device1->OpenSharedResource1(sharedHandle, IID_PPV_ARGS(texture.ReleaseAndGetAddressOf()));
+                }
+            }
+            if (FAILED(hr)) {
+                LOG_ERROR("SetSourceTexture: failed to open shared handle (0x%08X).", hr);
+                return false;
+            }
+
+            D3D11_TEXTURE2D_DESC desc;
+            texture->GetDesc(&desc);
+            if (desc.Format != DXGI_FORMAT_B8G8R8A8_UNORM || !(desc.BindFlags & D3D11_BIND_SHADER_RESOURCE) ||
+                desc.SampleDesc.Count != 1) {
+                LOG_ERROR("SetSourceTexture: unsupported texture (format %d, bind 0x%X, %u samples).",
+                    desc.Format, desc.BindFlags, desc.SampleDesc.Count);
+                return false;
+            }
+            LOG_INFO("SetSourceTexture: %ux%u shared texture.", desc.Width, desc.Height);
+        } else {
+            LOG_INFO("SetSourceTexture: back to screen capture.");
+        }
+
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        m_sourceTexture = std::move(texture);
+        // A new texture could reuse the old one's address, so drop the cached view
+        m_capturedSRV.Reset();
+        m_lastCapturedTexture = nullptr;
+        return true;
+    }
+
+    // Uploads a copy, so the caller's buffer can go right away. Swizzled to
+    // BGRA to match captured frames, which effects copy from and into.
+    bool SetBackgroundImage(const uint8_t* rgba, uint32_t width, uint32_t height) {
+        ComPtr<ID3D11Texture2D> texture;
+        if (rgba) {
+            if (width == 0 || height == 0 || width > D3D11_REQ_TEXTURE2D_U_OR_V_DIMENSION ||
+                height > D3D11_REQ_TEXTURE2D_U_OR_V_DIMENSION) {
+                LOG_ERROR("SetBackgroundImage: unsupported size %ux%u.", width, height);
+                return false;
+            }
+            ID3D11Device* device = BlurSystem::Instance().GetDevice();
+            if (!device) return false;
+
+            std::vector<uint8_t> bgra(rgba, rgba + static_cast<size_t>(width) * height * 4);
+            for (size_t i = 0; i < bgra.size(); i += 4) {
+                std::swap(bgra[i], bgra[i + 2]);
+            }
+
+            D3D11_TEXTURE2D_DESC desc = {};
+            desc.Width = width;
+            desc.Height = height;
+            desc.MipLevels = 1;
+            desc.ArraySize = 1;
+            desc.Format = DXGI_FORMAT_B8G8R8A8_UNORM;
+            desc.SampleDesc.Count = 1;
+            desc.Usage = D3D11_USAGE_IMMUTABLE;
+            desc.BindFlags = D3D11_BIND_SHADER_RESOURCE;
+
+            D3D11_SUBRESOURCE_DATA data = {};
+            data.pSysMem = bgra.data();
+            data.SysMemPitch = width * 4;
+
+            HRESULT hr = device->CreateTexture2D(&desc, &data, texture.GetAddressOf());
+            if (FAILED(hr)) {
+                LOG_ERROR("SetBackgroundImage: failed to create texture (0x%08X).", hr);
+                return false;
+            }
+            LOG_INFO("SetBackgroundImage: %ux%u image.", width, height);
+        } else {
+            LOG_INFO("SetBackgroundImage: cleared.");
+        }
+
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        m_backgroundImage = std::move(texture);
+        m_capturedSRV.Reset();
+        m_lastCapturedTexture = nullptr;
+        return true;
+    }
+
+    // The shader applies one tint as lerp(color, tint.rgb, tint.a^2), so a
+    // stack of lerps collapses into a single equivalent tint.
+    void SetTintLayers(const float* rgba, size_t count) {
+        float weight = 0.0f;
+        float rgb[3] = { 0.0f, 0.0f, 0.0f };
+        for (size_t i = 0; i < count; i++) {
+            const float* layer = rgba + i * 4;
+            float w = layer[3] * layer[3];
+            for (int c = 0; c < 3; c++) {
+                rgb[c] = rgb[c] * (1.0f - w) + layer[c] * w;
+            }
+            weight = weight + w - weight * w;
+        }
+
+        if (weight > 0.0f) {
+            SetBlurColor(rgb[0] / weight, rgb[1] / weight, rgb[2] / weight, std::sqrt(weight));
+        } else {
+            SetBlurColor(0.0f, 0.0f, 0.0f, 0.0f);
+        }
+        LOG_INFO("SetTintLayers: %zu layers", count);
+    }
+
+    void SetEffectType(int type) {
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        SetEffectTypeInternal(type);
+    }
+    
+    // Internal version without lock (must be called with m_graphicsMutex held)
+    void SetEffectTypeInternal(int type) {
+        EffectType effectType = static_cast<EffectType>(type);
+        LOG_DEBUG("SetEffectTypeInternal: switching to type {}", type);
+        
+        auto newEffect = SubsystemFactory::CreateEffect(effectType);
+        if (!newEffect) {
+            LOG_ERROR("SetEffectTypeInternal: CreateEffect failed for type {}", type);
+            return;
+        }
+        
+        if (!newEffect->Initialize(m_device)) {
+            LOG_ERROR("SetEffectTypeInternal: Initialize failed, m_device={}", (void*)m_device);
+            return;
+        }
+        
+        newEffect->SetStrengthGradient(m_currentStrength, m_strengthBottom);
+        newEffect->SetNoiseIntensity(m_noiseIntensity);
+        newEffect->SetNoiseScale(m_noiseScale);
+        newEffect->SetNoiseSpeed(m_noiseSpeed);
+        newEffect->SetNoiseType(m_noiseType);
+        newEffect->SetNoiseBlendMode(m_noiseBlendMode);
+        newEffect->SetNoiseColor(m_noiseColor[0], m_noiseColor[1], m_noiseColor[2]);
+        newEffect->SetKernelRadius(m_kernelRadius);
+        newEffect->SetNoiseSeed(m_noiseSeed);
+        newEffect->SetDirection(m_directionAngle, m_directionStrength);
+        newEffect->SetAxisScale(m_axisScale[0], m_axisScale[1]);
+        newEffect->SetColor(m_tintColor[0], m_tintColor[1], m_tintColor[2], m_tintColor[3]);
+        m_effect = std::move(newEffect);
+        LOG_INFO("SetEffectTypeInternal: Successfully switched to type {}", type);
+    }
+    
+    // Helper: Ensure RainEffect is active (must be called with m_graphicsMutex held)
+    RainEffect* EnsureRainEffect() {
+        auto* rain = dynamic_cast<RainEffect*>(m_effect.get());
+        if (!rain) {
+            LOG_INFO("EnsureRainEffect: Current effect is not RainEffect, switching...");
+            SetEffectTypeInternal(static_cast<int>(EffectType::Rain));
+            rain = dynamic_cast<RainEffect*>(m_effect.get());
+            if (!rain) {
+                LOG_ERROR("EnsureRainEffect: Failed to switch to RainEffect");
+            } else {
+                LOG_INFO("EnsureRainEffect: Successfully switched to RainEffect");
+            }
+        }
+        return rain;
+    }
+
+    void SetBlurParam(float param) {
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        if (m_effect) {
+            // This is a bit of a hack since IBlurEffect doesn't have a generic SetParam
+            // We'll need to check the effect type or add SetParam to IBlurEffect
+            // For now, let's assume SetParameters can handle a simple float-check
+            char buffer[64];
+            snprintf(buffer, sizeof(buffer), "{\"param\": %.2f}", param);
+            m_effect->SetParameters(buffer);
+        }
+    }
+
+    void SetKernelRadius(int radius) {
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        m_kernelRadius = radius;
+        LOG_INFO("SetKernelRadius: %d", radius);
+        if (m_effect) m_effect->SetKernelRadius(radius);
+    }
+
+    void SetEdgeFeather(float pixels) {
+        m_edgeFeather = (std::max)(pixels, 0.0f);
+        LOG_INFO("SetEdgeFeather: %.1f px", pixels);
+    }
+
+    // Compiled against the system's device right away, so errors reach the
+    // caller even before the window's graphics exist. Empty clears the pass.
+    bool SetCustomShader(const std::string& hlsl, std::string* errors) {
+        if (hlsl.empty()) {
+            std::lock_guard<std::mutex> lock(m_graphicsMutex);
+            m_customPS.Reset();
+            LOG_INFO("SetCustomShader: cleared");
+            return true;
+        }
+
+        ID3D11Device* device = BlurSystem::Instance().GetDevice();
+        if (!device) {
+            if (errors) *errors = "Blur system is not initialized";
+            return false;
+        }
+        std::string source = std::string(g_CustomShaderPrologue) + hlsl + g_CustomShaderEpilogue;
+        ComPtr<ID3D11PixelShader> shader;
+        if (!ShaderLoader::CompilePixelShader(device, source.c_str(), source.size(), "main",
+                                              shader.GetAddressOf(), errors)) {
+            return false;
+        }
+
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        m_customPS = std::move(shader);
+        LOG_INFO("SetCustomShader: %zu bytes compiled", hlsl.size());
+        return true;
+    }
+
+    void SetEdgeAA(uint32_t samples) {
+        m_edgeAASamples = samples;
+        LOG_INFO("SetEdgeAA: %u samples", samples);
+    }
+
+    void SetNoiseIntensity(float intensity) {
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        m_noiseIntensity = intensity;
+        if (m_effect) m_effect->SetNoiseIntensity(intensity);
+    }
+
+    void SetNoiseScale(float scale) {
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        m_noiseScale = scale;
+        if (m_effect) m_effect->SetNoiseScale(scale);
+    }
+
+    void SetNoiseSpeed(float speed) {
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        m_noiseSpeed = speed;
+        if (m_effect) m_effect->SetNoiseSpeed(speed);
+    }
+
+    void SetNoiseType(int type) {
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        m_noiseType = type;
+        if (m_effect) m_effect->SetNoiseType(type);
+    }
+
+    void SetNoiseBlendMode(int mode) {
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        m_noiseBlendMode = mode;
+        if (m_effect) m_effect->SetNoiseBlendMode(mode);
+    }
+
+    void SetNoiseColor(float r, float g, float b) {
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        m_noiseColor[0] = r;
+        m_noiseColor[1] = g;
+        m_noiseColor[2] = b;
+        if (m_effect) m_effect->SetNoiseColor(r, g, b);
+    }
+
+    void SetNoiseSeed(uint32_t seed) {
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        m_noiseSeed = seed;
+        if (m_effect) m_effect->SetNoiseSeed(seed);
+    }
+
+    void SetDirection(float angleDegrees, float strength) {
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        m_directionAngle = angleDegrees;
+        m_directionStrength = strength;
+        LOG_INFO("SetDirection: %.1f deg, strength %.2f", angleDegrees, strength);
+        if (m_effect) m_effect->SetDirection(angleDegrees, strength);
+    }
+
+    // Experimental features without a typed entry point yet. Entries may
+    // change or disappear between releases; promote them to real setters
+    // once they settle.
+    bool RunCommand(const std::string& command, const std::string& args, std::string& error) {
+        struct Command {
+            const char* name;
+            bool (Impl::*run)(const std::string& args, std::string& error);
+        };
+        static const Command commands[] = {
+            {"effect.parameters", &Impl::CommandEffectParameters},
+        };
+        for (const Command& entry : commands) {
+            if (command == entry.name) {
+                LOG_INFO("RunCommand: %s %s", command.c_str(), args.c_str());
+                return (this->*entry.run)(args, error);
+            }
+        }
+        error = "Unknown command '" + command + "'";
+        return false;
+    }
+
+    // Hand a JSON object straight to the active effect's SetParameters, e.g.
+    // {"iterations": 6} for Kawase
+    bool CommandEffectParameters(const std::string& args, std::string& error) {
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        if (!m_effect) {
+            error = "No effect is active yet";
+            return false;
+        }
+        if (!m_effect->SetParameters(args.c_str())) {
+            error = std::string("Effect ") + m_effect->GetName() + " rejected the parameters";
+            return false;
+        }
+        return true;
+    }
+
+    // --- Rain Effect Control ---
+
+    void SetRainIntensity(float intensity) {
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        // Auto-enable RainEffect if intensity > 0
+        RainEffect* rain = (intensity > 0) ? EnsureRainEffect() : dynamic_cast<RainEffect*>(m_effect.get());
+        if (rain) rain->SetRainIntensity(intensity);
+    }
+
+    void SetRainDropSpeed(float speed) {
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        RainEffect* rain = EnsureRainEffect();
+        if (rain) rain->SetDropSpeed(speed);
+    }
+
+    void SetRainRefraction(float strength) {
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        RainEffect* rain = EnsureRainEffect();
+        if (rain) rain->SetRefractionStrength(strength);
+    }
+
+    void SetRainTrailLength(float length) {
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        RainEffect* rain = EnsureRainEffect();
+        if (rain) rain->SetTrailLength(length);
+    }
+
+    void SetRainDropSize(float minSize, float maxSize) {
+        std::lock_guard<std::mutex> lock(m_graphicsMutex);
+        RainEffect* rain = EnsureRainEffect();
+        if (rain) rain->SetDropSizeRange(minSize, maxSize);
+    }
+
+    // --- Click Callback ---
+    void SetOwnerClosedCallback(BlurWindow::OwnerClosedCallback callback, void* userData) {
+        std::lock_guard<std::mutex> lock(m_ownerClosedMutex);
+        m_ownerClosedCallback = callback;
+        m_ownerClosedUserData = userData;
+    }
+
+    void SetDisplayChangedCallback(BlurWindow::DisplayChangedCallback callback, void* userData) {
+        std::lock_guard<std::mutex> lock(m_displayChangedMutex);
+        m_displayChangedCallback = callback;
+        m_displayChangedUserData = userData;
+    }
+
+    void SetPowerChangedCallback(BlurWindow::PowerChangedCallback callback, void* userData) {
+        std::lock_guard<std::mutex> lock(m_powerChangedMutex);
+        m_powerChangedCallback = callback;
+        m_powerChangedUserData = userData;
+    }
+
+    // Window's thread, like NotifyDisplayChanged
+    void NotifyPowerChanged() {
+        LOG_INFO("Power status changed.");
+        std::lock_guard<std::mutex> lock(m_powerChangedMutex);
+        if (m_powerChangedCallback) {
+            m_powerChangedCallback(m_powerChangedUserData);
+        }
+    }
+
+    // Runs on the window's thread, which may call setters such as SetBounds
+    // from the callback
+    void NotifyDisplayChanged() {
+        LOG_INFO("Display configuration changed.");
+        std::lock_guard<std::mutex> lock(m_displayChangedMutex);
+        if (m_displayChangedCallback) {
+            m_displayChangedCallback(m_displayChangedUserData);
+        }
+    }
+
+    // Owner thread, from the subclass
+    void NotifyOwnerPaint() {
+        {
+            std::lock_guard<std::mutex> lock(m_ownerPaintMutex);
+            m_ownerPaintCount++;
+        }
+        m_ownerPaintCondition.notify_one();
+    }
+
+    // Render thread only. True once the owner painted since the last frame;
+    // false after OWNER_PAINT_WAIT without a paint
+    bool WaitForOwnerPaint() {
+        std::unique_lock<std::mutex> lock(m_ownerPaintMutex);
+        bool painted = m_ownerPaintCondition.wait_for(lock, OWNER_PAINT_WAIT, [this]() {
+            return m_ownerPaintCount != m_renderedPaintCount || !m_running || !m_syncToOwnerPaint;
+        });
+        if (!painted || m_ownerPaintCount == m_renderedPaintCount) return false;
+        m_renderedPaintCount = m_ownerPaintCount;
+        return true;
+    }
+
+    // The subclass can only be removed on the owner's thread. From any other
+    // thread the hook is detached instead and removes itself on the owner's
+    // next message.
+    void ReleaseOwnerPaintHook() {
+        OwnerPaintHook* hook = m_ownerPaintHook;
+        if (!hook) return;
+        m_ownerPaintHook = nullptr;
+        m_syncToOwnerPaint = false;
+        m_ownerPaintCondition.notify_one();
+
+        bool release;
+        {
+            std::lock_guard<std::mutex> lock(hook->mutex);
+            hook->window = nullptr;
+            if (hook->attached && GetWindowThreadProcessId(m_options.owner, nullptr) == GetCurrentThreadId() &&
+                RemoveWindowSubclass(m_options.owner, OwnerSubclassProc, reinterpret_cast<UINT_PTR>(this))) {
+                hook->attached = false;
+            }
+            release = !hook->attached;
+        }
+        if (release) delete hook;
+    }
+
+    static LRESULT CALLBACK OwnerSubclassProc(HWND hwnd, UINT msg, WPARAM wParam, LPARAM lParam,
+                                              UINT_PTR id, DWORD_PTR refData) {
+        auto* hook = reinterpret_cast<OwnerPaintHook*>(refData);
+        bool release = false;
+        {
+            std::lock_guard<std::mutex> lock(hook->mutex);
+            if (!hook->window || msg == WM_NCDESTROY) {
+                RemoveWindowSubclass(hwnd, OwnerSubclassProc, id);
+                hook->attached = false;
+                release = !hook->window;
+            } else if (msg == WM_PAINT) {
+                hook->window->NotifyOwnerPaint();
+            }
+        }
+        if (release) delete hook;
+        return DefSubclassProc(hwnd, msg, wParam, lParam);
+    }
+
+    // Render thread only. Polls while pausing on occlusion or reporting it,
+    // so windows nobody asked about pay nothing.
+    void UpdateOcclusion() {
+        if (!m_pauseWhenOccluded && !m_occlusionWatched) {
+            m_occluded = false;
+            return;
+        }
+        auto now = std::chrono::steady_clock::now();
+        if (now - m_lastOcclusionCheck < OCCLUSION_POLL_INTERVAL) return;
+        m_lastOcclusionCheck = now;
+
+        bool occluded = m_hwnd && IsWindowOccluded(m_hwnd);
+        if (m_occluded.exchange(occluded) == occluded) return;
+        LOG_INFO("Window %s.", occluded ? "occluded" : "revealed");
+
+        std::lock_guard<std::mutex> lock(m_occlusionMutex);
+        if (m_occlusionCallback) {
+            m_occlusionCallback(occluded ? 1 : 0, m_occlusionUserData);
+        }
+    }
+
+    void SetFrameCallback(BlurWindow::FrameCallback callback, void* userData) {
+        std::lock_guard<std::mutex> lock(m_frameCallbackMutex);
+        m_frameCallback = callback;
+        m_frameUserData = userData;
+    }
+
+    uint32_t GetFrameHistory(BlurWindow::FrameSample* out, uint32_t capacity) const {
+        std::lock_guard<std::mutex> lock(m_frameHistoryMutex);
+        size_t kept = m_frameHistory.size();
+        if (!out) return static_cast<uint32_t>(kept);
+
+        // Once full, the oldest sample sits where the next one goes
+        size_t count = (std::min)(kept, static_cast<size_t>(capacity));
+        size_t oldest = (kept < BlurWindow::FrameHistorySize) ? 0 : m_frameHistoryNext;
+        size_t skip = kept - count;
+        for (size_t i = 0; i < count; i++) {
+            out[i] = m_frameHistory[(oldest + skip + i) % kept];
+        }
+        return static_cast<uint32_t>(count);
+    }
+
+    // Called after each presented frame, outside m_graphicsMutex so the
+    // callback may call setters
+    void NotifyFrame(float frameTimeMs) {
+        uint64_t index = m_frameIndex++;
+        {
+            std::lock_guard<std::mutex> lock(m_frameHistoryMutex);
+            BlurWindow::FrameSample sample = { index, frameTimeMs, m_currentFPS.load() };
+            if (m_frameHistory.size() < BlurWindow::FrameHistorySize) {
+                m_frameHistory.push_back(sample);
+            } else {
+                m_frameHistory[m_frameHistoryNext] = sample;
+            }
+            m_frameHistoryNext = (m_frameHistoryNext + 1) % BlurWindow::FrameHistorySize;
+        }
+        std::lock_guard<std::mutex> lock(m_frameCallbackMutex);
+        if (m_frameCallback) {
+            m_frameCallback(index, frameTimeMs, m_frameUserData);
+        }
+    }
+
+    // Runs at most once, on the render thread (owner polled each frame) or the
+    // window's thread (our owned/child window destroyed along with its owner).
+    void NotifyOwnerClosed() {
+        if (m_ownerClosed.exchange(true)) return;
+        LOG_INFO("Owner window was destroyed.");
+
+        // Held while calling so a callback being replaced is never called afterwards
+        std::lock_guard<std::mutex> lock(m_ownerClosedMutex);
+        if (m_ownerClosedCallback) {
+            m_ownerClosedCallback(m_ownerClosedUserData);
+        } else {
+            // Default: stop rendering. This may be the render thread itself, so
+            // only signal the loop; Stop() or the destructor joins it.
+            m_running = false;
+        }
+    }
+
+    void SetClickCallback(BlurWindow::ClickCallback callback, void* userData) {
+        m_clickCallback = callback;
+        m_clickUserData = userData;
+        LOG_INFO("SetClickCallback: callback=%p, userData=%p", (void*)callback, userData);
+    }
+
+    bool IsInitialized() const {
+        return m_graphicsInitialized;
+    }
+
+    bool Initialize() {
+        if (m_graphicsInitialized) return true;
+        
+        LOG_INFO("Manual initialization requested.");
+        if (!m_hwnd) CreateBlurWindow();
+        bool success = InitializeGraphicsBasics() && InitializeSubsystems();
+        if (success && m_capture && m_hwnd) {
+            m_capture->SetSelfWindow(m_hwnd);
         }
         return success;
     }
@@ -342,21 +1616,51 @@ private:
         return CreateOutputTexture();
     }
 
+    // DXGI for the desktop, WGC for a source window. A system preference for
+    // DXGI captures the desktop even with a source window, falling back to WGC
+    // if DXGI fails; WGC only captures windows, so preferring it changes
+    // nothing for the desktop. Returns nullptr on failure.
+    std::unique_ptr<ICaptureSubsystem> CreateCaptureFor(HWND source, CaptureType* outType) {
+        if (source && BlurSystem::Instance().GetPreferredCaptureMethod() == 1) {
+            *outType = CaptureType::DXGI;
+            if (auto capture = StartCapture(CaptureType::DXGI, nullptr)) return capture;
+            LOG_WARN("Preferred Desktop Duplication unavailable, capturing the source window.");
+        }
+        *outType = source ? CaptureType::WGC : CaptureType::DXGI;
+        return StartCapture(*outType, source);
+    }
+
+    std::unique_ptr<ICaptureSubsystem> StartCapture(CaptureType type, HWND source) {
+        auto capture = SubsystemFactory::CreateCapture(type);
+        if (!capture || !capture->Initialize(m_device)) {
+            LOG_ERROR("Failed to initialize %s capture.", type == CaptureType::WGC ? "WGC" : "DXGI");
+            m_captureStatus = capture ? capture->GetStatus() : CaptureStatus::Failed;
+            return nullptr;
+        }
+        capture->SetSelfWindow(m_hwnd);
+        if (!capture->SetSourceWindow(source)) {
+            m_captureStatus = capture->GetStatus();
+            capture->Shutdown();
+            return nullptr;
+        }
+        m_captureStatus = capture->GetStatus();
+        return capture;
+    }
+
     bool InitializeSubsystems() {
         if (!m_device || !m_hwnd) return false;
 
         LOG_INFO("Initializing subsystems...");
 
         // 1. Initialize capture
-        m_capture = SubsystemFactory::CreateCapture(CaptureType::DXGI);
+        m_capture = CreateCaptureFor(m_captureSource, &m_captureType);
+        if (!m_capture && m_captureSource) {
+            LOG_WARN("Window capture unavailable, falling back to desktop capture.");
+            m_captureSource = nullptr;
+            m_capture = CreateCaptureFor(nullptr, &m_captureType);
+        }
         if (m_capture) {
-            if (!m_capture->Initialize(m_device)) {
-                LOG_ERROR("Failed to initialize DXGI capture.");
-                m_capture.reset();
-            } else {
-                m_capture->SetSelfWindow(m_hwnd);
-                LOG_INFO("Capture initialized.");
-            }
+            LOG_INFO("Capture initialized.");
         }
         // 2. Initialize effect
         m_effect = SubsystemFactory::CreateEffect(EffectType::Gaussian);
@@ -394,6 +1698,8 @@ private:
         if (presenter) {
             m_presenter = std::move(presenter);
             LOG_INFO("Presenter initialized (%s).", m_useDirectComp ? "DirectComp" : "ULW");
+            // Presenters start with 2 buffers; a count set beforehand applies on the first frame
+            if (m_bufferCount != 2) m_bufferCountRequested = true;
         } else {
             LOG_ERROR("Failed to initialize any presenter.");
         }
@@ -416,11 +1722,29 @@ private:
         m_outputRTV.Reset();
         m_outputSRV.Reset();
         m_outputTexture.Reset();
+        m_capturedSRV.Reset();
+        m_lastCapturedTexture = nullptr;
+        ReleaseLayerTargets();
+        ReleaseFallbackResources();
+        ReleaseFeatherResources();
+        ReleaseCustomShaderResources();
+        ReleaseResampleResources();
+        ReleaseTemporalResources();
+        m_sampleStaging.Reset();
+        m_sampleSRV.Reset();
+        m_sampleTexture.Reset();
+        m_debugOverlay.reset();
         m_context.Reset();
         m_device = nullptr;
         
         m_capture.reset();
         m_effect.reset();
+        m_layers.clear();
+        // The regions stay; their effects are created again on the next device
+        for (auto& region : m_regionEffects) {
+            region.effect.reset();
+            region.failed = false;
+        }
         m_presenter.reset();
     }
 
@@ -454,11 +1778,39 @@ private:
         if (msg == WM_NCCREATE) {
             auto* cs = reinterpret_cast<CREATESTRUCT*>(lParam);
             SetWindowLongPtrW(hwnd, GWLP_USERDATA, reinterpret_cast<LONG_PTR>(cs->lpCreateParams));
+            SetPropW(hwnd, g_BlurWindowProp, reinterpret_cast<HANDLE>(1));
             return DefWindowProcW(hwnd, msg, wParam, lParam);
         }
+        if (msg == WM_NCDESTROY) {
+            RemovePropW(hwnd, g_BlurWindowProp);
+        }
         
         Impl* self = reinterpret_cast<Impl*>(GetWindowLongPtrW(hwnd, GWLP_USERDATA));
         
+        // WS_EX_TRANSPARENT doesn't pass clicks from a child to its parent;
+        // wallpaper windows are always click-through
+        if (self && msg == WM_NCHITTEST && (self->m_options.wallpaper ||
+            (self->m_options.child && (self->m_options.clickThrough || self->m_cursorFollow)))) {
+            return HTTRANSPARENT;
+        }
+
+        // Owned and child windows are destroyed together with their owner,
+        // wallpaper windows with the WorkerW when Explorer restarts
+        if (self && msg == WM_DESTROY && !self->m_destroyingWindow &&
+            (self->m_options.owner || self->m_options.wallpaper)) {
+            self->NotifyOwnerClosed();
+        }
+
+        // Broadcast to top-level windows only, so child windows never see it
+        if (self && msg == WM_DISPLAYCHANGE) {
+            self->NotifyDisplayChanged();
+        }
+
+        // Also top-level only; sent for battery level changes too
+        if (self && msg == WM_POWERBROADCAST && wParam == PBT_APMPOWERSTATUSCHANGE) {
+            self->NotifyPowerChanged();
+        }
+
         if (self && msg == WM_LBUTTONDOWN) {
             if (self->m_clickCallback) {
                 int x = GET_X_LPARAM(lParam);
@@ -491,8 +1843,23 @@ private:
             classRegistered = true;
         }
 
+        HWND parent = m_owner;
+        if (m_options.wallpaper) {
+            m_wallpaperHost = FindWallpaperHost();
+            if (!m_wallpaperHost) {
+                LOG_ERROR("CreateBlurWindow: no desktop WorkerW for wallpaper mode (is Explorer running?)");
+                return;
+            }
+            parent = m_wallpaperHost;
+        }
+        bool embedded = m_options.child || m_options.wallpaper;
+        POINT origin = { m_options.bounds.left, m_options.bounds.top };
+        if (m_options.wallpaper) ScreenToClient(parent, &origin);
+
         // Choose window style based on presenter type
-        DWORD exStyle = WS_EX_TOOLWINDOW | WS_EX_NOACTIVATE;
+        DWORD exStyle = embedded ? WS_EX_NOACTIVATE : (WS_EX_TOOLWINDOW | WS_EX_NOACTIVATE);
+        DWORD style = embedded ? (WS_CHILD | WS_CLIPSIBLINGS) : WS_POPUP;
+        if (m_visible) style |= WS_VISIBLE;
         
         if (m_useDirectComp) {
             // DirectComposition: use WS_EX_NOREDIRECTIONBITMAP for direct composition
@@ -504,116 +1871,913 @@ private:
             OutputDebugStringA("Creating window for UpdateLayeredWindow\n");
         }
         
-        if (m_options.topMost) {
+        if (m_options.topMost && !embedded) {
             exStyle |= WS_EX_TOPMOST;
         }
-        if (m_options.clickThrough) {
+        if (m_options.clickThrough || m_cursorFollow || m_options.wallpaper) {
             exStyle |= WS_EX_TRANSPARENT;
         }
 
-        m_hwnd = CreateWindowExW(
-            exStyle,
-            CLASS_NAME,
-            L"BlurWindow",
-            WS_POPUP | WS_VISIBLE,
-            m_options.bounds.left,
-            m_options.bounds.top,
-            m_options.bounds.right - m_options.bounds.left,
-            m_options.bounds.bottom - m_options.bounds.top,
-            m_owner,
-            nullptr,
-            GetModuleHandleW(nullptr),
-            this  // Pass Impl pointer for WndProc
-        );
-        
-        // Exclude blur window from screen capture (Windows 10 2004+)
-        // This prevents infinite recursion where the blur window captures itself
-        if (m_hwnd) {
-            SetWindowDisplayAffinity(m_hwnd, WDA_EXCLUDEFROMCAPTURE);
+        m_hwnd = CreateWindowExW(
+            exStyle,
+            CLASS_NAME,
+            L"BlurWindow",
+            style,
+            origin.x,
+            origin.y,
+            m_options.bounds.right - m_options.bounds.left,
+            m_options.bounds.bottom - m_options.bounds.top,
+            parent,
+            nullptr,
+            GetModuleHandleW(nullptr),
+            this  // Pass Impl pointer for WndProc
+        );
+        
+        // Exclude blur window from screen capture (Windows 10 2004+)
+        // This prevents infinite recursion where the blur window captures itself
+        // Display affinity only applies to top-level windows, so a child
+        // excludes its owner's root window (and with it, the whole app). The
+        // WorkerW belongs to Explorer, so wallpaper windows can't be excluded
+        if (m_hwnd) {
+            HWND captureHwnd = m_options.child ? GetAncestor(m_owner, GA_ROOT) : m_hwnd;
+            if (!m_options.wallpaper) SetWindowDisplayAffinity(captureHwnd, WDA_EXCLUDEFROMCAPTURE);
+            ApplyActiveRegion();
+            ApplyTaskbarRelation();
+        }
+    }
+
+    // Screen-space rect to capture; child bounds are owner client coordinates
+    RECT CaptureRect() const {
+        RECT rect = m_options.bounds;
+        if (m_options.child && m_owner) {
+            MapWindowPoints(m_owner, HWND_DESKTOP, reinterpret_cast<POINT*>(&rect), 2);
+        }
+        return rect;
+    }
+
+    // The window region clips both rendering and hit-testing, so the area
+    // outside the active region is transparent and click-through.
+    void ApplyActiveRegion() {
+        if (!m_hwnd) return;
+        // Cursor follow owns the region while enabled
+        if (m_cursorFollow) return;
+        // Applied together with the rest of the batch in EndUpdate
+        if (m_updateDepth > 0) {
+            m_regionDeferred = true;
+            return;
+        }
+
+        if (!m_hasActiveRegion) {
+            SetWindowRgn(m_hwnd, nullptr, TRUE);
+            return;
+        }
+
+        HRGN rgn = CreateRectRgn(m_activeRegion.left, m_activeRegion.top,
+                                 m_activeRegion.right, m_activeRegion.bottom);
+        // The system owns the region after a successful SetWindowRgn
+        if (rgn && !SetWindowRgn(m_hwnd, rgn, TRUE)) {
+            DeleteObject(rgn);
+        }
+    }
+
+    // Re-center a circular window region on the cursor; called from the
+    // render loop, outside the graphics lock since SetWindowRgn may block on
+    // the window's thread.
+    void UpdateCursorFollow() {
+        if (!m_hwnd) return;
+
+        if (!m_cursorFollow) {
+            if (m_cursorRegionDirty.exchange(false)) {
+                ApplyActiveRegion();
+            }
+            return;
+        }
+
+        POINT cursor;
+        if (!GetCursorPos(&cursor)) return;
+        RECT origin = CaptureRect();
+        cursor.x -= origin.left;
+        cursor.y -= origin.top;
+
+        if (!m_cursorRegionDirty.exchange(false) &&
+            cursor.x == m_lastCursor.x && cursor.y == m_lastCursor.y) {
+            return;
+        }
+        m_lastCursor = cursor;
+
+        int r = static_cast<int>(m_cursorRadius);
+        HRGN rgn = CreateEllipticRgn(cursor.x - r, cursor.y - r, cursor.x + r + 1, cursor.y + r + 1);
+        if (rgn && !SetWindowRgn(m_hwnd, rgn, TRUE)) {
+            DeleteObject(rgn);
+        }
+    }
+
+    void DestroyBlurWindow() {
+        if (m_hwnd) {
+            m_destroyingWindow = true;
+            DestroyWindow(m_hwnd);
+            m_hwnd = nullptr;
+        }
+    }
+
+    void RenderLoop() {
+        using clock = std::chrono::high_resolution_clock;
+        
+        // Enable high-resolution timer (1ms precision)
+        timeBeginPeriod(1);
+        
+        int frameCount = 0;
+        auto lastFPSUpdate = clock::now();
+
+        // Wait a bit for UI thread to finish ShowWindow/etc
+        std::this_thread::sleep_for(std::chrono::milliseconds(100));
+
+        while (m_running) {
+            // Owner paints pace the loop instead of the target frame rate
+            bool ownerPaced = m_syncToOwnerPaint;
+            bool ownerPainted = ownerPaced && WaitForOwnerPaint();
+            auto frameStart = clock::now();
+            
+            static bool firstFrameLogged = false;
+            
+            if (m_options.owner && !IsWindow(m_options.owner)) {
+                NotifyOwnerClosed();
+                if (!m_running) break;
+            }
+
+            // Hold frames while a batch is open so its changes land together
+            bool updating = m_updateDepth > 0;
+
+            // リサイズ要求の処理（RenderLoop内で安全にD3Dリソースを再作成）
+            if (!updating) {
+                ProcessPendingResize();
+                UpdateCursorFollow();
+            }
+            UpdateOcclusion();
+            bool skipOccluded = m_pauseWhenOccluded && m_occluded;
+            
+            bool rendered = false;
+            bool frameDue = !ownerPaced || ownerPainted;
+            if (!m_paused && !updating && !skipOccluded && frameDue) { // Strict lock around all D3D11 context usage
+                std::lock_guard<std::mutex> lock(m_graphicsMutex);
+                if (m_graphicsInitialized && m_capture && m_effect && m_presenter) {
+                    ID3D11Texture2D* capturedTexture = nullptr;
+                    // Inside lock, we rely on the 16ms/0ms timeout in DXGICapture to not block UI too long
+                    if (AcquireBackdrop(&capturedTexture)) {
+                        RenderFrame(capturedTexture);
+                        rendered = true;
+                        if (!firstFrameLogged) {
+                            LOG_INFO("First frame rendered and presented successfully.");
+                            firstFrameLogged = true;
+                        }
+                    }
+                }
+            }
+            m_frameTimeMs = std::chrono::duration<float, std::milli>(clock::now() - frameStart).count();
+            if (rendered) {
+                NotifyFrame(m_frameTimeMs);
+            }
+
+            frameCount++;
+            
+            // Update FPS every second
+            auto now = clock::now();
+            auto fpsDelta = std::chrono::duration_cast<std::chrono::milliseconds>(now - lastFPSUpdate);
+            if (fpsDelta.count() >= 1000) {
+                m_currentFPS = static_cast<float>(frameCount) * 1000.0f / fpsDelta.count();
+                frameCount = 0;
+                lastFPSUpdate = now;
+            }
+
+            // Frame timing - sleep to achieve target FPS. The minimum interval
+            // is a floor over both it and owner paints, which were already
+            // waited for
+            auto targetFrameTime = std::chrono::microseconds(m_minFrameIntervalMs * 1000LL);
+            if (!ownerPaced) {
+                targetFrameTime = (std::max)(targetFrameTime, std::chrono::microseconds(1000000 / GetTargetFPS()));
+            }
+            auto frameTime = clock::now() - frameStart;
+            
+            if (frameTime < targetFrameTime) {
+                auto sleepTime = targetFrameTime - frameTime;
+                // Use precise sleep (shorter intervals)
+                auto sleepMs = std::chrono::duration_cast<std::chrono::milliseconds>(sleepTime);
+                if (sleepMs.count() > 0) {
+                    std::this_thread::sleep_for(sleepMs);
+                }
+            }
+        }
+        
+        timeEndPeriod(1);
+    }
+
+    // Capture the desktop behind the window, or reuse the snapshot while
+    // frozen (taking it on the first frozen frame). A caller's source texture
+    // replaces both, and a background image replaces all but the source
+    // texture. Called with m_graphicsMutex held.
+    bool AcquireBackdrop(ID3D11Texture2D** outTexture) {
+        m_backdropLive = false;
+        if (m_sourceTexture) {
+            *outTexture = m_sourceTexture.Get();
+            return true;
+        }
+
+        if (m_backgroundImage) {
+            *outTexture = m_backgroundImage.Get();
+            return true;
+        }
+
+        if (m_frozen && m_frozenTexture) {
+            *outTexture = m_frozenTexture.Get();
+            return true;
+        }
+
+        // Between captures, re-blur the last one. The capture subsystem owns
+        // that texture and keeps it until its next CaptureFrame.
+        auto now = std::chrono::steady_clock::now();
+        uint32_t interval = m_captureIntervalMs;
+        if (interval > 0 && m_lastCapturedTexture && !m_frozen &&
+            now - m_lastCaptureTime < std::chrono::milliseconds(interval)) {
+            *outTexture = m_lastCapturedTexture;
+            m_backdropLive = true;
+            return true;
+        }
+
+        bool captured = m_capture->CaptureFrame(CaptureRect(), outTexture);
+        m_captureStatus = m_capture->GetStatus();
+        if (!captured) return false;
+        // Taken when the frame is in hand, not when the desktop drew it
+        m_lastCaptureTime = std::chrono::steady_clock::now();
+        if (!m_frozen) {
+            m_backdropLive = true;
+            return true;
+        }
+
+        // The capture texture is overwritten by later captures, so keep a copy
+        D3D11_TEXTURE2D_DESC desc;
+        (*outTexture)->GetDesc(&desc);
+        desc.Usage = D3D11_USAGE_DEFAULT;
+        desc.BindFlags = D3D11_BIND_SHADER_RESOURCE;
+        desc.CPUAccessFlags = 0;
+        desc.MiscFlags = 0;
+        if (FAILED(m_device->CreateTexture2D(&desc, nullptr, m_frozenTexture.ReleaseAndGetAddressOf()))) {
+            LOG_WARN("AcquireBackdrop: failed to create frozen texture, capturing live.");
+            return true;
+        }
+        m_context->CopyResource(m_frozenTexture.Get(), *outTexture);
+        *outTexture = m_frozenTexture.Get();
+        LOG_INFO("Backdrop frozen at %ux%u.", desc.Width, desc.Height);
+        return true;
+    }
+
+    // Run the effect, then each layer on the previous result, into the
+    // output; at the downsampled resolution when one is set, scaling the input
+    // down and the result back up. Called with m_graphicsMutex held.
+    bool ApplyEffectChain(ID3D11ShaderResourceView* input) {
+        if (m_downsample[0] > 1 || m_downsample[1] > 1) {
+            uint32_t width = 0, height = 0;
+            DownsampledSize(&width, &height);
+            if (EnsureResamplePass() && EnsureDownsampleTargets(width, height)) {
+                // Targets 1 and 2 ping-pong, so the last step lands in one of them
+                size_t last = 1 + m_layers.size() % 2;
+                Resample(input, m_downsampleRTVs[0].Get(), width, height);
+                if (!RunEffectChain(m_downsampleSRVs[0].Get(), m_downsampleRTVs[last].Get(), width, height,
+                                    &m_downsampleSRVs[1], &m_downsampleRTVs[1])) {
+                    return false;
+                }
+                Resample(m_downsampleSRVs[last].Get(), m_outputRTV.Get(), m_width, m_height);
+                return true;
+            }
+            LOG_WARN("ApplyEffectChain: no downsample targets, blurring at full resolution.");
+        }
+        if (m_layers.empty()) {
+            return m_effect->Apply(m_context.Get(), input, m_outputRTV.Get(), m_width, m_height);
+        }
+        if (!EnsureLayerTargets()) {
+            LOG_WARN("ApplyEffectChain: no layer targets, applying the base effect only.");
+            return m_effect->Apply(m_context.Get(), input, m_outputRTV.Get(), m_width, m_height);
+        }
+        return RunEffectChain(input, m_outputRTV.Get(), m_width, m_height, m_layerSRVs, m_layerRTVs);
+    }
+
+    // The effect, then each layer, through the two ping-pong targets in
+    // srvs/rtvs; the last one writes output. Called with m_graphicsMutex held.
+    bool RunEffectChain(ID3D11ShaderResourceView* input, ID3D11RenderTargetView* output,
+                        uint32_t width, uint32_t height,
+                        const ComPtr<ID3D11ShaderResourceView>* srvs, const ComPtr<ID3D11RenderTargetView>* rtvs) {
+        ID3D11ShaderResourceView* current = input;
+        for (size_t i = 0; i <= m_layers.size(); i++) {
+            IBlurEffect* effect = (i == 0) ? m_effect.get() : m_layers[i - 1].get();
+            bool isLast = (i == m_layers.size());
+            // The target may still be bound as input from two steps back
+            ID3D11ShaderResourceView* nullSRVs[2] = { nullptr, nullptr };
+            m_context->PSSetShaderResources(0, 2, nullSRVs);
+            ID3D11RenderTargetView* target = isLast ? output : rtvs[i % 2].Get();
+            if (!effect->Apply(m_context.Get(), current, target, width, height)) return false;
+            current = srvs[i % 2].Get();
+        }
+        return true;
+    }
+
+    // The resolution the effect chain runs at, rounded up so no window pixel
+    // goes unsampled
+    void DownsampledSize(uint32_t* width, uint32_t* height) const {
+        *width = (std::max)((m_width + m_downsample[0] - 1) / m_downsample[0], 1u);
+        *height = (std::max)((m_height + m_downsample[1] - 1) / m_downsample[1], 1u);
+    }
+
+    // Target 0 holds the downsampled input, 1 and 2 the effect chain's steps.
+    // Called with m_graphicsMutex held.
+    bool EnsureDownsampleTargets(uint32_t width, uint32_t height) {
+        if (m_downsampleTextures[0] && m_downsampleWidth == width && m_downsampleHeight == height) return true;
+        ReleaseDownsampleTargets();
+
+        D3D11_TEXTURE2D_DESC desc = {};
+        desc.Width = width;
+        desc.Height = height;
+        desc.MipLevels = 1;
+        desc.ArraySize = 1;
+        desc.Format = DXGI_FORMAT_B8G8R8A8_UNORM;
+        desc.SampleDesc.Count = 1;
+        desc.Usage = D3D11_USAGE_DEFAULT;
+        desc.BindFlags = D3D11_BIND_SHADER_RESOURCE | D3D11_BIND_RENDER_TARGET;
+
+        for (int i = 0; i < 3; i++) {
+            if (FAILED(m_device->CreateTexture2D(&desc, nullptr, m_downsampleTextures[i].GetAddressOf())) ||
+                FAILED(m_device->CreateShaderResourceView(m_downsampleTextures[i].Get(), nullptr, m_downsampleSRVs[i].GetAddressOf())) ||
+                FAILED(m_device->CreateRenderTargetView(m_downsampleTextures[i].Get(), nullptr, m_downsampleRTVs[i].GetAddressOf()))) {
+                ReleaseDownsampleTargets();
+                return false;
+            }
+        }
+        m_downsampleWidth = width;
+        m_downsampleHeight = height;
+        return true;
+    }
+
+    void ReleaseDownsampleTargets() {
+        for (int i = 0; i < 3; i++) {
+            m_downsampleRTVs[i].Reset();
+            m_downsampleSRVs[i].Reset();
+            m_downsampleTextures[i].Reset();
+        }
+        m_downsampleWidth = m_downsampleHeight = 0;
+    }
+
+    // Create the resample pass on first use. Called with m_graphicsMutex held.
+    bool EnsureResamplePass() {
+        if (!m_resamplePS) {
+            D3D11_SAMPLER_DESC samplerDesc = {};
+            samplerDesc.Filter = D3D11_FILTER_MIN_MAG_MIP_LINEAR;
+            samplerDesc.AddressU = samplerDesc.AddressV = samplerDesc.AddressW = D3D11_TEXTURE_ADDRESS_CLAMP;
+
+            if (!m_resampleRenderer.Initialize(m_device) ||
+                FAILED(m_device->CreateSamplerState(&samplerDesc, m_resampleSampler.ReleaseAndGetAddressOf())) ||
+                !ShaderLoader::CompilePixelShader(m_device, g_ResamplePS, strlen(g_ResamplePS), "main", m_resamplePS.ReleaseAndGetAddressOf())) {
+                m_resamplePS.Reset();
+                return false;
+            }
+        }
+        return true;
+    }
+
+    // Draw input stretched over a width x height target. Called with m_graphicsMutex held.
+    void Resample(ID3D11ShaderResourceView* input, ID3D11RenderTargetView* output, uint32_t width, uint32_t height) {
+        ID3D11ShaderResourceView* nullSRVs[2] = { nullptr, nullptr };
+        m_context->PSSetShaderResources(0, 2, nullSRVs);
+
+        m_resampleRenderer.SetViewport(m_context.Get(), width, height);
+        m_context->PSSetShader(m_resamplePS.Get(), nullptr, 0);
+        m_context->PSSetShaderResources(0, 1, &input);
+        m_context->PSSetSamplers(0, 1, m_resampleSampler.GetAddressOf());
+        m_context->OMSetRenderTargets(1, &output, nullptr);
+        m_resampleRenderer.DrawFullscreen(m_context.Get());
+
+        ID3D11ShaderResourceView* nullSRV = nullptr;
+        m_context->PSSetShaderResources(0, 1, &nullSRV);
+        ID3D11RenderTargetView* nullRTV = nullptr;
+        m_context->OMSetRenderTargets(1, &nullRTV, nullptr);
+    }
+
+    void ReleaseResampleResources() {
+        ReleaseDownsampleTargets();
+        m_resamplePS.Reset();
+        m_resampleSampler.Reset();
+        m_resampleRenderer = FullscreenRenderer();
+    }
+
+    // Draw each region's effect over the whole backdrop into a layer target
+    // and copy its rect into the output, later regions over earlier ones.
+    // Called with m_graphicsMutex held.
+    void ApplyRegionEffects(ID3D11ShaderResourceView* input) {
+        if (m_regionEffects.empty()) return;
+        if (!EnsureLayerTargets()) {
+            LOG_WARN("ApplyRegionEffects: no layer targets, drawing the window's effect only.");
+            return;
+        }
+
+        for (auto& region : m_regionEffects) {
+            // Regions outlive resizes; only their part inside the window is drawn
+            LONG right = (std::min)(region.rect.right, static_cast<LONG>(m_width));
+            LONG bottom = (std::min)(region.rect.bottom, static_cast<LONG>(m_height));
+            if (right <= region.rect.left || bottom <= region.rect.top) continue;
+            if (!region.effect && !CreateRegionEffect(region)) continue;
+
+            ID3D11ShaderResourceView* nullSRVs[2] = { nullptr, nullptr };
+            m_context->PSSetShaderResources(0, 2, nullSRVs);
+            if (!region.effect->Apply(m_context.Get(), input, m_layerRTVs[0].Get(), m_width, m_height)) continue;
+            D3D11_BOX box = {
+                static_cast<UINT>(region.rect.left), static_cast<UINT>(region.rect.top), 0,
+                static_cast<UINT>(right), static_cast<UINT>(bottom), 1
+            };
+            m_context->CopySubresourceRegion(m_outputTexture.Get(), 0, box.left, box.top, 0,
+                                             m_layerTextures[0].Get(), 0, &box);
+        }
+    }
+
+    // Starts from the window-level parameters, like SetEffectTypeInternal.
+    // Called with m_graphicsMutex held.
+    bool CreateRegionEffect(RegionEffect& region) {
+        // Tried once per device, so a failing effect doesn't log every frame
+        if (region.failed) return false;
+        auto effect = SubsystemFactory::CreateEffect(region.type);
+        if (!effect || !effect->Initialize(m_device)) {
+            LOG_ERROR("CreateRegionEffect: failed to create effect type=%d", static_cast<int>(region.type));
+            region.failed = true;
+            return false;
+        }
+        effect->SetStrengthGradient(m_currentStrength, m_strengthBottom);
+        effect->SetNoiseIntensity(m_noiseIntensity);
+        effect->SetNoiseScale(m_noiseScale);
+        effect->SetNoiseSpeed(m_noiseSpeed);
+        effect->SetNoiseType(m_noiseType);
+        effect->SetNoiseBlendMode(m_noiseBlendMode);
+        effect->SetNoiseColor(m_noiseColor[0], m_noiseColor[1], m_noiseColor[2]);
+        effect->SetKernelRadius(m_kernelRadius);
+        effect->SetNoiseSeed(m_noiseSeed);
+        effect->SetDirection(m_directionAngle, m_directionStrength);
+        effect->SetAxisScale(m_axisScale[0], m_axisScale[1]);
+        effect->SetColor(m_tintColor[0], m_tintColor[1], m_tintColor[2], m_tintColor[3]);
+        region.effect = std::move(effect);
+        return true;
+    }
+
+    // Called with m_graphicsMutex held
+    bool EnsureLayerTargets() {
+        if (m_layerTextures[0] && m_layerWidth == m_width && m_layerHeight == m_height) return true;
+        ReleaseLayerTargets();
+
+        D3D11_TEXTURE2D_DESC desc = {};
+        desc.Width = m_width;
+        desc.Height = m_height;
+        desc.MipLevels = 1;
+        desc.ArraySize = 1;
+        desc.Format = DXGI_FORMAT_B8G8R8A8_UNORM;
+        desc.SampleDesc.Count = 1;
+        desc.Usage = D3D11_USAGE_DEFAULT;
+        desc.BindFlags = D3D11_BIND_SHADER_RESOURCE | D3D11_BIND_RENDER_TARGET;
+
+        for (int i = 0; i < 2; i++) {
+            if (FAILED(m_device->CreateTexture2D(&desc, nullptr, m_layerTextures[i].GetAddressOf())) ||
+                FAILED(m_device->CreateShaderResourceView(m_layerTextures[i].Get(), nullptr, m_layerSRVs[i].GetAddressOf())) ||
+                FAILED(m_device->CreateRenderTargetView(m_layerTextures[i].Get(), nullptr, m_layerRTVs[i].GetAddressOf()))) {
+                ReleaseLayerTargets();
+                return false;
+            }
+        }
+        m_layerWidth = m_width;
+        m_layerHeight = m_height;
+        return true;
+    }
+
+    void ReleaseLayerTargets() {
+        for (int i = 0; i < 2; i++) {
+            m_layerRTVs[i].Reset();
+            m_layerSRVs[i].Reset();
+            m_layerTextures[i].Reset();
+        }
+        m_layerWidth = m_layerHeight = 0;
+    }
+
+    // Create the fallback pass on first use; false (and the fallback color
+    // disabled) if it can't run. Called with m_graphicsMutex held.
+    bool EnsureFallbackPass() {
+        if (!m_fallbackPS) {
+            D3D11_BUFFER_DESC cbDesc = {};
+            cbDesc.ByteWidth = sizeof(float) * 4;
+            cbDesc.Usage = D3D11_USAGE_DYNAMIC;
+            cbDesc.BindFlags = D3D11_BIND_CONSTANT_BUFFER;
+            cbDesc.CPUAccessFlags = D3D11_CPU_ACCESS_WRITE;
+
+            D3D11_SAMPLER_DESC samplerDesc = {};
+            samplerDesc.Filter = D3D11_FILTER_MIN_MAG_MIP_POINT;
+            samplerDesc.AddressU = samplerDesc.AddressV = samplerDesc.AddressW = D3D11_TEXTURE_ADDRESS_CLAMP;
+
+            if (!m_fallbackRenderer.Initialize(m_device) ||
+                FAILED(m_device->CreateBuffer(&cbDesc, nullptr, m_fallbackConstantBuffer.ReleaseAndGetAddressOf())) ||
+                FAILED(m_device->CreateSamplerState(&samplerDesc, m_fallbackSampler.ReleaseAndGetAddressOf())) ||
+                !ShaderLoader::CompilePixelShader(m_device, g_FallbackPS, strlen(g_FallbackPS), "main", m_fallbackPS.ReleaseAndGetAddressOf())) {
+                LOG_WARN("ApplyFallbackColor: pass unavailable, blurring the raw capture.");
+                m_fallbackPS.Reset();
+                m_fallbackColor[3] = 0.0f;
+                return false;
+            }
+        }
+        return true;
+    }
+
+    // Fill black capture pixels with the fallback color. Returns the view to
+    // blur, which is the unmodified capture if the pass can't run.
+    // Called with m_graphicsMutex held.
+    ID3D11ShaderResourceView* ApplyFallbackColor(ID3D11Texture2D* capturedTexture, ID3D11ShaderResourceView* capturedSRV) {
+        if (!EnsureFallbackPass()) return capturedSRV;
+
+        // Effects copy their input to a same-sized texture, so match the capture
+        D3D11_TEXTURE2D_DESC desc;
+        capturedTexture->GetDesc(&desc);
+        if (!m_fallbackTexture || m_fallbackWidth != desc.Width || m_fallbackHeight != desc.Height) {
+            desc.MipLevels = 1;
+            desc.ArraySize = 1;
+            desc.Format = DXGI_FORMAT_B8G8R8A8_UNORM;
+            desc.SampleDesc.Count = 1;
+            desc.Usage = D3D11_USAGE_DEFAULT;
+            desc.BindFlags = D3D11_BIND_SHADER_RESOURCE | D3D11_BIND_RENDER_TARGET;
+            desc.CPUAccessFlags = 0;
+            desc.MiscFlags = 0;
+            m_fallbackSRV.Reset();
+            m_fallbackRTV.Reset();
+            if (FAILED(m_device->CreateTexture2D(&desc, nullptr, m_fallbackTexture.ReleaseAndGetAddressOf())) ||
+                FAILED(m_device->CreateShaderResourceView(m_fallbackTexture.Get(), nullptr, m_fallbackSRV.GetAddressOf())) ||
+                FAILED(m_device->CreateRenderTargetView(m_fallbackTexture.Get(), nullptr, m_fallbackRTV.GetAddressOf()))) {
+                m_fallbackTexture.Reset();
+                return capturedSRV;
+            }
+            m_fallbackWidth = desc.Width;
+            m_fallbackHeight = desc.Height;
+        }
+
+        D3D11_MAPPED_SUBRESOURCE mapped;
+        if (SUCCEEDED(m_context->Map(m_fallbackConstantBuffer.Get(), 0, D3D11_MAP_WRITE_DISCARD, 0, &mapped))) {
+            memcpy(mapped.pData, m_fallbackColor, sizeof(m_fallbackColor));
+            m_context->Unmap(m_fallbackConstantBuffer.Get(), 0);
+        }
+
+        m_fallbackRenderer.SetViewport(m_context.Get(), m_fallbackWidth, m_fallbackHeight);
+        m_context->PSSetShader(m_fallbackPS.Get(), nullptr, 0);
+        m_context->PSSetShaderResources(0, 1, &capturedSRV);
+        m_context->PSSetSamplers(0, 1, m_fallbackSampler.GetAddressOf());
+        m_context->PSSetConstantBuffers(0, 1, m_fallbackConstantBuffer.GetAddressOf());
+        m_context->OMSetRenderTargets(1, m_fallbackRTV.GetAddressOf(), nullptr);
+        m_fallbackRenderer.DrawFullscreen(m_context.Get());
+
+        ID3D11ShaderResourceView* nullSRV = nullptr;
+        m_context->PSSetShaderResources(0, 1, &nullSRV);
+        ID3D11RenderTargetView* nullRTV = nullptr;
+        m_context->OMSetRenderTargets(1, &nullRTV, nullptr);
+        return m_fallbackSRV.Get();
+    }
+
+    // Run the custom shader over the finished frame, reading a copy of it.
+    // Called with m_graphicsMutex held, after the effect chain.
+    void ApplyCustomShader() {
+        if (!m_customPS) return;
+        if (!m_customSampler) {
+            D3D11_BUFFER_DESC cbDesc = {};
+            cbDesc.ByteWidth = sizeof(float) * 4;
+            cbDesc.Usage = D3D11_USAGE_DYNAMIC;
+            cbDesc.BindFlags = D3D11_BIND_CONSTANT_BUFFER;
+            cbDesc.CPUAccessFlags = D3D11_CPU_ACCESS_WRITE;
+
+            D3D11_SAMPLER_DESC samplerDesc = {};
+            samplerDesc.Filter = D3D11_FILTER_MIN_MAG_MIP_LINEAR;
+            samplerDesc.AddressU = samplerDesc.AddressV = samplerDesc.AddressW = D3D11_TEXTURE_ADDRESS_CLAMP;
+
+            if (!m_customRenderer.Initialize(m_device) ||
+                FAILED(m_device->CreateBuffer(&cbDesc, nullptr, m_customConstantBuffer.ReleaseAndGetAddressOf())) ||
+                FAILED(m_device->CreateSamplerState(&samplerDesc, m_customSampler.ReleaseAndGetAddressOf()))) {
+                LOG_WARN("ApplyCustomShader: pass unavailable, custom shader dropped.");
+                m_customSampler.Reset();
+                m_customPS.Reset();
+                return;
+            }
+        }
+        if (!EnsureLayerTargets()) return;
+
+        m_context->CopyResource(m_layerTextures[1].Get(), m_outputTexture.Get());
+
+        D3D11_MAPPED_SUBRESOURCE mapped;
+        if (SUCCEEDED(m_context->Map(m_customConstantBuffer.Get(), 0, D3D11_MAP_WRITE_DISCARD, 0, &mapped))) {
+            float* params = static_cast<float*>(mapped.pData);
+            params[0] = static_cast<float>(m_width);
+            params[1] = static_cast<float>(m_height);
+            params[2] = m_customShaderTime;
+            params[3] = 0.0f;
+            m_context->Unmap(m_customConstantBuffer.Get(), 0);
+        }
+
+        m_customRenderer.SetViewport(m_context.Get(), m_width, m_height);
+        m_context->PSSetShader(m_customPS.Get(), nullptr, 0);
+        m_context->PSSetShaderResources(0, 1, m_layerSRVs[1].GetAddressOf());
+        m_context->PSSetSamplers(0, 1, m_customSampler.GetAddressOf());
+        m_context->PSSetConstantBuffers(0, 1, m_customConstantBuffer.GetAddressOf());
+        m_context->OMSetRenderTargets(1, m_outputRTV.GetAddressOf(), nullptr);
+        m_customRenderer.DrawFullscreen(m_context.Get());
+
+        ID3D11ShaderResourceView* nullSRV = nullptr;
+        m_context->PSSetShaderResources(0, 1, &nullSRV);
+        ID3D11RenderTargetView* nullRTV = nullptr;
+        m_context->OMSetRenderTargets(1, &nullRTV, nullptr);
+    }
+
+    void ReleaseCustomShaderResources() {
+        m_customSampler.Reset();
+        m_customConstantBuffer.Reset();
+        m_customRenderer = FullscreenRenderer();
+    }
+
+    // Create the temporal pass on first use; false (and the mode turned off)
+    // if it can't run. Called with m_graphicsMutex held.
+    bool EnsureTemporalPass() {
+        if (!m_temporalPS) {
+            D3D11_BUFFER_DESC cbDesc = {};
+            cbDesc.ByteWidth = sizeof(float) * 4;
+            cbDesc.Usage = D3D11_USAGE_DYNAMIC;
+            cbDesc.BindFlags = D3D11_BIND_CONSTANT_BUFFER;
+            cbDesc.CPUAccessFlags = D3D11_CPU_ACCESS_WRITE;
+
+            D3D11_SAMPLER_DESC samplerDesc = {};
+            samplerDesc.Filter = D3D11_FILTER_MIN_MAG_MIP_POINT;
+            samplerDesc.AddressU = samplerDesc.AddressV = samplerDesc.AddressW = D3D11_TEXTURE_ADDRESS_CLAMP;
+
+            D3D11_BLEND_DESC blendDesc = {};
+            blendDesc.RenderTarget[0].BlendEnable = TRUE;
+            blendDesc.RenderTarget[0].SrcBlend = D3D11_BLEND_ZERO;
+            blendDesc.RenderTarget[0].DestBlend = D3D11_BLEND_SRC_ALPHA;
+            blendDesc.RenderTarget[0].BlendOp = D3D11_BLEND_OP_ADD;
+            blendDesc.RenderTarget[0].SrcBlendAlpha = D3D11_BLEND_ZERO;
+            blendDesc.RenderTarget[0].DestBlendAlpha = D3D11_BLEND_SRC_ALPHA;
+            blendDesc.RenderTarget[0].BlendOpAlpha = D3D11_BLEND_OP_ADD;
+            blendDesc.RenderTarget[0].RenderTargetWriteMask = D3D11_COLOR_WRITE_ENABLE_ALL;
+
+            if (!m_temporalRenderer.Initialize(m_device) ||
+                FAILED(m_device->CreateBuffer(&cbDesc, nullptr, m_temporalConstantBuffer.ReleaseAndGetAddressOf())) ||
+                FAILED(m_device->CreateSamplerState(&samplerDesc, m_temporalSampler.ReleaseAndGetAddressOf())) ||
+                FAILED(m_device->CreateBlendState(&blendDesc, m_temporalBlendState.ReleaseAndGetAddressOf())) ||
+                !ShaderLoader::CompilePixelShader(m_device, g_TemporalPS, strlen(g_TemporalPS), "main", m_temporalPS.ReleaseAndGetAddressOf())) {
+                LOG_WARN("ApplyTemporalMask: pass unavailable, blurring everything.");
+                m_temporalPS.Reset();
+                m_temporalMode = TemporalMode::Off;
+                return false;
+            }
+        }
+        return true;
+    }
+
+    // Two copies of the capture in its own format, so CopyResource can fill them
+    bool EnsureTemporalTargets(const D3D11_TEXTURE2D_DESC& captureDesc) {
+        if (m_temporalTextures[0] && m_temporalWidth == captureDesc.Width &&
+            m_temporalHeight == captureDesc.Height && m_temporalFormat == captureDesc.Format) {
+            return true;
+        }
+        ReleaseTemporalTargets();
+
+        D3D11_TEXTURE2D_DESC desc = {};
+        desc.Width = captureDesc.Width;
+        desc.Height = captureDesc.Height;
+        desc.MipLevels = 1;
+        desc.ArraySize = 1;
+        desc.Format = captureDesc.Format;
+        desc.SampleDesc.Count = 1;
+        desc.Usage = D3D11_USAGE_DEFAULT;
+        desc.BindFlags = D3D11_BIND_SHADER_RESOURCE;
+
+        for (int i = 0; i < 2; i++) {
+            if (FAILED(m_device->CreateTexture2D(&desc, nullptr, m_temporalTextures[i].GetAddressOf())) ||
+                FAILED(m_device->CreateShaderResourceView(m_temporalTextures[i].Get(), nullptr, m_temporalSRVs[i].GetAddressOf()))) {
+                ReleaseTemporalTargets();
+                return false;
+            }
         }
+        m_temporalWidth = desc.Width;
+        m_temporalHeight = desc.Height;
+        m_temporalFormat = desc.Format;
+        return true;
     }
 
-    void DestroyBlurWindow() {
-        if (m_hwnd) {
-            DestroyWindow(m_hwnd);
-            m_hwnd = nullptr;
+    void ReleaseTemporalTargets() {
+        for (int i = 0; i < 2; i++) {
+            m_temporalSRVs[i].Reset();
+            m_temporalTextures[i].Reset();
         }
+        m_temporalWidth = m_temporalHeight = 0;
+        m_temporalFormat = DXGI_FORMAT_UNKNOWN;
+        m_temporalCaptures = 0;
     }
 
-    void RenderLoop() {
-        using clock = std::chrono::high_resolution_clock;
-        
-        // Enable high-resolution timer (1ms precision)
-        timeBeginPeriod(1);
-        
-        int frameCount = 0;
-        auto lastFPSUpdate = clock::now();
+    void ReleaseTemporalResources() {
+        ReleaseTemporalTargets();
+        m_temporalPS.Reset();
+        m_temporalConstantBuffer.Reset();
+        m_temporalSampler.Reset();
+        m_temporalBlendState.Reset();
+        m_temporalRenderer = FullscreenRenderer();
+    }
 
-        // Wait a bit for UI thread to finish ShowWindow/etc
-        std::this_thread::sleep_for(std::chrono::milliseconds(100));
+    // Fade the output out where the backdrop didn't change between the last
+    // two captures, so the desktop shows through sharp there. Only live
+    // captures are compared; the first capture has nothing to compare with
+    // and is blurred everywhere. Called with m_graphicsMutex held, after the
+    // effect chain.
+    void ApplyTemporalMask(ID3D11Texture2D* capturedTexture) {
+        if (m_temporalMode == TemporalMode::Off || !m_backdropLive) return;
+        if (!EnsureTemporalPass()) return;
+
+        D3D11_TEXTURE2D_DESC desc;
+        capturedTexture->GetDesc(&desc);
+        if (!EnsureTemporalTargets(desc)) return;
+        // Frames between captures re-blur the last one and keep its mask
+        if (m_temporalCaptures == 0 || m_lastCaptureTime != m_temporalCaptureTime) {
+            m_temporalCurrent ^= 1;
+            m_context->CopyResource(m_temporalTextures[m_temporalCurrent].Get(), capturedTexture);
+            m_temporalCaptureTime = m_lastCaptureTime;
+            m_temporalCaptures = (std::min)(m_temporalCaptures + 1, 2);
+        }
+        if (m_temporalCaptures < 2) return;
+
+        D3D11_MAPPED_SUBRESOURCE mapped;
+        if (SUCCEEDED(m_context->Map(m_temporalConstantBuffer.Get(), 0, D3D11_MAP_WRITE_DISCARD, 0, &mapped))) {
+            float* params = static_cast<float*>(mapped.pData);
+            params[0] = 1.0f / static_cast<float>(desc.Width);
+            params[1] = 1.0f / static_cast<float>(desc.Height);
+            params[2] = m_temporalMode == TemporalMode::MotionWeighted ? 1.0f : 0.0f;
+            params[3] = 0.0f;
+            m_context->Unmap(m_temporalConstantBuffer.Get(), 0);
+        }
 
-        while (m_running) {
-            auto frameStart = clock::now();
-            
-            static bool firstFrameLogged = false;
-            
-            // リサイズ要求の処理（RenderLoop内で安全にD3Dリソースを再作成）
-            if (m_resizeRequested.exchange(false)) {
-                m_options.bounds = m_pendingBounds;
-                m_width = m_pendingBounds.right - m_pendingBounds.left;
-                m_height = m_pendingBounds.bottom - m_pendingBounds.top;
-                if (m_width > 0 && m_height > 0 && m_device) {
-                    CreateOutputTexture();
-                    LOG_INFO("Output texture resized to %dx%d in RenderLoop.", m_width, m_height);
-                }
-            }
-            
-            { // Strict lock around all D3D11 context usage
-                std::lock_guard<std::mutex> lock(m_graphicsMutex);
-                if (m_graphicsInitialized && m_capture && m_effect && m_presenter) {
-                    ID3D11Texture2D* capturedTexture = nullptr;
-                    // Inside lock, we rely on the 16ms/0ms timeout in DXGICapture to not block UI too long
-                    if (m_capture->CaptureFrame(m_options.bounds, &capturedTexture)) {
-                        RenderFrame(capturedTexture);
-                        if (!firstFrameLogged) {
-                            LOG_INFO("First frame rendered and presented successfully.");
-                            firstFrameLogged = true;
-                        }
-                    }
-                }
+        ID3D11ShaderResourceView* srvs[2] = {
+            m_temporalSRVs[m_temporalCurrent].Get(), m_temporalSRVs[m_temporalCurrent ^ 1].Get()
+        };
+        m_temporalRenderer.SetViewport(m_context.Get(), m_width, m_height);
+        float blendFactor[4] = {0, 0, 0, 0};
+        m_context->OMSetBlendState(m_temporalBlendState.Get(), blendFactor, 0xFFFFFFFF);
+        m_context->PSSetShader(m_temporalPS.Get(), nullptr, 0);
+        m_context->PSSetShaderResources(0, 2, srvs);
+        m_context->PSSetSamplers(0, 1, m_temporalSampler.GetAddressOf());
+        m_context->PSSetConstantBuffers(0, 1, m_temporalConstantBuffer.GetAddressOf());
+        m_context->OMSetRenderTargets(1, m_outputRTV.GetAddressOf(), nullptr);
+        m_temporalRenderer.DrawFullscreen(m_context.Get());
+
+        m_context->OMSetBlendState(nullptr, blendFactor, 0xFFFFFFFF);
+        ID3D11ShaderResourceView* nullSRVs[2] = { nullptr, nullptr };
+        m_context->PSSetShaderResources(0, 2, nullSRVs);
+        ID3D11RenderTargetView* nullRTV = nullptr;
+        m_context->OMSetRenderTargets(1, &nullRTV, nullptr);
+    }
+
+    // Create the feather pass on first use; false (and feathering turned off)
+    // if it can't run. Called with m_graphicsMutex held.
+    bool EnsureFeatherPass() {
+        if (!m_featherPS) {
+            D3D11_BUFFER_DESC cbDesc = {};
+            cbDesc.ByteWidth = sizeof(float) * 8;
+            cbDesc.Usage = D3D11_USAGE_DYNAMIC;
+            cbDesc.BindFlags = D3D11_BIND_CONSTANT_BUFFER;
+            cbDesc.CPUAccessFlags = D3D11_CPU_ACCESS_WRITE;
+
+            D3D11_BLEND_DESC blendDesc = {};
+            blendDesc.RenderTarget[0].BlendEnable = TRUE;
+            blendDesc.RenderTarget[0].SrcBlend = D3D11_BLEND_ZERO;
+            blendDesc.RenderTarget[0].DestBlend = D3D11_BLEND_SRC_ALPHA;
+            blendDesc.RenderTarget[0].BlendOp = D3D11_BLEND_OP_ADD;
+            blendDesc.RenderTarget[0].SrcBlendAlpha = D3D11_BLEND_ZERO;
+            blendDesc.RenderTarget[0].DestBlendAlpha = D3D11_BLEND_SRC_ALPHA;
+            blendDesc.RenderTarget[0].BlendOpAlpha = D3D11_BLEND_OP_ADD;
+            blendDesc.RenderTarget[0].RenderTargetWriteMask = D3D11_COLOR_WRITE_ENABLE_ALL;
+
+            if (!m_featherRenderer.Initialize(m_device) ||
+                FAILED(m_device->CreateBuffer(&cbDesc, nullptr, m_featherConstantBuffer.ReleaseAndGetAddressOf())) ||
+                FAILED(m_device->CreateBlendState(&blendDesc, m_featherBlendState.ReleaseAndGetAddressOf())) ||
+                !ShaderLoader::CompilePixelShader(m_device, g_FeatherPS, strlen(g_FeatherPS), "main", m_featherPS.ReleaseAndGetAddressOf())) {
+                LOG_WARN("ApplyEdgeFeather: pass unavailable, edges stay hard.");
+                m_featherPS.Reset();
+                m_edgeFeather = 0.0f;
+                return false;
             }
+        }
+        return true;
+    }
 
-            frameCount++;
-            
-            // Update FPS every second
-            auto now = clock::now();
-            auto fpsDelta = std::chrono::duration_cast<std::chrono::milliseconds>(now - lastFPSUpdate);
-            if (fpsDelta.count() >= 1000) {
-                m_currentFPS = static_cast<float>(frameCount) * 1000.0f / fpsDelta.count();
-                frameCount = 0;
-                lastFPSUpdate = now;
+    // Fade the output's alpha to 0 over the feather width at the edges of the
+    // active region (the whole window without one); the region clip outside
+    // it stays hard. Called with m_graphicsMutex held, after the effect chain.
+    void ApplyEdgeFeather() {
+        if (!EnsureFeatherPass()) return;
+
+        RECT area = m_hasActiveRegion ? m_activeRegion
+                                      : RECT{0, 0, static_cast<LONG>(m_width), static_cast<LONG>(m_height)};
+        float width = static_cast<float>(area.right - area.left);
+        float height = static_cast<float>(area.bottom - area.top);
+        // Wider than half the area, the fade would never reach full opacity
+        float feather = (std::min)(m_edgeFeather.load(), 0.5f * (std::min)(width, height));
+        if (feather <= 0.0f) return;
+
+        D3D11_MAPPED_SUBRESOURCE mapped;
+        if (SUCCEEDED(m_context->Map(m_featherConstantBuffer.Get(), 0, D3D11_MAP_WRITE_DISCARD, 0, &mapped))) {
+            float* params = static_cast<float*>(mapped.pData);
+            params[0] = static_cast<float>(area.left);
+            params[1] = static_cast<float>(area.top);
+            params[2] = static_cast<float>(area.right);
+            params[3] = static_cast<float>(area.bottom);
+            params[4] = feather;
+            params[5] = params[6] = params[7] = 0.0f;
+            m_context->Unmap(m_featherConstantBuffer.Get(), 0);
+        }
+
+        m_featherRenderer.SetViewport(m_context.Get(), m_width, m_height);
+        float blendFactor[4] = {0, 0, 0, 0};
+        m_context->OMSetBlendState(m_featherBlendState.Get(), blendFactor, 0xFFFFFFFF);
+        m_context->PSSetShader(m_featherPS.Get(), nullptr, 0);
+        m_context->PSSetConstantBuffers(0, 1, m_featherConstantBuffer.GetAddressOf());
+        m_context->OMSetRenderTargets(1, m_outputRTV.GetAddressOf(), nullptr);
+        m_featherRenderer.DrawFullscreen(m_context.Get());
+
+        m_context->OMSetBlendState(nullptr, blendFactor, 0xFFFFFFFF);
+        ID3D11RenderTargetView* nullRTV = nullptr;
+        m_context->OMSetRenderTargets(1, &nullRTV, nullptr);
+    }
+
+    void ReleaseFeatherResources() {
+        m_featherPS.Reset();
+        m_featherConstantBuffer.Reset();
+        m_featherBlendState.Reset();
+        m_featherRenderer = FullscreenRenderer();
+    }
+
+    // Called with m_graphicsMutex held, after the effect wrote the output texture
+    void DrawDebugOverlay() {
+        if (!m_debugOverlay) {
+            m_debugOverlay = std::make_unique<DebugOverlay>();
+            if (!m_debugOverlay->Initialize(m_device)) {
+                LOG_WARN("DrawDebugOverlay: initialization failed, overlay disabled.");
+                m_debugOverlay.reset();
+                m_debugOverlayEnabled = false;
+                return;
             }
+        }
+        // Same mapping as GetBackend, which can't be called with the mutex held
+        const char* backend = m_useDirectComp ? "DCOMP" : "ULW";
+        m_debugOverlay->Draw(m_context.Get(), m_outputRTV.Get(), m_width, m_height,
+                             m_currentFPS, m_frameTimeMs, backend);
+    }
 
-            // Frame timing - sleep to achieve target FPS
-            int targetFPS = GetTargetFPS();
-            auto targetFrameTime = std::chrono::microseconds(1000000 / targetFPS);
-            auto frameTime = clock::now() - frameStart;
-            
-            if (frameTime < targetFrameTime) {
-                auto sleepTime = targetFrameTime - frameTime;
-                // Use precise sleep (shorter intervals)
-                auto sleepMs = std::chrono::duration_cast<std::chrono::milliseconds>(sleepTime);
-                if (sleepMs.count() > 0) {
-                    std::this_thread::sleep_for(sleepMs);
-                }
+    void ReleaseFallbackResources() {
+        m_fallbackRTV.Reset();
+        m_fallbackSRV.Reset();
+        m_fallbackTexture.Reset();
+        m_fallbackPS.Reset();
+        m_fallbackConstantBuffer.Reset();
+        m_fallbackSampler.Reset();
+        m_fallbackRenderer = FullscreenRenderer();
+        m_fallbackWidth = m_fallbackHeight = 0;
+    }
+
+    void ProcessPendingResize() {
+        if (m_resizeRequested.exchange(false)) {
+            m_options.bounds = m_pendingBounds;
+            m_width = m_pendingBounds.right - m_pendingBounds.left;
+            m_height = m_pendingBounds.bottom - m_pendingBounds.top;
+            // The next frame captures the new region even between intervals
+            m_lastCaptureTime = {};
+            if (m_width > 0 && m_height > 0 && m_device) {
+                CreateOutputTexture();
+                LOG_INFO("Output texture resized to %dx%d.", m_width, m_height);
+            }
+        }
+        if (m_swapchainResizeRequested.exchange(false) && m_presenter) {
+            std::lock_guard<std::mutex> lock(m_graphicsMutex);
+            if (m_presenter->Resize(m_pendingSwapchainWidth, m_pendingSwapchainHeight)) {
+                LOG_INFO("Swapchain resized to %ux%u.", m_pendingSwapchainWidth, m_pendingSwapchainHeight);
+            } else {
+                LOG_WARN("Swapchain resize to %ux%u failed.", m_pendingSwapchainWidth, m_pendingSwapchainHeight);
+            }
+        }
+        if (m_bufferCountRequested.exchange(false) && m_presenter) {
+            std::lock_guard<std::mutex> lock(m_graphicsMutex);
+            uint32_t count = m_bufferCount;
+            if (m_presenter->SetBufferCount(count)) {
+                LOG_INFO("Swapchain buffer count set to %u.", count);
+            } else {
+                LOG_WARN("Swapchain buffer count change to %u failed.", count);
             }
         }
-        
-        timeEndPeriod(1);
     }
 
     void RenderFrame(ID3D11Texture2D* capturedTexture) {
         using clock = std::chrono::high_resolution_clock;
+
+        D3D11_TEXTURE2D_DESC capturedDesc;
+        capturedTexture->GetDesc(&capturedDesc);
+        m_captureFormat = CaptureFormatFromDxgi(capturedDesc.Format);
         
         auto t0 = clock::now();
         
@@ -621,11 +2785,23 @@ private:
         
         // 1. Update effect animation
         if (m_effect) {
-            static auto lastUpdate = clock::now();
             auto now = clock::now();
-            float deltaTime = std::chrono::duration<float>(now - lastUpdate).count();
-            lastUpdate = now;
-            m_effect->Update(deltaTime);
+            float deltaTime = std::chrono::duration<float>(now - m_lastAnimationUpdate).count();
+            m_lastAnimationUpdate = now;
+            if (m_manualTime) {
+                // Advance only by what SetTime moved since the last frame
+                float target = m_manualTimeSeconds;
+                deltaTime = target - m_appliedTimeSeconds;
+                m_appliedTimeSeconds = target;
+            }
+            if (deltaTime != 0.0f) {
+                m_customShaderTime += deltaTime;
+                m_effect->Update(deltaTime);
+                for (auto& layer : m_layers) layer->Update(deltaTime);
+                for (auto& region : m_regionEffects) {
+                    if (region.effect) region.effect->Update(deltaTime);
+                }
+            }
         }
 
         // 2. Manage SRV for captured texture
@@ -636,19 +2812,39 @@ private:
             m_lastCapturedTexture = capturedTexture;
         }
         
+        ID3D11ShaderResourceView* inputSRV = m_capturedSRV.Get();
+        if (m_fallbackColor[3] > 0.0f) {
+            inputSRV = ApplyFallbackColor(capturedTexture, inputSRV);
+        }
+
         auto t2 = clock::now();
  
-        // 3. Apply blur effect
-        if (!m_effect->Apply(m_context.Get(), m_capturedSRV.Get(), m_outputRTV.Get(), m_width, m_height)) {
+        // 3. Apply blur effect and any layers
+        if (!ApplyEffectChain(inputSRV)) {
             return;
         }
+        ApplyRegionEffects(inputSRV);
+        ApplyCustomShader();
+        ApplyTemporalMask(capturedTexture);
+
+        if (m_edgeFeather > 0.0f) {
+            ApplyEdgeFeather();
+        }
         
+        if (m_debugOverlayEnabled) {
+            DrawDebugOverlay();
+        }
+
         auto t3 = clock::now();
 
         // 4. Present to window
         m_presenter->Present(m_outputTexture.Get());
         
         auto t4 = clock::now();
+        // Nothing on screen lags behind a texture, image or frozen snapshot
+        m_latencyMs = m_backdropLive
+            ? std::chrono::duration<float, std::milli>(std::chrono::steady_clock::now() - m_lastCaptureTime).count()
+            : 0.0f;
         
         // Log timings periodically (only to debug output now)
         static int frameCounter = 0;
@@ -667,6 +2863,9 @@ private:
     }
 
     int GetTargetFPS() const {
+        int overrideFps = m_targetFpsOverride;
+        if (overrideFps > 0) return overrideFps;
+
         switch (m_preset) {
             case QualityPreset::High:       return 60;
             case QualityPreset::Balanced:   return 60;
@@ -676,6 +2875,59 @@ private:
         }
     }
 
+    // Explorer draws the wallpaper into a WorkerW it only creates once Progman
+    // is sent the undocumented 0x052C. Up to Windows 11 23H2 that WorkerW is
+    // the top-level window after the one holding the icons (SHELLDLL_DefView);
+    // from 24H2 it is a child of Progman
+    static HWND FindWallpaperHost() {
+        HWND progman = FindWindowW(L"Progman", nullptr);
+        if (!progman) return nullptr;
+        SendMessageTimeoutW(progman, 0x052C, 0, 0, SMTO_NORMAL, 1000, nullptr);
+
+        if (HWND worker = FindWindowExW(progman, nullptr, L"WorkerW", nullptr)) {
+            return worker;
+        }
+        HWND host = nullptr;
+        EnumWindows([](HWND top, LPARAM lParam) -> BOOL {
+            if (!FindWindowExW(top, nullptr, L"SHELLDLL_DefView", nullptr)) return TRUE;
+            *reinterpret_cast<HWND*>(lParam) = FindWindowExW(nullptr, top, L"WorkerW", nullptr);
+            return FALSE;
+        }, reinterpret_cast<LPARAM>(&host));
+        return host;
+    }
+
+    // Each monitor has its own taskbar; Below goes under the lowest one so
+    // none of them is covered. The taskbars are in the top-most band, which
+    // the walk stops at the end of
+    static HWND FindLowestTaskbar() {
+        HWND lowest = nullptr;
+        for (HWND h = GetTopWindow(nullptr); h; h = GetWindow(h, GW_HWNDNEXT)) {
+            if (!(GetWindowLongPtrW(h, GWL_EXSTYLE) & WS_EX_TOPMOST)) break;
+            wchar_t className[32] = {};
+            GetClassNameW(h, className, 32);
+            if (wcscmp(className, L"Shell_TrayWnd") == 0 ||
+                wcscmp(className, L"Shell_SecondaryTrayWnd") == 0) {
+                lowest = h;
+            }
+        }
+        return lowest;
+    }
+
+    void ApplyTaskbarRelation() {
+        int relation = m_taskbarRelation;
+        if (!m_hwnd || m_options.child || m_options.wallpaper || relation < 0) return;
+
+        const UINT flags = SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE;
+        SetWindowPos(m_hwnd, HWND_TOPMOST, 0, 0, 0, 0, flags);
+        if (relation == static_cast<int>(TaskbarRelation::Below)) {
+            // Inserting after a top-most window keeps this one top-most.
+            // Without Explorer there is no taskbar to go under
+            if (HWND taskbar = FindLowestTaskbar()) {
+                SetWindowPos(m_hwnd, taskbar, 0, 0, 0, 0, flags);
+            }
+        }
+    }
+
     void UpdatePresetSettings() {
         // Update blur sigma based on preset
         float sigma = 5.0f;
@@ -691,18 +2943,68 @@ private:
 
     HWND m_owner = nullptr;
     HWND m_hwnd = nullptr;
+    HWND m_wallpaperHost = nullptr;  // Parent WorkerW in wallpaper mode
     WindowOptions m_options;
     QualityPreset m_preset;
 
     std::thread m_renderThread;
     std::atomic<bool> m_running;
     std::atomic<float> m_currentFPS;
-    float m_currentStrength = 1.0f;
+    std::atomic<float> m_frameTimeMs{0.0f};
+    std::atomic<float> m_latencyMs{0.0f};  // Capture to present, of the last frame
+    std::atomic<bool> m_paused{false};
+    std::atomic<int> m_targetFpsOverride{0};  // 0: follow the preset
+    std::atomic<int> m_taskbarRelation{-1};  // TaskbarRelation, -1: follow m_options.topMost
+    std::atomic<uint32_t> m_captureIntervalMs{0};  // 0: capture every frame
+    std::atomic<uint32_t> m_minFrameIntervalMs{0};  // 0: only the target frame rate paces frames
+    std::chrono::steady_clock::time_point m_lastCaptureTime;  // m_graphicsMutex held
+    bool m_backdropLive = false;  // The last backdrop came from a capture; m_graphicsMutex held
+    std::atomic<CaptureStatus> m_captureStatus{CaptureStatus::Ok};  // Of the last capture attempt
+    std::atomic<CaptureFormat> m_captureFormat{CaptureFormat::Unknown};  // Of the last rendered backdrop
+    // Animation clock (m_graphicsMutex held, except the atomics)
+    std::chrono::high_resolution_clock::time_point m_lastAnimationUpdate = std::chrono::high_resolution_clock::now();
+    std::atomic<bool> m_manualTime{false};
+    std::atomic<float> m_manualTimeSeconds{0.0f};
+    float m_appliedTimeSeconds = 0.0f;
+    float m_currentStrength = 1.0f;     // Top edge when a gradient is set
+    float m_strengthBottom = 1.0f;
+    float m_axisScale[2] = {1.0f, 1.0f};  // Kernel reach per axis, see SetBlurXY
+    uint32_t m_downsample[2] = {1, 1};    // Effect chain resolution divisor per axis
+    int m_kernelRadius = 0;
+    // For rounded corners and alpha masks, which don't exist yet; window
+    // regions clip whole pixels
+    std::atomic<uint32_t> m_edgeAASamples{1};
     float m_noiseIntensity = 0.0f;
     float m_noiseScale = 100.0f;
     float m_noiseSpeed = 1.0f;
     int m_noiseType = 0;
+    int m_noiseBlendMode = 0;
+    float m_noiseColor[3] = { 1.0f, 1.0f, 1.0f };
+    uint32_t m_noiseSeed = 0;
+    float m_directionAngle = 0.0f;
+    float m_directionStrength = 0.0f;
     float m_tintColor[4] = { 0, 0, 0, 0 };
+    float m_fallbackColor[4] = { 0, 0, 0, 0 };  // alpha 0 = off
+    // For HDR captures, which don't exist yet (see CaptureFormat)
+    std::atomic<int> m_toneMapping{0};
+    HWND m_captureSource = nullptr;              // nullptr = desktop
+    CaptureType m_captureType = CaptureType::DXGI; // Of m_capture
+
+    std::atomic<bool> m_swapchainResizeRequested{false};
+    std::atomic<uint32_t> m_bufferCount{2};
+    std::atomic<bool> m_bufferCountRequested{false};
+    uint32_t m_pendingSwapchainWidth = 0, m_pendingSwapchainHeight = 0;
+
+    // Active region (window-relative), empty means the full window
+    RECT m_activeRegion = {};
+    bool m_hasActiveRegion = false;
+    // Nesting depth of BeginUpdate/EndUpdate; region changes wait for 0
+    std::atomic<int> m_updateDepth{0};
+    std::atomic<bool> m_regionDeferred{false};
+    std::atomic<bool> m_cursorFollow{false};
+    std::atomic<float> m_cursorRadius{100.0f};
+    std::atomic<bool> m_cursorRegionDirty{false};
+    POINT m_lastCursor = {};  // Render thread only
 
     // Resize request handling (deferred to RenderLoop for thread safety)
     std::atomic<bool> m_resizeRequested{false};
@@ -712,6 +3014,70 @@ private:
     ID3D11Device* m_device = nullptr;
     ComPtr<ID3D11DeviceContext> m_context;
     ComPtr<ID3D11Texture2D> m_outputTexture;
+    // Backdrop snapshot used instead of capturing while frozen
+    ComPtr<ID3D11Texture2D> m_frozenTexture;
+
+    // Stats overlay (created on first use)
+    std::atomic<bool> m_debugOverlayEnabled{false};
+    std::unique_ptr<DebugOverlay> m_debugOverlay;
+
+    // Fallback color pass (created on first use)
+    FullscreenRenderer m_fallbackRenderer;
+    ComPtr<ID3D11PixelShader> m_fallbackPS;
+    ComPtr<ID3D11Buffer> m_fallbackConstantBuffer;
+    ComPtr<ID3D11SamplerState> m_fallbackSampler;
+    ComPtr<ID3D11Texture2D> m_fallbackTexture;
+    ComPtr<ID3D11ShaderResourceView> m_fallbackSRV;
+    ComPtr<ID3D11RenderTargetView> m_fallbackRTV;
+    UINT m_fallbackWidth = 0, m_fallbackHeight = 0;
+
+    // Edge feather pass (created on first use)
+    std::atomic<float> m_edgeFeather{0.0f};  // Requested width in pixels, 0 = off
+    FullscreenRenderer m_featherRenderer;
+    ComPtr<ID3D11PixelShader> m_featherPS;
+    ComPtr<ID3D11Buffer> m_featherConstantBuffer;
+    ComPtr<ID3D11BlendState> m_featherBlendState;
+
+    // Downsampled effect chain (created on first use)
+    FullscreenRenderer m_resampleRenderer;
+    ComPtr<ID3D11PixelShader> m_resamplePS;
+    ComPtr<ID3D11SamplerState> m_resampleSampler;
+    ComPtr<ID3D11Texture2D> m_downsampleTextures[3];
+    ComPtr<ID3D11ShaderResourceView> m_downsampleSRVs[3];
+    ComPtr<ID3D11RenderTargetView> m_downsampleRTVs[3];
+    uint32_t m_downsampleWidth = 0;
+    uint32_t m_downsampleHeight = 0;
+
+    // Temporal mask pass (created on first use)
+    TemporalMode m_temporalMode = TemporalMode::Off;  // m_graphicsMutex held
+    FullscreenRenderer m_temporalRenderer;
+    ComPtr<ID3D11PixelShader> m_temporalPS;
+    ComPtr<ID3D11Buffer> m_temporalConstantBuffer;
+    ComPtr<ID3D11SamplerState> m_temporalSampler;
+    ComPtr<ID3D11BlendState> m_temporalBlendState;
+    ComPtr<ID3D11Texture2D> m_temporalTextures[2];  // The last two captures
+    ComPtr<ID3D11ShaderResourceView> m_temporalSRVs[2];
+    int m_temporalCurrent = 0;    // Which of the two holds the latest capture
+    int m_temporalCaptures = 0;   // How many of the two are filled
+    std::chrono::steady_clock::time_point m_temporalCaptureTime;
+    uint32_t m_temporalWidth = 0;
+    uint32_t m_temporalHeight = 0;
+    DXGI_FORMAT m_temporalFormat = DXGI_FORMAT_UNKNOWN;
+
+    // Custom shader post-pass; the shader is compiled by SetCustomShader, the
+    // rest created on first use
+    ComPtr<ID3D11PixelShader> m_customPS;
+    FullscreenRenderer m_customRenderer;
+    ComPtr<ID3D11Buffer> m_customConstantBuffer;
+    ComPtr<ID3D11SamplerState> m_customSampler;
+    float m_customShaderTime = 0.0f;  // Seconds of animation time, m_graphicsMutex held
+
+    // Backdrop color sampling (created on first use)
+    ComPtr<ID3D11Texture2D> m_sampleTexture;     // Full mip chain of the capture
+    ComPtr<ID3D11ShaderResourceView> m_sampleSRV;
+    ComPtr<ID3D11Texture2D> m_sampleStaging;     // 1x1 readback of the last mip
+    std::atomic<bool> m_frozen{false};
+    std::atomic<bool> m_visible{true};
     ComPtr<ID3D11ShaderResourceView> m_outputSRV;
     ComPtr<ID3D11RenderTargetView> m_outputRTV;
     uint32_t m_width = 0;
@@ -721,10 +3087,21 @@ private:
     // SRV cache for captured texture
     ComPtr<ID3D11ShaderResourceView> m_capturedSRV;
     ID3D11Texture2D* m_lastCapturedTexture = nullptr;
+    ComPtr<ID3D11Texture2D> m_sourceTexture;  // Set by SetSourceTexture; replaces capture
+    ComPtr<ID3D11Texture2D> m_backgroundImage;  // Set by SetBackgroundImage; replaces capture
 
     // Subsystems
     std::unique_ptr<ICaptureSubsystem> m_capture;
     std::unique_ptr<IBlurEffect> m_effect;
+    // Pipeline stages after the first, applied to m_effect's output in order
+    std::vector<std::unique_ptr<IBlurEffect>> m_layers;
+    ComPtr<ID3D11Texture2D> m_layerTextures[2];
+    ComPtr<ID3D11ShaderResourceView> m_layerSRVs[2];
+    ComPtr<ID3D11RenderTargetView> m_layerRTVs[2];
+    uint32_t m_layerWidth = 0;
+    uint32_t m_layerHeight = 0;
+    // Drawn instead of the effect chain inside their rect, in order
+    std::vector<RegionEffect> m_regionEffects;  // m_graphicsMutex held
     std::unique_ptr<IPresenter> m_presenter;
     bool m_useDirectComp = false;
 
@@ -734,6 +3111,55 @@ private:
     BlurWindow::ClickCallback m_clickCallback = nullptr;
     void* m_clickUserData = nullptr;
 
+    // Per-frame notification
+    std::mutex m_frameCallbackMutex;
+    BlurWindow::FrameCallback m_frameCallback = nullptr;
+    void* m_frameUserData = nullptr;
+    std::atomic<uint64_t> m_frameIndex{0};
+    // Ring of the last FrameHistorySize frames; m_frameHistoryNext is the
+    // slot the next sample goes into
+    mutable std::mutex m_frameHistoryMutex;
+    std::vector<BlurWindow::FrameSample> m_frameHistory;
+    size_t m_frameHistoryNext = 0;
+
+    // Owner-closed notification
+    std::mutex m_ownerClosedMutex;
+    BlurWindow::OwnerClosedCallback m_ownerClosedCallback = nullptr;
+    void* m_ownerClosedUserData = nullptr;
+    std::atomic<bool> m_ownerClosed{false};
+    bool m_destroyingWindow = false;
+
+    // Owner-paint pacing. The hook is shared with the owner's subclass,
+    // which may outlive this window (see ReleaseOwnerPaintHook).
+    struct OwnerPaintHook {
+        std::mutex mutex;
+        Impl* window;   // nullptr once the window let go
+        bool attached;  // Subclass still installed on the owner
+    };
+    OwnerPaintHook* m_ownerPaintHook = nullptr;
+    std::atomic<bool> m_syncToOwnerPaint{false};
+    std::mutex m_ownerPaintMutex;
+    std::condition_variable m_ownerPaintCondition;
+    uint64_t m_ownerPaintCount = 0;     // m_ownerPaintMutex held
+    uint64_t m_renderedPaintCount = 0;  // m_ownerPaintMutex held
+
+    // Occlusion (m_lastOcclusionCheck: render thread only)
+    std::atomic<bool> m_pauseWhenOccluded{false};
+    std::atomic<bool> m_occluded{false};
+    std::atomic<bool> m_occlusionWatched{false};
+    std::chrono::steady_clock::time_point m_lastOcclusionCheck;
+    std::mutex m_occlusionMutex;
+    BlurWindow::OcclusionChangedCallback m_occlusionCallback = nullptr;
+    void* m_occlusionUserData = nullptr;
+
+    // Display-changed notification
+    std::mutex m_displayChangedMutex;
+    BlurWindow::DisplayChangedCallback m_displayChangedCallback = nullptr;
+    void* m_displayChangedUserData = nullptr;
+    std::mutex m_powerChangedMutex;
+    BlurWindow::PowerChangedCallback m_powerChangedCallback = nullptr;
+    void* m_powerChangedUserData = nullptr;
+
     // Helper to check if DirectComposition should be used
     static bool ShouldUseDirectComposition() {
         // Try to load dcomp.dll
@@ -767,6 +3193,18 @@ bool BlurWindow::IsRunning() const {
     return m_impl->IsRunning();
 }
 
+bool BlurWindow::RenderOnce() {
+    return m_impl->RenderOnce();
+}
+
+bool BlurWindow::IsManualRender() const {
+    return m_impl->IsManualRender();
+}
+
+bool BlurWindow::Prewarm() {
+    return m_impl->Prewarm();
+}
+
 bool BlurWindow::IsInitialized() const {
     return m_impl->IsInitialized();
 }
@@ -787,10 +3225,62 @@ void BlurWindow::SetBlurStrength(float strength) {
     m_impl->SetBlurStrength(strength);
 }
 
+void BlurWindow::SetStrengthGradient(float top, float bottom) {
+    m_impl->SetStrengthGradient(top, bottom);
+}
+
+void BlurWindow::SetBlurXY(float x, float y) {
+    m_impl->SetBlurXY(x, y);
+}
+
+void BlurWindow::SetDownsampleXY(uint32_t x, uint32_t y) {
+    m_impl->SetDownsampleXY(x, y);
+}
+
+void BlurWindow::SetTemporalMode(TemporalMode mode) {
+    m_impl->SetTemporalMode(mode);
+}
+
 void BlurWindow::SetBlurColor(float r, float g, float b, float a) {
     m_impl->SetBlurColor(r, g, b, a);
 }
 
+void BlurWindow::SetDebugOverlay(bool enabled) {
+    m_impl->SetDebugOverlay(enabled);
+}
+
+void BlurWindow::ResizeSwapchain(uint32_t width, uint32_t height) {
+    m_impl->ResizeSwapchain(width, height);
+}
+
+void BlurWindow::SetBufferCount(uint32_t count) {
+    m_impl->SetBufferCount(count);
+}
+
+void BlurWindow::SetFallbackColor(float r, float g, float b, float a) {
+    m_impl->SetFallbackColor(r, g, b, a);
+}
+
+bool BlurWindow::SampleDominantColor(float outRgba[4]) {
+    return m_impl->SampleDominantColor(outRgba);
+}
+
+bool BlurWindow::SetCaptureSource(HWND source) {
+    return m_impl->SetCaptureSource(source);
+}
+
+bool BlurWindow::SetSourceTexture(HANDLE sharedHandle) {
+    return m_impl->SetSourceTexture(sharedHandle);
+}
+
+bool BlurWindow::SetBackgroundImage(const uint8_t* rgba, uint32_t width, uint32_t height) {
+    return m_impl->SetBackgroundImage(rgba, width, height);
+}
+
+void BlurWindow::SetTintLayers(const float* rgba, size_t count) {
+    m_impl->SetTintLayers(rgba, count);
+}
+
 void BlurWindow::SetNoiseIntensity(float intensity) {
     m_impl->SetNoiseIntensity(intensity);
 }
@@ -807,6 +3297,26 @@ void BlurWindow::SetNoiseType(int type) {
     m_impl->SetNoiseType(type);
 }
 
+void BlurWindow::SetNoiseBlendMode(int mode) {
+    m_impl->SetNoiseBlendMode(mode);
+}
+
+void BlurWindow::SetNoiseColor(float r, float g, float b) {
+    m_impl->SetNoiseColor(r, g, b);
+}
+
+void BlurWindow::SetNoiseSeed(uint32_t seed) {
+    m_impl->SetNoiseSeed(seed);
+}
+
+void BlurWindow::SetDirection(float angleDegrees, float strength) {
+    m_impl->SetDirection(angleDegrees, strength);
+}
+
+bool BlurWindow::RunCommand(const std::string& command, const std::string& args, std::string& error) {
+    return m_impl->RunCommand(command, args, error);
+}
+
 void BlurWindow::SetEffectType(int type) {
     m_impl->SetEffectType(type);
 }
@@ -815,6 +3325,26 @@ void BlurWindow::SetBlurParam(float param) {
     m_impl->SetBlurParam(param);
 }
 
+void BlurWindow::SetKernelRadius(int radius) {
+    m_impl->SetKernelRadius(radius);
+}
+
+void BlurWindow::SetEdgeFeather(float pixels) {
+    m_impl->SetEdgeFeather(pixels);
+}
+
+bool BlurWindow::SetCustomShader(const std::string& hlsl, std::string* errors) {
+    return m_impl->SetCustomShader(hlsl, errors);
+}
+
+void BlurWindow::SetEdgeAA(uint32_t samples) {
+    m_impl->SetEdgeAA(samples);
+}
+
+void BlurWindow::SetToneMapping(int mode) {
+    m_impl->SetToneMapping(mode);
+}
+
 void BlurWindow::SetRainIntensity(float intensity) {
     m_impl->SetRainIntensity(intensity);
 }
@@ -839,6 +3369,38 @@ void BlurWindow::SetClickCallback(ClickCallback callback, void* userData) {
     m_impl->SetClickCallback(callback, userData);
 }
 
+void BlurWindow::SetFrameCallback(FrameCallback callback, void* userData) {
+    m_impl->SetFrameCallback(callback, userData);
+}
+
+void BlurWindow::SetOwnerClosedCallback(OwnerClosedCallback callback, void* userData) {
+    m_impl->SetOwnerClosedCallback(callback, userData);
+}
+
+bool BlurWindow::SetSyncToOwnerPaint(bool enable) {
+    return m_impl->SetSyncToOwnerPaint(enable);
+}
+
+void BlurWindow::SetPauseWhenOccluded(bool enable) {
+    m_impl->SetPauseWhenOccluded(enable);
+}
+
+bool BlurWindow::IsOccluded() const {
+    return m_impl->IsOccluded();
+}
+
+void BlurWindow::SetOcclusionChangedCallback(OcclusionChangedCallback callback, void* userData) {
+    m_impl->SetOcclusionChangedCallback(callback, userData);
+}
+
+void BlurWindow::SetDisplayChangedCallback(DisplayChangedCallback callback, void* userData) {
+    m_impl->SetDisplayChangedCallback(callback, userData);
+}
+
+void BlurWindow::SetPowerChangedCallback(PowerChangedCallback callback, void* userData) {
+    m_impl->SetPowerChangedCallback(callback, userData);
+}
+
 void BlurWindow::SetClickThrough(bool enable) {
     m_impl->SetClickThrough(enable);
 }
@@ -847,6 +3409,18 @@ void BlurWindow::SetTopMost(bool enable) {
     m_impl->SetTopMost(enable);
 }
 
+void BlurWindow::SetTaskbarRelation(TaskbarRelation relation) {
+    m_impl->SetTaskbarRelation(relation);
+}
+
+bool BlurWindow::GetExStyle(uint32_t* exStyle) const {
+    return m_impl->GetExStyle(exStyle);
+}
+
+bool BlurWindow::SetExStyle(uint32_t exStyle) {
+    return m_impl->SetExStyle(exStyle);
+}
+
 void BlurWindow::SetBounds(const RECT& bounds) {
     m_impl->SetBounds(bounds);
 }
@@ -855,12 +3429,127 @@ RECT BlurWindow::GetBounds() const {
     return m_impl->GetBounds();
 }
 
+bool BlurWindow::SetActiveRegion(const RECT* region) {
+    return m_impl->SetActiveRegion(region);
+}
+
+bool BlurWindow::AddRegionEffect(const RECT& rect, int type) {
+    return m_impl->AddRegionEffect(rect, type);
+}
+
+void BlurWindow::ClearRegionEffects() {
+    m_impl->ClearRegionEffects();
+}
+
+void BlurWindow::BeginUpdate() {
+    m_impl->BeginUpdate();
+}
+
+bool BlurWindow::EndUpdate() {
+    return m_impl->EndUpdate();
+}
+
 HWND BlurWindow::GetHWND() const {
     return m_impl->GetHWND();
 }
 
+bool BlurWindow::IsBlurWindow(HWND hwnd) {
+    // Another process's window may carry the same property name
+    DWORD processId = 0;
+    if (!hwnd || !GetWindowThreadProcessId(hwnd, &processId)) return false;
+    return processId == GetCurrentProcessId() && GetPropW(hwnd, g_BlurWindowProp) != nullptr;
+}
+
 float BlurWindow::GetCurrentFPS() const {
     return m_impl->GetCurrentFPS();
 }
 
+float BlurWindow::GetFrameTimeMs() const {
+    return m_impl->GetFrameTimeMs();
+}
+
+float BlurWindow::GetLatencyMs() const {
+    return m_impl->GetLatencyMs();
+}
+
+int BlurWindow::GetBackend() const {
+    return m_impl->GetBackend();
+}
+
+bool BlurWindow::SetCursorFollow(bool enabled, float radius) {
+    return m_impl->SetCursorFollow(enabled, radius);
+}
+
+uint32_t BlurWindow::GetFrameHistory(FrameSample* out, uint32_t capacity) const {
+    return m_impl->GetFrameHistory(out, capacity);
+}
+
+uint64_t BlurWindow::GetVideoMemoryUsage() const {
+    return m_impl->GetVideoMemoryUsage();
+}
+
+int BlurWindow::GetCaptureStatus() const {
+    return m_impl->GetCaptureStatus();
+}
+
+bool BlurWindow::GetCaptureRect(RECT* out) const {
+    return m_impl->GetCaptureRect(out);
+}
+
+int BlurWindow::GetCaptureFormat() const {
+    return m_impl->GetCaptureFormat();
+}
+
+int BlurWindow::GetCaptureMethod() const {
+    return m_impl->GetCaptureMethod();
+}
+
+void BlurWindow::SetTimeMode(bool manual) {
+    m_impl->SetTimeMode(manual);
+}
+
+void BlurWindow::SetTime(float seconds) {
+    m_impl->SetTime(seconds);
+}
+
+void BlurWindow::SetTargetFPS(int fps) {
+    m_impl->SetTargetFPS(fps);
+}
+
+void BlurWindow::SetCaptureInterval(uint32_t intervalMs) {
+    m_impl->SetCaptureInterval(intervalMs);
+}
+
+void BlurWindow::SetMinFrameInterval(uint32_t intervalMs) {
+    m_impl->SetMinFrameInterval(intervalMs);
+}
+
+float BlurWindow::GetDisplayRefreshHz() const {
+    return m_impl->GetDisplayRefreshHz();
+}
+
+void BlurWindow::SetPaused(bool paused) {
+    m_impl->SetPaused(paused);
+}
+
+bool BlurWindow::IsPaused() const {
+    return m_impl->IsPaused();
+}
+
+void BlurWindow::SetFrozen(bool frozen) {
+    m_impl->SetFrozen(frozen);
+}
+
+bool BlurWindow::IsFrozen() const {
+    return m_impl->IsFrozen();
+}
+
+void BlurWindow::SetVisible(bool visible) {
+    m_impl->SetVisible(visible);
+}
+
+bool BlurWindow::IsVisible() const {
+    return m_impl->IsVisible();
+}
+
 } // namespace blurwindow
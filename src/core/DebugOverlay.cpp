@@ -0,0 +1,138 @@
+#include "DebugOverlay.h"
+#include "ShaderLoader.h"
+#include <cstdio>
+#include <cstring>
+
+namespace blurwindow {
+
+namespace {
+
+constexpr int kColumns = 16;
+constexpr int kLines = 3;
+
+// Glyphs are 3x5 bitmasks (bit = row * 3 + column) decoded by the shader
+static const char* g_OverlayPS = R"(
+cbuffer OverlayParams : register(b0) {
+    uint4 glyphs[12];   // kColumns * kLines masks, row-major
+    float2 origin;
+    float scale;
+    float padding;
+};
+
+float4 main(float4 position : SV_Position, float2 texcoord : TEXCOORD0) : SV_Target {
+    float2 local = (position.xy - origin) / scale;
+    // Each cell is the 3x5 glyph plus one pixel of spacing
+    if (local.x < -1.0f || local.y < -1.0f || local.x >= 16.0f * 4.0f || local.y >= 3.0f * 6.0f) {
+        discard;
+    }
+
+    float4 panel = float4(0.0f, 0.0f, 0.0f, 0.6f);
+    if (local.x < 0.0f || local.y < 0.0f) return panel;
+
+    uint2 cell = uint2(local.x / 4.0f, local.y / 6.0f);
+    uint2 pixel = uint2(local.x - cell.x * 4.0f, local.y - cell.y * 6.0f);
+    if (pixel.x >= 3 || pixel.y >= 5) return panel;
+
+    uint index = cell.y * 16 + cell.x;
+    uint mask = glyphs[index / 4][index % 4];
+    return ((mask >> (pixel.y * 3 + pixel.x)) & 1) ? float4(1.0f, 1.0f, 1.0f, 1.0f) : panel;
+}
+)";
+
+struct OverlayParams {
+    uint32_t glyphs[kColumns * kLines];
+    float origin[2];
+    float scale;
+    float padding;
+};
+
+// Rows top to bottom, 3 bits each with the left column in the high bit
+uint32_t Glyph(const uint8_t (&rows)[5]) {
+    uint32_t mask = 0;
+    for (int r = 0; r < 5; r++) {
+        for (int c = 0; c < 3; c++) {
+            if (rows[r] & (4 >> c)) mask |= 1u << (r * 3 + c);
+        }
+    }
+    return mask;
+}
+
+uint32_t GlyphFor(char ch) {
+    switch (ch) {
+        case '0': return Glyph({ 7, 5, 5, 5, 7 });
+        case '1': return Glyph({ 2, 6, 2, 2, 7 });
+        case '2': return Glyph({ 7, 1, 7, 4, 7 });
+        case '3': return Glyph({ 7, 1, 3, 1, 7 });
+        case '4': return Glyph({ 5, 5, 7, 1, 1 });
+        case '5': return Glyph({ 7, 4, 7, 1, 7 });
+        case '6': return Glyph({ 7, 4, 7, 5, 7 });
+        case '7': return Glyph({ 7, 1, 1, 1, 1 });
+        case '8': return Glyph({ 7, 5, 7, 5, 7 });
+        case '9': return Glyph({ 7, 5, 7, 1, 7 });
+        case '.': return Glyph({ 0, 0, 0, 0, 2 });
+        case '-': return Glyph({ 0, 0, 7, 0, 0 });
+        case 'C': return Glyph({ 7, 4, 4, 4, 7 });
+        case 'D': return Glyph({ 6, 5, 5, 5, 6 });
+        case 'F': return Glyph({ 7, 4, 6, 4, 4 });
+        case 'L': return Glyph({ 4, 4, 4, 4, 7 });
+        case 'M': return Glyph({ 5, 7, 7, 5, 5 });
+        case 'O': return Glyph({ 2, 5, 5, 5, 2 });
+        case 'P': return Glyph({ 6, 5, 6, 4, 4 });
+        case 'S': return Glyph({ 3, 4, 2, 1, 6 });
+        case 'U': return Glyph({ 5, 5, 5, 5, 7 });
+        case 'W': return Glyph({ 5, 5, 7, 7, 5 });
+        default:  return 0;
+    }
+}
+
+void WriteLine(OverlayParams& params, int line, const char* text) {
+    for (int i = 0; i < kColumns && text[i]; i++) {
+        params.glyphs[line * kColumns + i] = GlyphFor(text[i]);
+    }
+}
+
+} // namespace
+
+bool DebugOverlay::Initialize(ID3D11Device* device) {
+    if (!ShaderLoader::CompilePixelShader(device, g_OverlayPS, strlen(g_OverlayPS), "main", m_pixelShader.GetAddressOf())) {
+        return false;
+    }
+    if (!m_fullscreenRenderer.Initialize(device)) return false;
+
+    D3D11_BUFFER_DESC cbDesc = {};
+    cbDesc.ByteWidth = sizeof(OverlayParams);
+    cbDesc.Usage = D3D11_USAGE_DYNAMIC;
+    cbDesc.BindFlags = D3D11_BIND_CONSTANT_BUFFER;
+    cbDesc.CPUAccessFlags = D3D11_CPU_ACCESS_WRITE;
+    return SUCCEEDED(device->CreateBuffer(&cbDesc, nullptr, m_constantBuffer.GetAddressOf()));
+}
+
+void DebugOverlay::Draw(
+    ID3D11DeviceContext* context,
+    ID3D11RenderTargetView* target,
+    uint32_t width, uint32_t height,
+    float fps, float frameTimeMs, const char* backend
+) {
+    OverlayParams params = {};
+    char line[kColumns + 1];
+    snprintf(line, sizeof(line), "FPS %.1f", fps);
+    WriteLine(params, 0, line);
+    snprintf(line, sizeof(line), "%.2f MS", frameTimeMs);
+    WriteLine(params, 1, line);
+    WriteLine(params, 2, backend);
+    params.origin[0] = params.origin[1] = 8.0f;
+    params.scale = 2.0f;
+
+    D3D11_MAPPED_SUBRESOURCE mapped;
+    if (FAILED(context->Map(m_constantBuffer.Get(), 0, D3D11_MAP_WRITE_DISCARD, 0, &mapped))) return;
+    memcpy(mapped.pData, &params, sizeof(params));
+    context->Unmap(m_constantBuffer.Get(), 0);
+
+    m_fullscreenRenderer.SetViewport(context, width, height);
+    context->PSSetShader(m_pixelShader.Get(), nullptr, 0);
+    context->PSSetConstantBuffers(0, 1, m_constantBuffer.GetAddressOf());
+    context->OMSetRenderTargets(1, &target, nullptr);
+    m_fullscreenRenderer.DrawFullscreen(context);
+}
+
+} // namespace blurwindow
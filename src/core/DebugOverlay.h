@@ -0,0 +1,34 @@
+#pragma once
+
+#include <d3d11.h>
+#include <wrl/client.h>
+#include <cstdint>
+#include "FullscreenRenderer.h"
+
+using Microsoft::WRL::ComPtr;
+
+namespace blurwindow {
+
+/// On-screen stats panel (FPS, frame time, backend) for development
+class DebugOverlay {
+public:
+    DebugOverlay() = default;
+    ~DebugOverlay() = default;
+
+    bool Initialize(ID3D11Device* device);
+
+    /// Draw the panel over the top-left corner of target
+    void Draw(
+        ID3D11DeviceContext* context,
+        ID3D11RenderTargetView* target,
+        uint32_t width, uint32_t height,
+        float fps, float frameTimeMs, const char* backend
+    );
+
+private:
+    FullscreenRenderer m_fullscreenRenderer;
+    ComPtr<ID3D11PixelShader> m_pixelShader;
+    ComPtr<ID3D11Buffer> m_constantBuffer;
+};
+
+} // namespace blurwindow
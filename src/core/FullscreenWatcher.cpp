@@ -0,0 +1,84 @@
+#include "FullscreenWatcher.h"
+#include "Logger.h"
+#include <chrono>
+#include <cwchar>
+
+namespace blurwindow {
+
+// Short enough to pause overlays right after a game takes over the screen,
+// long enough to cost nothing measurable
+static constexpr auto POLL_INTERVAL = std::chrono::milliseconds(500);
+
+bool IsFullscreenAppForeground() {
+    HWND foreground = GetForegroundWindow();
+    if (!foreground || foreground == GetDesktopWindow() || foreground == GetShellWindow()) return false;
+
+    // The host app (and its blur windows) going fullscreen is not a reason to pause
+    DWORD processId = 0;
+    GetWindowThreadProcessId(foreground, &processId);
+    if (processId == GetCurrentProcessId()) return false;
+
+    // The desktop behind the icons is a full-monitor WorkerW
+    wchar_t className[32] = {};
+    GetClassNameW(foreground, className, ARRAYSIZE(className));
+    if (wcscmp(className, L"WorkerW") == 0 || wcscmp(className, L"Progman") == 0) return false;
+
+    RECT rect;
+    MONITORINFO monitor = {};
+    monitor.cbSize = sizeof(monitor);
+    if (!GetWindowRect(foreground, &rect) ||
+        !GetMonitorInfoW(MonitorFromWindow(foreground, MONITOR_DEFAULTTONEAREST), &monitor)) {
+        return false;
+    }
+    return rect.left <= monitor.rcMonitor.left && rect.top <= monitor.rcMonitor.top &&
+           rect.right >= monitor.rcMonitor.right && rect.bottom >= monitor.rcMonitor.bottom;
+}
+
+void FullscreenWatcher::SetCallback(BlurSystem::FullscreenChangedCallback callback, void* userData) {
+    {
+        std::lock_guard<std::mutex> lock(m_callbackMutex);
+        m_callback = callback;
+        m_userData = userData;
+    }
+
+    if (!callback) {
+        Stop();
+        return;
+    }
+    if (m_thread.joinable()) return;
+
+    m_stopRequested = false;
+    m_thread = std::thread([this]() { Run(); });
+}
+
+void FullscreenWatcher::Stop() {
+    if (!m_thread.joinable()) return;
+    {
+        std::lock_guard<std::mutex> lock(m_stopMutex);
+        m_stopRequested = true;
+    }
+    m_stopCondition.notify_one();
+    m_thread.join();
+}
+
+void FullscreenWatcher::Run() {
+    LOG_INFO("FullscreenWatcher started.");
+    // Starts out "not fullscreen", so an app that already is gets reported
+    bool lastFullscreen = false;
+
+    std::unique_lock<std::mutex> stopLock(m_stopMutex);
+    while (!m_stopCondition.wait_for(stopLock, POLL_INTERVAL, [this]() { return m_stopRequested; })) {
+        bool fullscreen = IsFullscreenAppForeground();
+        if (fullscreen == lastFullscreen) continue;
+        lastFullscreen = fullscreen;
+        LOG_INFO("FullscreenWatcher: fullscreen app %s.", fullscreen ? "in front" : "gone");
+
+        std::lock_guard<std::mutex> lock(m_callbackMutex);
+        if (m_callback) {
+            m_callback(fullscreen, m_userData);
+        }
+    }
+    LOG_INFO("FullscreenWatcher stopped.");
+}
+
+} // namespace blurwindow
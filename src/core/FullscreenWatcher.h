@@ -0,0 +1,42 @@
+#pragma once
+
+#include "blurwindow/blurwindow.h"
+#include <windows.h>
+#include <condition_variable>
+#include <mutex>
+#include <thread>
+
+namespace blurwindow {
+
+/// Whether the foreground window belongs to another process and covers its
+/// whole monitor (exclusive or borderless fullscreen)
+bool IsFullscreenAppForeground();
+
+/// Polls IsFullscreenAppForeground on its own thread and reports changes.
+/// Polling also catches a game switching to fullscreen while it already has
+/// the focus, which foreground events don't.
+class FullscreenWatcher {
+public:
+    FullscreenWatcher() = default;
+    ~FullscreenWatcher() { SetCallback(nullptr, nullptr); }
+
+    /// Start watching with callback, or stop for nullptr. The callback runs
+    /// on the watcher thread; once this returns a replaced one is never called.
+    /// Must not be called from the callback itself.
+    void SetCallback(BlurSystem::FullscreenChangedCallback callback, void* userData);
+
+private:
+    void Run();
+    void Stop();
+
+    std::thread m_thread;
+    std::mutex m_stopMutex;
+    std::condition_variable m_stopCondition;
+    bool m_stopRequested = false;
+
+    std::mutex m_callbackMutex;
+    BlurSystem::FullscreenChangedCallback m_callback = nullptr;
+    void* m_userData = nullptr;
+};
+
+} // namespace blurwindow
@@ -0,0 +1,26 @@
+#pragma once
+
+#include <d3d11.h>
+#include <cstdint>
+
+namespace blurwindow {
+
+/// Bytes per pixel for the formats this library allocates (4 for anything unknown)
+inline uint32_t BytesPerPixel(DXGI_FORMAT format) {
+    switch (format) {
+    case DXGI_FORMAT_R32G32B32A32_FLOAT: return 16;
+    case DXGI_FORMAT_R16G16B16A16_FLOAT: return 8;
+    case DXGI_FORMAT_R8_UNORM: return 1;
+    default: return 4;
+    }
+}
+
+/// Approximate memory held by a texture (top mip level only)
+inline uint64_t TextureBytes(ID3D11Texture2D* texture) {
+    if (!texture) return 0;
+    D3D11_TEXTURE2D_DESC desc;
+    texture->GetDesc(&desc);
+    return static_cast<uint64_t>(desc.Width) * desc.Height * desc.ArraySize * BytesPerPixel(desc.Format);
+}
+
+} // namespace blurwindow
@@ -110,12 +110,14 @@ public:
     }
 
     /// Compile pixel shader from source at runtime
+    /// @param outErrors Receives the compiler's diagnostics on failure
     static bool CompilePixelShader(
         ID3D11Device* device,
         const char* source,
         size_t sourceSize,
         const char* entryPoint,
-        ID3D11PixelShader** outShader
+        ID3D11PixelShader** outShader,
+        std::string* outErrors = nullptr
     ) {
         ComPtr<ID3DBlob> shaderBlob;
         ComPtr<ID3DBlob> errorBlob;
@@ -139,6 +141,16 @@ public:
         if (FAILED(hr)) {
             if (errorBlob) {
                 LOG_ERROR("PS Compilation Error: %s", (const char*)errorBlob->GetBufferPointer());
+                if (outErrors) {
+                    outErrors->assign(static_cast<const char*>(errorBlob->GetBufferPointer()),
+                                      errorBlob->GetBufferSize());
+                    // The blob's size counts its terminating null
+                    while (!outErrors->empty() && (outErrors->back() == '\0' || outErrors->back() == '\n')) {
+                        outErrors->pop_back();
+                    }
+                }
+            } else if (outErrors) {
+                *outErrors = "Shader compilation failed";
             }
             return false;
         }
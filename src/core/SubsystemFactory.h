@@ -10,7 +10,7 @@ namespace blurwindow {
 /// Capture subsystem types
 enum class CaptureType {
     DXGI,           // DXGI Desktop Duplication (default)
-    WGC             // Windows.Graphics.Capture (future)
+    WGC             // Windows.Graphics.Capture (single source window)
 };
 
 /// Effect types
@@ -31,6 +31,7 @@ enum class PresenterType {
 
 // Forward declarations for factory functions (defined in respective .cpp files)
 std::unique_ptr<ICaptureSubsystem> CreateDXGICapture();
+std::unique_ptr<ICaptureSubsystem> CreateWGCCapture();
 std::unique_ptr<IBlurEffect> CreateGaussianBlur();
 std::unique_ptr<IBlurEffect> CreateKawaseBlur();
 std::unique_ptr<IBlurEffect> CreateBoxBlur();
@@ -47,6 +48,8 @@ public:
         switch (type) {
             case CaptureType::DXGI:
                 return CreateDXGICapture();
+            case CaptureType::WGC:
+                return CreateWGCCapture();
             default:
                 return nullptr;
         }
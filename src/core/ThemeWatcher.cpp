@@ -0,0 +1,119 @@
+#include "ThemeWatcher.h"
+#include "Logger.h"
+#include <cwchar>
+
+namespace blurwindow {
+
+SystemTheme ReadSystemTheme() {
+    DWORD light = 1;
+    DWORD size = sizeof(light);
+    LSTATUS status = RegGetValueW(
+        HKEY_CURRENT_USER,
+        L"Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize",
+        L"AppsUseLightTheme",
+        RRF_RT_REG_DWORD,
+        nullptr,
+        &light,
+        &size
+    );
+    // Missing on systems that predate dark mode, which are light
+    if (status != ERROR_SUCCESS) return SystemTheme::Light;
+    return light ? SystemTheme::Light : SystemTheme::Dark;
+}
+
+void ThemeWatcher::SetCallback(BlurSystem::ThemeChangedCallback callback, void* userData) {
+    {
+        std::lock_guard<std::mutex> lock(m_callbackMutex);
+        m_callback = callback;
+        m_userData = userData;
+    }
+
+    if (!callback) {
+        Stop();
+        return;
+    }
+    if (m_thread.joinable()) return;
+
+    m_lastTheme = ReadSystemTheme();
+    HANDLE ready = CreateEventW(nullptr, TRUE, FALSE, nullptr);
+    m_thread = std::thread([this, ready]() { Run(ready); });
+    // Stop posts WM_QUIT, which needs the thread's message queue to exist
+    WaitForSingleObject(ready, INFINITE);
+    CloseHandle(ready);
+}
+
+void ThemeWatcher::Stop() {
+    if (!m_thread.joinable()) return;
+    PostThreadMessageW(m_threadId, WM_QUIT, 0, 0);
+    m_thread.join();
+}
+
+void ThemeWatcher::Run(HANDLE ready) {
+    static const wchar_t* CLASS_NAME = L"BlurThemeWatcherClass";
+    static bool classRegistered = false;
+    if (!classRegistered) {
+        WNDCLASSEXW wc = {};
+        wc.cbSize = sizeof(WNDCLASSEXW);
+        wc.lpfnWndProc = WindowProc;
+        wc.hInstance = GetModuleHandleW(nullptr);
+        wc.lpszClassName = CLASS_NAME;
+        RegisterClassExW(&wc);
+        classRegistered = true;
+    }
+
+    m_threadId = GetCurrentThreadId();
+    m_hwnd = CreateWindowExW(
+        WS_EX_TOOLWINDOW, CLASS_NAME, L"BlurThemeWatcher", WS_POPUP,
+        0, 0, 0, 0, nullptr, nullptr, GetModuleHandleW(nullptr), this
+    );
+    // Make sure the thread has a message queue for WM_QUIT
+    MSG msg;
+    PeekMessageW(&msg, nullptr, WM_USER, WM_USER, PM_NOREMOVE);
+    SetEvent(ready);
+
+    if (!m_hwnd) {
+        LOG_ERROR("ThemeWatcher: failed to create window (%lu).", GetLastError());
+        return;
+    }
+    LOG_INFO("ThemeWatcher started.");
+
+    while (GetMessageW(&msg, nullptr, 0, 0) > 0) {
+        TranslateMessage(&msg);
+        DispatchMessageW(&msg);
+    }
+
+    DestroyWindow(m_hwnd);
+    m_hwnd = nullptr;
+    LOG_INFO("ThemeWatcher stopped.");
+}
+
+LRESULT CALLBACK ThemeWatcher::WindowProc(HWND hwnd, UINT msg, WPARAM wParam, LPARAM lParam) {
+    if (msg == WM_NCCREATE) {
+        auto* create = reinterpret_cast<CREATESTRUCTW*>(lParam);
+        SetWindowLongPtrW(hwnd, GWLP_USERDATA, reinterpret_cast<LONG_PTR>(create->lpCreateParams));
+    }
+
+    auto* self = reinterpret_cast<ThemeWatcher*>(GetWindowLongPtrW(hwnd, GWLP_USERDATA));
+    if (msg == WM_SETTINGCHANGE && self) {
+        self->OnSettingChange(reinterpret_cast<const wchar_t*>(lParam));
+        return 0;
+    }
+    return DefWindowProcW(hwnd, msg, wParam, lParam);
+}
+
+void ThemeWatcher::OnSettingChange(const wchar_t* area) {
+    // Theme switches are broadcast with this area name
+    if (!area || wcscmp(area, L"ImmersiveColorSet") != 0) return;
+
+    // The broadcast also fires for accent color changes; only report switches
+    SystemTheme theme = ReadSystemTheme();
+    if (theme == m_lastTheme) return;
+    m_lastTheme = theme;
+
+    std::lock_guard<std::mutex> lock(m_callbackMutex);
+    if (m_callback) {
+        m_callback(theme, m_userData);
+    }
+}
+
+} // namespace blurwindow
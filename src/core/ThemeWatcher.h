@@ -0,0 +1,42 @@
+#pragma once
+
+#include "blurwindow/blurwindow.h"
+#include <windows.h>
+#include <mutex>
+#include <thread>
+
+namespace blurwindow {
+
+/// Read the app theme (AppsUseLightTheme) from the registry; Light if unset
+SystemTheme ReadSystemTheme();
+
+/// Hidden top-level window on its own thread that reports theme switches.
+/// Message-only windows don't receive WM_SETTINGCHANGE broadcasts, so this
+/// has to be a real (never shown) window.
+class ThemeWatcher {
+public:
+    ThemeWatcher() = default;
+    ~ThemeWatcher() { SetCallback(nullptr, nullptr); }
+
+    /// Start watching with callback, or stop for nullptr. The callback runs
+    /// on the watcher thread; once this returns a replaced one is never called.
+    /// Must not be called from the callback itself.
+    void SetCallback(BlurSystem::ThemeChangedCallback callback, void* userData);
+
+private:
+    static LRESULT CALLBACK WindowProc(HWND hwnd, UINT msg, WPARAM wParam, LPARAM lParam);
+    void Run(HANDLE ready);
+    void Stop();
+    void OnSettingChange(const wchar_t* area);
+
+    std::thread m_thread;
+    HWND m_hwnd = nullptr;
+    DWORD m_threadId = 0;
+    SystemTheme m_lastTheme = SystemTheme::Light;
+
+    std::mutex m_callbackMutex;
+    BlurSystem::ThemeChangedCallback m_callback = nullptr;
+    void* m_userData = nullptr;
+};
+
+} // namespace blurwindow
@@ -1,9 +1,11 @@
 #include "IBlurEffect.h"
 #include "../core/ShaderLoader.h"
 #include "../core/FullscreenRenderer.h"
+#include "../core/GpuMemory.h"
 #include "../core/Logger.h"
 #include <algorithm>
 #include <memory>
+#include <cstring>
 
 namespace blurwindow {
 
@@ -65,8 +67,25 @@ cbuffer NoiseParams : register(b0) {
     float noiseScale;
     float time;
     int noiseType;
+    int noiseBlendMode;
+    float3 noiseColor;
 };
 
+// Noise blend: 0 = Additive, 1 = Overlay, 2 = Multiply. The grain is tinted
+// by noiseColor; white keeps it monochrome.
+float3 blendNoise(float3 base, float n) {
+    float3 grain = n * noiseColor;
+    if (noiseBlendMode == 1) {
+        float3 layer = saturate(0.5f + grain);
+        float3 overlay = base < 0.5f ? 2.0f * base * layer : 1.0f - 2.0f * (1.0f - base) * (1.0f - layer);
+        return lerp(base, overlay, noiseIntensity);
+    }
+    if (noiseBlendMode == 2) {
+        return base * (1.0f + grain * noiseIntensity);
+    }
+    return base + grain * noiseIntensity;
+}
+
 float random(float2 st) {
     return frac(sin(dot(st.xy, float2(12.9898, 78.233))) * 43758.5453123);
 }
@@ -139,7 +158,7 @@ float4 main(float4 position : SV_Position, float2 texcoord : TEXCOORD0) : SV_Tar
     } else if (noiseType == 5) { // Voronoi
         n = (1.0f - voronoi(uv * 0.2f)) * 2.0f - 0.5f;
     }
-    color.rgb += n * noiseIntensity;
+    color.rgb = blendNoise(color.rgb, n);
     return color;
 }
 )";
@@ -150,15 +169,16 @@ Texture2D blurredTexture : register(t1);
 SamplerState linearSampler : register(s0);
 
 cbuffer CompositeParams : register(b0) {
-    float strength;
-    float3 padding;
+    float strength;         // Top edge
+    float strengthBottom;
+    float2 padding;
     float4 tintColor;
 };
 
 float4 main(float4 position : SV_Position, float2 texcoord : TEXCOORD0) : SV_Target {
     float4 original = originalTexture.Sample(linearSampler, texcoord);
     float4 blurred = blurredTexture.Sample(linearSampler, texcoord);
-    float4 result = lerp(original, blurred, strength);
+    float4 result = lerp(original, blurred, lerp(strength, strengthBottom, texcoord.y));
     result.rgb = lerp(result.rgb, tintColor.rgb, tintColor.a * tintColor.a); 
     result.a = 1.0f; 
     return result;
@@ -290,12 +310,23 @@ public:
         return true;
     }
 
-    void SetStrength(float strength) override { m_strength = std::clamp(strength, 0.0f, 1.0f); }
+    void SetStrength(float strength) override { SetStrengthGradient(strength, strength); }
+    void SetStrengthGradient(float top, float bottom) override { m_strength = std::clamp(top, 0.0f, 1.0f); m_strengthBottom = std::clamp(bottom, 0.0f, 1.0f); }
     void SetColor(float r, float g, float b, float a) override { m_tintColor[0] = r; m_tintColor[1] = g; m_tintColor[2] = b; m_tintColor[3] = a; }
     void SetNoiseIntensity(float intensity) override { m_noiseIntensity = std::clamp(intensity, 0.0f, 1.0f); }
     void SetNoiseScale(float scale) override { m_noiseScale = std::clamp(scale, 1.0f, 1000.0f); }
     void SetNoiseSpeed(float speed) override { m_noiseSpeed = std::clamp(speed, 0.0f, 100.0f); }
     void SetNoiseType(int type) override { m_noiseType = std::clamp(type, 0, 5); }
+    void SetNoiseBlendMode(int mode) override { m_noiseBlendMode = std::clamp(mode, 0, 2); }
+    void SetNoiseColor(float r, float g, float b) override { m_noiseColor[0] = r; m_noiseColor[1] = g; m_noiseColor[2] = b; }
+    void SetNoiseSeed(uint32_t seed) override { m_noiseSeedOffset = NoiseSeedTimeOffset(seed); m_currentTime = 0; }
+    void SetKernelRadius(int radius) override { m_kernelRadius = std::clamp(radius, 0, 32); }
+    void SetDirection(float, float) override {}
+    void SetAxisScale(float x, float y) override { m_axisScale[0] = std::clamp(x, 0.0f, 1.0f); m_axisScale[1] = std::clamp(y, 0.0f, 1.0f); }
+    uint64_t GetVideoMemoryUsage() const override {
+        return TextureBytes(m_intermediateTexture.Get()) + TextureBytes(m_noisedTexture.Get()) +
+               TextureBytes(m_blurredTexture.Get()) + TextureBytes(m_originalTexture.Get());
+    }
     void Update(float deltaTime) override { 
         m_currentTime += deltaTime * m_noiseSpeed;
         if (m_currentTime > 10000.0f) m_currentTime = fmod(m_currentTime, 10000.0f);
@@ -316,14 +347,14 @@ public:
 
 private:
     struct BoxParams { float texelSize[2]; int radius; float padding; };
-    struct NoiseParams { float noiseIntensity; float noiseScale; float time; int noiseType; };
-    struct CompositeParams { float strength; float padding[3]; float tintColor[4]; };
+    struct NoiseParams { float noiseIntensity; float noiseScale; float time; int noiseType; int noiseBlendMode; float noiseColor[3]; };
+    struct CompositeParams { float strength, strengthBottom; float padding[2]; float tintColor[4]; };
 
     void UpdateConstantBuffer(ID3D11DeviceContext* ctx, uint32_t w, uint32_t h) {
         D3D11_MAPPED_SUBRESOURCE m;
         if (SUCCEEDED(ctx->Map(m_constantBuffer.Get(), 0, D3D11_MAP_WRITE_DISCARD, 0, &m))) {
             BoxParams* p = (BoxParams*)m.pData;
-            p->texelSize[0] = 1.0f / w; p->texelSize[1] = 1.0f / h; p->radius = m_radius;
+            p->texelSize[0] = m_axisScale[0] / w; p->texelSize[1] = m_axisScale[1] / h; p->radius = m_kernelRadius > 0 ? m_kernelRadius : m_radius;
             ctx->Unmap(m_constantBuffer.Get(), 0);
         }
     }
@@ -331,7 +362,8 @@ private:
         D3D11_MAPPED_SUBRESOURCE m;
         if (SUCCEEDED(ctx->Map(m_noiseConstantBuffer.Get(), 0, D3D11_MAP_WRITE_DISCARD, 0, &m))) {
             NoiseParams* p = (NoiseParams*)m.pData;
-            p->noiseIntensity = m_noiseIntensity; p->noiseScale = m_noiseScale; p->time = m_currentTime; p->noiseType = m_noiseType;
+            p->noiseIntensity = m_noiseIntensity; p->noiseScale = m_noiseScale; p->time = m_currentTime + m_noiseSeedOffset; p->noiseType = m_noiseType; p->noiseBlendMode = m_noiseBlendMode;
+            std::memcpy(p->noiseColor, m_noiseColor, sizeof(m_noiseColor));
             ctx->Unmap(m_noiseConstantBuffer.Get(), 0);
         }
     }
@@ -339,7 +371,7 @@ private:
         D3D11_MAPPED_SUBRESOURCE m;
         if (SUCCEEDED(ctx->Map(m_compositeConstantBuffer.Get(), 0, D3D11_MAP_WRITE_DISCARD, 0, &m))) {
             CompositeParams* p = (CompositeParams*)m.pData;
-            p->strength = m_strength; p->tintColor[0] = m_tintColor[0]; p->tintColor[1] = m_tintColor[1];
+            p->strength = m_strength; p->strengthBottom = m_strengthBottom; p->tintColor[0] = m_tintColor[0]; p->tintColor[1] = m_tintColor[1];
             p->tintColor[2] = m_tintColor[2]; p->tintColor[3] = m_tintColor[3];
             ctx->Unmap(m_compositeConstantBuffer.Get(), 0);
         }
@@ -382,8 +414,12 @@ private:
     ComPtr<ID3D11RenderTargetView> m_intermediateRTV, m_noisedRTV, m_blurredRTV;
     uint32_t m_w = 0, m_h = 0;
     int m_radius = 3;
-    float m_strength = 1.0f, m_tintColor[4] = {0}, m_noiseIntensity = 0, m_noiseScale = 100, m_noiseSpeed = 1, m_currentTime = 0;
-    int m_noiseType = 0;
+    int m_kernelRadius = 0;
+    float m_strength = 1.0f, m_strengthBottom = 1.0f, m_tintColor[4] = {0}, m_noiseIntensity = 0, m_noiseScale = 100, m_noiseSpeed = 1, m_currentTime = 0;
+    float m_axisScale[2] = {1.0f, 1.0f};
+    float m_noiseSeedOffset = 0;
+    int m_noiseType = 0, m_noiseBlendMode = 0;
+    float m_noiseColor[3] = {1.0f, 1.0f, 1.0f};
 };
 
 std::unique_ptr<IBlurEffect> CreateBoxBlur() { return std::make_unique<BoxBlur>(); }
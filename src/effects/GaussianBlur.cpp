@@ -1,6 +1,7 @@
 #include "IBlurEffect.h"
 #include "../core/ShaderLoader.h"
 #include "../core/FullscreenRenderer.h"
+#include "../core/GpuMemory.h"
 #include <vector>
 #include <cmath>
 #include <algorithm>
@@ -20,8 +21,25 @@ cbuffer NoiseParams : register(b0) {
     float noiseScale;
     float time;
     int noiseType;
+    int noiseBlendMode;
+    float3 noiseColor;
 };
 
+// Noise blend: 0 = Additive, 1 = Overlay, 2 = Multiply. The grain is tinted
+// by noiseColor; white keeps it monochrome.
+float3 blendNoise(float3 base, float n) {
+    float3 grain = n * noiseColor;
+    if (noiseBlendMode == 1) {
+        float3 layer = saturate(0.5f + grain);
+        float3 overlay = base < 0.5f ? 2.0f * base * layer : 1.0f - 2.0f * (1.0f - base) * (1.0f - layer);
+        return lerp(base, overlay, noiseIntensity);
+    }
+    if (noiseBlendMode == 2) {
+        return base * (1.0f + grain * noiseIntensity);
+    }
+    return base + grain * noiseIntensity;
+}
+
 float random(float2 st) {
     return frac(sin(dot(st.xy, float2(12.9898f, 78.233f))) * 43758.5453123f);
 }
@@ -96,7 +114,7 @@ float4 main(float4 position : SV_Position, float2 texcoord : TEXCOORD0) : SV_Tar
     else if (noiseType == 4) n = simplexNoise(uv * 0.15f) * 3.5f;
     else if (noiseType == 5) n = (1.0f - voronoi(uv * 0.2f)) * 2.0f - 0.5f;
 
-    color.rgb += n * noiseIntensity;
+    color.rgb = blendNoise(color.rgb, n);
     return color;
 }
 )";
@@ -164,15 +182,16 @@ Texture2D blurredTexture : register(t1);
 SamplerState linearSampler : register(s0);
 
 cbuffer CompositeParams : register(b0) {
-    float strength;
-    float3 padding;
+    float strength;         // Top edge
+    float strengthBottom;
+    float2 padding;
     float4 tintColor;
 };
 
 float4 main(float4 position : SV_Position, float2 texcoord : TEXCOORD0) : SV_Target {
     float4 original = originalTexture.Sample(linearSampler, texcoord);
     float4 blurred = blurredTexture.Sample(linearSampler, texcoord);
-    float4 result = lerp(original, blurred, strength);
+    float4 result = lerp(original, blurred, lerp(strength, strengthBottom, texcoord.y));
     result.rgb = lerp(result.rgb, tintColor.rgb, tintColor.a * tintColor.a); // Apply tinting, tintColor.a acts as strength
     result.a = 1.0f; // Force opaque to prevent transparency issues
     return result;
@@ -232,7 +251,7 @@ public:
         
         // Create constant buffer for composite shader (strength only)
         D3D11_BUFFER_DESC compCbDesc = {};
-        compCbDesc.ByteWidth = 16;  // float strength + float3 padding = 16 bytes
+        compCbDesc.ByteWidth = 16;  // float strength + float strengthBottom + float2 padding = 16 bytes
         compCbDesc.Usage = D3D11_USAGE_DYNAMIC;
         compCbDesc.BindFlags = D3D11_BIND_CONSTANT_BUFFER;
         compCbDesc.CPUAccessFlags = D3D11_CPU_ACCESS_WRITE;
@@ -347,7 +366,12 @@ public:
     }
 
     void SetStrength(float strength) override {
-        m_strength = std::clamp(strength, 0.0f, 1.0f);
+        SetStrengthGradient(strength, strength);
+    }
+
+    void SetStrengthGradient(float top, float bottom) override {
+        m_strength = std::clamp(top, 0.0f, 1.0f);
+        m_strengthBottom = std::clamp(bottom, 0.0f, 1.0f);
     }
 
     void SetColor(float r, float g, float b, float a) override {
@@ -373,6 +397,37 @@ public:
         m_noiseType = std::clamp(type, 0, 5);
     }
 
+    void SetNoiseBlendMode(int mode) override {
+        m_noiseBlendMode = std::clamp(mode, 0, 2);
+    }
+
+    void SetNoiseColor(float r, float g, float b) override {
+        m_noiseColor[0] = r;
+        m_noiseColor[1] = g;
+        m_noiseColor[2] = b;
+    }
+
+    void SetNoiseSeed(uint32_t seed) override {
+        m_noiseSeedOffset = NoiseSeedTimeOffset(seed);
+        m_currentTime = 0.0f;
+    }
+
+    void SetKernelRadius(int radius) override {
+        m_kernelRadius = std::clamp(radius, 0, 32);
+    }
+
+    void SetDirection(float, float) override {}
+
+    void SetAxisScale(float x, float y) override {
+        m_axisScale[0] = std::clamp(x, 0.0f, 1.0f);
+        m_axisScale[1] = std::clamp(y, 0.0f, 1.0f);
+    }
+
+    uint64_t GetVideoMemoryUsage() const override {
+        return TextureBytes(m_intermediateTexture.Get()) + TextureBytes(m_noisedTexture.Get()) +
+               TextureBytes(m_blurredTexture.Get()) + TextureBytes(m_originalTexture.Get());
+    }
+
     void Update(float deltaTime) override {
         m_currentTime += deltaTime * m_noiseSpeed;
         // Keep time in reasonable range to avoid precision issues
@@ -411,11 +466,14 @@ private:
         float noiseScale;
         float time;
         int noiseType;
+        int noiseBlendMode;
+        float noiseColor[3];
     };
 
     struct CompositeParams {
         float strength;
-        float padding[3];
+        float strengthBottom;
+        float padding[2];
         float tintColor[4];
     };
 
@@ -424,6 +482,7 @@ private:
         if (SUCCEEDED(context->Map(m_compositeConstantBuffer.Get(), 0, D3D11_MAP_WRITE_DISCARD, 0, &mapped))) {
             CompositeParams* params = static_cast<CompositeParams*>(mapped.pData);
             params->strength = m_strength;
+            params->strengthBottom = m_strengthBottom;
             params->tintColor[0] = m_tintColor[0];
             params->tintColor[1] = m_tintColor[1];
             params->tintColor[2] = m_tintColor[2];
@@ -438,8 +497,12 @@ private:
             NoiseParams* params = static_cast<NoiseParams*>(mapped.pData);
             params->noiseIntensity = m_noiseIntensity;
             params->noiseScale = m_noiseScale;
-            params->time = m_currentTime;
+            params->time = m_currentTime + m_noiseSeedOffset;
             params->noiseType = m_noiseType;
+            params->noiseBlendMode = m_noiseBlendMode;
+            params->noiseColor[0] = m_noiseColor[0];
+            params->noiseColor[1] = m_noiseColor[1];
+            params->noiseColor[2] = m_noiseColor[2];
             context->Unmap(m_noiseConstantBuffer.Get(), 0);
         }
     }
@@ -449,12 +512,19 @@ private:
         HRESULT hr = context->Map(m_constantBuffer.Get(), 0, D3D11_MAP_WRITE_DISCARD, 0, &mapped);
         if (SUCCEEDED(hr)) {
             BlurParams* params = static_cast<BlurParams*>(mapped.pData);
-            params->texelSize[0] = 1.0f / static_cast<float>(width);
-            params->texelSize[1] = 1.0f / static_cast<float>(height);
+            // A zero scale keeps every tap on the center texel, so that axis stays sharp
+            params->texelSize[0] = m_axisScale[0] / static_cast<float>(width);
+            params->texelSize[1] = m_axisScale[1] / static_cast<float>(height);
             
             float sigma = (sigmaOverride > 0.0f) ? sigmaOverride : m_sigma;
-            params->sigma = sigma;
-            params->radius = (std::min)(std::ceil(sigma * 3.0f), 32.0f);
+            if (m_kernelRadius > 0 && sigmaOverride <= 0.0f) {
+                // Explicit radius: pick sigma so the kernel covers +/- 3 sigma
+                params->radius = static_cast<float>(m_kernelRadius);
+                params->sigma = params->radius / 3.0f;
+            } else {
+                params->sigma = sigma;
+                params->radius = (std::min)(std::ceil(sigma * 3.0f), 32.0f);
+            }
             
             context->Unmap(m_constantBuffer.Get(), 0);
         }
@@ -545,7 +615,10 @@ private:
     
     // Blur parameters
     float m_sigma = 5.0f;
+    int m_kernelRadius = 0;
     float m_strength = 1.0f;
+    float m_strengthBottom = 1.0f;
+    float m_axisScale[2] = { 1.0f, 1.0f };
     float m_tintColor[4] = { 0.0f, 0.0f, 0.0f, 0.0f };
 
     // Noise parameters
@@ -553,7 +626,10 @@ private:
     float m_noiseScale = 100.0f;
     float m_noiseSpeed = 1.0f;
     float m_currentTime = 0.0f;
+    float m_noiseSeedOffset = 0.0f;
     int m_noiseType = 0;
+    int m_noiseBlendMode = 0;
+    float m_noiseColor[3] = { 1.0f, 1.0f, 1.0f };
 };
 
 // Factory function
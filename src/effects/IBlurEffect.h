@@ -9,6 +9,13 @@ namespace blurwindow {
 
 using Microsoft::WRL::ComPtr;
 
+/// Noise clock offset for a seed. The noise shaders are driven only by uv and
+/// time, so a seed selects a distinct starting point on the time axis (kept
+/// well below the 10000s wrap used by the effects).
+inline float NoiseSeedTimeOffset(uint32_t seed) {
+    return static_cast<float>(seed % 4096u) * 1.618034f;
+}
+
 /// Abstract interface for blur effects
 class IBlurEffect {
 public:
@@ -40,6 +47,10 @@ public:
     /// Set blur strength
     virtual void SetStrength(float strength) = 0;
 
+    /// Set blur strength interpolated linearly from the top edge to the bottom
+    /// edge. SetStrength(s) is the same as SetStrengthGradient(s, s).
+    virtual void SetStrengthGradient(float top, float bottom) = 0;
+
     /// Set tint color
     virtual void SetColor(float r, float g, float b, float a) = 0;
 
@@ -55,6 +66,32 @@ public:
     /// Set noise type (0: White, 1: Sinusoid, 2: Grid, 3: Perlin, 4: Simplex)
     virtual void SetNoiseType(int type) = 0;
 
+    /// Set noise blend mode (0: Additive, 1: Overlay, 2: Multiply)
+    virtual void SetNoiseBlendMode(int mode) = 0;
+
+    /// Tint the noise grain (1, 1, 1 for monochrome grain, the default)
+    virtual void SetNoiseColor(float r, float g, float b) = 0;
+
+    /// Select the noise pattern by seed; also restarts the noise clock so a
+    /// fixed seed reproduces the same grain. Effects without noise ignore this.
+    virtual void SetNoiseSeed(uint32_t seed) = 0;
+
+    /// Set a directional (motion-style) blur: angle in degrees counter-clockwise
+    /// from +x, strength 0.0 (off) to 1.0. Only Radial uses it, replacing its
+    /// zoom blur while strength is above 0; other effects ignore it.
+    virtual void SetDirection(float angleDegrees, float strength) = 0;
+
+    /// Scale the kernel's reach along x and y (0.0 to 1.0 each; 1, 1 is the
+    /// normal isotropic blur). Effects without a per-axis kernel ignore this.
+    virtual void SetAxisScale(float x, float y) = 0;
+
+    /// Set explicit kernel radius in pixels (0: derive from the effect parameter).
+    /// Effects without a pixel kernel ignore this.
+    virtual void SetKernelRadius(int radius) = 0;
+
+    /// Approximate GPU memory held by the effect's textures, in bytes
+    virtual uint64_t GetVideoMemoryUsage() const = 0;
+
     /// Update animation state
     virtual void Update(float deltaTime) = 0;
 
@@ -1,6 +1,7 @@
 #include "IBlurEffect.h"
 #include "../core/ShaderLoader.h"
 #include "../core/FullscreenRenderer.h"
+#include "../core/GpuMemory.h"
 #include <vector>
 #include <cmath>
 #include <algorithm>
@@ -20,8 +21,25 @@ cbuffer NoiseParams : register(b0) {
     float noiseScale;
     float time;
     int noiseType;
+    int noiseBlendMode;
+    float3 noiseColor;
 };
 
+// Noise blend: 0 = Additive, 1 = Overlay, 2 = Multiply. The grain is tinted
+// by noiseColor; white keeps it monochrome.
+float3 blendNoise(float3 base, float n) {
+    float3 grain = n * noiseColor;
+    if (noiseBlendMode == 1) {
+        float3 layer = saturate(0.5f + grain);
+        float3 overlay = base < 0.5f ? 2.0f * base * layer : 1.0f - 2.0f * (1.0f - base) * (1.0f - layer);
+        return lerp(base, overlay, noiseIntensity);
+    }
+    if (noiseBlendMode == 2) {
+        return base * (1.0f + grain * noiseIntensity);
+    }
+    return base + grain * noiseIntensity;
+}
+
 float random(float2 st) {
     return frac(sin(dot(st.xy, float2(12.9898f, 78.233f))) * 43758.5453123f);
 }
@@ -89,7 +107,7 @@ float4 main(float4 position : SV_Position, float2 texcoord : TEXCOORD0) : SV_Tar
     } else if (noiseType == 3) n = perlinNoise(uv * 0.3f) * 2.5f;
     else if (noiseType == 4) n = simplexNoise(uv * 0.15f) * 3.5f;
     else if (noiseType == 5) n = (1.0f - voronoi(uv * 0.2f)) * 2.0f - 0.5f;
-    color.rgb += n * noiseIntensity;
+    color.rgb = blendNoise(color.rgb, n);
     return color;
 }
 )";
@@ -104,15 +122,24 @@ cbuffer RadialBlurParams : register(b0) {
     float blurAmount;
     float radius;
     float samples;
-    float3 padding;
+    float directional;
+    float2 direction;   // Full smear length in UV units when directional
 };
 
 float4 main(float4 position : SV_Position, float2 texcoord : TEXCOORD0) : SV_Target {
     float4 color = float4(0, 0, 0, 0);
+    int numSamples = (int)samples;
+    if (directional > 0.5f) {
+        // Motion-style smear centered on the pixel
+        for (int j = 0; j < numSamples; j++) {
+            float t = float(j) / float(numSamples - 1) - 0.5f;
+            color += inputTexture.Sample(linearSampler, texcoord + direction * t);
+        }
+        return color / float(numSamples);
+    }
     float2 dir = texcoord - center;
     float dist = length(dir);
     float amount = blurAmount * saturate(dist / radius);
-    int numSamples = (int)samples;
     for (int i = 0; i < numSamples; i++) {
         float scale = 1.0f - amount * (float(i) / float(numSamples - 1));
         color += inputTexture.Sample(linearSampler, center + dir * scale);
@@ -128,15 +155,16 @@ Texture2D blurredTexture : register(t1);
 SamplerState linearSampler : register(s0);
 
 cbuffer CompositeParams : register(b0) {
-    float strength;
-    float3 padding;
+    float strength;         // Top edge
+    float strengthBottom;
+    float2 padding;
     float4 tintColor;
 };
 
 float4 main(float4 position : SV_Position, float2 texcoord : TEXCOORD0) : SV_Target {
     float4 original = originalTexture.Sample(linearSampler, texcoord);
     float4 blurred = blurredTexture.Sample(linearSampler, texcoord);
-    float4 result = lerp(original, blurred, strength);
+    float4 result = lerp(original, blurred, lerp(strength, strengthBottom, texcoord.y));
     result.rgb = lerp(result.rgb, tintColor.rgb, tintColor.a * tintColor.a);
     result.a = 1.0f;
     return result;
@@ -185,8 +213,8 @@ public:
         CopyInputToOriginal(context, input);
         m_fullscreenRenderer.SetViewport(context, width, height);
 
-        // Pass 1: Radial Blur
-        UpdateConstantBuffer(context, m_blurAmount);
+        // Pass 1: Radial (or directional) Blur
+        UpdateConstantBuffer(context, m_blurAmount, width, height);
         context->PSSetShader(m_radialPS.Get(), nullptr, 0);
         context->PSSetShaderResources(0, 1, &input);
         context->PSSetSamplers(0, 1, m_sampler.GetAddressOf());
@@ -204,7 +232,7 @@ public:
         m_fullscreenRenderer.DrawFullscreen(context);
 
         // Pass 3: Soften
-        UpdateConstantBuffer(context, m_blurAmount * 0.3f);
+        UpdateConstantBuffer(context, m_blurAmount * 0.3f, width, height);
         context->PSSetShader(m_radialPS.Get(), nullptr, 0);
         ID3D11ShaderResourceView* noisedSRV = m_noisedSRV.Get();
         context->PSSetShaderResources(0, 1, &noisedSRV);
@@ -227,12 +255,26 @@ public:
         return true;
     }
 
-    void SetStrength(float strength) override { m_strength = std::clamp(strength, 0.0f, 1.0f); }
+    void SetStrength(float strength) override { SetStrengthGradient(strength, strength); }
+    void SetStrengthGradient(float top, float bottom) override { m_strength = std::clamp(top, 0.0f, 1.0f); m_strengthBottom = std::clamp(bottom, 0.0f, 1.0f); }
     void SetColor(float r, float g, float b, float a) override { m_tintColor[0] = r; m_tintColor[1] = g; m_tintColor[2] = b; m_tintColor[3] = a; }
     void SetNoiseIntensity(float intensity) override { m_noiseIntensity = std::clamp(intensity, 0.0f, 1.0f); }
     void SetNoiseScale(float scale) override { m_noiseScale = std::clamp(scale, 1.0f, 1000.0f); }
     void SetNoiseSpeed(float speed) override { m_noiseSpeed = std::clamp(speed, 0.0f, 100.0f); }
     void SetNoiseType(int type) override { m_noiseType = std::clamp(type, 0, 5); }
+    void SetNoiseBlendMode(int mode) override { m_noiseBlendMode = std::clamp(mode, 0, 2); }
+    void SetNoiseColor(float r, float g, float b) override { m_noiseColor[0] = r; m_noiseColor[1] = g; m_noiseColor[2] = b; }
+    void SetNoiseSeed(uint32_t seed) override { m_noiseSeedOffset = NoiseSeedTimeOffset(seed); m_currentTime = 0; }
+    void SetKernelRadius(int) override {}
+    void SetDirection(float angleDegrees, float strength) override {
+        m_directionAngle = angleDegrees;
+        m_directionStrength = std::clamp(strength, 0.0f, 1.0f);
+    }
+    void SetAxisScale(float, float) override {}
+    uint64_t GetVideoMemoryUsage() const override {
+        return TextureBytes(m_intermediateTexture.Get()) + TextureBytes(m_noisedTexture.Get()) +
+               TextureBytes(m_blurredTexture.Get()) + TextureBytes(m_originalTexture.Get());
+    }
     void Update(float deltaTime) override { 
         m_currentTime += deltaTime * m_noiseSpeed;
         if (m_currentTime > 10000.0f) m_currentTime = fmod(m_currentTime, 10000.0f);
@@ -250,23 +292,33 @@ public:
     std::string GetParameters() const override { char buffer[64]; snprintf(buffer, sizeof(buffer), "{\"amount\": %.4f}", m_blurAmount); return buffer; }
 
 private:
-    struct NoiseParams { float noiseIntensity, noiseScale, time; int noiseType; };
-    struct RadialParams { float center[2]; float blurAmount, radius, samples; float padding[3]; };
-    struct CompositeParams { float strength; float padding[3]; float tintColor[4]; };
+    struct NoiseParams { float noiseIntensity, noiseScale, time; int noiseType; int noiseBlendMode; float noiseColor[3]; };
+    struct RadialParams { float center[2]; float blurAmount, radius, samples, directional; float direction[2]; };
+
+    /// Longest directional smear at strength 1, in pixels
+    static constexpr float kMaxDirectionalLength = 64.0f;
+    struct CompositeParams { float strength, strengthBottom; float padding[2]; float tintColor[4]; };
 
     void UpdateNoiseConstantBuffer(ID3D11DeviceContext* ctx) {
         D3D11_MAPPED_SUBRESOURCE m;
         if (SUCCEEDED(ctx->Map(m_noiseConstantBuffer.Get(), 0, D3D11_MAP_WRITE_DISCARD, 0, &m))) {
             NoiseParams* p = (NoiseParams*)m.pData;
-            p->noiseIntensity = m_noiseIntensity; p->noiseScale = m_noiseScale; p->time = m_currentTime; p->noiseType = m_noiseType;
+            p->noiseIntensity = m_noiseIntensity; p->noiseScale = m_noiseScale; p->time = m_currentTime + m_noiseSeedOffset; p->noiseType = m_noiseType; p->noiseBlendMode = m_noiseBlendMode;
+            std::memcpy(p->noiseColor, m_noiseColor, sizeof(m_noiseColor));
             ctx->Unmap(m_noiseConstantBuffer.Get(), 0);
         }
     }
-    void UpdateConstantBuffer(ID3D11DeviceContext* ctx, float amount) {
+    void UpdateConstantBuffer(ID3D11DeviceContext* ctx, float amount, uint32_t width, uint32_t height) {
         D3D11_MAPPED_SUBRESOURCE m;
         if (SUCCEEDED(ctx->Map(m_constantBuffer.Get(), 0, D3D11_MAP_WRITE_DISCARD, 0, &m))) {
             RadialParams* p = (RadialParams*)m.pData;
             p->center[0] = 0.5f; p->center[1] = 0.5f; p->blurAmount = amount; p->radius = 1.0f; p->samples = 16.0f;
+            // 0 degrees points right, 90 up; the soften pass scales the smear like the radial amount
+            float length = kMaxDirectionalLength * m_directionStrength * (amount / m_blurAmount);
+            float radians = m_directionAngle * 3.14159265f / 180.0f;
+            p->directional = m_directionStrength > 0.0f ? 1.0f : 0.0f;
+            p->direction[0] = std::cos(radians) * length / static_cast<float>(width);
+            p->direction[1] = -std::sin(radians) * length / static_cast<float>(height);
             ctx->Unmap(m_constantBuffer.Get(), 0);
         }
     }
@@ -274,7 +326,7 @@ private:
         D3D11_MAPPED_SUBRESOURCE m;
         if (SUCCEEDED(ctx->Map(m_compositeConstantBuffer.Get(), 0, D3D11_MAP_WRITE_DISCARD, 0, &m))) {
             CompositeParams* p = (CompositeParams*)m.pData;
-            p->strength = m_strength; memcpy(p->tintColor, m_tintColor, sizeof(m_tintColor));
+            p->strength = m_strength; p->strengthBottom = m_strengthBottom; memcpy(p->tintColor, m_tintColor, sizeof(m_tintColor));
             ctx->Unmap(m_compositeConstantBuffer.Get(), 0);
         }
     }
@@ -315,8 +367,11 @@ private:
     ComPtr<ID3D11ShaderResourceView> m_intermediateSRV, m_noisedSRV, m_blurredSRV, m_originalSRV;
     ComPtr<ID3D11RenderTargetView> m_intermediateRTV, m_noisedRTV, m_blurredRTV;
     uint32_t m_w = 0, m_h = 0;
-    float m_strength = 1.0f, m_blurAmount = 0.15f, m_tintColor[4] = {0}, m_noiseIntensity = 0, m_noiseScale = 100, m_noiseSpeed = 1, m_currentTime = 0;
-    int m_noiseType = 0;
+    float m_strength = 1.0f, m_strengthBottom = 1.0f, m_blurAmount = 0.15f, m_tintColor[4] = {0}, m_noiseIntensity = 0, m_noiseScale = 100, m_noiseSpeed = 1, m_currentTime = 0;
+    float m_noiseSeedOffset = 0;
+    float m_directionAngle = 0, m_directionStrength = 0;
+    int m_noiseType = 0, m_noiseBlendMode = 0;
+    float m_noiseColor[3] = {1.0f, 1.0f, 1.0f};
 };
 
 std::unique_ptr<IBlurEffect> CreateRadialBlur() { return std::make_unique<RadialBlur>(); }
@@ -2,6 +2,7 @@
 #include "../core/ShaderLoader.h"
 #include "../core/FullscreenRenderer.h"
 #include "../core/Logger.h"
+#include "../core/GpuMemory.h"
 #include <cstring>
 #include <cstdio>
 #include <algorithm>
@@ -328,8 +329,10 @@ void RainEffect::SetColor(float r, float g, float b, float a) {
 void RainEffect::Update(float deltaTime) {
     m_time += deltaTime * m_noiseSpeed;
     
-    // Update raindrop simulation
-    UpdateDrops(deltaTime);
+    // Update raindrop simulation (it can only run forward; a manual clock may step back)
+    if (deltaTime > 0.0f) {
+        UpdateDrops(deltaTime);
+    }
 }
 
 void RainEffect::UpdateDrops(float deltaTime) {
@@ -723,7 +726,7 @@ bool RainEffect::SetParameters(const char* json) {
     // Parse simple JSON parameters
     float intensity = 0.0f;
     if (std::sscanf(json, "{\"intensity\": %f}", &intensity) == 1) {
-        m_rainIntensity = intensity;
+        SetRainIntensity(intensity);
         return true;
     }
     
@@ -738,6 +741,10 @@ std::string RainEffect::GetParameters() const {
     return std::string(buffer);
 }
 
+uint64_t RainEffect::GetVideoMemoryUsage() const {
+    return TextureBytes(m_dropTexture.Get()) + TextureBytes(m_blurredTexture.Get());
+}
+
 // Factory function for SubsystemFactory
 std::unique_ptr<IBlurEffect> CreateRainEffect() {
     return std::make_unique<RainEffect>();
@@ -2,6 +2,7 @@
 
 #include "IBlurEffect.h"
 #include "../core/FullscreenRenderer.h"
+#include <algorithm>
 #include <vector>
 #include <random>
 
@@ -25,17 +26,26 @@ public:
     ) override;
 
     void SetStrength(float strength) override { m_strength = strength; }
+    // The rain backdrop blurs uniformly, at the gradient's average
+    void SetStrengthGradient(float top, float bottom) override { m_strength = (top + bottom) * 0.5f; }
     void SetColor(float r, float g, float b, float a) override;
     void SetNoiseIntensity(float intensity) override { m_noiseIntensity = intensity; }
     void SetNoiseScale(float scale) override { m_noiseScale = scale; }
     void SetNoiseSpeed(float speed) override { m_noiseSpeed = speed; }
     void SetNoiseType(int type) override { m_noiseType = type; }
+    void SetNoiseBlendMode(int mode) override { m_noiseBlendMode = mode; }
+    void SetNoiseColor(float, float, float) override {}
+    void SetNoiseSeed(uint32_t) override {}
+    void SetKernelRadius(int) override {}
+    void SetDirection(float, float) override {}
+    void SetAxisScale(float, float) override {}
+    uint64_t GetVideoMemoryUsage() const override;
     void Update(float deltaTime) override;
     bool SetParameters(const char* json) override;
     std::string GetParameters() const override;
 
     // Rain-specific settings
-    void SetRainIntensity(float intensity) { m_rainIntensity = intensity; }
+    void SetRainIntensity(float intensity) { m_rainIntensity = std::clamp(intensity, 0.0f, 1.0f); }
     void SetDropSpeed(float speed) { m_dropSpeed = speed; }
     void SetRefractionStrength(float strength) { m_refractionStrength = strength; }
     void SetTrailLength(float length) { m_trailLength = length; }
@@ -102,6 +112,7 @@ private:
     float m_noiseScale = 100.0f;
     float m_noiseSpeed = 1.0f;
     int m_noiseType = 0;
+    int m_noiseBlendMode = 0;
     float m_time = 0.0f;
 
     // Rain-specific parameters (Codrops compatible)
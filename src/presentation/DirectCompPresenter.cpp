@@ -90,12 +90,30 @@ public:
         m_height = height;
 
         // Release back buffer references before resize
-        m_swapChain->ResizeBuffers(2, width, height, DXGI_FORMAT_B8G8R8A8_UNORM, 0);
+        m_swapChain->ResizeBuffers(m_bufferCount, width, height, DXGI_FORMAT_B8G8R8A8_UNORM, 0);
 
         m_dcompDevice->Commit();
         return true;
     }
 
+    // Present holds no back buffer between frames, so the buffers can be
+    // recreated in place; the visual keeps the same swap chain
+    bool SetBufferCount(uint32_t count) override {
+        if (count == m_bufferCount) return true;
+        if (!m_swapChain) {
+            m_bufferCount = count;
+            return true;
+        }
+        HRESULT hr = m_swapChain->ResizeBuffers(count, m_width, m_height, DXGI_FORMAT_B8G8R8A8_UNORM, 0);
+        if (FAILED(hr)) {
+            LOG_WARN("ResizeBuffers to %u buffers failed (0x%08X).", count, hr);
+            return false;
+        }
+        m_bufferCount = count;
+        m_dcompDevice->Commit();
+        return true;
+    }
+
     void Shutdown() override {
         m_initialized = false;
         m_visual.Reset();
@@ -106,6 +124,13 @@ public:
         m_hwnd = nullptr;
     }
 
+    uint64_t GetVideoMemoryUsage() const override {
+        if (!m_swapChain) return 0;
+        DXGI_SWAP_CHAIN_DESC1 desc;
+        m_swapChain->GetDesc1(&desc);
+        return static_cast<uint64_t>(desc.Width) * desc.Height * 4 * desc.BufferCount;
+    }
+
 private:
     bool CreateSwapChain() {
         RECT rect;
@@ -133,7 +158,7 @@ private:
         desc.Format = DXGI_FORMAT_B8G8R8A8_UNORM;
         desc.SampleDesc.Count = 1;
         desc.BufferUsage = DXGI_USAGE_RENDER_TARGET_OUTPUT;
-        desc.BufferCount = 2;
+        desc.BufferCount = m_bufferCount;
         desc.SwapEffect = DXGI_SWAP_EFFECT_FLIP_SEQUENTIAL;
         desc.AlphaMode = DXGI_ALPHA_MODE_PREMULTIPLIED;
 
@@ -151,6 +176,7 @@ private:
     ID3D11Device* m_device = nullptr;
     uint32_t m_width = 0;
     uint32_t m_height = 0;
+    uint32_t m_bufferCount = 2;
     bool m_initialized = false;
 
     ComPtr<IDCompositionDevice> m_dcompDevice;
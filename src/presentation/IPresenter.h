@@ -30,8 +30,16 @@ public:
     /// @return true on success
     virtual bool Resize(uint32_t width, uint32_t height) = 0;
 
+    /// Change how many buffers the presentation surface cycles through
+    /// @param count 2 or 3
+    /// @return true on success, or if the presenter has no buffers to change
+    virtual bool SetBufferCount(uint32_t count) = 0;
+
     /// Release resources
     virtual void Shutdown() = 0;
+
+    /// Approximate GPU memory held by the presentation surfaces, in bytes
+    virtual uint64_t GetVideoMemoryUsage() const = 0;
 };
 
 } // namespace blurwindow
@@ -1,4 +1,5 @@
 #include "IPresenter.h"
+#include "../core/GpuMemory.h"
 #include <memory>
 
 namespace blurwindow {
@@ -92,6 +93,11 @@ public:
         return CreateStagingTexture();
     }
 
+    // UpdateLayeredWindow keeps a single surface
+    bool SetBufferCount(uint32_t) override {
+        return true;
+    }
+
     void Shutdown() override {
         m_stagingTexture.Reset();
         m_context.Reset();
@@ -99,6 +105,11 @@ public:
         m_hwnd = nullptr;
     }
 
+    // The staging copy usually lives in system memory, but counts against the driver's budget
+    uint64_t GetVideoMemoryUsage() const override {
+        return TextureBytes(m_stagingTexture.Get());
+    }
+
 private:
     bool CreateStagingTexture() {
         m_stagingTexture.Reset();